@@ -0,0 +1,104 @@
+//! Programs compiled to Arc binary with "--emit=arcb", running the ".arcb" file skips compiling
+use crate::lib::benchmark::Measurement;
+use crate::lib::util;
+use once_cell::sync::Lazy;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use vinci::ast::instructions::location::Location;
+use vinci::ast::AST;
+use vinci::binary::{self, Header};
+use vinci::diagnostic::Diagnostic;
+
+/// Files written by another build of Loop are rejected, as the AST it creates can differ. Builds
+/// of the same version are told apart by a hash of the executable.
+pub static COMPILER_VERSION: Lazy<String> = Lazy::new(|| {
+    let version = env!("CARGO_PKG_VERSION");
+
+    match env::current_exe().and_then(fs::read) {
+        Ok(executable) => format!("{}+{:016x}", version, binary::hash(&executable)),
+        Err(_) => version.to_string(),
+    }
+});
+
+pub fn is_arcb(path: &str) -> bool {
+    Path::new(path).extension() == Some(OsStr::new("arcb"))
+}
+
+/// Compiles the file and writes its AST to "<path>.arcb" instead of running it
+pub fn emit(path: String) -> Result<(), ExitCode> {
+    let content = util::read_file(&path, |path| fs::read_to_string(path));
+    let (ast, _) = util::compile(&content, &path, true, &mut Measurement::default())?;
+
+    let header = Header::new(&COMPILER_VERSION, binary::source_hash(&content));
+    util::save_to_file(format!("{}.arcb", path), binary::encode(&header, &ast));
+
+    Ok(())
+}
+
+/// Decodes and validates the AST, when the source it was compiled from is next to it ("main.loop"
+/// for "main.loop.arcb") it should not have changed since
+pub fn decode(path: &str, bytes: &[u8]) -> Result<AST, ExitCode> {
+    let (header, ast) = binary::decode(bytes)
+        .map_err(|error| report("arcb::invalid", format!("{}: {}", path, error), None))?;
+
+    if header.compiler_version != *COMPILER_VERSION {
+        return Err(report(
+            "arcb::version",
+            format!(
+                "{} was compiled by Loop {}, but this is Loop {}",
+                path, header.compiler_version, *COMPILER_VERSION
+            ),
+            recompile(path),
+        ));
+    }
+
+    let source = Path::new(path).with_extension("");
+    if let Ok(content) = fs::read_to_string(&source) {
+        if binary::source_hash(&content) != header.source_hash {
+            return Err(report(
+                "arcb::stale",
+                format!(
+                    "{} is out of date, {} changed after it was compiled",
+                    path,
+                    source.display()
+                ),
+                recompile(path),
+            ));
+        }
+    }
+
+    // The file might not have been written by Loop, mistakes in it are found before running it
+    vinci::validator::validate(&ast).map_err(|errors| {
+        for error in errors {
+            let text = error.location.as_ref().and_then(Location::source_line);
+            error.diagnostic(text).emit();
+        }
+
+        ExitCode::FAILURE
+    })?;
+
+    Ok(ast)
+}
+
+fn recompile(path: &str) -> Option<String> {
+    let source = Path::new(path).with_extension("");
+
+    Some(format!(
+        "compile it again with: loop --emit=arcb {}",
+        source.display()
+    ))
+}
+
+fn report(code: &str, message: String, note: Option<String>) -> ExitCode {
+    let mut diagnostic = Diagnostic::error(code, message);
+
+    if let Some(note) = note {
+        diagnostic = diagnostic.with_note(note);
+    }
+
+    diagnostic.emit();
+    ExitCode::FAILURE
+}
@@ -0,0 +1,190 @@
+//! Phase level timings and memory usage of running a program, reported with the "--benchmark" flag
+use miniserde::{json, Serialize};
+use std::time::Duration;
+
+/// How the report is printed, either as a table for people or as JSON for tools
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BenchmarkFormat {
+    Table,
+    Json,
+}
+
+/// How long every phase of a single run took, in the order they ran
+#[derive(Default)]
+pub struct Measurement {
+    pub phases: Vec<(&'static str, Duration)>,
+    // The most memory in bytes the backend used while executing, None when it can not be measured
+    pub peak_memory: Option<usize>,
+}
+
+impl Measurement {
+    pub fn phase(&mut self, phase: &'static str, duration: Duration) {
+        self.phases.push((phase, duration));
+    }
+
+    pub fn compilation(&mut self, timings: picasso::Timings) {
+        self.phase("lexing", timings.lexing);
+        self.phase("parsing", timings.parsing);
+        self.phase("compiling", timings.compiling);
+    }
+
+    fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+/// All runs of a program, the report shows the min, median and max of every phase
+#[derive(Default)]
+pub struct Benchmark {
+    runs: Vec<Measurement>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    runs: usize,
+    phases: Vec<PhaseReport>,
+    // Missing when the backend does not measure memory
+    peak_memory: Option<Summary>,
+}
+
+#[derive(Serialize)]
+struct PhaseReport {
+    phase: String,
+    // In milliseconds
+    min: f64,
+    median: f64,
+    max: f64,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    min: f64,
+    median: f64,
+    max: f64,
+}
+
+impl Benchmark {
+    pub fn add(&mut self, measurement: Measurement) {
+        self.runs.push(measurement);
+    }
+
+    pub fn report(&self, format: BenchmarkFormat) -> String {
+        let report = self.summarize();
+
+        match format {
+            BenchmarkFormat::Json => json::to_string(&report),
+            BenchmarkFormat::Table => table(&report),
+        }
+    }
+
+    fn summarize(&self) -> Report {
+        // Phases are taken from the first run, every run goes through the same phases
+        let mut names: Vec<&'static str> = match self.runs.first() {
+            Some(run) => run.phases.iter().map(|(phase, _)| *phase).collect(),
+            None => Vec::new(),
+        };
+        names.push("total");
+
+        let phases = names
+            .into_iter()
+            .map(|name| {
+                let milliseconds = self
+                    .runs
+                    .iter()
+                    .map(|run| {
+                        let duration = if name == "total" {
+                            run.total()
+                        } else {
+                            run.phases
+                                .iter()
+                                .find(|(phase, _)| *phase == name)
+                                .map_or(Duration::ZERO, |(_, duration)| *duration)
+                        };
+
+                        duration.as_secs_f64() * 1000.0
+                    })
+                    .collect();
+                let summary = summary(milliseconds);
+
+                PhaseReport {
+                    phase: name.to_string(),
+                    min: summary.min,
+                    median: summary.median,
+                    max: summary.max,
+                }
+            })
+            .collect();
+
+        Report {
+            runs: self.runs.len(),
+            phases,
+            peak_memory: self
+                .runs
+                .iter()
+                .map(|run| run.peak_memory.map(|memory| memory as f64))
+                .collect::<Option<Vec<f64>>>()
+                .map(summary),
+        }
+    }
+}
+
+fn summary(mut values: Vec<f64>) -> Summary {
+    if values.is_empty() {
+        return Summary {
+            min: 0.0,
+            median: 0.0,
+            max: 0.0,
+        };
+    }
+
+    // Timings and memory are never NaN
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let middle = values.len() / 2;
+    let median = if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    };
+
+    Summary {
+        min: values[0],
+        median,
+        max: values[values.len() - 1],
+    }
+}
+
+fn table(report: &Report) -> String {
+    let mut text = format!(
+        "Benchmark ({} run{})\n#---------\n",
+        report.runs,
+        if report.runs == 1 { "" } else { "s" }
+    );
+    text.push_str(&format!(
+        "{:<16}{:>14}{:>14}{:>14}\n",
+        "phase", "min", "median", "max"
+    ));
+
+    for phase in &report.phases {
+        text.push_str(&format!(
+            "{:<16}{:>14}{:>14}{:>14}\n",
+            phase.phase,
+            format!("{:.3} ms", phase.min),
+            format!("{:.3} ms", phase.median),
+            format!("{:.3} ms", phase.max)
+        ));
+    }
+
+    let [min, median, max] = match &report.peak_memory {
+        Some(memory) => [memory.min, memory.median, memory.max]
+            .map(|bytes| format!("{:.1} KiB", bytes / 1024.0)),
+        None => ["n/a", "n/a", "n/a"].map(str::to_string),
+    };
+    text.push_str(&format!(
+        "{:<16}{:>14}{:>14}{:>14}\n",
+        "peak memory", min, median, max
+    ));
+    text.push_str("---------#");
+
+    text
+}
@@ -0,0 +1,131 @@
+//! Programs that were compiled before are kept in "~/.loop/cache", so running a file again skips
+//! compiling it as long as it and the files it imports did not change. Disabled with
+//! "--no-cache" and emptied with "loop cache clean".
+use crate::lib::arcb::COMPILER_VERSION;
+use crate::lib::benchmark::Measurement;
+use crate::lib::config::CONFIG;
+use dirs::home_dir;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+use vinci::ast::AST;
+use vinci::binary::{self, Header};
+
+fn directory() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".loop").join("cache"))
+}
+
+/// Every file has a single entry which is replaced when it is compiled again. Paths in the AST
+/// depend on where it was run from, and optimizing creates another AST, so both are part of it.
+fn entry(path: &str) -> Option<PathBuf> {
+    let working_directory = env::current_dir().ok()?;
+    let name = binary::source_hash(&format!(
+        "{}\0{}\0{}",
+        working_directory.display(),
+        path,
+        CONFIG.enable_optimize
+    ));
+
+    Some(directory()?.join(format!("{:016x}", name)))
+}
+
+/// Hash of everything the compiled program depends on, nothing when an imported file can not be
+/// read anymore
+fn key(content: &str, imported_files: &[String]) -> Option<u64> {
+    let mut sources = format!("{}\0{}", *COMPILER_VERSION, content);
+
+    for file in imported_files {
+        let imported = fs::read_to_string(file).ok()?;
+        sources.push_str(&format!("\0{}\0{}", file, imported));
+    }
+
+    Some(binary::source_hash(&sources))
+}
+
+/// The AST of the file when it is in the cache and still valid
+pub fn load(path: &str, content: &str, measurement: &mut Measurement) -> Option<AST> {
+    let start = Instant::now();
+    let entry = entry(path)?;
+
+    let imported_files: Vec<String> = fs::read_to_string(entry.with_extension("imports"))
+        .ok()?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let bytes = fs::read(entry.with_extension("arcb")).ok()?;
+
+    let header = binary::decode_header(&bytes).ok()?;
+    if header.compiler_version != *COMPILER_VERSION
+        || Some(header.source_hash) != key(content, &imported_files)
+    {
+        return None;
+    }
+
+    let (_, ast) = binary::decode(&bytes).ok()?;
+    measurement.phase("cache loading", start.elapsed());
+
+    Some(ast)
+}
+
+/// Adds the compiled file to the cache, a cache that can not be written only means it is
+/// compiled again next time
+pub fn store(path: &str, content: &str, imported_files: &[String], ast: &AST) {
+    // Imports are found relative to the working directory, they are stored as absolute paths
+    let imported_files: Option<Vec<String>> = imported_files
+        .iter()
+        .map(|file| {
+            fs::canonicalize(file)
+                .ok()
+                .map(|file| file.display().to_string())
+        })
+        .collect();
+
+    let (entry, imported_files) = match (entry(path), imported_files) {
+        (Some(entry), Some(imported_files)) => (entry, imported_files),
+        _ => return,
+    };
+
+    let key = match key(content, &imported_files) {
+        Some(key) => key,
+        None => return,
+    };
+
+    if let Some(directory) = directory() {
+        if fs::create_dir_all(directory).is_err() {
+            return;
+        }
+    }
+
+    let header = Header::new(&COMPILER_VERSION, key);
+    let _ = fs::write(entry.with_extension("imports"), imported_files.join("\n"));
+    let _ = fs::write(entry.with_extension("arcb"), binary::encode(&header, ast));
+}
+
+/// Runs "loop cache <command>", only "clean" exists which removes every entry
+pub fn command(command: &str) -> Result<(), ExitCode> {
+    if command != "clean" {
+        println!(
+            "Unknown cache command \"{}\", expected: loop cache clean",
+            command
+        );
+        return Err(ExitCode::FAILURE);
+    }
+
+    let directory = match directory() {
+        Some(directory) => directory,
+        None => return Ok(()),
+    };
+
+    if directory.exists() {
+        if let Err(err) = fs::remove_dir_all(&directory) {
+            println!("{}", err);
+            return Err(ExitCode::FAILURE);
+        }
+    }
+
+    println!("Removed the cache in {}", directory.display());
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 //! Persistent configuration library that is for Loop itself
+use crate::lib::benchmark::BenchmarkFormat;
 use crate::lib::config::LoadType::{FirstRun, Normal};
 use crate::lib::exception::Exception;
 use crate::lib::util::get_flags;
@@ -8,6 +9,7 @@ use once_cell::sync::Lazy;
 use std::fs::{create_dir_all, read_to_string, File};
 use std::io::Write;
 use std::path::Path;
+use vinci::diagnostic::{ColorChoice, DiagnosticFormat};
 
 pub static CONFIG: Lazy<Config> = Lazy::new(|| {
     let cfg = match load_config() {
@@ -25,55 +27,133 @@ pub static CONFIG: Lazy<Config> = Lazy::new(|| {
         lua_output: false,
         arc_output: false,
         enable_benchmark: false,
+        benchmark_runs: 1,
+        benchmark_format: BenchmarkFormat::Table,
         enable_optimize: false,
+        enable_cache: true,
+        max_errors: picasso::DEFAULT_MAX_ERRORS,
+        error_format: DiagnosticFormat::Human,
+        color: ColorChoice::Auto,
+        backend: DEFAULT_BACKEND,
     };
 
     // The flags go over the config file.
     // If config has: "jit_enabled = true" and flag has: "jit_enabled = false",
     // Than the config will disable JIT
 
-    if flags.flags.debug_mode.is_some() {
-        config.debug_mode = flags.flags.debug_mode.unwrap();
-    } else if cfg.debug_mode.is_some() {
-        config.debug_mode = cfg.debug_mode.unwrap();
+    if let Some(debug_mode) = flags.flags.debug_mode {
+        config.debug_mode = debug_mode;
+    } else if let Some(debug_mode) = cfg.debug_mode {
+        config.debug_mode = debug_mode;
     }
 
-    if flags.flags.lua_output.is_some() {
-        config.lua_output = flags.flags.lua_output.unwrap();
-    } else if cfg.lua_output.is_some() {
-        config.lua_output = cfg.lua_output.unwrap();
+    if let Some(lua_output) = flags.flags.lua_output {
+        config.lua_output = lua_output;
+    } else if let Some(lua_output) = cfg.lua_output {
+        config.lua_output = lua_output;
     }
 
-    if flags.flags.arc_output.is_some() {
-        config.arc_output = flags.flags.arc_output.unwrap();
-    } else if cfg.arc_output.is_some() {
-        config.arc_output = cfg.arc_output.unwrap();
+    if let Some(arc_output) = flags.flags.arc_output {
+        config.arc_output = arc_output;
+    } else if let Some(arc_output) = cfg.arc_output {
+        config.arc_output = arc_output;
     }
 
-    if flags.flags.enable_benchmark.is_some() {
-        config.enable_benchmark = flags.flags.enable_benchmark.unwrap();
-    } else if cfg.enable_benchmark.is_some() {
-        config.enable_benchmark = cfg.enable_benchmark.unwrap();
+    if let Some(enable_benchmark) = flags.flags.enable_benchmark {
+        config.enable_benchmark = enable_benchmark;
+    } else if let Some(enable_benchmark) = cfg.enable_benchmark {
+        config.enable_benchmark = enable_benchmark;
     }
 
-    if flags.flags.enable_optimize.is_some() {
-        config.enable_optimize = flags.flags.enable_optimize.unwrap();
-    } else if cfg.enable_optimize.is_some() {
-        config.enable_optimize = cfg.enable_optimize.unwrap();
+    if let Some(benchmark_runs) = flags.flags.benchmark_runs {
+        config.benchmark_runs = benchmark_runs;
+    } else if let Some(benchmark_runs) = cfg.benchmark_runs {
+        config.benchmark_runs = benchmark_runs.max(1);
+    }
+
+    match flags
+        .flags
+        .benchmark_format
+        .or(cfg.benchmark_format)
+        .as_deref()
+    {
+        Some("json") => config.benchmark_format = BenchmarkFormat::Json,
+        _ => config.benchmark_format = BenchmarkFormat::Table,
+    }
+
+    if let Some(enable_optimize) = flags.flags.enable_optimize {
+        config.enable_optimize = enable_optimize;
+    } else if let Some(enable_optimize) = cfg.enable_optimize {
+        config.enable_optimize = enable_optimize;
+    }
+
+    if let Some(enable_cache) = flags.flags.enable_cache {
+        config.enable_cache = enable_cache;
+    } else if let Some(enable_cache) = cfg.enable_cache {
+        config.enable_cache = enable_cache;
+    }
+
+    if let Some(max_errors) = flags.flags.max_errors {
+        config.max_errors = max_errors;
+    } else if let Some(max_errors) = cfg.max_errors {
+        config.max_errors = max_errors;
+    }
+
+    match flags.flags.error_format.or(cfg.error_format).as_deref() {
+        Some("json") => config.error_format = DiagnosticFormat::Json,
+        _ => config.error_format = DiagnosticFormat::Human,
+    }
+
+    match flags.flags.color.or(cfg.color).as_deref() {
+        Some("always") => config.color = ColorChoice::Always,
+        Some("never") => config.color = ColorChoice::Never,
+        _ => config.color = ColorChoice::Auto,
+    }
+
+    match flags.flags.backend.or(cfg.backend).as_deref() {
+        Some("interp") => config.backend = Backend::Interpreter,
+        _ => config.backend = DEFAULT_BACKEND,
     }
 
     config
 });
 
 pub struct Config {
+    // Read from the config file, but there is no telemetry to enable yet
+    #[allow(dead_code)]
     pub enable_telemetry: bool,
     pub debug_mode: bool,
     pub lua_output: bool,
     pub arc_output: bool,
     pub enable_benchmark: bool,
+    // How often a file is run while benchmarking, the report shows the min, median and max
+    pub benchmark_runs: usize,
+    pub benchmark_format: BenchmarkFormat,
     pub enable_optimize: bool,
+    // Whether compiled programs are kept in "~/.loop/cache" to be run again without compiling
+    pub enable_cache: bool,
+    // Amount of errors after which compiling stops
+    pub max_errors: usize,
+    pub error_format: DiagnosticFormat,
+    pub color: ColorChoice,
+    pub backend: Backend,
+}
+
+/// What runs the compiled program
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    // Generates Lua, which is run by LuaJIT
+    #[cfg(feature = "lua")]
+    Lua,
+    // Walks the AST in Rust
+    Interpreter,
 }
 
+#[cfg(feature = "lua")]
+const DEFAULT_BACKEND: Backend = Backend::Lua;
+#[cfg(not(feature = "lua"))]
+const DEFAULT_BACKEND: Backend = Backend::Interpreter;
+
 #[derive(Deserialize, Serialize)]
 pub struct ConfigInternal {
     pub enable_telemetry: Option<bool>,
@@ -81,7 +161,14 @@ pub struct ConfigInternal {
     pub lua_output: Option<bool>,
     pub arc_output: Option<bool>,
     pub enable_benchmark: Option<bool>,
+    pub benchmark_runs: Option<usize>,
+    pub benchmark_format: Option<String>,
     pub enable_optimize: Option<bool>,
+    pub enable_cache: Option<bool>,
+    pub max_errors: Option<usize>,
+    pub error_format: Option<String>,
+    pub color: Option<String>,
+    pub backend: Option<String>,
 }
 
 impl Default for ConfigInternal {
@@ -92,7 +179,14 @@ impl Default for ConfigInternal {
             lua_output: None,
             arc_output: None,
             enable_benchmark: Some(false),
+            benchmark_runs: None,
+            benchmark_format: None,
             enable_optimize: Some(false),
+            enable_cache: None,
+            max_errors: None,
+            error_format: None,
+            color: None,
+            backend: None,
         }
     }
 }
@@ -0,0 +1,11 @@
+use crate::lib::exception::flag;
+use crate::lib::flags::FlagTypes;
+
+pub fn backend_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    // Without the "lua" feature there is no Lua backend to choose
+    if (parameter == "lua" && cfg!(feature = "lua")) || parameter == "interp" {
+        return Ok(FlagTypes::Backend(Some(parameter.to_string())));
+    }
+    flag::throw_exception_unexpected_value(format!("--backend = {}", parameter));
+    Err(())
+}
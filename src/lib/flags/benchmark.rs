@@ -15,3 +15,21 @@ pub fn benchmark_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
 pub fn benchmark_flag() -> Result<FlagTypes, ()> {
     Ok(FlagTypes::Benchmark(Some(true)))
 }
+
+pub fn benchmark_runs_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    if let Ok(runs) = parameter.parse::<usize>() {
+        if runs > 0 {
+            return Ok(FlagTypes::BenchmarkRuns(Some(runs)));
+        }
+    }
+    flag::throw_exception_unexpected_value(format!("--benchmark-runs = {}", parameter));
+    Err(())
+}
+
+pub fn benchmark_format_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    if parameter == "table" || parameter == "json" {
+        return Ok(FlagTypes::BenchmarkFormat(Some(parameter.to_string())));
+    }
+    flag::throw_exception_unexpected_value(format!("--benchmark-format = {}", parameter));
+    Err(())
+}
@@ -0,0 +1,5 @@
+use crate::lib::flags::FlagTypes;
+
+pub fn no_cache_flag() -> Result<FlagTypes, ()> {
+    Ok(FlagTypes::Cache(Some(false)))
+}
@@ -0,0 +1,10 @@
+use crate::lib::exception::flag;
+use crate::lib::flags::FlagTypes;
+
+pub fn color_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    if parameter == "auto" || parameter == "always" || parameter == "never" {
+        return Ok(FlagTypes::Color(Some(parameter.to_string())));
+    }
+    flag::throw_exception_unexpected_value(format!("--color = {}", parameter));
+    Err(())
+}
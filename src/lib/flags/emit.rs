@@ -0,0 +1,10 @@
+use crate::lib::exception::flag;
+use crate::lib::flags::FlagTypes;
+
+pub fn emit_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    if parameter == "arcb" {
+        return Ok(FlagTypes::Emit(parameter.to_string()));
+    }
+    flag::throw_exception_unexpected_value(format!("--emit = {}", parameter));
+    Err(())
+}
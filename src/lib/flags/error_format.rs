@@ -0,0 +1,10 @@
+use crate::lib::exception::flag;
+use crate::lib::flags::FlagTypes;
+
+pub fn error_format_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    if parameter == "human" || parameter == "json" {
+        return Ok(FlagTypes::ErrorFormat(Some(parameter.to_string())));
+    }
+    flag::throw_exception_unexpected_value(format!("--error-format = {}", parameter));
+    Err(())
+}
@@ -6,24 +6,48 @@
 ///
 /// Usage:
 ///     loop [FLAGS] [FILEPATH]
+///     loop lsp          ->  Starts the language server, it communicates over stdin and stdout
+///     loop cache clean  ->  Removes every compiled program from ~/.loop/cache
 ///
 /// Flags:
 ///     --debug     | -d  ->  Enables debug mode in Loop
-///     --benchmark | -b  ->  To time a programs execution
+///     --benchmark | -b  ->  Times every phase of running a program and its peak memory
+///     --benchmark-runs=<n>  ->  Runs the program <n> times and reports the min, median and max
+///     --benchmark-format=<table|json>  ->  Prints the benchmark as a table or as JSON
 ///     --jit       | -j  ->  [UNFINISHED] Enables the Just-In-Time compiler
-///     --optimize  | -o  ->  [UNFINISHED] Enables compiled optimisations
+///     --optimize  | -o  ->  Enables compiled optimisations
+///     --max-errors=<n>  ->  Stops compiling after <n> errors (default: 20)
+///     --error-format=<human|json>  ->  Prints errors for people or as JSON lines for tools
+///     --color=<auto|always|never>  ->  Whether errors are colored, "auto" respects NO_COLOR
+///     --backend=<lua|interp>  ->  Runs programs with LuaJIT (default) or with the interpreter (default without the lua feature)
 ///     --help            ->  Prints this helping text
 /// </pre>
 pub fn generate_help_text() -> Result<String, ()> {
     let mut text = "Loop Programming Language\n\n".to_string();
     text.push_str("Usage:\n");
-    text.push_str("    loop [FLAGS] [FILEPATH]\n\n");
+    text.push_str("    loop [FLAGS] [FILEPATH]\n");
+    text.push_str("    loop lsp          ->  Starts the language server, it communicates over stdin and stdout\n");
+    text.push_str(
+        "    loop cache clean  ->  Removes every compiled program from ~/.loop/cache\n\n",
+    );
     text.push_str("Flags:\n");
     text.push_str("    --debug     | -d  ->  Enables debug mode in Loop\n");
-    text.push_str("    --benchmark | -b  ->  To time a programs execution\n");
-    text.push_str("    --optimize  | -o  ->  [UNFINISHED] Enables compiled optimisations\n");
+    text.push_str(
+        "    --benchmark | -b  ->  Times every phase of running a program and its peak memory\n",
+    );
+    text.push_str("    --benchmark-runs=<n>  ->  Runs the program <n> times and reports the min, median and max\n");
+    text.push_str(
+        "    --benchmark-format=<table|json>  ->  Prints the benchmark as a table or as JSON\n",
+    );
+    text.push_str("    --optimize  | -o  ->  Enables compiled optimisations\n");
     text.push_str("    --lua             ->  Saves the generated lua code to <your_path>.lua\n");
-    text.push_str("    --arc             ->  Saves the generated lua code to <your_path>.arc\n");
+    text.push_str("    --arc             ->  Saves the generated Arc, optimized when using -o, to <your_path>.arc\n");
+    text.push_str("    --no-cache        ->  Always compiles, instead of running the program from ~/.loop/cache\n");
+    text.push_str("    --emit=arcb       ->  Writes the compiled program to <your_path>.arcb instead of running it\n");
+    text.push_str("    --max-errors=<n>  ->  Stops compiling after <n> errors (default: 20)\n");
+    text.push_str("    --error-format=<human|json>  ->  Prints errors for people or as JSON lines for tools\n");
+    text.push_str("    --color=<auto|always|never>  ->  Whether errors are colored, \"auto\" respects NO_COLOR\n");
+    text.push_str("    --backend=<lua|interp>  ->  Runs programs with LuaJIT (default) or with the interpreter (default without the lua feature)\n");
     text.push_str("    --help            ->  Prints this helping text\n\n");
     text.push_str("For more info go to: https://looplang.org\n");
     Ok(text)
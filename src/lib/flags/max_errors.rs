@@ -0,0 +1,12 @@
+use crate::lib::exception::flag;
+use crate::lib::flags::FlagTypes;
+
+pub fn max_errors_flag_with_param(parameter: &str) -> Result<FlagTypes, ()> {
+    if let Ok(max_errors) = parameter.parse::<usize>() {
+        if max_errors > 0 {
+            return Ok(FlagTypes::MaxErrors(Some(max_errors)));
+        }
+    }
+    flag::throw_exception_unexpected_value(format!("--max-errors = {}", parameter));
+    Err(())
+}
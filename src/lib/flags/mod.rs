@@ -5,10 +5,16 @@ use std::ffi::OsStr;
 use std::path::Path;
 use std::process;
 mod arc;
+mod backend;
 mod benchmark;
+mod cache;
+mod color;
 mod debug;
+mod emit;
+mod error_format;
 mod help;
 mod lua;
+mod max_errors;
 mod optimize;
 
 /// Value: `true` -> is enabled<br>
@@ -23,7 +29,16 @@ pub enum FlagTypes {
     Lua(Option<bool>),
     Arc(Option<bool>),
     Benchmark(Option<bool>),
-    Optimize(Option<bool>), // There are no optimizations yet, this is for the near future
+    BenchmarkRuns(Option<usize>), // How often the program is run while benchmarking
+    BenchmarkFormat(Option<String>), // Either "table" or "json"
+    Cache(Option<bool>),          // Whether compiled programs are kept in the cache
+    Optimize(Option<bool>),       // Folds constants, inlines small functions and removes dead code
+    MaxErrors(Option<usize>),
+    ErrorFormat(Option<String>), // Either "human" or "json"
+    Color(Option<String>),       // Either "auto", "always" or "never"
+    Lsp,                         // Starts the language server instead of running a file
+    Emit(String),                // Writes the compiled program instead of running it, only "arcb"
+    Backend(Option<String>),     // Either "lua" or "interp"
 }
 
 pub fn build_flags() -> Flags {
@@ -34,30 +49,60 @@ pub fn build_flags() -> Flags {
             lua_output: None,
             arc_output: None,
             enable_benchmark: None,
+            benchmark_runs: None,
+            benchmark_format: None,
             enable_optimize: None,
+            enable_cache: None,
+            max_errors: None,
+            error_format: None,
+            color: None,
+            backend: None,
         },
         file: None,
+        lsp: false,
+        emit_arcb: false,
+        cache_command: None,
     }
 }
 
 pub struct Flags {
     pub flags: ConfigInternal,
     pub file: Option<String>,
+    pub lsp: bool,
+    // Whether the file is compiled to "<file>.arcb" instead of run
+    pub emit_arcb: bool,
+    // The command of "loop cache <command>"
+    pub cache_command: Option<String>,
 }
 
 impl Flags {
     pub fn parse_flags(&mut self, args: Vec<String>) -> i32 {
+        // "loop cache clean" is a command of its own, not a flag
+        if args.first().map(String::as_str) == Some("cache") {
+            self.cache_command = Some(args.get(1).cloned().unwrap_or_default());
+            return args.len() as i32;
+        }
+
         let mut i: i32 = 0;
         for arg in args.clone() {
             let flag = Flags::get_flag(self, arg.as_str(), arg.eq(args.last().unwrap()));
             if let Ok(e) = flag {
                 match e {
                     FlagTypes::Optimize(b) => self.flags.enable_optimize = b,
+                    FlagTypes::Cache(b) => self.flags.enable_cache = b,
                     FlagTypes::Debug(b) => self.flags.debug_mode = b,
                     FlagTypes::Benchmark(b) => self.flags.enable_benchmark = b,
+                    FlagTypes::BenchmarkRuns(n) => self.flags.benchmark_runs = n,
+                    FlagTypes::BenchmarkFormat(f) => self.flags.benchmark_format = f,
                     FlagTypes::Lua(b) => self.flags.lua_output = b,
                     FlagTypes::Arc(b) => self.flags.arc_output = b,
+                    FlagTypes::MaxErrors(n) => self.flags.max_errors = n,
+                    FlagTypes::ErrorFormat(f) => self.flags.error_format = f,
+                    FlagTypes::Color(c) => self.flags.color = c,
+                    FlagTypes::Backend(b) => self.flags.backend = b,
                     FlagTypes::File(f) => self.file = Some(f),
+                    FlagTypes::Lsp => self.lsp = true,
+                    FlagTypes::Emit(_) => self.emit_arcb = true,
                     FlagTypes::Help(h) => {
                         // Prints help message and exists program
                         println!("{}", h);
@@ -82,7 +127,16 @@ impl Flags {
             return match flag_arguments[0] {
                 "--debug" | "-d" => debug::debug_flag_with_param(flag_arguments[1]),
                 "--benchmark" | "-b" => benchmark::benchmark_flag_with_param(flag_arguments[1]),
+                "--benchmark-runs" => benchmark::benchmark_runs_flag_with_param(flag_arguments[1]),
+                "--benchmark-format" => {
+                    benchmark::benchmark_format_flag_with_param(flag_arguments[1])
+                }
                 "--optimize" | "-o" => optimize::optimize_flag_with_param(flag_arguments[1]),
+                "--max-errors" => max_errors::max_errors_flag_with_param(flag_arguments[1]),
+                "--error-format" => error_format::error_format_flag_with_param(flag_arguments[1]),
+                "--color" => color::color_flag_with_param(flag_arguments[1]),
+                "--emit" => emit::emit_flag_with_param(flag_arguments[1]),
+                "--backend" => backend::backend_flag_with_param(flag_arguments[1]),
                 _ => self.handle_unknown_flag(string.to_string(), is_last),
             };
         }
@@ -92,6 +146,8 @@ impl Flags {
             "--optimize" | "-o" => optimize::optimize_flag(),
             "--lua" => lua::lua_flag(),
             "--arc" => arc::arc_flag(),
+            "--no-cache" => cache::no_cache_flag(),
+            "lsp" => Ok(FlagTypes::Lsp),
             "--help" => {
                 if let Ok(e) = help::generate_help_text() {
                     return Ok(FlagTypes::Help(e));
@@ -111,7 +167,9 @@ impl Flags {
         let ext = Path::new(string.as_str())
             .extension()
             .and_then(OsStr::to_str);
-        if ext.is_some() && (ext.unwrap() == "loop" || ext.unwrap() == "lp") {
+        if ext.is_some()
+            && (ext.unwrap() == "loop" || ext.unwrap() == "lp" || ext.unwrap() == "arcb")
+        {
             return Ok(FlagTypes::File(string.to_string()));
         }
         // Program quits, will never reach the Err return
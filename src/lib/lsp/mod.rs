@@ -0,0 +1,545 @@
+//! Language server for Loop, it talks the Language Server Protocol over stdin and stdout
+//!
+//! Documents are analysed with [picasso::analysis] every time they change, which provides the
+//! diagnostics, hover types, definitions and completions.
+mod protocol;
+mod test;
+
+use crate::lib::lsp::protocol::{
+    array, get, get_i64, get_str, number, object, read_message, string, write_message,
+};
+use miniserde::json::Value;
+use picasso::analysis::{analyze, Analysis, SymbolInfo};
+use picasso::span::Span;
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use vinci::diagnostic::{Diagnostic, Severity};
+
+// Error codes defined by JSON-RPC
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+// Kinds of completion items defined by the protocol
+const COMPLETION_METHOD: i64 = 2;
+const COMPLETION_FIELD: i64 = 5;
+
+struct Document {
+    text: String,
+    diagnostics: Vec<Diagnostic>,
+    // Latest analysis that was able to parse the document, while typing the document often does
+    // not parse and completions still need the symbols
+    analysis: Option<Analysis>,
+}
+
+#[derive(Default)]
+struct Server {
+    // Path, Document
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+pub fn start() -> Result<(), ExitCode> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+
+    let mut server = Server::default();
+
+    while let Some(message) = read_message(&mut input) {
+        let method = get_str(&message, &["method"]).unwrap_or("").to_string();
+        let params = get(&message, &["params"]).cloned().unwrap_or(Value::Null);
+
+        if method == "exit" {
+            return if server.shutdown {
+                Ok(())
+            } else {
+                Err(ExitCode::FAILURE)
+            };
+        }
+
+        // Without an id the message is a notification, which does not get a response
+        let id = match get(&message, &["id"]) {
+            Some(id) => id.clone(),
+            None => {
+                server.notification(&mut output, &method, &params);
+                continue;
+            }
+        };
+
+        let response = match server.request(&method, &params) {
+            Ok(result) => object(vec![
+                ("jsonrpc", string("2.0")),
+                ("id", id),
+                ("result", result),
+            ]),
+            Err((code, message)) => object(vec![
+                ("jsonrpc", string("2.0")),
+                ("id", id),
+                (
+                    "error",
+                    object(vec![("code", number(code)), ("message", string(&message))]),
+                ),
+            ]),
+        };
+
+        write_message(&mut output, &response);
+    }
+
+    Ok(())
+}
+
+impl Server {
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(object(vec![
+                (
+                    "capabilities",
+                    object(vec![
+                        // The full text is sent on every change
+                        ("textDocumentSync", number(1)),
+                        ("hoverProvider", Value::Bool(true)),
+                        ("definitionProvider", Value::Bool(true)),
+                        (
+                            "completionProvider",
+                            object(vec![("triggerCharacters", array(vec![string(".")]))]),
+                        ),
+                    ]),
+                ),
+                (
+                    "serverInfo",
+                    object(vec![
+                        ("name", string("loop")),
+                        ("version", string(env!("CARGO_PKG_VERSION"))),
+                    ]),
+                ),
+            ])),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => {
+                let (path, line, colon) = self.position(params)?;
+
+                Ok(match self.symbol_at(&path, line, colon) {
+                    Some(symbol) => object(vec![(
+                        "contents",
+                        object(vec![
+                            ("kind", string("markdown")),
+                            (
+                                "value",
+                                string(&format!(
+                                    "```loop\n{}: {}\n```",
+                                    symbol.name, symbol.type_name
+                                )),
+                            ),
+                        ]),
+                    )]),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/definition" => {
+                let (path, line, colon) = self.position(params)?;
+
+                let symbol = match self.symbol_at(&path, line, colon) {
+                    Some(symbol) => symbol,
+                    None => return Ok(Value::Null),
+                };
+
+                // Modules are defined by the file that is imported
+                if let Some(module) = &symbol.module {
+                    return Ok(location(module, (0, 0), (0, 0)));
+                }
+
+                if !symbol.definition.is_known() {
+                    return Ok(Value::Null);
+                }
+
+                Ok(self.span_location(&symbol.definition))
+            }
+            "textDocument/completion" => {
+                let (path, line, colon) = self.position(params)?;
+
+                Ok(array(self.completions(&path, line, colon)))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method: {}", method))),
+        }
+    }
+
+    fn notification(&mut self, output: &mut impl Write, method: &str, params: &Value) {
+        let uri = match get_str(params, &["textDocument", "uri"]) {
+            Some(uri) => uri.to_string(),
+            None => return,
+        };
+        let path = path_from_uri(&uri);
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = get_str(params, &["textDocument", "text"]).unwrap_or("");
+                self.update(&path, text.to_string());
+            }
+            "textDocument/didChange" => {
+                // With full synchronisation the last change contains the whole document
+                let text = match get(params, &["contentChanges"]) {
+                    Some(Value::Array(changes)) => changes
+                        .last()
+                        .and_then(|change| get_str(change, &["text"]))
+                        .map(str::to_string),
+                    _ => None,
+                };
+
+                if let Some(text) = text {
+                    self.update(&path, text);
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&path);
+            }
+            _ => return,
+        }
+
+        let diagnostics = match self.documents.get(&path) {
+            Some(document) => document
+                .diagnostics
+                .iter()
+                .filter_map(|diagnostic| self.diagnostic(&path, diagnostic))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        write_message(
+            output,
+            &object(vec![
+                ("jsonrpc", string("2.0")),
+                ("method", string("textDocument/publishDiagnostics")),
+                (
+                    "params",
+                    object(vec![
+                        ("uri", string(&uri)),
+                        ("diagnostics", array(diagnostics)),
+                    ]),
+                ),
+            ]),
+        );
+    }
+
+    /// Analyses the new text of a document
+    fn update(&mut self, path: &str, text: String) {
+        let (analysis, diagnostics) = match analyze_without_panicking(&text, path) {
+            Ok(analysis) => {
+                let diagnostics = analysis.diagnostics.clone();
+                (Some(analysis), diagnostics)
+            }
+            Err(message) => (
+                None,
+                vec![Diagnostic::error(
+                    "compiler::internal",
+                    format!(
+                        "the compiler crashed while analysing this file: {}",
+                        message
+                    ),
+                )
+                .with_note(
+                    "this is a bug in Loop, other errors in this file can not be shown".to_string(),
+                )],
+            ),
+        };
+
+        let previous = self.documents.remove(path).and_then(|doc| doc.analysis);
+
+        let analysis = match analysis {
+            Some(analysis) if !analysis.references.is_empty() || diagnostics.is_empty() => {
+                Some(analysis)
+            }
+            _ => previous,
+        };
+
+        self.documents.insert(
+            path.to_string(),
+            Document {
+                text,
+                diagnostics,
+                analysis,
+            },
+        );
+    }
+
+    /// Reads the document and position of a request, the position is converted to a line and
+    /// character that start at 1 like the spans of Loop
+    fn position(&self, params: &Value) -> Result<(String, i32, i32), (i64, String)> {
+        let uri = get_str(params, &["textDocument", "uri"]);
+        let line = get_i64(params, &["position", "line"]);
+        let character = get_i64(params, &["position", "character"]);
+
+        match (uri, line, character) {
+            (Some(uri), Some(line), Some(character)) => {
+                let path = path_from_uri(uri);
+                let text = self.line_text(&path, line as i32 + 1);
+                let colon = char_index(&text, character as usize) as i32 + 1;
+
+                Ok((path, line as i32 + 1, colon))
+            }
+            _ => Err((
+                INVALID_PARAMS,
+                "expected a document and position".to_string(),
+            )),
+        }
+    }
+
+    fn symbol_at(&self, path: &str, line: i32, colon: i32) -> Option<&SymbolInfo> {
+        let analysis = self.documents.get(path)?.analysis.as_ref()?;
+
+        analysis.symbol_at(path, line, colon)
+    }
+
+    /// Completes the fields and methods of the symbol in front of the dot before the cursor
+    fn completions(&self, path: &str, line: i32, colon: i32) -> Vec<Value> {
+        let analysis = match self.documents.get(path).and_then(|d| d.analysis.as_ref()) {
+            Some(analysis) => analysis,
+            None => return Vec::new(),
+        };
+
+        let text: Vec<char> = self.line_text(path, line).chars().collect();
+        let before: String = text[..(colon as usize - 1).min(text.len())]
+            .iter()
+            .collect();
+
+        // Skip the part of the member that is already typed, and the dot itself
+        let before = before.trim_end_matches(is_identifier_char);
+        let before = match before.strip_suffix('.') {
+            Some(before) => before,
+            None => return Vec::new(),
+        };
+
+        let name = &before[before.trim_end_matches(is_identifier_char).len()..];
+        let name_colon = before.chars().count() as i32;
+
+        let symbol = match analysis.symbol_named_before(path, name, line, name_colon) {
+            Some(symbol) => symbol,
+            None => return Vec::new(),
+        };
+
+        symbol
+            .members
+            .iter()
+            .map(|member| {
+                let kind = if member.type_name.starts_with("fn(") {
+                    COMPLETION_METHOD
+                } else {
+                    COMPLETION_FIELD
+                };
+
+                object(vec![
+                    ("label", string(&member.name)),
+                    ("detail", string(&member.type_name)),
+                    ("kind", number(kind)),
+                ])
+            })
+            .collect()
+    }
+
+    /// Converts a diagnostic to the protocol, only the parts that are inside of `path` are used
+    fn diagnostic(&self, path: &str, diagnostic: &Diagnostic) -> Option<Value> {
+        let span = diagnostic.spans.iter().find(|span| span.file == path);
+
+        let range = match span {
+            Some(span) => self.range(
+                path,
+                (span.line_start, span.column_start),
+                (span.line_end, span.column_end),
+            ),
+            // Errors that do not point at code are shown at the start of the file
+            None if diagnostic.spans.is_empty() => range((0, 0), (0, 0)),
+            None => return None,
+        };
+
+        let severity = match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+            Severity::Note => 3,
+        };
+
+        let mut message = diagnostic.message.clone();
+        for note in &diagnostic.notes {
+            message.push_str(&format!("\nnote: {}", note));
+        }
+
+        Some(object(vec![
+            ("range", range),
+            ("severity", number(severity)),
+            ("code", string(&diagnostic.code)),
+            ("source", string("loop")),
+            ("message", string(&message)),
+        ]))
+    }
+
+    fn span_location(&self, span: &Span) -> Value {
+        let range = self.range(
+            &span.file,
+            (span.start.line, span.start.colon),
+            (span.end.line, span.end.colon),
+        );
+
+        object(vec![
+            ("uri", string(&uri_from_path(&span.file))),
+            ("range", range),
+        ])
+    }
+
+    /// Converts the first and last character of a piece of code, both starting at 1, to a range
+    fn range(&self, path: &str, start: (i32, i32), end: (i32, i32)) -> Value {
+        let position = |(line, colon): (i32, i32)| {
+            let text = self.line_text(path, line);
+
+            (
+                (line - 1).max(0),
+                utf16_index(&text, (colon - 1).max(0) as usize),
+            )
+        };
+
+        // The end of a range is the character after the code
+        range(position(start), position((end.0, end.1 + 1)))
+    }
+
+    /// Returns a line of a file, open documents are used before files on disk
+    fn line_text(&self, path: &str, line: i32) -> String {
+        let text = match self.documents.get(path) {
+            Some(document) => document.text.clone(),
+            None => std::fs::read_to_string(path).unwrap_or_default(),
+        };
+
+        text.lines()
+            .nth((line - 1).max(0) as usize)
+            .unwrap_or("")
+            .to_string()
+    }
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn range(start: (i32, usize), end: (i32, usize)) -> Value {
+    let position = |(line, character): (i32, usize)| {
+        object(vec![
+            ("line", number(line as i64)),
+            ("character", number(character as i64)),
+        ])
+    };
+
+    object(vec![("start", position(start)), ("end", position(end))])
+}
+
+fn location(path: &str, start: (i32, usize), end: (i32, usize)) -> Value {
+    object(vec![
+        ("uri", string(&uri_from_path(path))),
+        ("range", range(start, end)),
+    ])
+}
+
+/// The protocol counts characters in UTF-16 code units, Loop counts them as Unicode characters
+fn utf16_index(text: &str, char_index: usize) -> usize {
+    text.chars().take(char_index).map(char::len_utf16).sum()
+}
+
+fn char_index(text: &str, utf16_index: usize) -> usize {
+    let mut units = 0;
+
+    for (index, ch) in text.chars().enumerate() {
+        if units >= utf16_index {
+            return index;
+        }
+        units += ch.len_utf16();
+    }
+
+    text.chars().count()
+}
+
+/// Analyses a document, a panic of the compiler is returned as an error instead of stopping the
+/// server. The message and backtrace of the panic are not printed, stderr is shown to the user by
+/// some editors.
+fn analyze_without_panicking(text: &str, path: &str) -> Result<Analysis, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let analysis = catch_unwind(AssertUnwindSafe(|| analyze(text, path)));
+
+    panic::set_hook(hook);
+
+    analysis.map_err(|payload| panic_message(payload.as_ref()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+
+    match payload.downcast_ref::<String>() {
+        Some(message) => message.clone(),
+        None => "unknown error".to_string(),
+    }
+}
+
+fn path_from_uri(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+
+    let mut bytes = Vec::new();
+    let mut chars = path.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex: Vec<u8> = chars.by_ref().take(2).collect();
+            let decoded = std::str::from_utf8(&hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+            match decoded {
+                Some(decoded) => bytes.push(decoded),
+                None => {
+                    bytes.push(byte);
+                    bytes.extend(hex);
+                }
+            }
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    let path = String::from_utf8_lossy(&bytes).to_string();
+
+    // "/C:/code/main.loop" on Windows
+    match path.chars().nth(2) {
+        Some(':') if path.starts_with('/') => path[1..].to_string(),
+        _ => path,
+    }
+}
+
+fn uri_from_path(path: &str) -> String {
+    let absolute = if Path::new(path).is_absolute() {
+        path.to_string()
+    } else {
+        std::fs::canonicalize(Path::new(path))
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string())
+    }
+    .replace('\\', "/");
+
+    let mut uri = String::from("file://");
+    if !absolute.starts_with('/') {
+        uri.push('/');
+    }
+
+    for byte in absolute.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    uri
+}
@@ -0,0 +1,105 @@
+//! Reading and writing Language Server Protocol messages, each message is a JSON body preceded by
+//! a "Content-Length" header
+use miniserde::json::{self, Array, Number, Object, Value};
+use std::io::{BufRead, Read, Write};
+
+/// Reads the next message, returns `None` when the input is closed or the message is not valid
+pub fn read_message(input: &mut impl BufRead) -> Option<Value> {
+    let mut length: Option<usize> = None;
+
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header).ok()? == 0 {
+            return None;
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    // The body is read as it comes in instead of allocating its length up front, a message can
+    // claim to be larger than it is
+    let length = length?;
+    let mut body = Vec::new();
+    input
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(&mut body)
+        .ok()?;
+
+    if body.len() != length {
+        return None;
+    }
+
+    json::from_str(std::str::from_utf8(&body).ok()?).ok()
+}
+
+pub fn write_message(output: &mut impl Write, message: &Value) {
+    let body = json::to_string(message);
+
+    let _ = write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let _ = output.flush();
+}
+
+/// Finds a value inside of nested objects, eg. `["textDocument", "uri"]`
+pub fn get<'a>(value: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    let mut current = value;
+
+    for key in path {
+        match current {
+            Value::Object(object) => current = object.get(*key)?,
+            _ => return None,
+        }
+    }
+
+    Some(current)
+}
+
+pub fn get_str<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    match get(value, path)? {
+        Value::String(string) => Some(string.as_str()),
+        _ => None,
+    }
+}
+
+pub fn get_i64(value: &Value, path: &[&str]) -> Option<i64> {
+    match get(value, path)? {
+        Value::Number(Number::U64(n)) => Some(*n as i64),
+        Value::Number(Number::I64(n)) => Some(*n),
+        Value::Number(Number::F64(n)) => Some(*n as i64),
+        _ => None,
+    }
+}
+
+pub fn object(entries: Vec<(&str, Value)>) -> Value {
+    let mut object = Object::new();
+
+    for (key, value) in entries {
+        object.insert(key.to_string(), value);
+    }
+
+    Value::Object(object)
+}
+
+pub fn array(values: Vec<Value>) -> Value {
+    let mut array = Array::new();
+    array.extend(values);
+
+    Value::Array(array)
+}
+
+pub fn string(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+pub fn number(value: i64) -> Value {
+    Value::Number(Number::I64(value))
+}
@@ -0,0 +1,3 @@
+//! Tests the language server
+#[allow(clippy::module_inception)]
+mod tests;
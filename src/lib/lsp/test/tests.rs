@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::lib::lsp::protocol::{
+        get_i64, get_str, object, read_message, string, write_message,
+    };
+    use crate::lib::lsp::{char_index, path_from_uri, uri_from_path, utf16_index};
+    use std::io::Cursor;
+
+    fn read(input: &str) -> Option<miniserde::json::Value> {
+        read_message(&mut Cursor::new(input.as_bytes()))
+    }
+
+    #[test]
+    fn read_messages() {
+        let mut input = Cursor::new(
+            "Content-Length: 12\r\n\r\n{\"id\": 1}   \
+            content-length: 8\r\nContent-Type: application/json\r\n\r\n{\"id\":2}"
+                .as_bytes(),
+        );
+
+        let first = read_message(&mut input).unwrap();
+        let second = read_message(&mut input).unwrap();
+
+        assert_eq!(get_i64(&first, &["id"]), Some(1));
+        assert_eq!(get_i64(&second, &["id"]), Some(2));
+        assert!(read_message(&mut input).is_none());
+    }
+
+    #[test]
+    fn read_invalid_messages() {
+        // Without a length, with a body that is not JSON and with a body that is cut off
+        assert!(read("\r\n{}").is_none());
+        assert!(read("Content-Length: 3\r\n\r\n{a}").is_none());
+        assert!(read("Content-Length: 20\r\n\r\n{\"id\": 1}").is_none());
+        assert!(read("Content-Length: 20\r\n").is_none());
+    }
+
+    #[test]
+    fn read_message_larger_than_input() {
+        // The length is not allocated before the body is read
+        assert!(read(&format!("Content-Length: {}\r\n\r\n{{}}", usize::MAX)).is_none());
+        assert!(read("Content-Length: 99999999999999999999999\r\n\r\n{}").is_none());
+    }
+
+    #[test]
+    fn write_messages() {
+        let mut output = Vec::new();
+        write_message(&mut output, &object(vec![("text", string("é😀"))]));
+
+        // The length is in bytes, not in characters
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "Content-Length: 17\r\n\r\n{\"text\":\"é😀\"}");
+
+        let message = read(&output).unwrap();
+        assert_eq!(get_str(&message, &["text"]), Some("é😀"));
+    }
+
+    #[test]
+    fn paths_from_uris() {
+        assert_eq!(path_from_uri("file:///code/main.loop"), "/code/main.loop");
+        assert_eq!(
+            path_from_uri("file:///code/my%20files/h%C3%A9llo.loop"),
+            "/code/my files/héllo.loop"
+        );
+        assert_eq!(
+            path_from_uri("file:///c%3A/code/main.loop"),
+            "c:/code/main.loop"
+        );
+        assert_eq!(
+            path_from_uri("file:///code/100%zz.loop"),
+            "/code/100%zz.loop"
+        );
+        assert_eq!(path_from_uri("/code/main.loop"), "/code/main.loop");
+    }
+
+    #[test]
+    fn uris_from_paths() {
+        let path = "/code/my files/héllo 😀.loop";
+        let uri = uri_from_path(path);
+
+        assert_eq!(
+            uri,
+            "file:///code/my%20files/h%C3%A9llo%20%F0%9F%98%80.loop"
+        );
+        assert_eq!(path_from_uri(&uri), path);
+    }
+
+    #[test]
+    fn utf16_offsets() {
+        // "😀" is outside of the basic multilingual plane, it is two UTF-16 code units
+        let text = "a😀b = é";
+
+        assert_eq!(utf16_index(text, 0), 0);
+        assert_eq!(utf16_index(text, 1), 1);
+        assert_eq!(utf16_index(text, 2), 3);
+        assert_eq!(utf16_index(text, 7), 8);
+
+        assert_eq!(char_index(text, 1), 1);
+        assert_eq!(char_index(text, 3), 2);
+        assert_eq!(char_index(text, 8), 7);
+        // Halfway through "😀" is the character after it, past the end is the end of the line
+        assert_eq!(char_index(text, 2), 2);
+        assert_eq!(char_index(text, 100), 7);
+
+        for index in 0..=text.chars().count() {
+            assert_eq!(char_index(text, utf16_index(text, index)), index);
+        }
+    }
+}
@@ -1,5 +1,9 @@
+pub mod arcb;
+pub mod benchmark;
+pub mod cache;
 pub mod config;
 pub mod exception;
 pub mod flags;
+pub mod lsp;
 pub mod repl;
 pub mod util;
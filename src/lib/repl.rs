@@ -1,16 +1,34 @@
-use crate::lib::config::CONFIG;
-use crate::lib::util::print_valuetype;
+use crate::lib::benchmark::{Benchmark, Measurement};
+use crate::lib::config::{Backend, CONFIG};
+use crate::lib::util::{print_valuetype, report_parse_errors};
+use picasso::compiler::CompilerState;
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
-use sanzio::parse_multivalue;
+use sanzio::Interpreter;
+#[cfg(feature = "lua")]
+use sanzio::{parse_multivalue, Sanzio};
 use std::process::ExitCode;
+use std::time::Instant;
 use vinci::types::ValueType;
+use vinci::ParserState;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Runs the lines of a session, what earlier lines defined stays available to later ones
+enum Session {
+    #[cfg(feature = "lua")]
+    Lua(Sanzio),
+    Interpreter(Box<Interpreter>),
+}
+
 pub fn start() -> Result<(), ExitCode> {
-    let mut backend = unsafe { sanzio::Sanzio::new() };
-    let mut compiler_state: Option<picasso::compiler::CompilerState> = None;
+    let mut session = match CONFIG.backend {
+        #[cfg(feature = "lua")]
+        Backend::Lua => Session::Lua(unsafe { Sanzio::new() }),
+        Backend::Interpreter => Session::Interpreter(Box::new(unsafe { Interpreter::new() })),
+    };
+    let mut compiler_state = CompilerState::default();
+    let mut parser_state = ParserState::default();
     let mut rl = Editor::<()>::new();
 
     println!(
@@ -38,28 +56,12 @@ pub fn start() -> Result<(), ExitCode> {
             Ok(line) => {
                 rl.add_history_entry(line.as_str());
 
-                let result = if let Some(compiler_state) = compiler_state.clone() {
-                    picasso::compile_with_state(line.as_str(), compiler_state)
-                } else {
-                    picasso::compile(line.as_str(), None)?
-                };
-
-                if CONFIG.debug_mode {
-                    println!("Arc\n{}", result.0.as_str());
-                }
-
-                let ast = vinci::parse(result.0.as_str());
-
-                let evaluated = backend.run(ast);
-                let parsed = parse_multivalue(evaluated);
-
-                compiler_state = Some(result.1);
-
-                print_valuetype(parsed.clone());
-
-                if parsed != ValueType::Void {
-                    println!();
-                }
+                run_line(
+                    line.as_str(),
+                    &mut session,
+                    &mut compiler_state,
+                    &mut parser_state,
+                );
             }
             Err(ReadlineError::Interrupted) => {
                 println!("CTRL-C");
@@ -77,3 +79,87 @@ pub fn start() -> Result<(), ExitCode> {
 
     Ok(())
 }
+
+/// Runs a single line. Both the compiler and parser continue from where the previous line left
+/// off, the state of the backend is shared between lines as well. The state is only updated
+/// when the line succeeds, so a mistake does not end the session.
+fn run_line(
+    line: &str,
+    session: &mut Session,
+    compiler_state: &mut CompilerState,
+    parser_state: &mut ParserState,
+) {
+    let mut measurement = Measurement::default();
+
+    // Timing the compilation lexes the line an extra time, which is only worth it for benchmarks
+    let compiled = if CONFIG.enable_benchmark {
+        picasso::compile_with_state_timed(line, compiler_state.clone())
+    } else {
+        picasso::compile_with_state(line, compiler_state.clone())
+            .map(|(arc, state)| (arc, state, picasso::Timings::default()))
+    };
+    let (arc, new_compiler_state, timings) = match compiled {
+        Ok(compiled) => compiled,
+        Err(error) => {
+            error.report();
+            return;
+        }
+    };
+    measurement.compilation(timings);
+
+    if CONFIG.debug_mode {
+        println!("Arc\n{}", arc.as_str());
+    }
+
+    let start = Instant::now();
+    let (ast, new_parser_state) = match vinci::parse_with_state(arc.as_str(), parser_state.clone())
+    {
+        Ok(parsed) => parsed,
+        Err(errors) => {
+            report_parse_errors(&arc, &errors);
+            return;
+        }
+    };
+    measurement.phase("arc parsing", start.elapsed());
+
+    let executed = match session {
+        #[cfg(feature = "lua")]
+        Session::Lua(backend) => {
+            let start = Instant::now();
+
+            Sanzio::compile_to_lua_with_source_map(&ast)
+                .and_then(|(lua_code, source_map)| {
+                    measurement.phase("lua generation", start.elapsed());
+                    backend.run_lua_measured(&lua_code, &source_map)
+                })
+                .map(|(evaluated, execution)| (parse_multivalue(evaluated), execution))
+        }
+        Session::Interpreter(interpreter) => interpreter.run_measured(ast),
+    };
+
+    let (parsed, execution) = match executed {
+        Ok(executed) => executed,
+        Err(error) => {
+            error.report();
+            return;
+        }
+    };
+    measurement.phase("execution", execution.duration);
+    measurement.peak_memory = execution.peak_memory;
+
+    *compiler_state = new_compiler_state;
+    *parser_state = new_parser_state;
+
+    // Lines without a value print nothing
+    if parsed != ValueType::Void {
+        print_valuetype(parsed);
+        println!();
+    }
+
+    // Every line is benchmarked on its own
+    if CONFIG.enable_benchmark {
+        let mut benchmark = Benchmark::default();
+        benchmark.add(measurement);
+        println!("{}", benchmark.report(CONFIG.benchmark_format));
+    }
+}
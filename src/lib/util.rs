@@ -1,29 +1,22 @@
-use crate::lib::config::CONFIG;
+use crate::lib::arcb;
+use crate::lib::benchmark::{Benchmark, Measurement};
+use crate::lib::cache;
+use crate::lib::config::{Backend, CONFIG};
 use crate::lib::flags;
-use sanzio::parse_multivalue;
+use sanzio::RuntimeError;
+#[cfg(feature = "lua")]
+use sanzio::{parse_multivalue, Execution};
 use std::env;
-use std::io::Read;
 use std::path::Path;
 use std::process::{exit, ExitCode};
+use std::time::Instant;
+use vinci::ast::AST;
+use vinci::diagnostic::Diagnostic;
 use vinci::types::ValueType;
+use vinci::ParseError;
 
 pub fn print_valuetype(value_type: ValueType) {
-    match value_type {
-        ValueType::Integer(c) => print!("{}", c),
-        ValueType::Boolean(c) => print!("{}", c),
-        ValueType::Character(c) => {
-            print!("{}", c)
-        }
-        ValueType::Array(arr) => {
-            if let Some(first) = arr.first() {
-                if let ValueType::Character(_) = first.clone() {}
-            }
-        }
-        ValueType::Void => print!("null"),
-        ValueType::Float(f) => print!("{}", f),
-        ValueType::Compound(_, _) => todo!(),
-        ValueType::Function(_, _, _, _) => todo!(),
-    }
+    print!("{}", value_type.format());
 }
 
 pub fn get_flags() -> flags::Flags {
@@ -35,68 +28,238 @@ pub fn get_flags() -> flags::Flags {
     flags
 }
 
+/// The interpreter nests a call in Rust for every call in the program, so it runs on a thread with
+/// a stack big enough to allow as many as LuaJIT does
+pub fn with_backend_stack<T: Send + 'static>(
+    run: impl FnOnce() -> Result<T, ExitCode> + Send + 'static,
+) -> Result<T, ExitCode> {
+    if CONFIG.backend != Backend::Interpreter {
+        return run();
+    }
+
+    std::thread::Builder::new()
+        .stack_size(sanzio::Interpreter::STACK_SIZE)
+        .spawn(run)
+        .map_err(|_| ExitCode::FAILURE)?
+        .join()
+        .unwrap_or(Err(ExitCode::FAILURE))
+}
+
 pub fn run_file(path: String) -> Result<(), ExitCode> {
-    let file = std::fs::File::open(Path::new(path.as_str()));
+    if arcb::is_arcb(&path) {
+        let bytes = read_file(&path, |path| std::fs::read(path));
+        return run(&path, |_, measurement| {
+            let start = Instant::now();
+            let ast = arcb::decode(&path, &bytes)?;
+            measurement.phase("arcb decoding", start.elapsed());
 
-    if let Err(err) = file {
-        println!("{}", err);
-        exit(1);
+            Ok(ast)
+        });
     }
 
-    let mut file = file.unwrap();
-    let mut content = String::new();
-    let result = file.read_to_string(&mut content);
+    let content = read_file(&path, |path| std::fs::read_to_string(path));
+
+    // The cache is skipped when the output of compiling is shown, or when compiling is measured
+    let use_cache =
+        CONFIG.enable_cache && !CONFIG.debug_mode && !CONFIG.arc_output && !CONFIG.enable_benchmark;
 
-    if let Err(err) = result {
-        println!("{}", err);
-        exit(1);
+    run(&path, |first_run, measurement| {
+        if use_cache {
+            if let Some(ast) = cache::load(&path, &content, measurement) {
+                return Ok(ast);
+            }
+        }
+
+        let (ast, imported_files) = compile(&content, &path, first_run, measurement)?;
+
+        if use_cache {
+            cache::store(&path, &content, &imported_files, &ast);
+        }
+
+        Ok(ast)
+    })
+}
+
+/// Prints the error and quits when the file can not be read
+pub fn read_file<T>(path: &str, read: fn(&Path) -> std::io::Result<T>) -> T {
+    match read(Path::new(path)) {
+        Ok(content) => content,
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
     }
+}
 
-    let arc = picasso::compile(content.as_str(), Some(path.as_str()))?;
+/// Compiles Loop into an AST, which is optimized when that is enabled, together with the paths of
+/// the files it imports
+pub fn compile(
+    content: &str,
+    path: &str,
+    first_run: bool,
+    measurement: &mut Measurement,
+) -> Result<(AST, Vec<String>), ExitCode> {
+    // Timing the compilation lexes the code an extra time, which is only worth it for benchmarks
+    let compiled = if CONFIG.enable_benchmark {
+        picasso::compile_timed(content, Some(path), CONFIG.max_errors)
+    } else {
+        picasso::compile(content, Some(path), CONFIG.max_errors)
+            .map(|(arc, state)| (arc, state, picasso::Timings::default()))
+    };
+    let (arc, state, timings) = compiled.map_err(|error| {
+        error.report();
+        ExitCode::FAILURE
+    })?;
+    measurement.compilation(timings);
 
-    if CONFIG.debug_mode {
-        println!("Arc\n#---------\n{}\n---------#", arc.0);
+    if CONFIG.debug_mode && first_run {
+        println!("Arc\n#---------\n{}\n---------#", arc);
     }
 
-    if CONFIG.arc_output {
-        save_to_file(format!("{}.arc", path), arc.clone().0);
+    let start = Instant::now();
+    let mut ast = vinci::parse(&arc).map_err(|errors| {
+        report_parse_errors(&arc, &errors);
+        ExitCode::FAILURE
+    })?;
+    measurement.phase("arc parsing", start.elapsed());
+
+    if CONFIG.enable_optimize {
+        let start = Instant::now();
+        let statistics = vinci::optimizer::optimize(&mut ast);
+        measurement.phase("optimizing", start.elapsed());
+
+        if CONFIG.debug_mode && first_run {
+            println!("Optimizations\n#---------");
+            for pass in statistics {
+                println!("{}", pass);
+            }
+            println!("---------#");
+        }
     }
 
-    let ast = vinci::parse(&*arc.0);
+    if CONFIG.arc_output && first_run {
+        save_to_file(format!("{}.arc", path), optimized_arc(&ast, &arc));
+    }
+
+    Ok((ast, state.imported_files))
+}
 
-    if CONFIG.debug_mode {
-        println!("AST\n#---------\n{}\n---------#", ast);
+/// Runs the program, loading its AST once for every run
+fn run(
+    // Only needed to save the Lua next to the program
+    #[cfg_attr(not(feature = "lua"), allow(unused_variables))] path: &str,
+    mut load: impl FnMut(bool, &mut Measurement) -> Result<AST, ExitCode>,
+) -> Result<(), ExitCode> {
+    // Benchmarks run the whole program more than once, everything besides the timings is only
+    // shown for the first run
+    let runs = if CONFIG.enable_benchmark {
+        CONFIG.benchmark_runs
+    } else {
+        1
+    };
+    let mut benchmark = Benchmark::default();
+
+    for run in 0..runs {
+        let first_run = run == 0;
+        let mut measurement = Measurement::default();
+
+        let ast = load(first_run, &mut measurement)?;
+
+        // What the program prints is the same every run
+        sanzio::set_output_enabled(first_run);
+
+        if CONFIG.debug_mode && first_run {
+            println!("AST\n#---------\n{}\n---------#", ast);
+        }
+
+        let (result, execution) = match CONFIG.backend {
+            #[cfg(feature = "lua")]
+            Backend::Lua => run_lua(path, &ast, first_run, &mut measurement)?,
+            Backend::Interpreter => unsafe { sanzio::Interpreter::new() }
+                .run_measured(ast)
+                .map_err(report_runtime_error)?,
+        };
+        measurement.phase("execution", execution.duration);
+        measurement.peak_memory = execution.peak_memory;
+
+        if result != ValueType::Void && first_run {
+            print_valuetype(result);
+            println!();
+        };
+
+        benchmark.add(measurement);
+    }
+
+    if CONFIG.enable_benchmark {
+        println!("{}", benchmark.report(CONFIG.benchmark_format));
     }
 
-    let mut backend = unsafe { sanzio::Sanzio::new() };
+    Ok(())
+}
+
+/// Generates Lua for the program and runs it with LuaJIT
+#[cfg(feature = "lua")]
+fn run_lua(
+    path: &str,
+    ast: &AST,
+    first_run: bool,
+    measurement: &mut Measurement,
+) -> Result<(ValueType, Execution), ExitCode> {
+    let backend = unsafe { sanzio::Sanzio::new() };
 
-    let lua_code = sanzio::Sanzio::compile_to_lua(&ast);
+    let start = Instant::now();
+    let (lua_code, source_map) =
+        sanzio::Sanzio::compile_to_lua_with_source_map(ast).map_err(report_runtime_error)?;
+    measurement.phase("lua generation", start.elapsed());
 
-    if CONFIG.debug_mode {
+    if CONFIG.debug_mode && first_run {
         println!("Lua\n#---------\n{}\n---------#", lua_code);
     }
 
-    if CONFIG.lua_output {
-        save_to_file(format!("{}.lua", path), lua_code);
+    if CONFIG.lua_output && first_run {
+        save_to_file(format!("{}.lua", path), lua_code.clone());
     }
 
-    let result = backend.run(ast);
-    let multivalue = parse_multivalue(result.clone());
+    let (result, execution) = backend
+        .run_lua_measured(&lua_code, &source_map)
+        .map_err(report_runtime_error)?;
 
-    if multivalue != ValueType::Void {
-        print_valuetype(parse_multivalue(result.clone()));
-        println!();
-    };
+    Ok((parse_multivalue(result), execution))
+}
 
-    Ok(())
+/// The Arc that is run, which is only different from what was compiled when it was optimized
+fn optimized_arc(ast: &AST, arc: &str) -> String {
+    if !CONFIG.enable_optimize {
+        return arc.to_string();
+    }
+
+    match vinci::printer::print_indented(ast, 4) {
+        Ok(optimized) => optimized,
+        Err(error) => {
+            Diagnostic::warning("arc::print", error.to_string()).emit();
+            arc.to_string()
+        }
+    }
+}
+
+/// Shows every error that was found in the Arc, which means Loop generated invalid Arc
+pub fn report_parse_errors(arc: &str, errors: &[ParseError]) {
+    for error in errors {
+        error.diagnostic(arc).emit();
+    }
+}
+
+fn report_runtime_error(error: RuntimeError) -> ExitCode {
+    error.report();
+    ExitCode::FAILURE
 }
 
-fn save_to_file(file_name: String, content: String) {
+pub fn save_to_file(file_name: String, content: impl AsRef<[u8]>) {
     use std::io::Write;
     let file = std::fs::File::create(file_name);
     match file {
         Ok(mut f) => {
-            if let Err(e) = f.write_all(content.as_bytes()) {
+            if let Err(e) = f.write_all(content.as_ref()) {
                 println!("{}", e);
                 std::process::exit(1);
             }
@@ -1,16 +1,40 @@
+// The binary keeps its code in a module named "lib", there is no library target
+#![allow(special_module_name)]
+// The derives of miniserde 0.1 implement the traits inside of a constant, which newer compilers
+// warn about
+#![allow(non_local_definitions)]
+
 mod lib;
 
+use crate::lib::config::CONFIG;
 use crate::lib::util::get_flags;
 use std::process::ExitCode;
 
 fn main() -> ExitCode {
     let flags = get_flags();
 
-    if let Some(file) = flags.file {
-        if let Err(code) = lib::util::run_file(file) {
+    vinci::diagnostic::set_format(CONFIG.error_format);
+    vinci::diagnostic::set_color(CONFIG.color);
+
+    if let Some(command) = flags.cache_command {
+        if let Err(code) = lib::cache::command(&command) {
+            return code;
+        }
+    } else if flags.lsp {
+        if let Err(code) = lib::lsp::start() {
+            return code;
+        }
+    } else if let Some(file) = flags.file {
+        let result = if flags.emit_arcb {
+            lib::arcb::emit(file)
+        } else {
+            lib::util::with_backend_stack(|| lib::util::run_file(file))
+        };
+
+        if let Err(code) = result {
             return code;
         }
-    } else if let Err(code) = lib::repl::start() {
+    } else if let Err(code) = lib::util::with_backend_stack(lib::repl::start) {
         return code;
     }
 
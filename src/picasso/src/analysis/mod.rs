@@ -0,0 +1,225 @@
+//! Analysis of Loop code that does not print or exit, used by tools such as the language server
+use crate::compiler::compile::expression_array_method::{array_method_type, ARRAY_METHODS};
+use crate::compiler::compile::expression_string_method::{string_method_type, STRING_METHODS};
+use crate::compiler::symbol_table::Symbol;
+use crate::compiler::Compiler;
+use crate::parser::types::{BaseTypes, Compound, Types};
+use crate::span::Span;
+use crate::{lexer, parser};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use vinci::diagnostic::Diagnostic;
+
+mod test;
+
+/// Everything known about a piece of code after parsing and compiling it
+pub struct Analysis {
+    pub diagnostics: Vec<Diagnostic>,
+    pub symbols: Vec<SymbolInfo>,
+    pub references: Vec<Reference>,
+}
+
+/// A symbol defined in the code, or in a module it imports
+#[derive(Debug, Clone)]
+pub struct SymbolInfo {
+    pub name: String,
+    /// The type as it is written in Loop
+    pub type_name: String,
+    pub definition: Span,
+    /// The file that was imported, when the symbol is a module
+    pub module: Option<String>,
+    /// Fields and methods that can be used on the symbol with a dot
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub name: String,
+    pub type_name: String,
+}
+
+/// A place in the code that defines or uses a symbol, `symbol` is its index in
+/// [Analysis::symbols]
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub span: Span,
+    pub symbol: usize,
+}
+
+impl Analysis {
+    /// Finds the symbol used at a position in a file, when references are nested (eg. a module and
+    /// one of its functions) the innermost one is used
+    pub fn symbol_at(&self, file: &str, line: i32, colon: i32) -> Option<&SymbolInfo> {
+        self.references
+            .iter()
+            .filter(|reference| {
+                reference.span.file == file && contains(&reference.span, line, colon)
+            })
+            .min_by_key(|reference| span_length(&reference.span))
+            .map(|reference| &self.symbols[reference.symbol])
+    }
+
+    /// Finds the symbol with this name that was referred to last before a position, used when the
+    /// code around the position can not be parsed because the user is still typing it
+    pub fn symbol_named_before(
+        &self,
+        file: &str,
+        name: &str,
+        line: i32,
+        colon: i32,
+    ) -> Option<&SymbolInfo> {
+        self.references
+            .iter()
+            .filter(|reference| {
+                let start = &reference.span.start;
+
+                reference.span.file == file
+                    && self.symbols[reference.symbol].name == name
+                    && (start.line, start.colon) <= (line, colon)
+            })
+            .max_by_key(|reference| (reference.span.start.line, reference.span.start.colon))
+            .map(|reference| &self.symbols[reference.symbol])
+    }
+}
+
+fn contains(span: &Span, line: i32, colon: i32) -> bool {
+    (span.start.line, span.start.colon) <= (line, colon)
+        && (line, colon) <= (span.end.line, span.end.colon)
+}
+
+fn span_length(span: &Span) -> (i32, i32) {
+    (
+        span.end.line - span.start.line,
+        span.end.colon - span.start.colon,
+    )
+}
+
+/// Parses and compiles the code of a file, collecting every error instead of printing them
+pub fn analyze(source: &str, file: &str) -> Analysis {
+    let lexer = lexer::build_lexer(source);
+    let mut parser = parser::build_parser(lexer, file);
+    parser.max_errors = usize::MAX;
+
+    let program = match parser.parse() {
+        Ok(program) => program,
+        Err(_) => {
+            return Analysis {
+                diagnostics: parser
+                    .errors
+                    .iter()
+                    .map(|error| parser.diagnostic(error))
+                    .collect(),
+                symbols: Vec::new(),
+                references: Vec::new(),
+            }
+        }
+    };
+
+    let mut compiler = Compiler {
+        compiled_from: source.to_string(),
+        max_errors: usize::MAX,
+        ..Compiler::default()
+    };
+    compiler.set_base_location(Some(file));
+
+    compiler.compile_program(program);
+
+    let diagnostics = compiler
+        .errors
+        .iter()
+        .map(|error| compiler.diagnostic(error))
+        .collect();
+
+    let mut symbols: Vec<SymbolInfo> = Vec::new();
+    let mut references: Vec<Reference> = Vec::new();
+    let mut indexes: HashMap<*const RefCell<Symbol>, usize> = HashMap::new();
+
+    for (span, symbol) in &compiler.references {
+        let index = *indexes.entry(Rc::as_ptr(symbol)).or_insert_with(|| {
+            symbols.push(symbol_info(&compiler, &symbol.as_ref().borrow()));
+            symbols.len() - 1
+        });
+
+        references.push(Reference {
+            span: span.clone(),
+            symbol: index,
+        });
+    }
+
+    Analysis {
+        diagnostics,
+        symbols,
+        references,
+    }
+}
+
+fn symbol_info(compiler: &Compiler, symbol: &Symbol) -> SymbolInfo {
+    let module = match &symbol._type {
+        Types::Module(module) => Some(module.clone()),
+        _ => None,
+    };
+
+    SymbolInfo {
+        name: symbol.name.clone(),
+        type_name: symbol._type.to_string(),
+        definition: symbol.span.clone(),
+        module,
+        members: members(compiler, &symbol._type),
+    }
+}
+
+fn members(compiler: &Compiler, _type: &Types) -> Vec<Member> {
+    let mut members = Vec::new();
+
+    match _type {
+        Types::Compound(Compound(_, fields)) => {
+            for field in fields.iter() {
+                members.push(Member {
+                    name: field.name.clone(),
+                    type_name: field.class_item_type.to_string(),
+                });
+            }
+        }
+        Types::Module(module) => {
+            if let Some(scope) = compiler.symbol_scope.get(module) {
+                for variable in &scope.borrow().variables {
+                    let variable = variable.borrow();
+
+                    members.push(Member {
+                        name: variable.name.clone(),
+                        type_name: variable._type.to_string(),
+                    });
+                }
+            }
+        }
+        Types::Array(_) => {
+            for name in ARRAY_METHODS {
+                members.push(Member {
+                    name: name.to_string(),
+                    type_name: array_method_type(name).unwrap_or_default(),
+                });
+            }
+        }
+        Types::Basic(BaseTypes::String) => {
+            for name in STRING_METHODS {
+                members.push(Member {
+                    name: name.to_string(),
+                    type_name: string_method_type(name).unwrap_or_default(),
+                });
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(methods) = compiler.extensions.get(&_type.transpile()) {
+        for method in methods {
+            members.push(Member {
+                name: method.name.clone(),
+                type_name: format!("fn(): {}", method.return_type),
+            });
+        }
+    }
+
+    members
+}
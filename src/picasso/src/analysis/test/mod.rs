@@ -0,0 +1,3 @@
+//! Tests the analysis used by tools
+#[allow(clippy::module_inception)]
+mod tests;
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::analysis::analyze;
+
+    #[test]
+    fn diagnostics() {
+        let analysis = analyze("x := 1\ny := x + z\nw := q", "main.loop");
+
+        let codes: Vec<(&str, i32)> = analysis
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.spans[0].line_start))
+            .collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                ("compiler::unknown-symbol", 2),
+                ("compiler::unknown-symbol", 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_diagnostics() {
+        let analysis = analyze("x := )\ny := 1\nz := )", "main.loop");
+
+        assert_eq!(analysis.diagnostics.len(), 2);
+        assert_eq!(analysis.diagnostics[0].code, "syntax::no-prefix-parser");
+        assert!(analysis.symbols.is_empty());
+    }
+
+    #[test]
+    fn hover_and_definition() {
+        let analysis = analyze("x := 10\ny := x + 2", "main.loop");
+
+        let symbol = analysis.symbol_at("main.loop", 2, 6).unwrap();
+
+        assert_eq!(symbol.name, "x");
+        assert_eq!(symbol.type_name, "int");
+        assert_eq!(
+            (symbol.definition.start.line, symbol.definition.start.colon),
+            (1, 1)
+        );
+        assert!(analysis.symbol_at("main.loop", 2, 4).is_none());
+    }
+
+    #[test]
+    fn members() {
+        let analysis = analyze(
+            "class Point {\n x = 1\n y = 2\n}\np := Point()\next int {\n int double() {\n return self * 2\n }\n}\nn := 4",
+            "main.loop",
+        );
+
+        let point = analysis
+            .symbol_named_before("main.loop", "p", 5, 1)
+            .unwrap();
+        let names: Vec<&str> = point.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["x", "y"]);
+
+        let n = analysis
+            .symbol_named_before("main.loop", "n", 11, 1)
+            .unwrap();
+        assert_eq!(n.members[0].name, "double");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::compiler::Compiler;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::parser::expression::Expression;
+use crate::parser::types::{BaseTypes, FunctionType, Types};
+use crate::span::Span;
+
+/// The names of every method built into arrays
+pub const ARRAY_METHODS: [&str; 17] = [
+    "push", "remove", "len", "map", "filter", "reduce", "fold", "sort", "reverse", "find",
+    "index_of", "contains", "any", "all", "concat", "flatten", "join",
+];
+
+/// The type of a method built into arrays as it is shown to the user, `T` is the type of the
+/// elements and `U` any other type
+pub fn array_method_type(name: &str) -> Option<String> {
+    let signature = match name {
+        "push" => "fn(T): void",
+        "remove" => "fn(int): void",
+        "len" => "fn(): int",
+        "map" => "fn(fn(T): U): U[]",
+        "filter" => "fn(fn(T): bool): T[]",
+        "reduce" => "fn(fn(T, T): T): T",
+        "fold" => "fn(fn(U, T): U, U): U",
+        "sort" => "fn(fn(T, T): bool?): T[]",
+        "reverse" => "fn(): T[]",
+        "find" => "fn(fn(T): bool): T",
+        "index_of" => "fn(T): int",
+        "contains" => "fn(T): bool",
+        "any" | "all" => "fn(fn(T): bool): bool",
+        "concat" => "fn(T[]): T[]",
+        "flatten" => "fn(): U[]",
+        "join" => "fn(string): string",
+        _ => return None,
+    };
+
+    Some(signature.to_string())
+}
+
+/// Compiles a method that is built into arrays, the backend implements them. The functions that
+/// are given to them are checked against the type of the elements.
+///
+/// Take this example in Loop
+/// ```loop
+/// [1, 2].map(fn(int x) { return x * 2 })
+/// ```
+///
+/// Will translate to this Arc code
+/// ```arc
+/// .CALL array::map { .CONSTANT INT[] [...]; .CONSTANT FUNCTION ...; };
+/// ```
+pub fn compile_expression_array_method(
+    compiler: &mut Compiler,
+    array: Expression,
+    element: Types,
+    name: String,
+    arguments: Vec<Expression>,
+    span: Span,
+) -> Result<Types, CompilerException> {
+    let boolean = Types::Basic(BaseTypes::Boolean);
+    let array_type = Types::Array(Box::new(element.clone()));
+
+    // The amount of arguments that are required and that are allowed
+    let (required, allowed) = match name.as_str() {
+        "map" | "filter" | "reduce" | "find" | "index_of" | "contains" | "any" | "all"
+        | "concat" | "join" => (1, 1),
+        "fold" => (2, 2),
+        "sort" => (0, 1),
+        "reverse" | "flatten" => (0, 0),
+        _ => {
+            return Err(CompilerException::new(
+                span,
+                CompilerExceptionCode::UnknownExtensionMethod(name),
+            ))
+        }
+    };
+
+    if arguments.len() < required || arguments.len() > allowed {
+        return Err(CompilerException::new(
+            span,
+            CompilerExceptionCode::WrongType(
+                format!("{} arguments", arguments.len()),
+                format!("{} arguments", allowed),
+            ),
+        ));
+    }
+
+    compiler.add_to_current_function(format!(".CALL array::{} {{ ", name));
+    compiler.compile_expression(array)?;
+
+    let mut argument_types = Vec::new();
+    let mut argument_spans = Vec::new();
+    for argument in arguments {
+        argument_spans.push(argument.span());
+        argument_types.push(compiler.compile_expression(argument)?);
+    }
+
+    compiler.add_to_current_function("};".to_string());
+
+    let argument = |index: usize| (&argument_types[index], argument_spans[index].clone());
+
+    match name.as_str() {
+        "map" => {
+            let (function, span) = argument(0);
+            let result = check_function(function, &[element], span)?;
+
+            Ok(Types::Array(Box::new(result)))
+        }
+        "filter" => {
+            let (function, span) = argument(0);
+            expect(
+                &check_function(function, &[element], span.clone())?,
+                &boolean,
+                span,
+            )?;
+
+            Ok(array_type)
+        }
+        "find" => {
+            let (function, span) = argument(0);
+            expect(
+                &check_function(function, std::slice::from_ref(&element), span.clone())?,
+                &boolean,
+                span,
+            )?;
+
+            Ok(element)
+        }
+        "any" | "all" => {
+            let (function, span) = argument(0);
+            expect(
+                &check_function(function, &[element], span.clone())?,
+                &boolean,
+                span,
+            )?;
+
+            Ok(boolean)
+        }
+        "reduce" => {
+            let (function, span) = argument(0);
+            let parameters = [element.clone(), element.clone()];
+            expect(
+                &check_function(function, &parameters, span.clone())?,
+                &element,
+                span,
+            )?;
+
+            Ok(element)
+        }
+        "fold" => {
+            let (initial, _) = argument(1);
+            let initial = initial.clone();
+            let (function, span) = argument(0);
+            let parameters = [initial.clone(), element];
+            expect(
+                &check_function(function, &parameters, span.clone())?,
+                &initial,
+                span,
+            )?;
+
+            Ok(initial)
+        }
+        "sort" => {
+            if !argument_types.is_empty() {
+                let (function, span) = argument(0);
+                let parameters = [element.clone(), element];
+                expect(
+                    &check_function(function, &parameters, span.clone())?,
+                    &boolean,
+                    span,
+                )?;
+            }
+
+            Ok(array_type)
+        }
+        "index_of" | "contains" => {
+            let (value, span) = argument(0);
+            expect(value, &element, span)?;
+
+            Ok(if name == "index_of" {
+                Types::Basic(BaseTypes::Integer)
+            } else {
+                boolean
+            })
+        }
+        "concat" => {
+            let (other, span) = argument(0);
+            expect(other, &array_type, span)?;
+
+            Ok(array_type)
+        }
+        "join" => {
+            let (separator, span) = argument(0);
+            expect(separator, &Types::Basic(BaseTypes::String), span.clone())?;
+
+            match element {
+                Types::Basic(BaseTypes::String)
+                | Types::Basic(BaseTypes::Integer)
+                | Types::Basic(BaseTypes::Float)
+                | Types::Auto => Ok(Types::Basic(BaseTypes::String)),
+                other => Err(CompilerException::new(
+                    span,
+                    CompilerExceptionCode::WrongType(
+                        other.to_string(),
+                        "string, int or float".to_string(),
+                    ),
+                )),
+            }
+        }
+        "flatten" => match element {
+            Types::Array(inner) => Ok(Types::Array(inner)),
+            Types::Auto => Ok(Types::Array(Box::new(Types::Auto))),
+            other => Err(CompilerException::new(
+                span,
+                CompilerExceptionCode::WrongType(other.to_string(), "an array".to_string()),
+            )),
+        },
+        // "reverse"
+        _ => Ok(array_type),
+    }
+}
+
+/// Checks that a value is a function which accepts the given parameters, and returns the type it
+/// returns
+fn check_function(
+    function: &Types,
+    parameters: &[Types],
+    span: Span,
+) -> Result<Types, CompilerException> {
+    let expected = || {
+        let expected = Types::Function(FunctionType {
+            return_type: Box::new(Types::Auto),
+            parameter_types: parameters.to_vec(),
+            reference: "".to_string(),
+            is_method: false,
+        });
+
+        CompilerExceptionCode::WrongType(function.to_string(), expected.to_string())
+    };
+
+    match function {
+        Types::Function(function) => {
+            if function.parameter_types.len() != parameters.len() {
+                return Err(CompilerException::new(span, expected()));
+            }
+
+            for (got, parameter) in function.parameter_types.iter().zip(parameters) {
+                if !compatible(got, parameter) {
+                    return Err(CompilerException::new(span, expected()));
+                }
+            }
+
+            Ok(*function.return_type.clone())
+        }
+        Types::Auto => Ok(Types::Auto),
+        _ => Err(CompilerException::new(span, expected())),
+    }
+}
+
+fn expect(got: &Types, expected: &Types, span: Span) -> Result<(), CompilerException> {
+    if compatible(got, expected) {
+        return Ok(());
+    }
+
+    Err(CompilerException::new(
+        span,
+        CompilerExceptionCode::WrongType(got.to_string(), expected.to_string()),
+    ))
+}
+
+/// Values that are only known at runtime are compatible with every type
+fn compatible(got: &Types, expected: &Types) -> bool {
+    match (got, expected) {
+        (Types::Auto, _) | (_, Types::Auto) => true,
+        (Types::Array(got), Types::Array(expected)) => compatible(got, expected),
+        (Types::Hashmap(got_key, got_value), Types::Hashmap(key, value)) => {
+            compatible(got_key, key) && compatible(got_value, value)
+        }
+        _ => got == expected,
+    }
+}
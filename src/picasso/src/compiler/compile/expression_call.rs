@@ -1,10 +1,14 @@
+use crate::compiler::compile::expression_array_method::compile_expression_array_method;
+use crate::compiler::compile::expression_string_method::compile_expression_string_method;
 use crate::compiler::Compiler;
-use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode, UnknownSymbol};
 use crate::parser::expression::function::{Call, Parameter};
 use crate::parser::expression::identifier::Identifier;
 use crate::parser::expression::index::Index;
 use crate::parser::expression::Expression;
 use crate::parser::types::{BaseTypes, Compound, Types};
+use crate::span::Span;
+use crate::stdlib::{self, STD_LOCATION};
 
 pub fn compile_expression_call(
     compiler: &mut Compiler,
@@ -21,6 +25,8 @@ pub fn compile_expression_call(
             let Compound(name, values) = class_type.clone();
 
             if let Some(definition) = idenfitier {
+                compiler.add_reference(i.span.clone(), &i.value);
+
                 // Wrapped in a call expression so that we can do more during execution
                 compiler.add_to_current_function(format!(
                     ".CALL {{ .FUNCTION \"\" {} {} ARGUMENTS {{}} FREE {{}} THEN {{",
@@ -34,6 +40,7 @@ pub fn compile_expression_call(
                     "temporary_class_holder".to_string(),
                     Types::Compound(class_type.clone()),
                     0,
+                    Span::default(),
                 );
 
                 // Instantiate the class using a constant and store it into the temporary value
@@ -51,7 +58,7 @@ pub fn compile_expression_call(
                         func.parameters.insert(
                             0,
                             Parameter {
-                                identifier: Identifier::new("self".to_string(), 0, 0),
+                                identifier: Identifier::new("self".to_string(), call.span.clone()),
                                 _type: Types::Compound(class_type.clone()),
                             },
                         );
@@ -115,6 +122,11 @@ pub fn compile_expression_call(
             let value = ident.value;
 
             if let Expression::Identifier(library) = &a.left {
+                // Intrinsics are provided by the backend, only the standard library calls them
+                if library.value == "intrinsic" && compiler.location == STD_LOCATION {
+                    return compile_intrinsic_call(compiler, value, call.parameters, call.span);
+                }
+
                 if compiler.imports.contains(&library.value) {
                     compiler.add_to_current_function(".CALL ".to_string());
                     compiler.add_to_current_function(format!("{}::{}", library.value, value));
@@ -148,6 +160,23 @@ pub fn compile_expression_call(
                         return Ok(succ);
                     }
                     "remove" => {
+                        // Removing from a hashmap is done by assigning nothing to the key
+                        if let Types::Hashmap(_, _) = succ {
+                            for parameter in &call.parameters {
+                                compiler.add_to_current_function(
+                                    ".ASSIGN { .INDEX HASHMAP {".to_string(),
+                                );
+                                compiler.compile_expression(a.left.clone())?;
+                                compiler.add_to_current_function("} { ".to_string());
+                                compiler.compile_expression(parameter.clone())?;
+                                compiler.add_to_current_function(
+                                    "}; } { .CONSTANT VOID; };".to_string(),
+                                );
+                            }
+
+                            return Ok(Types::Void);
+                        }
+
                         for parameter in &call.parameters {
                             compiler.add_to_current_function(".POP {".to_string());
                             compiler.compile_expression(a.left.clone())?;
@@ -160,6 +189,45 @@ pub fn compile_expression_call(
                     }
                     _ => {}
                 }
+
+                if let Types::Array(element) = &succ {
+                    if value != "len" {
+                        return compile_expression_array_method(
+                            compiler,
+                            a.left.clone(),
+                            *element.clone(),
+                            value,
+                            call.parameters,
+                            call.span,
+                        );
+                    }
+                }
+
+                // Methods of basic types are either written in an "ext" block or built in
+                if let Types::Basic(basic) = &succ {
+                    let extension = format!("{}_{}", succ.transpile(), value);
+                    let is_extension = compiler.resolve_symbol(&extension).is_some();
+
+                    if !is_extension
+                        && !matches!(basic, BaseTypes::UserDefined(_))
+                        && value != "len"
+                    {
+                        if *basic != BaseTypes::String {
+                            return Err(CompilerException::new(
+                                call.span,
+                                CompilerExceptionCode::UnknownExtensionMethod(value),
+                            ));
+                        }
+
+                        return compile_expression_string_method(
+                            compiler,
+                            a.left.clone(),
+                            value,
+                            call.parameters,
+                            call.span,
+                        );
+                    }
+                }
             }
         }
     }
@@ -182,6 +250,7 @@ pub fn compile_expression_call(
             if let Some(self_reference) = self_reference.clone() {
                 compiler.drier();
                 let result = compiler.compile_expression(self_reference.clone());
+                let is_hashmap = matches!(result, Ok(Types::Hashmap(_, _)));
                 // Should prob not be here, but I did not know how to make clippy happy
                 match result {
                     Ok(_type) => {
@@ -193,13 +262,16 @@ pub fn compile_expression_call(
                             | Types::Function(_)
                             | Types::Module(_)
                             | Types::Void
-                            | Types::Library(_) => {
-                                if _type != Types::Basic(BaseTypes::String) {
-                                    return Err(CompilerException::WrongType(
+                            | Types::Library(_)
+                                if _type != Types::Basic(BaseTypes::String) =>
+                            {
+                                return Err(CompilerException::new(
+                                    call.span.clone(),
+                                    CompilerExceptionCode::WrongType(
                                         "array or string".to_string(),
                                         format!("{}", _type),
-                                    ));
-                                }
+                                    ),
+                                ));
                             }
                             _ => {}
                         }
@@ -209,9 +281,21 @@ pub fn compile_expression_call(
                     }
                 }
                 compiler.undrier();
-                compiler.add_to_current_function(".LENGTH { ".to_string());
+
+                // The length of a hashmap is the amount of keys it has
+                if is_hashmap {
+                    compiler.add_to_current_function(".LENGTH { .KEYS { ".to_string());
+                } else {
+                    compiler.add_to_current_function(".LENGTH { ".to_string());
+                }
+
                 let result = compiler.compile_expression(self_reference);
-                compiler.add_to_current_function("};".to_string());
+
+                if is_hashmap {
+                    compiler.add_to_current_function("}; };".to_string());
+                } else {
+                    compiler.add_to_current_function("};".to_string());
+                }
                 if result.is_err() {
                     return result;
                 } else {
@@ -236,10 +320,10 @@ pub fn compile_expression_call(
                 Call {
                     identifier: Box::new(Expression::Identifier(Identifier::new(
                         format!("{}::{}", module, index.unwrap()),
-                        0,
-                        0,
+                        call.span.clone(),
                     ))),
                     parameters: call.parameters,
+                    span: call.span,
                 },
             );
         }
@@ -258,13 +342,17 @@ pub fn compile_expression_call(
             let method = split.get(1).unwrap().to_string();
 
             let _type = compiler.compile_expression(Expression::Index(Box::new(Index {
-                left: Expression::Identifier(Identifier::new(name.clone(), 0, 0)),
-                index: Expression::Identifier(Identifier::new(method, 0, 0)),
+                left: Expression::Identifier(Identifier::new(name.clone(), call.span.clone())),
+                index: Expression::Identifier(Identifier::new(method, call.span.clone())),
+                span: call.span.clone(),
             })))?;
 
             if let Types::Function(func) = _type.clone() {
                 if func.is_method {
-                    self_reference = Some(Expression::Identifier(Identifier::new(name, 0, 0)));
+                    self_reference = Some(Expression::Identifier(Identifier::new(
+                        name,
+                        call.span.clone(),
+                    )));
                 }
             }
 
@@ -281,8 +369,7 @@ pub fn compile_expression_call(
             Types::Function(func) => func,
             _ => {
                 return Err(CompilerException::new(
-                    0,
-                    0,
+                    call.span.clone(),
                     CompilerExceptionCode::CallingNonFunction(_type.transpile()),
                 ));
             }
@@ -306,3 +393,53 @@ pub fn compile_expression_call(
 
     Ok(method_type.unwrap())
 }
+
+/// Compiles a call to a function the backend provides, its arguments are checked against the
+/// signature in [stdlib::intrinsic]
+fn compile_intrinsic_call(
+    compiler: &mut Compiler,
+    name: String,
+    parameters: Vec<Expression>,
+    span: Span,
+) -> Result<Types, CompilerException> {
+    let (expected, return_type) = match stdlib::intrinsic(&name) {
+        Some(signature) => signature,
+        None => {
+            return Err(CompilerException::new(
+                span,
+                CompilerExceptionCode::UnknownSymbol(UnknownSymbol {
+                    name: format!("intrinsic.{}", name),
+                    scope_depth: 0,
+                }),
+            ));
+        }
+    };
+
+    if parameters.len() != expected.len() {
+        return Err(CompilerException::new(
+            span,
+            CompilerExceptionCode::WrongType(
+                format!("{} arguments", parameters.len()),
+                format!("{} arguments", expected.len()),
+            ),
+        ));
+    }
+
+    compiler.add_to_current_function(format!(".CALL intrinsic::{} {{ ", name));
+
+    for (parameter, expected) in parameters.into_iter().zip(expected) {
+        let parameter_span = parameter.span();
+        let got = compiler.compile_expression(parameter)?;
+
+        if expected != Types::Auto && got != Types::Auto && got != expected {
+            return Err(CompilerException::new(
+                parameter_span,
+                CompilerExceptionCode::WrongType(got.to_string(), expected.to_string()),
+            ));
+        }
+    }
+
+    compiler.add_to_current_function("};".to_string());
+
+    Ok(return_type)
+}
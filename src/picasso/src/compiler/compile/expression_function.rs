@@ -28,6 +28,21 @@ pub fn compile_expression_function(
     // function return value.
     let random_identifier: i64 = compiler.function_count as i64;
 
+    // A declared return type is known before the body is compiled, so it can call itself
+    let predefined_type = match func.predefined_type.clone() {
+        Some(Types::Basic(BaseTypes::UserDefined(name))) => match compiler.get_compound_type(&name)
+        {
+            Some(compound) => Some(compound),
+            None => {
+                return Err(CompilerException::new(
+                    func.span.clone(),
+                    CompilerExceptionCode::UnknownType(name),
+                ));
+            }
+        },
+        predefined_type => predefined_type,
+    };
+
     // A function can optionally be named or anonymous(lambda). This is a tuple with the data that
     // it needs
     // (Transpiled, Named, Index)
@@ -45,8 +60,7 @@ pub fn compile_expression_function(
                 .any(|p| p.name == parameter.identifier.value)
             {
                 return Err(CompilerException::new(
-                    0,
-                    0,
+                    parameter.identifier.span.clone(),
                     CompilerExceptionCode::DoubleParameterName(parameter.identifier.value.clone()),
                 ));
             }
@@ -71,8 +85,7 @@ pub fn compile_expression_function(
                     param_type = compound.clone()
                 } else {
                     return Err(CompilerException::new(
-                        0,
-                        0,
+                        func.span.clone(),
                         CompilerExceptionCode::UnknownType(name.clone()),
                     ));
                 }
@@ -81,16 +94,17 @@ pub fn compile_expression_function(
             type_parameters.push(param_type)
         }
 
-        // The return type here is "Auto" as we infer it later when we compile the function body
+        // Without a declared return type it is "Auto", as we infer it later when we compile the
+        // function body
         let function_type = Types::Function(FunctionType {
-            return_type: Box::from(Types::Auto),
+            return_type: Box::from(predefined_type.clone().unwrap_or(Types::Auto)),
             parameter_types: type_parameters,
             reference: format!("local::{}", func.name),
             is_method: false,
         });
 
         // Define it with the current type and set "named_function" which we use later as well
-        let var = compiler.define_symbol(func.name.clone(), function_type, -1);
+        let var = compiler.define_symbol(func.name.clone(), function_type, -1, func.span.clone());
         named_function = Option::from((format!("var_{}", var.index), var.name.clone(), var.index));
     }
 
@@ -135,8 +149,7 @@ pub fn compile_expression_function(
                 param_type = compound.clone()
             } else {
                 return Err(CompilerException::new(
-                    0,
-                    0,
+                    parameter.identifier.span.clone(),
                     CompilerExceptionCode::UnknownType(name.clone()),
                 ));
             }
@@ -144,7 +157,12 @@ pub fn compile_expression_function(
 
         // Define the parameter in the current scope, before compiling the function body so
         // parameters can be used inside the body
-        compiler.define_symbol(parameter.identifier.value.clone(), param_type, index as i32);
+        compiler.define_symbol(
+            parameter.identifier.value.clone(),
+            param_type,
+            index as i32,
+            parameter.identifier.span.clone(),
+        );
 
         let _type = parameter.get_type();
 
@@ -162,20 +180,27 @@ pub fn compile_expression_function(
 
     compiler.add_to_current_function("} FREE {} THEN ".to_string());
 
-    // Now we compile the function body
+    // Now we compile the function body, loops around the function can not be broken out of from within it
+    let loop_depth = compiler.loop_depth;
+    compiler.loop_depth = 0;
     let result = compiler.compile_block(func.body, func.name.is_empty());
+    compiler.loop_depth = loop_depth;
 
     compiler.add_to_current_function(";".to_string());
 
-    // Currently infer and dont allow manually setting type
-    let return_type = {
-        if let Ok(_type) = result {
-            _type
-        } else if let Err(exception) = result {
-            return Err(exception);
-        } else {
-            Types::Void
+    // The type is inferred from the body, unless it was declared
+    let return_type = match (result?, predefined_type) {
+        (inferred, Some(predefined)) => {
+            if inferred != Types::Auto && predefined != Types::Auto && inferred != predefined {
+                return Err(CompilerException::new(
+                    func.span.clone(),
+                    CompilerExceptionCode::WrongType(inferred.to_string(), predefined.to_string()),
+                ));
+            }
+
+            predefined
         }
+        (inferred, None) => inferred,
     };
 
     // Set return type of named function, if it exists
@@ -4,29 +4,70 @@ use crate::compiler::compile::expression_string::compile_expression_string;
 use crate::compiler::Compiler;
 use crate::exception::compiler::CompilerException;
 use crate::parser::expression::hashmap::{HashableExpression, Hashmap};
-use crate::parser::types::Types;
+use crate::parser::types::{BaseTypes, Types};
 
+/// Compiles a hashmap literal to Arc, keys and values follow each other. When the keys, or the
+/// values, do not all have the same type they are typed as `var`, which transpiles to `VOID`.
+///
+/// Take this Loop code:
+/// ```loop
+/// {20: 30, "hello": 40}
+/// ```
+///
+/// And generates this Arc code:
+/// ```arc
+/// .HASHMAP VOID INT { .CONSTANT INT 20; .CONSTANT INT 30; .CONSTANT CHAR[] "hello"; .CONSTANT INT 40; };
+/// ```
 pub fn compile_expression_hashmap(
-    _compiler: &mut Compiler,
+    compiler: &mut Compiler,
     hashmap: Hashmap,
 ) -> Result<Types, CompilerException> {
-    let _length = hashmap.values.len();
-
-    for value in hashmap.values {
-        let result = match value.0 {
-            HashableExpression::Integer(integer) => compile_expression_integer(_compiler, integer),
-            HashableExpression::String(string) => compile_expression_string(_compiler, string),
-            HashableExpression::Boolean(boolean) => compile_expression_boolean(_compiler, boolean),
-        };
-
-        #[allow(clippy::single_match)]
-        match &result {
-            Err(_exception) => return result,
-            _ => (),
-        }
-
-        _compiler.compile_expression(value.1)?;
+    // The types are known once the first entry is compiled, so the instruction is inserted after
+    let position = compiler.current_function_position();
+    let mut types: Option<(Types, Types)> = None;
+
+    for (key, value) in hashmap.values {
+        let key_type = match key {
+            HashableExpression::Integer(integer) => compile_expression_integer(compiler, integer),
+            HashableExpression::String(string) => compile_expression_string(compiler, string),
+            HashableExpression::Boolean(boolean) => compile_expression_boolean(compiler, boolean),
+        }?;
+
+        let value_type = compiler.compile_expression(value)?;
+
+        types = Some(match types {
+            None => (key_type, value_type),
+            Some((expected_key, expected_value)) => (
+                common_type(key_type, expected_key),
+                common_type(value_type, expected_value),
+            ),
+        });
     }
 
-    Ok(Types::Void)
+    // Just like arrays an empty hashmap defaults to integers
+    let (key_type, value_type) = types.unwrap_or((
+        Types::Basic(BaseTypes::Integer),
+        Types::Basic(BaseTypes::Integer),
+    ));
+
+    compiler.insert_at_current_function(
+        position,
+        format!(
+            ".HASHMAP {} {} {{",
+            key_type.transpile(),
+            value_type.transpile()
+        ),
+    );
+    compiler.add_to_current_function("};".to_string());
+
+    Ok(Types::Hashmap(Box::new(key_type), Box::new(value_type)))
+}
+
+/// The type of the entries so far, or `var` when this entry has another type
+fn common_type(got: Types, expected: Types) -> Types {
+    if got == expected {
+        expected
+    } else {
+        Types::Auto
+    }
 }
@@ -10,6 +10,8 @@ pub fn compile_expression_identifier(
     let var = compiler.resolve_symbol(&identifier.value);
 
     if let Some(var) = var {
+        compiler.add_reference(identifier.span.clone(), &identifier.value);
+
         if var.parameter_id > -1 {
             compiler.add_to_current_function(format!(
                 ".LOAD PARAMETER {} {};",
@@ -23,8 +25,7 @@ pub fn compile_expression_identifier(
                 && !var.modifiers.public
             {
                 return Err(CompilerException::new(
-                    0,
-                    0,
+                    identifier.span,
                     CompilerExceptionCode::NotPublic(var.modifiers.module, var.name),
                 ));
             }
@@ -36,8 +37,7 @@ pub fn compile_expression_identifier(
     }
 
     Err(CompilerException::new(
-        0,
-        0,
+        identifier.span,
         CompilerExceptionCode::UnknownSymbol(UnknownSymbol {
             name: identifier.value,
             scope_depth: compiler.scope_index as u16,
@@ -6,6 +6,7 @@ use crate::parser::expression::identifier::Identifier;
 use crate::parser::expression::index::{Index, Slice};
 use crate::parser::expression::Expression;
 use crate::parser::types::{BaseTypes, Compound, FunctionType, Types};
+use crate::span::Span;
 
 pub fn compile_expression_index(
     _compiler: &mut Compiler,
@@ -13,17 +14,35 @@ pub fn compile_expression_index(
 ) -> Result<Types, CompilerException> {
     #[allow(clippy::single_match)]
     match _index.index.clone() {
-        Expression::Identifier(ident) => {
-            compile_expression_class_index(_compiler, _index.left, ident.value)
+        Expression::Identifier(ident) if !is_variable_index(_compiler, &_index.left, &ident) => {
+            compile_expression_class_index(_compiler, _index.left, ident.value, _index.span)
         }
-        _ => compile_expression_index_internal(_compiler, _index.left, _index.index),
+        _ => compile_expression_index_internal(_compiler, _index.left, _index.index, _index.span),
     }
 }
 
+/// Both `array[key]` and `class.key` are parsed the same way, if the left side can not have fields
+/// and the identifier is a variable it is an index instead of a field
+fn is_variable_index(compiler: &mut Compiler, left: &Expression, ident: &Identifier) -> bool {
+    if compiler.resolve_symbol(&ident.value).is_none() {
+        return false;
+    }
+
+    compiler.drier();
+    let result = compiler.compile_expression(left.clone());
+    compiler.undrier();
+
+    matches!(
+        result,
+        Ok(Types::Array(_)) | Ok(Types::Hashmap(_, _)) | Ok(Types::Basic(BaseTypes::String))
+    )
+}
+
 fn compile_expression_class_index(
     _compiler: &mut Compiler,
     left: Expression,
     field: String,
+    span: Span,
 ) -> Result<Types, CompilerException> {
     if let Expression::Identifier(ident) = left.clone() {
         // Try to find var
@@ -31,10 +50,11 @@ fn compile_expression_class_index(
 
         if let Some(var) = var {
             if let Types::Module(module) = var._type {
+                _compiler.add_reference(ident.span, &ident.value);
+
                 return _compiler.compile_expression(Expression::Identifier(Identifier::new(
                     format!("{}::{}", module, field),
-                    0,
-                    0,
+                    span.clone(),
                 )));
             }
         }
@@ -44,37 +64,35 @@ fn compile_expression_class_index(
     let result = _compiler.compile_expression(left.clone());
     _compiler.undrier();
 
-    if let Ok(mut check) = result {
-        if let Types::Array(_) = check {
-            // Methods for arrays
-            return match field.as_str() {
-                "push" => Ok(Types::Function(FunctionType {
-                    return_type: Box::new(Types::Void),
-                    parameter_types: vec![],
-                    reference: "ADD_TO_ARRAY".to_string(),
-                    is_method: false,
-                })),
-                &_ => Err(CompilerException::new(
-                    0,
-                    0,
-                    CompilerExceptionCode::UnknownField(field, format!("{:?}", check)),
-                )),
-            };
-        }
+    let mut check = result?;
+
+    if let Types::Array(_) = check {
+        // Methods for arrays
+        return match field.as_str() {
+            "push" => Ok(Types::Function(FunctionType {
+                return_type: Box::new(Types::Void),
+                parameter_types: vec![],
+                reference: "ADD_TO_ARRAY".to_string(),
+                is_method: false,
+            })),
+            &_ => Err(CompilerException::new(
+                span.clone(),
+                CompilerExceptionCode::UnknownField(field, format!("{:?}", check)),
+            )),
+        };
+    }
 
-        if let Types::Function(func) = check {
-            check = *func.return_type;
-        }
+    if let Types::Function(func) = check {
+        check = *func.return_type;
+    }
 
-        // Check if function exists with this specific signature
-        let var = _compiler.resolve_symbol(&format!("{}_{}", check.transpile(), field));
+    // Check if function exists with this specific signature
+    let var = _compiler.resolve_symbol(&format!("{}_{}", check.transpile(), field));
 
-        if let Some(var) = var {
-            let result = compile_expression_identifier(_compiler, Identifier::new(var.name, 0, 0));
+    if let Some(var) = var {
+        let result =
+            compile_expression_identifier(_compiler, Identifier::new(var.name, span.clone()));
 
-            return result;
-        }
-    } else {
         return result;
     }
 
@@ -122,23 +140,20 @@ fn compile_expression_class_index(
                 Ok(field.class_item_type.clone())
             } else {
                 Err(CompilerException::new(
-                    0,
-                    0,
+                    span.clone(),
                     CompilerExceptionCode::UnknownField(field, name.clone()),
                 ))
             };
         }
 
         return Err(CompilerException::new(
-            0,
-            0,
+            span.clone(),
             CompilerExceptionCode::UnknownField(field, format!("{:?}", result)),
         ));
     }
 
     Err(CompilerException::new(
-        0,
-        0,
+        span.clone(),
         CompilerExceptionCode::UnknownField(field, format!("{:?}", result)),
     ))
 }
@@ -148,13 +163,21 @@ pub fn compile_expression_assign_index(
     assign: AssignIndex,
 ) -> Result<Types, CompilerException> {
     compiler.add_to_current_function(".ASSIGN { ".to_string());
-    if let Expression::Identifier(ident) = assign.index {
-        compile_expression_class_index(compiler, assign.left, ident.value)?;
+    let field = match assign.index {
+        Expression::Identifier(ref ident) if !is_variable_index(compiler, &assign.left, ident) => {
+            Some(ident.value.clone())
+        }
+        _ => None,
+    };
+
+    if let Some(field) = field {
+        compile_expression_class_index(compiler, assign.left, field, assign.span)?;
 
         compiler.add_to_current_function("} { ".to_string());
         compiler.compile_expression(assign.value)?;
     } else {
-        compiler.add_to_current_function(".INDEX {".to_string());
+        let instruction = index_instruction(compiler, &assign.left);
+        compiler.add_to_current_function(instruction);
         compiler.compile_expression(assign.left.clone())?;
 
         compiler.add_to_current_function("} {".to_string());
@@ -173,8 +196,10 @@ fn compile_expression_index_internal(
     compiler: &mut Compiler,
     left: Expression,
     index: Expression,
+    span: Span,
 ) -> Result<Types, CompilerException> {
-    compiler.add_to_current_function(".INDEX {".to_string());
+    let instruction = index_instruction(compiler, &left);
+    compiler.add_to_current_function(instruction);
     let result = compiler.compile_expression(left);
     compiler.add_to_current_function("} {".to_string());
     compiler.compile_expression(index)?;
@@ -184,20 +209,34 @@ fn compile_expression_index_internal(
         return Ok(*value_type);
     } else if let Ok(Types::Basic(BaseTypes::String)) = result {
         return Ok(Types::Basic(BaseTypes::String));
+    } else if let Ok(Types::Hashmap(_, value_type)) = result {
+        return Ok(*value_type);
     }
 
     if let Ok(_type) = result {
         return Err(CompilerException::new(
-            0,
-            0,
+            span.clone(),
             CompilerExceptionCode::WrongType(
                 format!("{}", _type),
-                "Expected a string or array".to_string(),
+                "Expected a string, array or hashmap".to_string(),
             ),
         ));
     }
 
-    Err(CompilerException::new(0, 0, CompilerExceptionCode::Unknown))
+    Err(CompilerException::new(span, CompilerExceptionCode::Unknown))
+}
+
+/// Hashmaps are indexed by their key instead of by position, which the backend needs to know
+fn index_instruction(compiler: &mut Compiler, left: &Expression) -> String {
+    compiler.drier();
+    let result = compiler.compile_expression(left.clone());
+    compiler.undrier();
+
+    if let Ok(Types::Hashmap(_, _)) = result {
+        ".INDEX HASHMAP {".to_string()
+    } else {
+        ".INDEX {".to_string()
+    }
 }
 /// Compiles a slice to Arc
 ///
@@ -229,8 +268,7 @@ pub fn compile_expression_slice(
             Types::Basic(BaseTypes::String) => slice_type = Types::Basic(BaseTypes::String),
             _ => {
                 return Err(CompilerException::new(
-                    0,
-                    0,
+                    slice.span,
                     CompilerExceptionCode::WrongType(
                         format!("{}", var),
                         "Expected a string or array".to_string(),
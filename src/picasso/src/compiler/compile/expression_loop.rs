@@ -1,12 +1,11 @@
+use crate::compiler::compile::expression_range::compile_integer;
 use crate::compiler::Compiler;
-use crate::exception::compiler::CompilerException;
-use crate::parser::expression::index::Index;
-use crate::parser::expression::integer::Integer;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::parser::expression::identifier::Identifier;
 use crate::parser::expression::loops::{Loop, LoopArrayIterator, LoopIterator};
 use crate::parser::expression::Expression;
 use crate::parser::types::{BaseTypes, Types};
-
-use super::expression_index::compile_expression_index;
+use crate::span::Span;
 
 /// Compiles (/transpiles) the "while" loop of Loop
 ///
@@ -35,26 +34,26 @@ pub fn compile_loop_expression(
 
     // Condition
     compiler.add_to_current_function(".WHILE CONDITION {".to_string());
-    let result = compiler.compile_expression(*lp.condition);
-    if let Err(exception) = result {
-        return Err(exception);
-    }
+    compiler.compile_expression(*lp.condition)?;
 
-    compiler.add_to_current_function("} THEN ".to_string());
+    compiler.add_to_current_function("} THEN {".to_string());
 
     // Body
-    let result = compiler.compile_block(lp.body, false);
-    compiler.add_to_current_function(";".to_string());
+    let result = compiler.compile_loop_block(lp.body);
+    compiler.add_to_current_function("};".to_string());
+
+    compiler.exit_symbol_scope();
 
     result
 }
 
-/// Compiles (/transpiles) the "iterator" loop of Loop
+/// Compiles (/transpiles) the "iterator" loop of Loop, the bounds and step are evaluated once
+/// before the loop starts
 ///
 /// Take this example in Loop
 /// ```loop
 /// x := 0
-/// for(var n = 0..10) {
+/// for(var n = 0 to 10 step 2) {
 ///     x = x + 1
 /// }
 /// println(x)
@@ -63,10 +62,12 @@ pub fn compile_loop_expression(
 /// Will translate to this D code (excluding imports & main declaration)
 /// ```d
 /// auto x = 0;
-/// auto n = 0;
-/// while(n < 10) {
+/// auto next = 0;
+/// auto till = 10;
+/// while(next < till) {
+///     auto n = next;
+///     next = next + 2;
 ///     x = x + 1;
-///     n = n + 1;
 /// }
 /// writeln(x);
 /// ```
@@ -75,17 +76,91 @@ pub fn compile_loop_iterator_expression(
     lp: LoopIterator,
 ) -> Result<Types, CompilerException> {
     compiler.enter_symbol_scope();
-    // Define the identifier variable, with the starting integer
-    let var = compiler.define_symbol(lp.identifier.value, Types::Basic(BaseTypes::Integer), -1);
 
+    let integer = Types::Basic(BaseTypes::Integer);
+
+    // The value the identifier gets in the next iteration
+    let next = compiler.define_symbol("NEXT_D".to_string(), integer.clone(), -1, Span::default());
+    compiler.add_to_current_function(format!(".STORE {} {{ ", next.index));
+    compile_integer(compiler, *lp.from)?;
+    compiler.add_to_current_function("};".to_string());
+
+    let till = compiler.define_symbol("TILL_D".to_string(), integer.clone(), -1, Span::default());
+    compiler.add_to_current_function(format!(".STORE {} {{ ", till.index));
+    compile_integer(compiler, *lp.till)?;
+    compiler.add_to_current_function("};".to_string());
+
+    // A step written as a number tells the direction at compile time, otherwise it is stored and
+    // the direction is checked every iteration
+    let direction = match lp.step.as_deref() {
+        None => Some(1),
+        Some(Expression::Integer(integer)) if integer.value == 0 => {
+            return Err(CompilerException::new(
+                integer.span.clone(),
+                CompilerExceptionCode::ZeroStep,
+            ));
+        }
+        Some(Expression::Integer(integer)) => Some(integer.value.signum()),
+        Some(_) => None,
+    };
+
+    let step = match lp.step {
+        Some(step) if direction.is_none() => {
+            let symbol =
+                compiler.define_symbol("STEP_D".to_string(), integer.clone(), -1, Span::default());
+            compiler.add_to_current_function(format!(".STORE {} {{ ", symbol.index));
+            compile_integer(compiler, *step)?;
+            compiler.add_to_current_function("};".to_string());
+
+            format!(".LOAD VARIABLE {};", symbol.index)
+        }
+        Some(step) => {
+            if let Expression::Integer(integer) = *step {
+                format!(".CONSTANT INT {};", integer.value)
+            } else {
+                unreachable!("Only integer steps have a known direction")
+            }
+        }
+        None => ".CONSTANT INT 1;".to_string(),
+    };
+
+    let (ascending, descending) = if lp.inclusive {
+        (".LESSTHANOREQUAL", ".GREATERTHANOREQUAL")
+    } else {
+        (".LESSTHAN", ".GREATERTHAN")
+    };
+    let compare = |instruction: &str| {
+        format!(
+            "{} {{ .LOAD VARIABLE {}; .LOAD VARIABLE {}; }};",
+            instruction, next.index, till.index
+        )
+    };
+
+    let condition = match direction {
+        Some(1) => compare(ascending),
+        Some(_) => compare(descending),
+        None => format!(
+            ".OR {{ .AND {{ .GREATERTHAN {{ {} .CONSTANT INT 0; }}; {} }}; .AND {{ .LESSTHAN {{ {} .CONSTANT INT 0; }}; {} }}; }};",
+            step,
+            compare(ascending),
+            step,
+            compare(descending)
+        ),
+    };
+
+    // Define the identifier variable after the bounds, so they can not refer to it
+    let var = compiler.define_symbol(lp.identifier.value, integer, -1, lp.identifier.span);
+
+    compiler.add_to_current_function(format!(".WHILE CONDITION {{ {} }} THEN {{", condition));
+
+    // Move to the next value first, so a "continue" in the body can not skip it
     compiler.add_to_current_function(format!(
-        ".STORE {} {{.CONSTANT INT {};}};",
-        var.index, lp.from
+        ".STORE {} {{ .LOAD VARIABLE {}; }};",
+        var.index, next.index
     ));
-
     compiler.add_to_current_function(format!(
-        ".WHILE CONDITION {{ .GREATERTHAN {{ .CONSTANT INT {}; .LOAD VARIABLE {}; }}; }} THEN {{",
-        lp.till, var.index
+        ".STORE {} {{ .ADD {{ .LOAD VARIABLE {}; {} }}; }};",
+        next.index, next.index, step
     ));
 
     // Compile the body that is executed
@@ -93,11 +168,6 @@ pub fn compile_loop_iterator_expression(
 
     compiler.exit_symbol_scope();
 
-    // Increase it
-    compiler.add_to_current_function(format!(
-        ".STORE {} {{ .ADD {{.LOAD VARIABLE {};.CONSTANT INT 1;}};}};",
-        var.index, var.index
-    ));
     compiler.add_to_current_function("};".to_string());
 
     Ok(result)
@@ -122,45 +192,87 @@ pub fn compile_loop_iterator_expression(
 /// ```
 pub fn compile_loop_array_iterator_expression(
     compiler: &mut Compiler,
-    lp: LoopArrayIterator,
+    mut lp: LoopArrayIterator,
 ) -> Result<Types, CompilerException> {
     compiler.enter_symbol_scope();
 
+    compiler.drier();
+    let iterating = compiler.compile_expression(*lp.array.clone());
+    compiler.undrier();
+
+    let iterating = iterating?;
+
+    // The identifier has the type of the elements, values only known at runtime stay unknown
+    let mut var_type = match &iterating {
+        Types::Array(element) => *element.clone(),
+        Types::Auto => Types::Auto,
+        _ => Types::Basic(BaseTypes::Integer),
+    };
+
+    // Hashmaps are iterated over by their keys, which are stored in an array first
+    if let Types::Hashmap(key_type, _) = iterating {
+        let keys = compiler.define_symbol(
+            "KEYS_D".to_string(),
+            Types::Array(Box::new(*key_type.clone())),
+            -1,
+            Span::default(),
+        );
+
+        compiler.add_to_current_function(format!(".STORE {} {{ .KEYS {{ ", keys.index));
+        compiler.compile_expression(*lp.array)?;
+        compiler.add_to_current_function("}; };".to_string());
+
+        lp.array = Box::new(Expression::Identifier(Identifier::new(
+            "KEYS_D".to_string(),
+            lp.span.clone(),
+        )));
+        var_type = *key_type;
+    } else if !matches!(*lp.array, Expression::Identifier(_)) {
+        // Other values, like ranges or calls, are only evaluated once instead of every iteration
+        let array = compiler.define_symbol("ARRAY_D".to_string(), iterating, -1, Span::default());
+
+        compiler.add_to_current_function(format!(".STORE {} {{ ", array.index));
+        compiler.compile_expression(*lp.array)?;
+        compiler.add_to_current_function("};".to_string());
+
+        lp.array = Box::new(Expression::Identifier(Identifier::new(
+            "ARRAY_D".to_string(),
+            lp.span.clone(),
+        )));
+    }
+
     // Define the identifier variable, with the starting value of the array
-    let var = compiler.define_symbol(lp.identifier.value, Types::Basic(BaseTypes::Integer), -1);
-    let index = compiler.define_symbol("INDEX_D".to_string(), Types::Basic(BaseTypes::Integer), -1);
+    let var = compiler.define_symbol(lp.identifier.value, var_type, -1, lp.identifier.span);
+    let index = compiler.define_symbol(
+        "INDEX_D".to_string(),
+        Types::Basic(BaseTypes::Integer),
+        -1,
+        Span::default(),
+    );
 
     compiler.add_to_current_function(format!(".STORE {} {{ .CONSTANT INT 0; }};", index.index));
-    compiler.add_to_current_function(format!(".STORE {} {{ ", var.index));
-
-    // TODO: Get result and set it as type of 'var'
-    let _ = compile_expression_index(
-        compiler,
-        Index {
-            left: *lp.array.clone(),
-            index: Expression::Integer(Integer { value: 0 }),
-        },
-    );
 
-    compiler.add_to_current_function("}; .WHILE CONDITION { .GREATERTHAN { .LENGTH {".to_string());
-    compiler.compile_expression(*lp.array.clone())?;
     compiler.add_to_current_function(format!(
-        " }}; .LOAD VARIABLE {}; }}; }} THEN {{",
+        ".WHILE CONDITION {{ .LESSTHAN {{ .LOAD VARIABLE {}; .LENGTH {{",
         index.index
     ));
+    compiler.compile_expression(*lp.array.clone())?;
+    compiler.add_to_current_function(" }; }; } THEN {".to_string());
 
-    // Compile body and then increase the index
-    let result = compiler.compile_loop_block(lp.body)?;
+    // Load the current value and increase the index before the body, so a "continue" in the body
+    // can not skip it
+    compiler.add_to_current_function(format!(".STORE {} {{ .INDEX {{", var.index));
+    compiler.compile_expression(*lp.array)?;
+    compiler.add_to_current_function(format!("}} {{ .LOAD VARIABLE {}; }}; }};", index.index));
 
     compiler.add_to_current_function(format!(
         ".STORE {} {{ .ADD {{.LOAD VARIABLE {};.CONSTANT INT 1;}};}};",
         index.index, index.index
     ));
-    compiler.add_to_current_function(format!(".STORE {} {{ .INDEX {{", var.index));
 
-    compiler.compile_expression(*lp.array)?;
+    let result = compiler.compile_loop_block(lp.body)?;
 
-    compiler.add_to_current_function(format!("}} {{ .LOAD VARIABLE {}; }}; }};", index.index));
+    compiler.exit_symbol_scope();
 
     compiler.add_to_current_function("};".to_string());
 
@@ -0,0 +1,46 @@
+use crate::compiler::Compiler;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::parser::expression::prefix::Prefix;
+use crate::parser::types::{BaseTypes, Types};
+
+pub fn compile_expression_prefix(
+    compiler: &mut Compiler,
+    prefix: Prefix,
+) -> Result<Types, CompilerException> {
+    let (instruction, expected) = match prefix.operator.as_str() {
+        "!" => (".NOT", "bool"),
+        "-" => (".NEGATE", "int or float"),
+        _ => {
+            return Err(CompilerException::new(
+                prefix.span,
+                CompilerExceptionCode::UnknownSuffixOperator(prefix.operator),
+            ))
+        }
+    };
+
+    let span = prefix.span.clone();
+
+    compiler.add_to_current_function(format!("{} {{", instruction));
+    let right = compiler.compile_expression(prefix.right)?;
+    compiler.add_to_current_function("};".to_string());
+
+    // "!" only inverts booleans and "-" only negates numbers, the type stays the same
+    let allowed = matches!(
+        (instruction, &right),
+        (_, Types::Auto)
+            | (".NOT", Types::Basic(BaseTypes::Boolean))
+            | (
+                ".NEGATE",
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float)
+            )
+    );
+
+    if !allowed {
+        return Err(CompilerException::new(
+            span,
+            CompilerExceptionCode::WrongType(format!("{}", right), expected.to_string()),
+        ));
+    }
+
+    Ok(right)
+}
@@ -0,0 +1,53 @@
+use crate::compiler::Compiler;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::parser::expression::range::Range;
+use crate::parser::expression::Expression;
+use crate::parser::types::{BaseTypes, Types};
+
+/// Compiles a range into an array of integers
+///
+/// Take this example in Loop
+/// ```loop
+/// 1..=3
+/// ```
+///
+/// Will translate to this Arc code
+/// ```arc
+/// .RANGE { .CONSTANT INT 1; } { .ADD { .CONSTANT INT 3; .CONSTANT INT 1; }; } { .CONSTANT INT 1; };
+/// ```
+pub fn compile_expression_range(
+    compiler: &mut Compiler,
+    range: Range,
+) -> Result<Types, CompilerException> {
+    compiler.add_to_current_function(".RANGE { ".to_string());
+    compile_integer(compiler, range.from)?;
+    compiler.add_to_current_function("} { ".to_string());
+
+    if range.inclusive {
+        compiler.add_to_current_function(".ADD { ".to_string());
+        compile_integer(compiler, range.till)?;
+        compiler.add_to_current_function(".CONSTANT INT 1; }; ".to_string());
+    } else {
+        compile_integer(compiler, range.till)?;
+    }
+
+    compiler.add_to_current_function("} { .CONSTANT INT 1; };".to_string());
+
+    Ok(Types::Array(Box::new(Types::Basic(BaseTypes::Integer))))
+}
+
+/// Compiles an expression that has to result in an integer, like the bounds of a range
+pub fn compile_integer(
+    compiler: &mut Compiler,
+    expression: Expression,
+) -> Result<(), CompilerException> {
+    let span = expression.span();
+
+    match compiler.compile_expression(expression)? {
+        Types::Basic(BaseTypes::Integer) | Types::Auto => Ok(()),
+        other => Err(CompilerException::new(
+            span,
+            CompilerExceptionCode::WrongType(other.to_string(), "int".to_string()),
+        )),
+    }
+}
@@ -0,0 +1,151 @@
+use crate::compiler::Compiler;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::parser::expression::Expression;
+use crate::parser::types::{BaseTypes, Types};
+use crate::span::Span;
+
+/// The signature of a method every string has, without the string it is called on
+struct StringMethod {
+    parameters: Vec<Types>,
+    /// Amount of parameters that have to be given, the others are optional
+    required: usize,
+    /// Whether any amount of values of any type can be given after the parameters
+    variadic: bool,
+    return_type: Types,
+}
+
+fn string_method(name: &str) -> Option<StringMethod> {
+    let int = || Types::Basic(BaseTypes::Integer);
+    let float = || Types::Basic(BaseTypes::Float);
+    let string = || Types::Basic(BaseTypes::String);
+    let boolean = || Types::Basic(BaseTypes::Boolean);
+    let strings = || Types::Array(Box::new(string()));
+
+    let (parameters, required, variadic, return_type) = match name {
+        "split" => (vec![string()], 1, false, strings()),
+        "join" => (vec![strings()], 1, false, string()),
+        "trim" | "to_upper" | "to_lower" => (vec![], 0, false, string()),
+        "replace" => (vec![string(), string()], 2, false, string()),
+        "contains" | "starts_with" | "ends_with" => (vec![string()], 1, false, boolean()),
+        "find" => (vec![string()], 1, false, int()),
+        "repeat" => (vec![int()], 1, false, string()),
+        "chars" => (vec![], 0, false, strings()),
+        // Without a fallback a string that is not a number is a runtime error
+        "to_int" => (vec![int()], 0, false, int()),
+        "to_float" => (vec![float()], 0, false, float()),
+        "format" => (vec![], 0, true, string()),
+        _ => return None,
+    };
+
+    Some(StringMethod {
+        parameters,
+        required,
+        variadic,
+        return_type,
+    })
+}
+
+/// The names of every method built into strings
+pub const STRING_METHODS: [&str; 16] = [
+    "split",
+    "join",
+    "trim",
+    "to_upper",
+    "to_lower",
+    "replace",
+    "contains",
+    "starts_with",
+    "ends_with",
+    "find",
+    "repeat",
+    "chars",
+    "to_int",
+    "to_float",
+    "format",
+    "len",
+];
+
+/// The type of a method built into strings as it is shown to the user, eg. "fn(string): bool"
+pub fn string_method_type(name: &str) -> Option<String> {
+    if name == "len" {
+        return Some("fn(): int".to_string());
+    }
+
+    let method = string_method(name)?;
+    let mut parameters: Vec<String> = method
+        .parameters
+        .iter()
+        .map(|parameter| parameter.to_string())
+        .collect();
+
+    if method.variadic {
+        parameters.push("var...".to_string());
+    }
+
+    Some(format!(
+        "fn({}): {}",
+        parameters.join(", "),
+        method.return_type
+    ))
+}
+
+/// Compiles a method that is built into strings, the backend implements them
+///
+/// Take this example in Loop
+/// ```loop
+/// "a,b".split(",")
+/// ```
+///
+/// Will translate to this Arc code
+/// ```arc
+/// .CALL string::split { .CONSTANT CHAR[] "a,b"; .CONSTANT CHAR[] ","; };
+/// ```
+pub fn compile_expression_string_method(
+    compiler: &mut Compiler,
+    text: Expression,
+    name: String,
+    arguments: Vec<Expression>,
+    span: Span,
+) -> Result<Types, CompilerException> {
+    let method = match string_method(&name) {
+        Some(method) => method,
+        None => {
+            return Err(CompilerException::new(
+                span,
+                CompilerExceptionCode::UnknownExtensionMethod(name),
+            ))
+        }
+    };
+
+    let too_many = !method.variadic && arguments.len() > method.parameters.len();
+    if arguments.len() < method.required || too_many {
+        return Err(CompilerException::new(
+            span,
+            CompilerExceptionCode::WrongType(
+                format!("{} arguments", arguments.len()),
+                format!("{} arguments", method.parameters.len()),
+            ),
+        ));
+    }
+
+    compiler.add_to_current_function(format!(".CALL string::{} {{ ", name));
+    compiler.compile_expression(text)?;
+
+    for (index, argument) in arguments.into_iter().enumerate() {
+        let argument_span = argument.span();
+        let got = compiler.compile_expression(argument)?;
+
+        if let Some(expected) = method.parameters.get(index) {
+            if got != Types::Auto && &got != expected {
+                return Err(CompilerException::new(
+                    argument_span,
+                    CompilerExceptionCode::WrongType(got.to_string(), expected.to_string()),
+                ));
+            }
+        }
+    }
+
+    compiler.add_to_current_function("};".to_string());
+
+    Ok(method.return_type)
+}
@@ -1,5 +1,5 @@
 use crate::compiler::Compiler;
-use crate::exception::compiler::CompilerException;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
 use crate::parser::expression::suffix::Suffix;
 use crate::parser::types::{BaseTypes, Types};
 
@@ -7,54 +7,133 @@ pub fn compile_expression_suffix(
     _compiler: &mut Compiler,
     _suffix: Suffix,
 ) -> Result<Types, CompilerException> {
-    match _suffix.operator.as_str() {
-        "^" => {
-            _compiler.add_to_current_function(".POWER {".to_string());
-        }
-        "and" => {
-            _compiler.add_to_current_function(".AND {".to_string());
-        }
-        "or" => {
-            _compiler.add_to_current_function(".OR {".to_string());
-        }
-        "+" => {
-            _compiler.add_to_current_function(".ADD {".to_string());
-        }
-        "-" => {
-            _compiler.add_to_current_function(".SUBTRACT {".to_string());
-        }
-        "*" => {
-            _compiler.add_to_current_function(".MULTIPLY {".to_string());
-        }
-        "/" => {
-            _compiler.add_to_current_function(".DIVIDE {".to_string());
-        }
-        ">" | "<" => {
-            _compiler.add_to_current_function(".GREATERTHAN {".to_string());
-        }
-        "==" => {
-            _compiler.add_to_current_function(".EQUALS {".to_string());
-        }
-        "!=" => {
-            _compiler.add_to_current_function(".NOTEQUALS {".to_string());
-        }
-        "%" => {
-            _compiler.add_to_current_function(".MODULO {".to_string());
-        }
+    let instruction = match _suffix.operator.as_str() {
+        "^" => ".POWER",
+        "and" => ".AND",
+        "or" => ".OR",
+        "+" => ".ADD",
+        "-" => ".SUBTRACT",
+        "*" => ".MULTIPLY",
+        "/" => ".DIVIDE",
+        ">" => ".GREATERTHAN",
+        "<" => ".LESSTHAN",
+        ">=" => ".GREATERTHANOREQUAL",
+        "<=" => ".LESSTHANOREQUAL",
+        "==" => ".EQUALS",
+        "!=" => ".NOTEQUALS",
+        "%" => ".MODULO",
         _ => {
-            _compiler.add_to_current_function("UNKNOWN_OPERATOR".to_string());
+            return Err(CompilerException::new(
+                _suffix.span,
+                CompilerExceptionCode::UnknownSuffixOperator(_suffix.operator),
+            ))
         }
-    }
+    };
+
+    // The instruction depends on the types of the operands, so it is inserted once those are known
+    let position = _compiler.current_function_position();
 
-    if _suffix.operator == "<" {
-        _compiler.compile_expression(_suffix.right)?;
-        _compiler.compile_expression(_suffix.left)?;
+    // Operands are always evaluated from left to right
+    let left = _compiler.compile_expression(_suffix.left)?;
+    let between = _compiler.current_function_position();
+    let right = _compiler.compile_expression(_suffix.right)?;
+    _compiler.add_to_current_function("};".to_string());
+
+    let result = match suffix_type(&_suffix.operator, &left, &right) {
+        Some(result) => result,
+        None => {
+            return Err(CompilerException::new(
+                _suffix.span,
+                CompilerExceptionCode::WrongType(
+                    format!("{} {} {}", left, _suffix.operator, right),
+                    expected_operands(&_suffix.operator).to_string(),
+                ),
+            ))
+        }
+    };
+
+    // Adding two strings together is pushing one string onto the other, which has its operands in
+    // separate blocks
+    if let Types::Basic(BaseTypes::String) = result {
+        _compiler.insert_at_current_function(between, "} {".to_string());
+        _compiler.insert_at_current_function(position, ".PUSH {".to_string());
     } else {
-        _compiler.compile_expression(_suffix.left)?;
-        _compiler.compile_expression(_suffix.right)?;
+        _compiler.insert_at_current_function(position, format!("{} {{", instruction));
     }
-    _compiler.add_to_current_function("};".to_string());
 
-    // Suffix expressions are currently only for integers
-    Ok(Types::Basic(BaseTypes::Integer))
+    Ok(result)
+}
+
+/// The type a binary operator results in for the types of its operands, [None] if the operator
+/// can not be used on those types. Types that are only known at runtime are allowed with every
+/// type the operator can be used on.
+fn suffix_type(operator: &str, left: &Types, right: &Types) -> Option<Types> {
+    let boolean = Types::Basic(BaseTypes::Boolean);
+
+    match operator {
+        "+" | "-" | "*" | "/" | "^" | "%" => match (left, right) {
+            (Types::Basic(BaseTypes::Integer), Types::Basic(BaseTypes::Integer)) => {
+                Some(Types::Basic(BaseTypes::Integer))
+            }
+            (
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float),
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float),
+            ) => Some(Types::Basic(BaseTypes::Float)),
+            (Types::Basic(BaseTypes::String), Types::Basic(BaseTypes::String))
+                if operator == "+" =>
+            {
+                Some(Types::Basic(BaseTypes::String))
+            }
+            // Whatever is known about the other side is used, otherwise numbers are integers
+            (Types::Auto, other) | (other, Types::Auto) => match other {
+                Types::Basic(BaseTypes::Float) => Some(Types::Basic(BaseTypes::Float)),
+                Types::Basic(BaseTypes::String) if operator == "+" => {
+                    Some(Types::Basic(BaseTypes::String))
+                }
+                Types::Basic(BaseTypes::Integer) | Types::Auto => {
+                    Some(Types::Basic(BaseTypes::Integer))
+                }
+                _ => None,
+            },
+            _ => None,
+        },
+        ">" | "<" | ">=" | "<=" => match (left, right) {
+            (
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float),
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float),
+            )
+            | (Types::Basic(BaseTypes::String), Types::Basic(BaseTypes::String))
+            | (Types::Auto, _)
+            | (_, Types::Auto) => Some(boolean),
+            _ => None,
+        },
+        "==" | "!=" => match (left, right) {
+            (
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float),
+                Types::Basic(BaseTypes::Integer | BaseTypes::Float),
+            ) => Some(boolean),
+            // Two different basic types are never equal
+            (Types::Basic(left), Types::Basic(right)) if left != right => None,
+            _ => Some(boolean),
+        },
+        "and" | "or" => match (left, right) {
+            (
+                Types::Basic(BaseTypes::Boolean) | Types::Auto,
+                Types::Basic(BaseTypes::Boolean) | Types::Auto,
+            ) => Some(boolean),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Describes which operands an operator can be used on, for the error message
+fn expected_operands(operator: &str) -> &'static str {
+    match operator {
+        "+" => "int, float or string",
+        ">" | "<" | ">=" | "<=" => "int, float or string",
+        "==" | "!=" => "the same type on both sides",
+        "and" | "or" => "bool",
+        _ => "int or float",
+    }
 }
@@ -1,6 +1,8 @@
 //! Contains compilation functions for all nodes
 /// Transpiling an array to D code
 pub mod expression_array;
+/// Transpiling the methods built into arrays to D code
+pub mod expression_array_method;
 /// Transpiling a bool to D code
 pub mod expression_bool;
 /// Transpiling function calls to D code
@@ -23,8 +25,14 @@ pub mod expression_integer;
 pub mod expression_loop;
 /// Transpiling nulls to D code
 pub mod expression_null;
+/// Transpiling prefix expressions to D code
+pub mod expression_prefix;
+/// Transpiling ranges to D code
+pub mod expression_range;
 /// Transpiling strings to D code
 pub mod expression_string;
+/// Transpiling the methods built into strings to D code
+pub mod expression_string_method;
 /// Transpiling suffix expressions to D code
 pub mod expression_suffix;
 /// Transpiling breaks to D code
@@ -32,6 +40,8 @@ pub mod statement_break;
 pub mod statement_class;
 /// Transpiling constant declarations to D code
 pub mod statement_constant_declaration;
+/// Transpiling continues to D code
+pub mod statement_continue;
 pub mod statement_extend;
 /// Transpiling imports to D code
 pub mod statement_import;
@@ -1,5 +1,5 @@
 use crate::compiler::Compiler;
-use crate::exception::compiler::CompilerException;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
 use crate::parser::statement::break_statement::BreakStatement;
 use crate::parser::types::Types;
 
@@ -7,7 +7,14 @@ pub fn compile_break_statement(
     _compiler: &mut Compiler,
     _br: BreakStatement,
 ) -> Result<Types, CompilerException> {
-    _compiler.add_to_current_function("break".to_string());
+    if _compiler.loop_depth == 0 {
+        return Err(CompilerException::new(
+            _br.span,
+            CompilerExceptionCode::BreakStatementNotAllowedOutsideLoop,
+        ));
+    }
+
+    _compiler.add_to_current_function(".BREAK;".to_string());
 
     Ok(Types::Void)
 }
@@ -1,10 +1,10 @@
 use crate::compiler::Compiler;
-use crate::exception::compiler::CompilerException;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
 use crate::parser::expression::function::Function;
 use crate::parser::expression::integer::Integer;
 use crate::parser::expression::Expression;
 use crate::parser::statement::class::{Class, ClassItem};
-use crate::parser::types::{ClassItemType, Compound, FunctionType, Types};
+use crate::parser::types::{BaseTypes, ClassItemType, Compound, FunctionType, Types};
 
 pub fn compile_class_statement(
     compiler: &mut Compiler,
@@ -14,8 +14,9 @@ pub fn compile_class_statement(
 
     let var = compiler.define_symbol(
         class.name.clone(),
-        Types::Compound(Compound("".to_string(), Box::new(vec![]))),
+        Types::Compound(Compound("".to_string(), Box::default())),
         -1,
+        class.span.clone(),
     );
 
     compiler.add_to_current_function(format!(".COMPOUND \"{}\" {{ ", var.transpile()));
@@ -48,25 +49,23 @@ pub fn compile_class_statement(
                 let node = compiler.compile_expression(*property.expression.clone());
                 compiler.undrier();
 
-                if let Ok(_type) = node {
-                    compiler.add_to_current_function(format!("{};", _type.transpile()));
-
-                    let mut new_item = ClassItemType {
-                        name,
-                        index,
-                        class_item_type: _type,
-                        value: *property.expression.clone(),
-                    };
-
-                    if let Some(replace) = replace {
-                        new_item.index = replace.index;
-                        *replace = new_item
-                    } else {
-                        items.push(new_item)
-                    }
-                } else {
-                    return node;
+                let _type = node?;
+
+                compiler.add_to_current_function(format!("{};", _type.transpile()));
+
+                let mut new_item = ClassItemType {
+                    name,
+                    index,
+                    class_item_type: _type,
+                    value: *property.expression.clone(),
                 };
+
+                if let Some(replace) = replace {
+                    new_item.index = replace.index;
+                    *replace = new_item
+                } else {
+                    items.push(new_item)
+                }
             }
             ClassItem::Method(method) => {
                 let mut new_item = ClassItemType {
@@ -89,6 +88,7 @@ pub fn compile_class_statement(
                         body: method.body.clone(),
                         predefined_type: Some(method.return_type.clone()),
                         public: false,
+                        span: method.body.span.clone(),
                     }),
                 };
 
@@ -102,7 +102,10 @@ pub fn compile_class_statement(
                 compiler.add_to_current_function(format!("{};", method.return_type.transpile()));
             }
             ClassItem::Lazy(lazy) => {
-                let value = Expression::Integer(Integer { value: 0 });
+                let value = Expression::Integer(Integer {
+                    value: 0,
+                    span: class.span.clone(),
+                });
 
                 let mut new_item = ClassItemType {
                     name,
@@ -130,7 +133,37 @@ pub fn compile_class_statement(
         }
     }
 
-    compiler.add_to_current_function("};".to_string());
+    // Classes can decide how they are shown by defining "to_string"
+    if let Some(to_string) = items.iter().find(|item| item.name == "to_string") {
+        let expected = Types::Function(FunctionType {
+            return_type: Box::new(Types::Basic(BaseTypes::String)),
+            parameter_types: vec![],
+            reference: "".to_string(),
+            is_method: true,
+        });
+
+        let valid = matches!(&to_string.class_item_type, Types::Function(function)
+            if function.parameter_types.is_empty()
+                && *function.return_type == Types::Basic(BaseTypes::String));
+
+        if !valid {
+            return Err(CompilerException::new(
+                class.span,
+                CompilerExceptionCode::WrongType(
+                    to_string.class_item_type.to_string(),
+                    expected.to_string(),
+                ),
+            ));
+        }
+    }
+
+    // The names of the fields in the order they are stored in, to show instances of the class
+    let fields: String = items
+        .iter()
+        .map(|item| format!("\"{}\";", item.name))
+        .collect();
+
+    compiler.add_to_current_function(format!("}} FIELDS \"{}\" {{ {}}};", class.name, fields));
 
     let var = compiler
         .get_symbol_mutable(var.index, var.name, None)
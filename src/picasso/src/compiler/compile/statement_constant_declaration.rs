@@ -7,7 +7,12 @@ pub fn compile_statement_constant_declaration(
     compiler: &mut Compiler,
     constant: ConstantDeclaration,
 ) -> Result<Types, CompilerException> {
-    let var = compiler.define_symbol(constant.ident.value, constant.data_type.clone(), -1);
+    let var = compiler.define_symbol(
+        constant.ident.value,
+        constant.data_type.clone(),
+        -1,
+        constant.ident.span,
+    );
     // let result = compiler.compile_expression(*variable.value);
 
     // TODO: Check whether value has the same type as the type, otherwise there will be a D error
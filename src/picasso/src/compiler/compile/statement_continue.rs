@@ -0,0 +1,20 @@
+use crate::compiler::Compiler;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
+use crate::parser::statement::continue_statement::ContinueStatement;
+use crate::parser::types::Types;
+
+pub fn compile_continue_statement(
+    _compiler: &mut Compiler,
+    _cn: ContinueStatement,
+) -> Result<Types, CompilerException> {
+    if _compiler.loop_depth == 0 {
+        return Err(CompilerException::new(
+            _cn.span,
+            CompilerExceptionCode::ContinueStatementNotAllowedOutsideLoop,
+        ));
+    }
+
+    _compiler.add_to_current_function(".CONTINUE;".to_string());
+
+    Ok(Types::Void)
+}
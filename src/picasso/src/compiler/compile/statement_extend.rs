@@ -21,7 +21,10 @@ pub fn compile_extend_statement(
     };
 
     if raw_type.is_none() {
-        return Err(CompilerException::new(0, 0, CompilerExceptionCode::Unknown));
+        return Err(CompilerException::new(
+            class.identifier.span,
+            CompilerExceptionCode::Unknown,
+        ));
     }
 
     if let Some(raw_type) = raw_type {
@@ -49,7 +52,7 @@ pub fn compile_extend_statement(
                 params.insert(
                     0,
                     Parameter {
-                        identifier: Identifier::new("self".to_string(), 0, 0),
+                        identifier: Identifier::new("self".to_string(), class.span.clone()),
                         _type: raw_type.clone(),
                     },
                 );
@@ -62,6 +65,7 @@ pub fn compile_extend_statement(
                         body: method.body.clone(),
                         predefined_type: None,
                         public: false,
+                        span: method.body.span.clone(),
                     },
                 )?;
             }
@@ -8,6 +8,7 @@ use crate::parser::statement::import::Import;
 use crate::parser::statement::variable::VariableDeclaration;
 use crate::parser::types::Types;
 use crate::parser::{build_parser, expression};
+use crate::stdlib::{STD_FILES, STD_LOCATION, STD_PATH};
 use std::ffi::OsStr;
 use std::fs;
 use std::path::Path;
@@ -19,6 +20,16 @@ pub fn compile_import_statement(
     let import_as = import.identifier.clone();
     let file_path = import.file.clone();
 
+    // The standard library is part of the compiler, so it is found from any directory
+    if file_path == STD_PATH {
+        let files = STD_FILES
+            .iter()
+            .map(|(name, contents)| (name.to_string(), contents.to_string()))
+            .collect();
+
+        return compile_module(compiler, import, STD_LOCATION.to_string(), files);
+    }
+
     // Find the file, based on the current location of the compiler
     let compiler_location = Path::new(compiler.location.as_str());
     let base_path: &Path = if compiler_location.display().to_string().is_empty() {
@@ -38,8 +49,7 @@ pub fn compile_import_statement(
             // Check if file exists
             if !path.exists() {
                 return Err(CompilerException::new(
-                    0,
-                    0,
+                    import.span,
                     CompilerExceptionCode::CanNotReadFile(path_as_string),
                 ));
             }
@@ -51,39 +61,16 @@ pub fn compile_import_statement(
                     contents
                 } else {
                     return Err(CompilerException::new(
-                        0,
-                        0,
+                        import.span,
                         CompilerExceptionCode::CanNotReadFile(path_as_string),
                     ));
                 }
             };
 
-            // Parse the file
-            let lexer = build_lexer(contents.as_str());
-            let mut parser = build_parser(lexer, path.to_str().unwrap());
-
-            let program = parser.parse()?;
-
-            compiler.enter_location(path_as_string);
-            compiler.compiled_from = contents;
-
-            let result = compiler.compile(program);
-
-            if let Err(result) = result {
-                return Err(result);
-            }
-
-            let variables = compiler.exit_location();
+            compiler.imported_files.push(path_as_string.clone());
 
-            let assign = VariableDeclaration {
-                ident: Identifier::new(import_as, 0, 0),
-                // Value is irrelevant, but required
-                value: Box::new(expression::Expression::Integer(Integer { value: 0 })),
-                data_type: Types::Module(variables),
-                location: (-1, 0),
-            };
-
-            return compile_statement_variable_declaration(compiler, assign);
+            let files = vec![(path_as_string.clone(), contents)];
+            return compile_module(compiler, import, path_as_string, files);
         }
     }
 
@@ -97,3 +84,73 @@ pub fn compile_import_statement(
 
     Ok(Types::Void)
 }
+
+/// Compiles the files of a module in a location of their own, and declares the module as a variable
+/// with the name it is imported as
+fn compile_module(
+    compiler: &mut Compiler,
+    import: Import,
+    location: String,
+    files: Vec<(String, String)>,
+) -> Result<Types, CompilerException> {
+    let mut programs = Vec::new();
+
+    // Parse the files, each with their own name so errors point to the right one
+    for (name, contents) in files {
+        let lexer = build_lexer(contents.as_str());
+        let mut parser = build_parser(lexer, name.as_str());
+        parser.max_errors = compiler.max_errors - compiler.errors.len();
+
+        let program = parser.parse();
+
+        if program.is_err() {
+            // The diagnostics come from the parser, it knows the line of code of its errors
+            for error in parser.errors.iter() {
+                compiler.diagnostics.push(parser.diagnostic(error));
+            }
+
+            for error in parser.errors {
+                compiler.errors.push(CompilerException::from(error));
+            }
+
+            return Err(CompilerException::new(
+                import.span,
+                CompilerExceptionCode::ImportFailed(location),
+            ));
+        }
+
+        compiler.sources.insert(name, contents);
+        programs.push(program.unwrap());
+    }
+
+    compiler.enter_location(location.clone());
+
+    let error_count = compiler.errors.len();
+    for program in programs {
+        compiler.compile_program(program);
+    }
+
+    if compiler.errors.len() > error_count {
+        compiler.exit_location();
+
+        return Err(CompilerException::new(
+            import.span,
+            CompilerExceptionCode::ImportFailed(location),
+        ));
+    }
+
+    let variables = compiler.exit_location();
+
+    let assign = VariableDeclaration {
+        ident: Identifier::new(import.identifier, import.span.clone()),
+        // Value is irrelevant, but required
+        value: Box::new(expression::Expression::Integer(Integer {
+            value: 0,
+            span: import.span.clone(),
+        })),
+        data_type: Types::Module(variables),
+        span: import.span,
+    };
+
+    compile_statement_variable_declaration(compiler, assign)
+}
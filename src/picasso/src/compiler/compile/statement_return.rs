@@ -9,8 +9,7 @@ pub fn compile_return_statement(
 ) -> Result<Types, CompilerException> {
     if _compiler.scope_index == 0 {
         return Err(CompilerException::new(
-            0,
-            0,
+            rt.span,
             CompilerExceptionCode::ReturnStatementNotAllowedOutsideFunction,
         ));
     }
@@ -10,13 +10,19 @@ pub fn compile_statement_variable_assign(
     let var = compiler.resolve_symbol(&variable.ident.value);
 
     if let Some(var_type) = var {
+        compiler.add_reference(variable.ident.span.clone(), &variable.ident.value);
+
         if var_type.modifiers.constant {
-            // Program will stop here.
-            compiler.throw_exception(String::from("a constant cannot be reassigned"), None);
+            return Err(CompilerException::new(
+                variable.ident.span,
+                CompilerExceptionCode::ConstantReassigned(var_type.name),
+            ));
         }
 
         compiler.add_to_current_function(format!(".STORE {} {{", var_type.index));
 
+        let span = variable.value.span();
+
         let result = compiler.compile_expression(*variable.value);
 
         compiler.add_to_current_function("};".to_string());
@@ -25,8 +31,7 @@ pub fn compile_statement_variable_assign(
             Ok(result_type) => {
                 if *result_type != var_type._type {
                     Err(CompilerException::new(
-                        0,
-                        0,
+                        span,
                         CompilerExceptionCode::WrongType(
                             result_type.transpile(),
                             var_type._type.transpile(),
@@ -40,8 +45,7 @@ pub fn compile_statement_variable_assign(
     }
 
     Err(CompilerException::new(
-        variable.ident.location.line,
-        variable.ident.location.colon,
+        variable.ident.span,
         CompilerExceptionCode::UnknownSymbol(UnknownSymbol {
             name: variable.ident.value,
             scope_depth: compiler.scope_index as u16,
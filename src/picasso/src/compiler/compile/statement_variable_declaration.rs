@@ -7,7 +7,13 @@ pub fn compile_statement_variable_declaration(
     compiler: &mut Compiler,
     variable: VariableDeclaration,
 ) -> Result<Types, CompilerException> {
-    let var = compiler.define_symbol(variable.ident.value, variable.data_type.clone(), -1);
+    let span = variable.value.span();
+    let var = compiler.define_symbol(
+        variable.ident.value,
+        variable.data_type.clone(),
+        -1,
+        variable.ident.span,
+    );
 
     compiler.add_to_current_function(format!(".STORE {} {{", var.index));
 
@@ -28,8 +34,7 @@ pub fn compile_statement_variable_declaration(
         && !matches!(variable.data_type, Types::Module(_))
     {
         return Err(CompilerException::new(
-            0,
-            0,
+            span,
             CompilerExceptionCode::WrongType(result.transpile(), variable.data_type.transpile()),
         ));
     }
@@ -1,7 +1,7 @@
 //! Responsible for transpiling Loop to D
 pub mod compile;
 mod modifiers;
-mod symbol_table;
+pub(crate) mod symbol_table;
 mod test;
 
 use crate::compiler::compile::expression_array::compile_expression_array;
@@ -21,11 +21,14 @@ use crate::compiler::compile::expression_loop::{
     compile_loop_iterator_expression,
 };
 use crate::compiler::compile::expression_null::compile_expression_null;
+use crate::compiler::compile::expression_prefix::compile_expression_prefix;
+use crate::compiler::compile::expression_range::compile_expression_range;
 use crate::compiler::compile::expression_string::compile_expression_string;
 use crate::compiler::compile::expression_suffix::compile_expression_suffix;
 use crate::compiler::compile::statement_break::compile_break_statement;
 use crate::compiler::compile::statement_class::compile_class_statement;
 use crate::compiler::compile::statement_constant_declaration::compile_statement_constant_declaration;
+use crate::compiler::compile::statement_continue::compile_continue_statement;
 use crate::compiler::compile::statement_extend::compile_extend_statement;
 use crate::compiler::compile::statement_import::compile_import_statement;
 use crate::compiler::compile::statement_return::compile_return_statement;
@@ -33,21 +36,22 @@ use crate::compiler::compile::statement_variable_assign::compile_statement_varia
 use crate::compiler::compile::statement_variable_declaration::compile_statement_variable_declaration;
 use crate::compiler::modifiers::Modifiers;
 use crate::compiler::symbol_table::{
-    build_deeper_variable_scope, build_variable_scope, Symbol, SymbolScope,
+    build_deeper_variable_scope, build_variable_scope, copy_scope, Symbol, SymbolScope,
 };
-use crate::exception::compiler::CompilerException;
-use crate::exception::compiler_new::CompilerError;
+use crate::exception::compiler::{CompilerException, CompilerExceptionCode};
 use crate::parser::expression::Expression;
 use crate::parser::program::Program;
 use crate::parser::statement::block::Block;
 use crate::parser::statement::class::Method;
 use crate::parser::statement::Statement;
 use crate::parser::types::{Compound, Types};
-use crate::{lexer, parser};
-use colored::Colorize;
+use crate::span::Span;
+use crate::{lexer, parser, DEFAULT_MAX_ERRORS};
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::Path;
 use std::rc::Rc;
+use vinci::diagnostic::Diagnostic;
 
 /// The result of the transpiler, which will be passed to the D compiler [crate::util::execute_code]
 pub struct Arc {
@@ -80,7 +84,8 @@ pub struct Compiler {
     pub locations: Vec<String>,
     pub export_name: String,
     pub prev_location: String,
-    pub breaks: Vec<u32>,
+    // Amount of loops the compiler is currently in, break and continue are only allowed inside one
+    pub loop_depth: u32,
 
     pub imports: Vec<String>,
     pub functions: HashMap<String, Function>,
@@ -93,13 +98,73 @@ pub struct Compiler {
     pub dry: u32,
     pub base_location: String,
     pub compiled_from: String,
+    // File, Contents. Sources of imported files, so errors can show the line they happened on
+    pub sources: HashMap<String, String>,
+    // Paths of every imported ".loop" file, in the order they were imported
+    pub imported_files: Vec<String>,
+    // Every error that occurred, compilation continues with the next statement after an error
+    pub errors: Vec<CompilerException>,
+    // Amount of errors after which the compiler stops
+    pub max_errors: usize,
+    // Diagnostic of every error, they are printed by whoever started compiling
+    pub diagnostics: Vec<Diagnostic>,
+    // Every place in the code that defines or uses a symbol, together with that symbol
+    pub references: Vec<(Span, Rc<RefCell<Symbol>>)>,
+    // Every statement is preceded by a `.LOCATION`, so errors while running can point at the code
+    pub source_locations: bool,
 }
 
-#[derive(Clone)]
+/// State of the compiler from before a statement was compiled, see [Compiler::checkpoint]
+struct Checkpoint {
+    location: String,
+    locations: Vec<String>,
+    scope: Rc<RefCell<SymbolScope>>,
+    scope_index: i32,
+    loop_depth: u32,
+    dry: u32,
+    function_stack: Vec<String>,
+    current_function: String,
+}
+
+/// Everything that has to survive between two compilations that share a session (eg. the REPL)
 pub struct CompilerState {
     pub variable_scope: HashMap<String, Rc<RefCell<SymbolScope>>>,
     pub variable_count: u32,
     pub function_count: i32,
+    pub extensions: HashMap<String, Vec<Method>>,
+    pub imports: Vec<String>,
+    // Classes known to the parser, so they can be used as a type on a later line
+    pub defined_types: Vec<String>,
+    // Paths of every imported ".loop" file, the result of compiling depends on them
+    pub imported_files: Vec<String>,
+}
+
+/// The scopes are copied as well, compiling with a clone defines symbols in the clone only. That way
+/// the state from before a compilation that failed can still be used.
+impl Clone for CompilerState {
+    fn clone(&self) -> Self {
+        let mut copies = HashMap::new();
+
+        CompilerState {
+            variable_scope: self
+                .variable_scope
+                .iter()
+                .map(|(module, scope)| (module.clone(), copy_scope(scope, &mut copies)))
+                .collect(),
+            variable_count: self.variable_count,
+            function_count: self.function_count,
+            extensions: self.extensions.clone(),
+            imports: self.imports.clone(),
+            defined_types: self.defined_types.clone(),
+            imported_files: self.imported_files.clone(),
+        }
+    }
+}
+
+impl Default for CompilerState {
+    fn default() -> Self {
+        Compiler::default().get_compiler_state()
+    }
 }
 
 impl Default for Compiler {
@@ -115,7 +180,7 @@ impl Default for Compiler {
             location: String::new(),
             export_name: String::new(),
             prev_location: String::new(),
-            breaks: Vec::new(),
+            loop_depth: 0,
             extensions: HashMap::new(),
             locations: vec![],
             imports: Vec::new(),
@@ -134,6 +199,13 @@ impl Default for Compiler {
             dry: 0,
             base_location: "".to_string(),
             compiled_from: "".to_string(),
+            sources: HashMap::new(),
+            imported_files: Vec::new(),
+            errors: Vec::new(),
+            max_errors: DEFAULT_MAX_ERRORS,
+            diagnostics: Vec::new(),
+            references: Vec::new(),
+            source_locations: false,
         }
     }
 }
@@ -155,74 +227,139 @@ impl Compiler {
     ///     // Handle error
     /// }
     /// ```
-    pub fn compile(&mut self, program: Program) -> Result<Arc, CompilerException> {
-        for statement in program.statements {
-            let err = self.compile_statement(statement);
+    pub fn compile(&mut self, mut program: Program) -> Result<Arc, CompilerException> {
+        // The last expression is the value of the program
+        let value = match program.statements.last() {
+            Some(Statement::Expression(_)) => program.statements.pop(),
+            _ => None,
+        };
 
-            #[allow(clippy::single_match)]
-            match err {
-                Err(exception) => {
-                    self.print_error(exception.clone());
-                    return Err(exception);
-                }
-                _ => (),
-            }
+        self.compile_program(program);
+
+        if let Some(value) = value {
+            self.compile_program_value(value);
+        }
+
+        if let Some(error) = self.errors.first() {
+            return Err(error.clone());
         }
 
         Ok(self.get_arc())
     }
 
-    fn print_error(&self, error: CompilerException) {
-        if self
-            .compiled_from
-            .lines()
-            .nth((error.location.1) as usize)
-            .is_none()
-        {
-            println!("{}", error);
-            return;
-        }
+    /// Compiles all statements of a program, when a statement fails the error is reported and
+    /// compilation continues with the next statement until [Compiler::max_errors] is reached
+    pub fn compile_program(&mut self, program: Program) {
+        for statement in program.statements {
+            if self.errors.len() >= self.max_errors {
+                return;
+            }
 
-        let mut width = String::new();
+            let checkpoint = self.checkpoint();
 
-        for _ in 0..error.location.1.to_string().len() {
-            width.push(' ');
+            self.add_location(&statement.span());
+
+            if let Err(exception) = self.compile_statement(statement) {
+                self.restore(checkpoint);
+                self.report(exception);
+            }
         }
+    }
 
-        // Should be replaced by a match if different requirements
-        let minus = 2;
+    /// Compiles the expression that is the value of the program. Whole floats can not be told apart
+    /// from ints after running, so a value that holds floats is shown using its type.
+    fn compile_program_value(&mut self, statement: Statement) {
+        if self.errors.len() >= self.max_errors {
+            return;
+        }
 
-        let colon = error.location.1 - minus;
+        let checkpoint = self.checkpoint();
 
-        println!("{}", "CompilerException".red());
-        println!(
-            "{} | -> {} [{}:{}]",
-            width, self.location, error.location.0, colon
-        );
+        self.add_location(&statement.span());
+        let position = self.current_function_position();
 
-        println!("{} | ", width);
-        println!(
-            "{} | {}",
-            error.location.0,
-            self.compiled_from
-                .lines()
-                .nth((error.location.1) as usize)
-                .unwrap()
-        );
+        match self.compile_statement(statement) {
+            Ok(tp) if tp.contains_float() => {
+                self.insert_at_current_function(position, ".CALL intrinsic::show { ".to_string());
+                self.add_to_current_function(format!(
+                    ".CONSTANT CHAR[] \"{}\";}};",
+                    tp.transpile()
+                ));
+            }
+            Ok(_) => {}
+            Err(exception) => {
+                self.restore(checkpoint);
+                self.report(exception);
+            }
+        }
+    }
 
-        let spaces = colon;
+    /// Keeps track of an error and its diagnostic, errors of imported files that failed are
+    /// reported by the file itself
+    pub fn report(&mut self, error: CompilerException) {
+        if let CompilerExceptionCode::ImportFailed(_) = error.exception {
+            return;
+        }
 
-        let mut cursor_width = String::new();
+        self.diagnostics.push(self.diagnostic(&error));
+        self.errors.push(error);
+    }
 
-        for _ in 0..(spaces - (width.len() as i32)) {
-            cursor_width.push(' ');
+    /// Saves the state that is changed while compiling a statement, a statement can fail halfway
+    /// through so it has to be restored before compiling the next one
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            location: self.location.clone(),
+            locations: self.locations.clone(),
+            scope: self.get_symbol_scope(),
+            scope_index: self.scope_index,
+            loop_depth: self.loop_depth,
+            dry: self.dry,
+            function_stack: self.function_stack.clone(),
+            current_function: self.current_function.clone(),
         }
+    }
+
+    fn restore(&mut self, checkpoint: Checkpoint) {
+        self.location = checkpoint.location;
+        self.locations = checkpoint.locations;
+        self.symbol_scope
+            .insert(self.location.clone(), checkpoint.scope);
+        self.scope_index = checkpoint.scope_index;
+        self.loop_depth = checkpoint.loop_depth;
+        self.dry = checkpoint.dry;
+        self.function_stack = checkpoint.function_stack;
+        self.current_function = checkpoint.current_function;
+    }
 
-        println!("{} | {}{}", width, cursor_width, "^".red());
+    /// Creates a diagnostic for an exception, including the line it happened on from the file it
+    /// happened in
+    pub fn diagnostic(&self, error: &CompilerException) -> Diagnostic {
+        let source = self
+            .sources
+            .get(&error.span.file)
+            .unwrap_or(&self.compiled_from);
+        let line = error.span.start.line;
 
-        println!("{} | ", width);
+        let text = source
+            .lines()
+            .nth((line - 1).max(0) as usize)
+            .filter(|_| error.span.is_known())
+            .map(str::to_string);
 
-        println!("{} = {}", width, format!("{}", error).blue());
+        error.diagnostic(text)
+    }
+
+    /// Sets the directory imports are looked up from, based on the file that is compiled
+    pub fn set_base_location(&mut self, file_location: Option<&str>) {
+        if let Some(file) = file_location {
+            let path = Path::new(file);
+            if path.extension().is_some() {
+                self.base_location = path.parent().unwrap().to_str().unwrap().to_string()
+            } else {
+                self.base_location = file.to_string();
+            }
+        }
     }
 
     /// Enters a compilation "location" aka a module. A module has its own variable scope and thus
@@ -242,15 +379,11 @@ impl Compiler {
     /// Exits a compilation "location" aka a module. When exiting a location it pops the last
     /// location from the stack.
     pub fn exit_location(&mut self) -> String {
-        let last_loc = self.location.clone();
+        let last_loc = self.locations.pop().unwrap_or_default();
 
         // This is needed as if we are only one location "deep" the previous location wont exist,
-        // so we set the location in the else block to "" which is the default root location.
-        if self.locations.len() > 1 {
-            self.location = self.locations.pop().unwrap();
-        } else {
-            self.location = "".to_string();
-        }
+        // so we set the location to "" which is the default root location.
+        self.location = self.locations.last().cloned().unwrap_or_default();
 
         self.exit_symbol_scope();
 
@@ -301,6 +434,9 @@ impl Compiler {
             function_count: compiler_state.function_count,
             variable_count: compiler_state.variable_count,
             symbol_scope: compiler_state.variable_scope,
+            extensions: compiler_state.extensions,
+            imports: compiler_state.imports,
+            imported_files: compiler_state.imported_files,
             ..Compiler::default()
         }
     }
@@ -310,6 +446,10 @@ impl Compiler {
             function_count: self.function_count,
             variable_count: self.variable_count,
             variable_scope: self.symbol_scope.clone(),
+            extensions: self.extensions.clone(),
+            imports: self.imports.clone(),
+            defined_types: Vec::new(),
+            imported_files: self.imported_files.clone(),
         }
     }
 
@@ -322,20 +462,39 @@ impl Compiler {
         }
     }
 
+    /// Position in the code of the current function, code can be inserted there later on using
+    /// [Compiler::insert_at_current_function]
+    pub fn current_function_position(&self) -> usize {
+        self.functions
+            .get(&*self.current_function)
+            .unwrap()
+            .code
+            .len()
+    }
+
+    /// Inserts code at a position of the current function, when the code depends on what was compiled after it
+    pub fn insert_at_current_function(&mut self, position: usize, code: String) {
+        if self.dry == 0 {
+            let func = self.functions.get_mut(&*self.current_function);
+
+            func.unwrap().code.insert_str(position, code.as_str());
+        }
+    }
+
     /// Allows replacing context
     pub fn replace_at_current_function(&mut self, replace: String, with: String) {
         if self.dry == 0 {
             let func = self.functions.get_mut(&*self.current_function);
 
             let unwrapped = func.unwrap();
-            let replaced = unwrapped.code.replace(replace.as_str(), &*with);
+            let replaced = unwrapped.code.replace(replace.as_str(), &with);
 
             unwrapped.code = replaced;
         }
     }
 
     fn get_symbol_scope(&self) -> Rc<RefCell<SymbolScope>> {
-        return (*self.symbol_scope.get(&self.location).as_ref().unwrap()).clone();
+        (*self.symbol_scope.get(&self.location).as_ref().unwrap()).clone()
     }
 
     fn get_symbol_mutable(
@@ -398,6 +557,7 @@ impl Compiler {
             Expression::Identifier(identifier) => compile_expression_identifier(self, identifier),
             Expression::Integer(int) => compile_expression_integer(self, int),
             Expression::Suffix(suffix) => compile_expression_suffix(self, *suffix),
+            Expression::Prefix(prefix) => compile_expression_prefix(self, *prefix),
             Expression::Boolean(boolean) => compile_expression_boolean(self, boolean),
             Expression::Function(func) => compile_expression_function(self, func),
             Expression::Conditional(conditional) => {
@@ -415,13 +575,16 @@ impl Compiler {
             Expression::LoopArrayIterator(lp) => compile_loop_array_iterator_expression(self, lp),
             Expression::Hashmap(hash) => compile_expression_hashmap(self, hash),
             Expression::Slice(slice) => compile_expression_slice(self, slice),
+            Expression::Range(range) => compile_expression_range(self, *range),
         }
     }
 
     /// Compiles a loop [Block], this differs from [Compiler::compile_block] in that it wont add curly braces
+    /// and that break and continue statements are allowed inside of it
     fn compile_loop_block(&mut self, block: Block) -> Result<Types, CompilerException> {
         let mut return_type = Types::Void;
         self.enter_symbol_scope();
+        self.loop_depth += 1;
 
         let mut index = 0;
         for statement in block.statements.clone() {
@@ -446,18 +609,29 @@ impl Compiler {
 
             #[allow(clippy::single_match)]
             match &err {
-                Err(_exception) => return Err(_exception.clone()),
+                Err(_exception) => {
+                    self.loop_depth -= 1;
+                    return Err(_exception.clone());
+                }
                 _ => (),
             }
         }
 
+        self.loop_depth -= 1;
         self.exit_symbol_scope();
 
         Ok(return_type)
     }
 
-    /// Defines a new variable and increases the amount of variables that exist
-    fn define_symbol(&mut self, name: String, var_type: Types, parameter_id: i32) -> Symbol {
+    /// Defines a new variable and increases the amount of variables that exist, `span` is where it
+    /// is defined in the code
+    fn define_symbol(
+        &mut self,
+        name: String,
+        var_type: Types,
+        parameter_id: i32,
+        span: Span,
+    ) -> Symbol {
         let var = self
             .symbol_scope
             .get_mut(&self.location)
@@ -471,10 +645,19 @@ impl Compiler {
                 Modifiers::new(false, self.location.clone(), false),
                 parameter_id,
                 self.function_count,
+                span.clone(),
             );
 
         self.variable_count += 1;
 
+        if span.is_known() {
+            let symbol = self.get_symbol_scope().borrow().get_by_index(var.index);
+
+            if let Some(symbol) = symbol {
+                self.references.push((span, symbol));
+            }
+        }
+
         var
     }
 
@@ -505,6 +688,42 @@ impl Compiler {
             .resolve(name.to_string())
     }
 
+    /// Finds a variable like [Compiler::resolve_symbol], but returns the symbol itself
+    fn resolve_reference(&self, name: &str) -> Option<Rc<RefCell<Symbol>>> {
+        if let Some((module, name)) = name.split_once("::") {
+            let var = self
+                .symbol_scope
+                .get(module)
+                .and_then(|scope| scope.borrow().resolve_reference(name));
+
+            if var.is_some() {
+                return var;
+            }
+        }
+
+        self.get_symbol_scope().borrow().resolve_reference(name)
+    }
+
+    /// Adds a `.LOCATION` with the file and line the statement at `span` was written on, when
+    /// [Compiler::source_locations] is enabled
+    fn add_location(&mut self, span: &Span) {
+        if !self.source_locations || !span.is_known() {
+            return;
+        }
+
+        // Strings in the Arc can not escape backslashes or quotes
+        let file = span.file.replace('\\', "/").replace('"', "'");
+
+        self.add_to_current_function(format!(".LOCATION \"{}\" {};", file, span.start.line));
+    }
+
+    /// Remembers that the code at `span` refers to the symbol with this name
+    fn add_reference(&mut self, span: Span, name: &str) {
+        if let Some(symbol) = self.resolve_reference(name) {
+            self.references.push((span, symbol));
+        }
+    }
+
     /// Compiles a deeper [Block] adding curly braces
     fn compile_block(
         &mut self,
@@ -536,9 +755,19 @@ impl Compiler {
                         };
                     }
 
-                    if index == block.statements.len()
-                        && !matches!(*exp.expression, Expression::AssignIndex(_))
-                    {
+                    self.add_location(&statement.span());
+
+                    // Loops are statements in the Arc, returning from inside of them returns
+                    // from the surrounding function instead
+                    let returns_value = !matches!(
+                        *exp.expression,
+                        Expression::AssignIndex(_)
+                            | Expression::Loop(_)
+                            | Expression::LoopIterator(_)
+                            | Expression::LoopArrayIterator(_)
+                    );
+
+                    if index == block.statements.len() && returns_value {
                         self.add_to_current_function(".RETURN { ".to_string());
                     }
 
@@ -557,13 +786,15 @@ impl Compiler {
                             block_type = _type.clone();
                         }
 
-                        if !matches!(*exp.expression, Expression::AssignIndex(_)) {
+                        if returns_value {
                             self.add_to_current_function("};".to_string());
                         }
                     }
 
                     result
                 } else {
+                    self.add_location(&statement.span());
+
                     let result = self.compile_statement(statement.clone());
 
                     // Find first "return" as that is the only way to return
@@ -619,17 +850,9 @@ impl Compiler {
             Statement::Return(_return) => compile_return_statement(self, _return),
             Statement::Import(import) => compile_import_statement(self, import),
             Statement::Break(br) => compile_break_statement(self, br),
+            Statement::Continue(cn) => compile_continue_statement(self, cn),
             Statement::Class(class) => compile_class_statement(self, class),
             Statement::Extend(extend) => compile_extend_statement(self, extend),
         }
     }
-
-    /// Throws an [CompilerError](crate::exception::compiler_new::CompilerError;) and exists with code '1'.
-    fn throw_exception(&self, message: String, extra_message: Option<String>) {
-        let mut err = CompilerError {
-            error_message: message,
-            extra_message,
-        };
-        err.throw_exception();
-    }
 }
@@ -1,7 +1,9 @@
 //! Helper for symbols defined by the user
 use crate::compiler::modifiers::Modifiers;
 use crate::parser::types::Types;
+use crate::span::Span;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 #[derive(Clone, Debug)]
@@ -12,6 +14,8 @@ pub struct Symbol {
     pub modifiers: Modifiers,
     pub parameter_id: i32,
     pub function_identifier: i32,
+    // Where the symbol is defined, unknown for symbols created by the compiler itself
+    pub span: Span,
 }
 
 #[derive(Debug)]
@@ -33,6 +37,35 @@ pub fn build_deeper_variable_scope(outer: Option<Rc<RefCell<SymbolScope>>>) -> S
     }
 }
 
+/// Copies a scope together with its outer scopes and their symbols, so changes made to the copy do
+/// not affect the original. Scopes in `copies` were copied before, scopes shared between modules
+/// stay shared in the copy.
+pub fn copy_scope(
+    scope: &Rc<RefCell<SymbolScope>>,
+    copies: &mut HashMap<*const RefCell<SymbolScope>, Rc<RefCell<SymbolScope>>>,
+) -> Rc<RefCell<SymbolScope>> {
+    if let Some(copy) = copies.get(&Rc::as_ptr(scope)) {
+        return copy.clone();
+    }
+
+    let original = scope.as_ref().borrow();
+    let copy = Rc::new(RefCell::new(SymbolScope {
+        variables: original
+            .variables
+            .iter()
+            .map(|symbol| Rc::new(RefCell::new(symbol.as_ref().borrow().clone())))
+            .collect(),
+        outer: original
+            .outer
+            .as_ref()
+            .map(|outer| copy_scope(outer, copies)),
+    }));
+
+    copies.insert(Rc::as_ptr(scope), copy.clone());
+
+    copy
+}
+
 impl Symbol {
     pub fn transpile(&self) -> String {
         match self._type {
@@ -43,6 +76,7 @@ impl Symbol {
 }
 
 impl SymbolScope {
+    #[allow(clippy::too_many_arguments)]
     pub fn define(
         &mut self,
         index: u32,
@@ -51,6 +85,7 @@ impl SymbolScope {
         modifiers: Modifiers,
         parameter_id: i32,
         function_identifier: i32,
+        span: Span,
     ) -> Symbol {
         if name.starts_with("__export_") {
             if let Some(outer) = &self.outer {
@@ -61,6 +96,7 @@ impl SymbolScope {
                     modifiers,
                     parameter_id,
                     function_identifier,
+                    span,
                 );
             }
         }
@@ -72,6 +108,7 @@ impl SymbolScope {
             modifiers: modifiers.clone(),
             parameter_id,
             function_identifier,
+            span: span.clone(),
         })));
 
         let var = self.variables.last().expect("inserted").as_ref().borrow();
@@ -83,6 +120,7 @@ impl SymbolScope {
             modifiers,
             parameter_id,
             function_identifier,
+            span,
         }
     }
 
@@ -103,23 +141,47 @@ impl SymbolScope {
     }
 
     pub fn resolve(&self, name: String) -> Option<Symbol> {
-        for variable in &self.variables {
+        self.resolve_reference(&name).map(|variable| {
             let variable = variable.as_ref().borrow();
 
-            if variable.name == name {
-                return Some(Symbol {
-                    index: variable.index,
-                    name,
-                    _type: variable._type.clone(),
-                    modifiers: variable.modifiers.clone(),
-                    parameter_id: variable.parameter_id,
-                    function_identifier: variable.function_identifier,
-                });
+            Symbol {
+                index: variable.index,
+                name,
+                _type: variable._type.clone(),
+                modifiers: variable.modifiers.clone(),
+                parameter_id: variable.parameter_id,
+                function_identifier: variable.function_identifier,
+                span: variable.span.clone(),
+            }
+        })
+    }
+
+    /// Finds a symbol by its index, which is unique to every symbol
+    pub fn get_by_index(&self, index: u32) -> Option<Rc<RefCell<Symbol>>> {
+        for variable in &self.variables {
+            if variable.as_ref().borrow().index == index {
+                return Some(variable.clone());
+            }
+        }
+
+        if let Some(outer) = self.outer.clone() {
+            return outer.as_ref().borrow().get_by_index(index);
+        }
+
+        None
+    }
+
+    /// Finds the symbol itself instead of a copy, so changes made to it later on are visible. A
+    /// symbol that is declared again is the last declaration from then on.
+    pub fn resolve_reference(&self, name: &str) -> Option<Rc<RefCell<Symbol>>> {
+        for variable in self.variables.iter().rev() {
+            if variable.as_ref().borrow().name == name {
+                return Some(variable.clone());
             }
         }
 
         if let Some(outer) = self.outer.clone() {
-            return outer.as_ref().borrow_mut().resolve(name);
+            return outer.as_ref().borrow().resolve_reference(name);
         }
 
         None
@@ -1,2 +1,3 @@
 //! Tests the compiler
+#[allow(clippy::module_inception)]
 mod tests;
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::exception::compiler::{CompilerExceptionCode, UnknownSymbol};
-    use crate::exception::Exception;
+    use crate::parser::expression::integer::Integer;
+    use crate::parser::expression::suffix::Suffix;
+    use crate::parser::expression::Expression;
+    use crate::span::Span;
     use crate::{compiler, lexer, parser};
 
     // Commented out due to the compiler not generating instructions anymore. Instead it generates D code.
@@ -223,17 +226,6 @@ mod tests {
     //         //compiler_test(input, expected);
     //     }
 
-    //#[test]
-    fn class() {
-        compiler_test(
-            "class Person { \
-        age = 100; \
-        test = 42; }\
-        x := Person()",
-            ".COMPOUND \"Person\" { INT;INT;};.STORE 1 {.CONSTANT Person {.CONSTANT INT 42;.CONSTANT INT 100;};};",
-        );
-    }
-
     #[test]
     fn scoping_rules_1() {
         compiler_test_error("test := 100; if(true) { test }", None);
@@ -272,6 +264,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn function_return_type() {
+        compiler_test(
+            "fn half(int value) float { return value / 2.0 }",
+            ".FUNCTION \"var_0\" 1 FLOAT ARGUMENTS {INT;} FREE {} THEN {.RETURN {.DIVIDE {.LOAD PARAMETER 1 0;.CONSTANT FLOAT 2;};};};",
+        );
+        compiler_test_error(
+            "fn half(int value) int { return value / 2.0 }",
+            Some(CompilerExceptionCode::WrongType(
+                "float".to_string(),
+                "int".to_string(),
+            )),
+        );
+    }
+
     #[test]
     fn scoping_rules_functions_1() {
         compiler_test_error(
@@ -442,6 +449,637 @@ mod tests {
     //#[test]
     //fn divide_by_float() { compiler_test_error("302 / 1.14", None) }
 
+    #[test]
+    fn comparisons() {
+        compiler_test(
+            "1 < 2\n1 <= 2\n1 >= 2\n1 > 2",
+            ".LESSTHAN {.CONSTANT INT 1;.CONSTANT INT 2;};.LESSTHANOREQUAL {.CONSTANT INT 1;.CONSTANT INT 2;};.GREATERTHANOREQUAL {.CONSTANT INT 1;.CONSTANT INT 2;};.GREATERTHAN {.CONSTANT INT 1;.CONSTANT INT 2;};",
+        )
+    }
+
+    #[test]
+    fn suffix_types() {
+        compiler_test(
+            "bool ok := 1 == 2.0 and true; float f := 1 / 2.0",
+            ".STORE 0 {.AND {.EQUALS {.CONSTANT INT 1;.CONSTANT FLOAT 2;};.CONSTANT BOOL true;};};.STORE 1 {.DIVIDE {.CONSTANT INT 1;.CONSTANT FLOAT 2;};};",
+        )
+    }
+
+    #[test]
+    fn suffix_division() {
+        compiler_test(
+            "int x := 10 / 2; float y := 7.0 / 2",
+            ".STORE 0 {.DIVIDE {.CONSTANT INT 10;.CONSTANT INT 2;};};.STORE 1 {.DIVIDE {.CONSTANT FLOAT 7;.CONSTANT INT 2;};};",
+        );
+        compiler_test_error(
+            "\"a\" / 2",
+            Some(CompilerExceptionCode::WrongType(
+                "string / int".to_string(),
+                "int or float".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn program_value_floats() {
+        compiler_test(
+            "[1.0, 2.5]",
+            ".CALL intrinsic::show { .CONSTANT INT[] [.CONSTANT FLOAT 1;.CONSTANT FLOAT 2.5;];.CONSTANT CHAR[] \"FLOAT[]\";};",
+        );
+        compiler_test("x := 2.0; x + 1", ".STORE 0 {.CONSTANT FLOAT 2;};.CALL intrinsic::show { .ADD {.LOAD VARIABLE 0;.CONSTANT INT 1;};.CONSTANT CHAR[] \"FLOAT\";};");
+        compiler_test("10 / 2", ".DIVIDE {.CONSTANT INT 10;.CONSTANT INT 2;};");
+    }
+
+    #[test]
+    fn suffix_string_concatenation() {
+        compiler_test(
+            "\"a\" + \"b\"",
+            ".PUSH {.CONSTANT CHAR[] \"a\";} {.CONSTANT CHAR[] \"b\";};",
+        )
+    }
+
+    #[test]
+    fn suffix_wrong_type() {
+        compiler_test_error(
+            "\"a\" * 3",
+            Some(CompilerExceptionCode::WrongType(
+                "string * int".to_string(),
+                "int or float".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "1 and true",
+            Some(CompilerExceptionCode::WrongType(
+                "int and bool".to_string(),
+                "bool".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "int x := 1.5 * 2",
+            Some(CompilerExceptionCode::WrongType(
+                "FLOAT".to_string(),
+                "INT".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn unknown_suffix_operator() {
+        let mut comp = compiler::Compiler::default();
+
+        let result = comp.compile_expression(Expression::Suffix(Box::new(Suffix {
+            left: Expression::Integer(Integer {
+                value: 1,
+                span: Span::default(),
+            }),
+            operator: "<>".to_string(),
+            right: Expression::Integer(Integer {
+                value: 2,
+                span: Span::default(),
+            }),
+            span: Span::default(),
+        })));
+
+        assert_eq!(
+            result.err().unwrap().exception,
+            CompilerExceptionCode::UnknownSuffixOperator("<>".to_string())
+        );
+    }
+
+    #[test]
+    fn prefix_operators() {
+        compiler_test(
+            "x := 1; y := false; -x; !y; -2; !true",
+            ".STORE 0 {.CONSTANT INT 1;};.STORE 1 {.CONSTANT BOOL false;};.NEGATE {.LOAD VARIABLE 0;};.NOT {.LOAD VARIABLE 1;};.CONSTANT INT -2;.CONSTANT BOOL false;",
+        )
+    }
+
+    #[test]
+    fn prefix_operators_wrong_type() {
+        compiler_test_error(
+            "x := \"a\"; -x",
+            Some(CompilerExceptionCode::WrongType(
+                "string".to_string(),
+                "int or float".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "!1",
+            Some(CompilerExceptionCode::WrongType(
+                "int".to_string(),
+                "bool".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn hashmap() {
+        compiler_test(
+            "{20: 30, \"hi\": {100: 20}, false: true}",
+            ".HASHMAP VOID VOID {.CONSTANT INT 20;.CONSTANT INT 30;.CONSTANT CHAR[] \"hi\";.HASHMAP INT INT {.CONSTANT INT 100;.CONSTANT INT 20;};.CONSTANT BOOL false;.CONSTANT BOOL true;};",
+        )
+    }
+
+    #[test]
+    fn hashmap_same_types() {
+        compiler_test(
+            "{\"a\": {100: 20}, \"b\": {}}",
+            ".HASHMAP CHAR[] HASHMAP[INT,INT] {.CONSTANT CHAR[] \"a\";.HASHMAP INT INT {.CONSTANT INT 100;.CONSTANT INT 20;};.CONSTANT CHAR[] \"b\";.HASHMAP INT INT {};};",
+        );
+        compiler_test(
+            "{1: 2, 3: \"s\"}",
+            ".HASHMAP INT VOID {.CONSTANT INT 1;.CONSTANT INT 2;.CONSTANT INT 3;.CONSTANT CHAR[] \"s\";};",
+        )
+    }
+
+    #[test]
+    fn hashmap_index_assign() {
+        compiler_test(
+            "x := {\"a\": 1}\nx[\"a\"] = 2\nx[\"a\"]",
+            ".STORE 0 {.HASHMAP CHAR[] INT {.CONSTANT CHAR[] \"a\";.CONSTANT INT 1;};};.ASSIGN { .INDEX HASHMAP {.LOAD VARIABLE 0;} {.CONSTANT CHAR[] \"a\";}; } {.CONSTANT INT 2;};.INDEX HASHMAP {.LOAD VARIABLE 0;} {.CONSTANT CHAR[] \"a\";};",
+        )
+    }
+
+    #[test]
+    fn hashmap_length() {
+        compiler_test(
+            "x := {1: 2}\nx.len()",
+            ".STORE 0 {.HASHMAP INT INT {.CONSTANT INT 1;.CONSTANT INT 2;};};.LENGTH { .KEYS { .LOAD VARIABLE 0;}; };",
+        )
+    }
+
+    #[test]
+    fn hashmap_remove() {
+        compiler_test(
+            "x := {1: 2}\nx.remove(1)",
+            ".STORE 0 {.HASHMAP INT INT {.CONSTANT INT 1;.CONSTANT INT 2;};};.ASSIGN { .INDEX HASHMAP {.LOAD VARIABLE 0;} { .CONSTANT INT 1;}; } { .CONSTANT VOID; };",
+        )
+    }
+
+    #[test]
+    fn hashmap_non_hashable_key() {
+        let l = lexer::build_lexer("{[1]: 2}");
+        let mut parser = parser::build_parser(l, "");
+
+        assert!(parser.parse().is_err());
+    }
+
+    #[test]
+    fn loop_break_continue() {
+        compiler_test(
+            "for (true) { if (false) { continue }\n break }",
+            ".WHILE CONDITION {.CONSTANT BOOL true;} THEN {.IF CONDITION { .CONSTANT BOOL false; } THEN {.CONTINUE;} ELSE { };.BREAK;};",
+        )
+    }
+
+    #[test]
+    fn break_outside_loop() {
+        compiler_test_error(
+            "break",
+            Some(CompilerExceptionCode::BreakStatementNotAllowedOutsideLoop),
+        )
+    }
+
+    #[test]
+    fn continue_outside_loop() {
+        compiler_test_error(
+            "for (var i = 0 to 3) { f := fn() { continue } }",
+            Some(CompilerExceptionCode::ContinueStatementNotAllowedOutsideLoop),
+        )
+    }
+
+    #[test]
+    fn range() {
+        compiler_test(
+            "x := 1..=3",
+            ".STORE 0 {.RANGE { .CONSTANT INT 1;} { .ADD { .CONSTANT INT 3;.CONSTANT INT 1; }; } { .CONSTANT INT 1; };};",
+        )
+    }
+
+    #[test]
+    fn loop_iterator_step() {
+        compiler_test(
+            "for var i = 0 through 4 step 2 { i }",
+            ".STORE 0 { .CONSTANT INT 0;};.STORE 1 { .CONSTANT INT 4;};.WHILE CONDITION { .LESSTHANOREQUAL { .LOAD VARIABLE 0; .LOAD VARIABLE 1; }; } THEN {.STORE 2 { .LOAD VARIABLE 0; };.STORE 0 { .ADD { .LOAD VARIABLE 0; .CONSTANT INT 2; }; };.LOAD VARIABLE 2;};",
+        )
+    }
+
+    #[test]
+    fn loop_iterator_zero_step() {
+        compiler_test_error(
+            "for var i = 0 to 10 step 0 { }",
+            Some(CompilerExceptionCode::ZeroStep),
+        )
+    }
+
+    #[test]
+    fn loop_iterator_wrong_type() {
+        compiler_test_error(
+            "for var i = 0 to \"10\" { }",
+            Some(CompilerExceptionCode::WrongType(
+                "string".to_string(),
+                "int".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn loop_array_iterator_element_type() {
+        let l = lexer::build_lexer("out := \"\"\nfor (var s in [\"a\", \"b\"]) { out = out + s }");
+        let mut parser = parser::build_parser(l, "");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler::default();
+        assert!(comp.compile(program).is_ok());
+    }
+
+    #[test]
+    fn std_import() {
+        let l = lexer::build_lexer("import \"std\" as std\nx := std.abs(-2) + std.sum([1, 2])");
+        let mut parser = parser::build_parser(l, "");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler::default();
+        let arc = comp.compile(program).unwrap().get_arc();
+
+        assert!(arc.contains(".CALL intrinsic::println { .LOAD PARAMETER"));
+        assert!(comp.sources.contains_key("<std>/math.loop"));
+        assert!(comp.imported_files.is_empty());
+        assert_eq!(comp.location, "");
+    }
+
+    #[test]
+    fn imported_files() {
+        let path = "../../tests/loop-packages/one.loop";
+        let input = std::fs::read_to_string(path).unwrap();
+
+        let (_, state) = crate::compile(&input, Some(path), 20).unwrap();
+        let files: Vec<&str> = state
+            .imported_files
+            .iter()
+            .map(|file| file.rsplit("loop-packages/").next().unwrap())
+            .collect();
+
+        assert_eq!(
+            files,
+            vec![
+                "two.loop",
+                "three.loop",
+                "deeper/four.loop",
+                "deeper/deeper/five.loop"
+            ]
+        );
+    }
+
+    #[test]
+    fn import_same_name() {
+        // Both modules export "f", each call has to load the function of its own module
+        let path = "../../tests/test_import_same_name.loop";
+        let input = std::fs::read_to_string(path).unwrap();
+
+        let (arc, _) = crate::compile(&input, Some(path), 20).unwrap();
+        let called: Vec<&str> = arc
+            .split(".CALL {.LOAD VARIABLE ")
+            .skip(1)
+            .map(|call| call.split(';').next().unwrap())
+            .collect();
+
+        assert_eq!(called[called.len() - 2..], ["6", "10"]);
+    }
+
+    #[test]
+    fn end2end_programs_validate() {
+        // The programs the end to end tests run, eg: add_test("test_constant.loop", "22")
+        let tests = std::fs::read_to_string("../../tests/end2end_test.py").unwrap();
+        let paths: Vec<String> = tests
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("add_test(\""))
+            .map(|line| format!("../../tests/{}", line.split('"').next().unwrap()))
+            .collect();
+        assert!(!paths.is_empty());
+
+        for path in &paths {
+            let input = std::fs::read_to_string(path).unwrap();
+
+            let (arc, _) = crate::compile(&input, Some(path), 20)
+                .unwrap_or_else(|error| panic!("{}: {}", path, error));
+            let ast = vinci::parse(&arc).unwrap_or_else(|_| panic!("{}: invalid Arc", path));
+
+            if let Err(errors) = vinci::validator::validate(&ast) {
+                let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+                panic!("{}:\n{}", path, errors.join("\n"));
+            }
+        }
+    }
+
+    #[test]
+    fn intrinsic_outside_std() {
+        compiler_test_error(
+            "intrinsic.println(1)",
+            Some(CompilerExceptionCode::UnknownSymbol(UnknownSymbol {
+                name: "intrinsic".to_string(),
+                scope_depth: 0,
+            })),
+        )
+    }
+
+    #[test]
+    fn string_method() {
+        compiler_test(
+            "\"a,b\".split(\",\")",
+            ".CALL string::split { .CONSTANT CHAR[] \"a,b\";.CONSTANT CHAR[] \",\";};",
+        )
+    }
+
+    #[test]
+    fn string_method_extension() {
+        compiler_test(
+            "ext string { string split() { return self } }; \"a\".split()",
+            ".FUNCTION \"var_0\" 1 CHAR[] ARGUMENTS {CHAR[];} FREE {} THEN {.RETURN {.LOAD PARAMETER 1 0;};};.CALL {.LOAD VARIABLE 0;} {.CONSTANT CHAR[] \"a\";};",
+        )
+    }
+
+    #[test]
+    fn string_method_wrong_type() {
+        compiler_test_error(
+            "\"a\".repeat(\"b\")",
+            Some(CompilerExceptionCode::WrongType(
+                "string".to_string(),
+                "int".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn array_method() {
+        compiler_test(
+            "[1].contains(1)",
+            ".CALL array::contains { .CONSTANT INT[] [.CONSTANT INT 1;];.CONSTANT INT 1;};",
+        )
+    }
+
+    #[test]
+    fn array_method_inferred_type() {
+        compiler_test_error(
+            "x := [1].map(fn(int v) { return v > 0 }); x = [1]",
+            Some(CompilerExceptionCode::WrongType(
+                "INT[]".to_string(),
+                "BOOL[]".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "x := [[1], [2]].flatten().reverse(); x = 1",
+            Some(CompilerExceptionCode::WrongType(
+                "INT".to_string(),
+                "INT[]".to_string(),
+            )),
+        );
+    }
+
+    #[test]
+    fn array_method_wrong_function() {
+        compiler_test_error(
+            "[1].filter(fn(int v) { return v })",
+            Some(CompilerExceptionCode::WrongType(
+                "int".to_string(),
+                "bool".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "[1].map(fn(string v) { return v })",
+            Some(CompilerExceptionCode::WrongType(
+                "fn(CHAR[]): string".to_string(),
+                "fn(INT): var".to_string(),
+            )),
+        );
+    }
+
+    #[test]
+    fn unknown_method() {
+        compiler_test_error(
+            "[1].shuffle()",
+            Some(CompilerExceptionCode::UnknownExtensionMethod(
+                "shuffle".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "\"a\".reverse()",
+            Some(CompilerExceptionCode::UnknownExtensionMethod(
+                "reverse".to_string(),
+            )),
+        );
+        compiler_test_error(
+            "x := 5; x.double()",
+            Some(CompilerExceptionCode::UnknownExtensionMethod(
+                "double".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn exception_span() {
+        let l = lexer::build_lexer("x := 1\ny := x + z");
+        let mut parser = parser::build_parser(l, "main.loop");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler::default();
+        let error = comp.compile(program).err().unwrap();
+
+        assert_eq!(error.span.file, "main.loop");
+        assert_eq!((error.span.start.line, error.span.start.colon), (2, 10));
+        assert_eq!((error.span.end.line, error.span.end.colon), (2, 10));
+    }
+
+    #[test]
+    fn exception_diagnostic() {
+        let l = lexer::build_lexer("x := 1\ny := x + z");
+        let mut parser = parser::build_parser(l, "main.loop");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler::default();
+        let error = comp.compile(program).err().unwrap();
+        let diagnostic = error.diagnostic(Some("y := x + z".to_string()));
+
+        assert_eq!(diagnostic.code, "compiler::unknown-symbol");
+        assert_eq!(diagnostic.message, "unknown symbol. got=\"z\"");
+        assert_eq!(diagnostic.spans[0].file, "main.loop");
+        assert_eq!(
+            (
+                diagnostic.spans[0].line_start,
+                diagnostic.spans[0].column_start
+            ),
+            (2, 10)
+        );
+    }
+
+    #[test]
+    fn exception_continues() {
+        let l = lexer::build_lexer("x := a\nf := fn() { return b }\ny := x\nz := c");
+        let mut parser = parser::build_parser(l, "");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler::default();
+        let error = comp.compile(program).err().unwrap();
+
+        assert_eq!(error.span.start.line, 1);
+        let lines: Vec<i32> = comp.errors.iter().map(|e| e.span.start.line).collect();
+        assert_eq!(lines, vec![1, 2, 4]);
+        assert_eq!(comp.current_function, "main");
+        assert_eq!(comp.scope_index, 0);
+    }
+
+    #[test]
+    fn exception_declared_again() {
+        let l = lexer::build_lexer("m := nope\nm := \"s\"\nx := m.len()\nm = 2");
+        let mut parser = parser::build_parser(l, "");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler::default();
+        assert!(comp.compile(program).is_err());
+
+        let lines: Vec<i32> = comp.errors.iter().map(|e| e.span.start.line).collect();
+        assert_eq!(lines, vec![1, 4]);
+    }
+
+    #[test]
+    fn exception_max_errors() {
+        let l = lexer::build_lexer("a;\nb;\nc;\nd;");
+        let mut parser = parser::build_parser(l, "");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler {
+            max_errors: 3,
+            ..compiler::Compiler::default()
+        };
+
+        assert!(comp.compile(program).is_err());
+        assert_eq!(comp.errors.len(), 3);
+    }
+
+    #[test]
+    fn session_variables() {
+        compiler_session_test(vec![
+            ("x := 10", ".STORE 0 {.CONSTANT INT 10;};"),
+            (
+                "x = x + 5",
+                ".STORE 0 {.ADD {.LOAD VARIABLE 0;.CONSTANT INT 5;};};",
+            ),
+            ("y := x", ".STORE 1 {.LOAD VARIABLE 0;};"),
+            ("x", ".LOAD VARIABLE 0;"),
+        ]);
+    }
+
+    #[test]
+    fn session_functions() {
+        compiler_session_test(vec![
+            (
+                "fn add(int a, int b) { return a + b }",
+                ".FUNCTION \"var_0\" 1 INT ARGUMENTS {INT;INT;} FREE {} THEN {.RETURN {.ADD {.LOAD PARAMETER 1 0;.LOAD PARAMETER 1 1;};};};",
+            ),
+            (
+                "add(1, 2)",
+                ".CALL {.LOAD VARIABLE 0;} {.CONSTANT INT 1;.CONSTANT INT 2;};",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn session_classes() {
+        compiler_session_test(vec![
+            (
+                "class Person { age = 4 }",
+                ".COMPOUND \"class_0\" { INT;} FIELDS \"Person\" { \"age\";};",
+            ),
+            (
+                "p := Person()",
+                ".STORE 1 {.CALL { .FUNCTION \"\" 0 class_0 ARGUMENTS {} FREE {} THEN {.STORE 2 { .CONSTANT class_0 {.CONSTANT INT 4;};};.RETURN { .LOAD VARIABLE 2; };};} {};};",
+            ),
+            ("p.age", ".INDEX { .LOAD VARIABLE 1;} { .CONSTANT INT 0; };"),
+            // The class has to be known to the parser as a type as well
+            (
+                "class Holder { Person person }",
+                ".COMPOUND \"class_3\" { class_0;} FIELDS \"Holder\" { \"person\";};",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn session_failed_line() {
+        let state = compiler::CompilerState::default();
+
+        // The symbol of a line that failed must not remain in the session
+        assert!(crate::compile_with_state("a := nope", state.clone()).is_err());
+        let (_, state) = crate::compile_with_state("b := 5", state).unwrap();
+        let error = crate::compile_with_state("a", state).err().unwrap();
+
+        assert_eq!(error.error_count(), 1);
+        assert_eq!(error.diagnostics[0].code, "compiler::unknown-symbol");
+    }
+
+    #[test]
+    fn class_to_string_wrong_type() {
+        compiler_test_error(
+            "class Point { x = 1 int to_string() { return self.x } }",
+            Some(CompilerExceptionCode::WrongType(
+                "fn(): int".to_string(),
+                "fn(): string".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn session_extensions() {
+        compiler_session_test(vec![
+            (
+                "ext int { int double() { return self * 2 } }",
+                ".FUNCTION \"var_0\" 1 INT ARGUMENTS {INT;} FREE {} THEN {.RETURN {.MULTIPLY {.LOAD PARAMETER 1 0;.CONSTANT INT 2;};};};",
+            ),
+            ("3.double()", ".CALL {.LOAD VARIABLE 0;} {.CONSTANT INT 3;};"),
+        ]);
+    }
+
+    #[test]
+    fn source_locations() {
+        let input = "x := 1\nfn double(int a) {\n    return a * 2\n}";
+        let l = lexer::build_lexer(input);
+        let mut parser = parser::build_parser(l, "main.loop");
+        let program = parser.parse().unwrap();
+
+        let mut comp = compiler::Compiler {
+            source_locations: true,
+            ..compiler::Compiler::default()
+        };
+        let arc = comp.compile(program).unwrap().get_arc();
+
+        assert_eq!(
+            arc,
+            ".LOCATION \"main.loop\" 1;.STORE 0 {.CONSTANT INT 1;};\
+            .LOCATION \"main.loop\" 2;.FUNCTION \"var_1\" 1 INT ARGUMENTS {INT;} FREE {} THEN {\
+            .LOCATION \"main.loop\" 3;.RETURN {.MULTIPLY {.LOAD PARAMETER 1 0;.CONSTANT INT 2;};};};"
+        );
+    }
+
+    /// Compiles every line on top of the state of the lines before it, like the REPL does
+    fn compiler_session_test(lines: Vec<(&str, &str)>) {
+        let mut state = compiler::CompilerState::default();
+
+        for (input, expected) in lines {
+            let l = lexer::build_lexer(input);
+            let mut parser = parser::build_parser(l, "");
+            parser.defined_types = state.defined_types.clone();
+
+            let program = parser.parse().unwrap();
+
+            let mut comp = compiler::Compiler::default_with_state(state);
+            let err = comp.compile(program);
+
+            assert_eq!(err.unwrap().get_arc(), expected.to_string());
+
+            state = comp.get_compiler_state();
+            state.defined_types = parser.defined_types;
+        }
+    }
+
     fn compiler_test(input: &str, expected: &str) {
         let l = lexer::build_lexer(input);
         let mut parser = parser::build_parser(l, "");
@@ -450,9 +1088,7 @@ mod tests {
 
         if !parser.errors.is_empty() {
             for err in parser.errors {
-                if let Exception::Syntax(err) = err {
-                    println!("ParserException: {}", err);
-                }
+                println!("ParserException: {}", err);
             }
 
             panic!("Parser exceptions occurred!")
@@ -472,9 +1108,7 @@ mod tests {
 
         if !parser.errors.is_empty() {
             for err in parser.errors {
-                if let Exception::Syntax(err) = err {
-                    println!("ParserException: {}", err);
-                }
+                println!("ParserException: {}", err);
             }
 
             panic!("Parser exceptions occurred!")
@@ -483,12 +1117,12 @@ mod tests {
         let mut comp = compiler::Compiler::default();
         let err = comp.compile(program);
 
-        if expected.is_some() && err.is_ok() {
-            panic!("expected error to be \"{:?}\". got=NULL", expected.unwrap())
-        }
-
-        if expected.is_some() && err.is_err() {
-            assert_eq!(expected.unwrap(), err.err().unwrap().exception)
+        match (expected, err) {
+            (Some(expected), Ok(_)) => {
+                panic!("expected error to be \"{:?}\". got=NULL", expected)
+            }
+            (Some(expected), Err(err)) => assert_eq!(expected, err.exception),
+            (None, _) => {}
         }
     }
 
@@ -1,5 +1,7 @@
 use crate::parser::exception::SyntaxException;
+use crate::span::Span;
 use std::fmt::{Display, Formatter};
+use vinci::diagnostic::Diagnostic;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct UnknownSymbol {
@@ -9,16 +11,13 @@ pub struct UnknownSymbol {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CompilerException {
-    pub location: (i32, i32),
+    pub span: Span,
     pub exception: CompilerExceptionCode,
 }
 
 impl CompilerException {
-    pub fn new(line: i32, colon: i32, exception: CompilerExceptionCode) -> CompilerException {
-        CompilerException {
-            location: (line, colon),
-            exception,
-        }
+    pub fn new(span: Span, exception: CompilerExceptionCode) -> CompilerException {
+        CompilerException { span, exception }
     }
 }
 
@@ -36,6 +35,8 @@ pub enum CompilerExceptionCode {
     TooManyFrees,
     UnknownSuffixOperator(String),
     ReturnStatementNotAllowedOutsideFunction,
+    BreakStatementNotAllowedOutsideLoop,
+    ContinueStatementNotAllowedOutsideLoop,
     UnknownExtensionMethod(String),
     CanOnlyAssignToVariableArray,
     CanNotReadFile(String),
@@ -49,19 +50,76 @@ pub enum CompilerExceptionCode {
     UnknownType(String),
     /// Module, Name
     NotPublic(String, String),
+    ConstantReassigned(String),
+    /// The step of an iterator loop is zero, so it would never reach its end
+    ZeroStep,
+    /// The parser of an imported file failed, its diagnostic comes from the parser
+    Syntax,
+    /// An imported file failed to compile, its errors are reported separately
+    ImportFailed(String),
     Unknown,
 }
 
 impl From<SyntaxException> for CompilerException {
-    fn from(_: SyntaxException) -> Self {
+    fn from(exception: SyntaxException) -> Self {
         CompilerException {
-            location: (0, 0),
-            exception: CompilerExceptionCode::Unknown,
+            span: exception.span,
+            exception: CompilerExceptionCode::Syntax,
         }
     }
 }
 
 impl CompilerException {
+    /// Identifies the kind of exception in diagnostics
+    pub fn code(&self) -> &'static str {
+        match &self.exception {
+            CompilerExceptionCode::UnknownSymbol(_) => "compiler::unknown-symbol",
+            CompilerExceptionCode::DivideByZero => "compiler::divide-by-zero",
+            CompilerExceptionCode::TooManyLocals => "compiler::too-many-locals",
+            CompilerExceptionCode::TooManyFrees => "compiler::too-many-frees",
+            CompilerExceptionCode::UnknownSuffixOperator(_) => "compiler::unknown-suffix-operator",
+            CompilerExceptionCode::ReturnStatementNotAllowedOutsideFunction => {
+                "compiler::return-outside-function"
+            }
+            CompilerExceptionCode::BreakStatementNotAllowedOutsideLoop => {
+                "compiler::break-outside-loop"
+            }
+            CompilerExceptionCode::ContinueStatementNotAllowedOutsideLoop => {
+                "compiler::continue-outside-loop"
+            }
+            CompilerExceptionCode::UnknownExtensionMethod(_) => {
+                "compiler::unknown-extension-method"
+            }
+            CompilerExceptionCode::CanOnlyAssignToVariableArray => "compiler::assign-to-non-array",
+            CompilerExceptionCode::CanNotReadFile(_) => "compiler::can-not-read-file",
+            CompilerExceptionCode::DoubleParameterName(_) => "compiler::double-parameter-name",
+            CompilerExceptionCode::CallingNonFunction(_) => "compiler::calling-non-function",
+            CompilerExceptionCode::WrongType(_, _) => "compiler::wrong-type",
+            CompilerExceptionCode::ValueDifferentFromType(_, _) => {
+                "compiler::value-different-from-type"
+            }
+            CompilerExceptionCode::UnknownField(_, _) => "compiler::unknown-field",
+            CompilerExceptionCode::UnknownType(_) => "compiler::unknown-type",
+            CompilerExceptionCode::NotPublic(_, _) => "compiler::not-public",
+            CompilerExceptionCode::ConstantReassigned(_) => "compiler::constant-reassigned",
+            CompilerExceptionCode::ZeroStep => "compiler::zero-step",
+            CompilerExceptionCode::Syntax => "compiler::syntax",
+            CompilerExceptionCode::ImportFailed(_) => "compiler::import-failed",
+            CompilerExceptionCode::Unknown => "compiler::unknown",
+        }
+    }
+
+    /// Creates a diagnostic for the exception, `text` is the line of code it happened on. Without
+    /// it the diagnostic does not point at any code.
+    pub fn diagnostic(&self, text: Option<String>) -> Diagnostic {
+        let diagnostic = Diagnostic::error(self.code(), self.pretty_print());
+
+        match text {
+            Some(text) => diagnostic.with_span(self.span.diagnostic(Some(text))),
+            None => diagnostic,
+        }
+    }
+
     pub fn pretty_print(&self) -> String {
         match &self.exception {
             CompilerExceptionCode::UnknownSymbol(var) => {
@@ -76,6 +134,12 @@ impl CompilerException {
             CompilerExceptionCode::ReturnStatementNotAllowedOutsideFunction => {
                 String::from("return statements are not allowed outside of functions")
             }
+            CompilerExceptionCode::BreakStatementNotAllowedOutsideLoop => {
+                String::from("break statements are not allowed outside of loops")
+            }
+            CompilerExceptionCode::ContinueStatementNotAllowedOutsideLoop => {
+                String::from("continue statements are not allowed outside of loops")
+            }
             CompilerExceptionCode::UnknownExtensionMethod(method) => {
                 format!("unknown extension method. got=\"{}\"", method)
             }
@@ -112,6 +176,16 @@ impl CompilerException {
             CompilerExceptionCode::UnknownType(tp) => {
                 format!("type does not exist. got=\"{}\"", tp)
             }
+            CompilerExceptionCode::ConstantReassigned(name) => {
+                format!("a constant cannot be reassigned. got=\"{}\"", name)
+            }
+            CompilerExceptionCode::ZeroStep => {
+                String::from("the step of a loop can not be zero, it would never reach the end")
+            }
+            CompilerExceptionCode::Syntax => "unable to parse file".to_string(),
+            CompilerExceptionCode::ImportFailed(file) => {
+                format!("unable to compile imported file. got=\"{}\"", file)
+            }
             CompilerExceptionCode::Unknown => "got an error, unknown what went wrong".to_string(),
             CompilerExceptionCode::NotPublic(module, name) => format!(
                 "Method \"{}\" inside module \"{}\" is not public!",
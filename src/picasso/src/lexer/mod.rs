@@ -3,7 +3,8 @@ mod test;
 pub mod token;
 
 use crate::lexer::token::create_token;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
+use crate::span::{Location, Span};
 use token::Token;
 use token::TokenType;
 
@@ -16,6 +17,10 @@ pub struct Lexer {
     pub peek_token: Option<Token>,
     pub current_line: i32,
     pub current_col: i32,
+    // Where the token that is being lexed starts
+    token_start: Location,
+    // Amount of braces the current token is inside of
+    pub brace_depth: i32,
 }
 
 impl Lexer {
@@ -36,6 +41,12 @@ impl Lexer {
         if self.get_current_token().is_none() {
             self.current_token = Some(create_token(TokenType::Unknown, "".to_string()));
         }
+
+        match self.current_token.as_ref().unwrap().token {
+            TokenType::LeftBrace => self.brace_depth += 1,
+            TokenType::RightBrace => self.brace_depth -= 1,
+            _ => {}
+        }
     }
 
     pub fn get_current_token(&self) -> Option<&Token> {
@@ -52,23 +63,57 @@ impl Lexer {
     pub fn get_line(&self, line: i32) -> String {
         self.input
             .lines()
-            .nth((line - 1) as usize)
-            .unwrap()
+            .nth((line.max(1) - 1) as usize)
+            .unwrap_or("")
             .to_string()
     }
 
+    /// Returns the span of the current token, the file is left empty as the lexer does not know it
+    pub fn current_span(&self) -> Span {
+        Self::token_span(self.get_current_token())
+    }
+
+    /// Returns the span of the peek token, the file is left empty as the lexer does not know it
+    pub fn peek_span(&self) -> Span {
+        Self::token_span(self.get_peek_token())
+    }
+
+    fn token_span(token: Option<&Token>) -> Span {
+        match token {
+            Some(token) => Span::new("", token.start.clone(), token.end.clone()),
+            None => Span::default(),
+        }
+    }
+
     fn internal_next_token(&mut self) -> Token {
+        let mut token = self.read_token();
+
+        token.start = self.token_start.clone();
+        token.end = Location {
+            line: self.current_line,
+            colon: self.current_col,
+        };
+
+        token
+    }
+
+    fn read_token(&mut self) -> Token {
         let possible_char = self.input.chars().nth(self.current as usize);
 
         self.next_character();
 
+        self.token_start = Location {
+            line: self.current_line,
+            colon: self.current_col,
+        };
+
         if possible_char.is_none() {
             return create_token(TokenType::Eof, "".to_string());
         }
 
         let ch: char = possible_char.unwrap();
         if ch.is_whitespace() {
-            return self.internal_next_token();
+            return self.read_token();
         }
 
         match ch {
@@ -91,7 +136,13 @@ impl Lexer {
             '.' => {
                 if self.get_character(1) == '.' {
                     self.next_character();
-                    create_token(TokenType::Range, "..".to_string())
+
+                    if self.get_character(1) == '=' {
+                        self.next_character();
+                        create_token(TokenType::RangeInclusive, "..=".to_string())
+                    } else {
+                        create_token(TokenType::Range, "..".to_string())
+                    }
                 } else {
                     create_token(TokenType::Dot, ch.to_string())
                 }
@@ -102,11 +153,11 @@ impl Lexer {
             '/' => {
                 if self.get_character(1) == '<' {
                     self.remove_block_comment();
-                    self.internal_next_token()
+                    self.read_token()
                 } else if self.get_character(1) == '/' {
                     self.next_character();
                     self.remove_line_comment();
-                    self.internal_next_token()
+                    self.read_token()
                 } else {
                     create_token(TokenType::Divide, ch.to_string())
                 }
@@ -164,9 +215,8 @@ impl Lexer {
         self.next_character();
 
         while self.get_character(0) != '"' && self.get_character(0) != char::from(0) {
-            let res: Option<String> = self.find_escape_sequence();
-            if res != None {
-                string.push_str(res.unwrap().as_str());
+            if let Some(escaped) = self.find_escape_sequence() {
+                string.push_str(escaped.as_str());
                 self.next_character();
             } else {
                 string.push_str(self.get_character(0).to_string().as_str());
@@ -227,7 +277,7 @@ impl Lexer {
 
         // Keeping the line and column counter correct for the syntax error handeling
         let possible_char = self.input.chars().nth(self.current as usize);
-        if possible_char != None && self.get_character(0) == '\n' {
+        if possible_char.is_some() && self.get_character(0) == '\n' {
             self.current_line += 1;
             self.current_col = 0;
         } else {
@@ -253,7 +303,7 @@ impl Lexer {
     fn get_character(&self, diff: i32) -> char {
         let val = self.input.chars().nth((self.current + diff - 1) as usize);
 
-        if val == None {
+        if val.is_none() {
             return char::from(0);
         }
 
@@ -271,7 +321,7 @@ impl Lexer {
         let mut end_index = start_index;
         let mut possible_char = self.input.chars().nth(self.current as usize);
 
-        while possible_char != None && self.get_character(0) != '\n' {
+        while possible_char.is_some() && self.get_character(0) != '\n' {
             end_index += 1;
             possible_char = self.input.chars().nth(self.current as usize);
             self.next_character();
@@ -316,7 +366,7 @@ impl Lexer {
                 self.input
                     .replace_range((self.current - 1) as usize..(self.current) as usize, " ");
                 break;
-            } else if possible_char == None {
+            } else if possible_char.is_none() {
                 break;
             } else {
                 self.input
@@ -353,7 +403,10 @@ impl Lexer {
             }
         }
 
-        Err(SyntaxException::ExpectedToken(token))
+        Err(SyntaxException::new(
+            self.peek_span(),
+            SyntaxExceptionCode::ExpectedToken(token),
+        ))
     }
 
     /// Checks if given TokenType is the same as the current token,
@@ -391,6 +444,7 @@ impl Lexer {
             "in" => TokenType::In,
             "to" => TokenType::To,
             "break" => TokenType::Break,
+            "continue" => TokenType::Continue,
             "class" => TokenType::Class,
             "ext" => TokenType::Extends,
             "pub" => TokenType::Public,
@@ -425,6 +479,8 @@ pub fn build_lexer(input: &str) -> Lexer {
         peek_token: None,
         current_line: 1,
         current_col: 0,
+        token_start: Location::default(),
+        brace_depth: 0,
     };
 
     l.next_token();
@@ -1,3 +1,5 @@
 //! Unit Tests for the Lexer
+#[allow(clippy::module_inception)]
 mod test_helper;
+#[allow(clippy::module_inception)]
 mod tests;
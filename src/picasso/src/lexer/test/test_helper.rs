@@ -1,11 +1,8 @@
 #[cfg(test)]
 pub mod test_helper {
-    use crate::lexer::token::{Token, TokenType};
+    use crate::lexer::token::{create_token, Token, TokenType};
 
     pub fn generate_token(literal: &str, token_type: TokenType) -> Token {
-        Token {
-            token: token_type,
-            literal: literal.to_string(),
-        }
+        create_token(token_type, literal.to_string())
     }
 }
@@ -245,7 +245,7 @@ mod tests {
                 current_token.literal, expected[i].literal, current_token.token, expected[i].token
             );
 
-            i = i + 1;
+            i += 1;
             l.next_token();
             current_token = l.get_current_token().unwrap();
         }
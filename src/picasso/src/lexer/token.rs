@@ -1,8 +1,13 @@
 //! Helper for tokens generated by the Lexer
+use crate::span::Location;
+
 #[derive(Clone, Debug)]
 pub struct Token {
     pub token: TokenType,
     pub literal: String,
+    // First and last character of the token
+    pub start: Location,
+    pub end: Location,
 }
 
 #[derive(PartialEq, Clone, Debug, Copy, Eq, Hash)]
@@ -63,6 +68,8 @@ pub enum TokenType {
     Dot,
     /// Example: '..'
     Range,
+    /// Example: '..='
+    RangeInclusive,
     /// Example: '['
     LeftBracket,
     /// Example: ']'
@@ -115,6 +122,8 @@ pub enum TokenType {
     In,
     /// Example: 'break'
     Break,
+    /// Example: 'continue'
+    Continue,
     FunctionType,
     Class,
     Extends,
@@ -125,5 +134,10 @@ pub enum TokenType {
 }
 
 pub fn create_token(token: TokenType, literal: String) -> Token {
-    Token { token, literal }
+    Token {
+        token,
+        literal,
+        start: Location::default(),
+        end: Location::default(),
+    }
 }
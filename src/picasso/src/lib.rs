@@ -1,66 +1,270 @@
 use crate::compiler::{Compiler, CompilerState};
-use std::path::Path;
-use std::process::{exit, ExitCode};
+use crate::exception::compiler::CompilerException;
+use crate::parser::exception::SyntaxException;
+use colored::Colorize;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use vinci::diagnostic::{self, Diagnostic, DiagnosticFormat};
 
+pub mod analysis;
 pub mod compiler;
 pub mod exception;
 mod lexer;
 mod parser;
+pub mod span;
+pub mod stdlib;
 
-pub fn compile_with_state(str: &str, _state: CompilerState) -> (String, CompilerState) {
+/// Amount of errors after which parsing and compiling stop, when none is given
+pub const DEFAULT_MAX_ERRORS: usize = 20;
+
+/// How long every phase of compiling took
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Timings {
+    pub lexing: Duration,
+    // Tokens are lexed while parsing, so this includes lexing them again
+    pub parsing: Duration,
+    pub compiling: Duration,
+}
+
+/// Why compiling failed, nothing has been reported yet, that is up to the caller using
+/// [CompileError::report]
+#[derive(Debug, Clone)]
+pub struct CompileError {
+    pub exceptions: Exceptions,
+    // Diagnostic of every error, including the line of code it happened on
+    pub diagnostics: Vec<Diagnostic>,
+    // The limit of errors that was used, compiling stopped early when it was reached
+    pub max_errors: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum Exceptions {
+    /// The code could not be parsed
+    Syntax(Vec<SyntaxException>),
+    /// The code was parsed, but could not be compiled
+    Compiler(Vec<CompilerException>),
+}
+
+impl CompileError {
+    /// Prints every diagnostic, followed by how many errors were found
+    pub fn report(&self) {
+        for diagnostic in &self.diagnostics {
+            diagnostic.emit();
+        }
+
+        print_error_summary(self.error_count(), self.max_errors);
+    }
+
+    /// Amount of errors found, syntax errors in imported files included
+    pub fn error_count(&self) -> usize {
+        match &self.exceptions {
+            Exceptions::Syntax(errors) => errors.len(),
+            Exceptions::Compiler(errors) => errors.len(),
+        }
+    }
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let messages: Vec<String> = match &self.exceptions {
+            Exceptions::Syntax(errors) => errors.iter().map(|e| e.pretty_print()).collect(),
+            Exceptions::Compiler(errors) => errors.iter().map(|e| e.pretty_print()).collect(),
+        };
+
+        write!(f, "{}", messages.join("\n"))
+    }
+}
+
+/// Compiles a piece of code on top of the state left behind by an earlier compilation, symbols,
+/// classes, extensions and imports remain available. This is what the REPL uses for every line.
+/// When it fails the state that was given is still valid.
+pub fn compile_with_state(
+    str: &str,
+    state: CompilerState,
+) -> Result<(String, CompilerState), CompileError> {
+    let (arc, state, _) = run_compile_with_state(str, state, false)?;
+
+    Ok((arc, state))
+}
+
+/// Same as [compile_with_state], but also measures how long every phase took. Lexing is timed
+/// with an extra pass over the code, so this is only meant for benchmarking.
+pub fn compile_with_state_timed(
+    str: &str,
+    state: CompilerState,
+) -> Result<(String, CompilerState, Timings), CompileError> {
+    run_compile_with_state(str, state, true)
+}
+
+fn run_compile_with_state(
+    str: &str,
+    state: CompilerState,
+    time_lexer: bool,
+) -> Result<(String, CompilerState, Timings), CompileError> {
+    let mut timings = Timings::default();
+    if time_lexer {
+        timings.lexing = time_lexing(str);
+    }
+
+    let start = Instant::now();
     let lexer = lexer::build_lexer(str);
     let mut parser = parser::build_parser(lexer, "");
+    parser.defined_types = state.defined_types.clone();
+
+    let program = parser.parse();
+    timings.parsing = start.elapsed();
+
+    if program.is_err() {
+        return Err(syntax_error(parser));
+    }
 
-    let program = parser.parse().unwrap();
     let mut compiler = Compiler {
         compiled_from: str.to_string(),
-        ..Compiler::default()
+        ..Compiler::default_with_state(state)
     };
 
-    let compiled = compiler.compile(program);
+    let start = Instant::now();
+    let compiled = compiler.compile(program.unwrap());
+    timings.compiling = start.elapsed();
 
     if compiled.is_err() {
-        println!("Picasso: {:?}", compiled.err().unwrap());
-        exit(1);
+        return Err(compiler_error(compiler));
     }
 
-    (compiled.unwrap().get_arc(), compiler.get_compiler_state())
+    let mut state = compiler.get_compiler_state();
+    state.defined_types = parser.defined_types;
+
+    Ok((compiled.unwrap().get_arc(), state, timings))
 }
 
+/// Compiles a file to Arc, at most `max_errors` errors are reported before compilation stops
 pub fn compile(
     str: &str,
     file_location: Option<&str>,
-) -> Result<(String, CompilerState), ExitCode> {
+    max_errors: usize,
+) -> Result<(String, CompilerState), CompileError> {
+    let (arc, state, _) = run_compile(str, file_location, max_errors, false)?;
+
+    Ok((arc, state))
+}
+
+/// Same as [compile], but also measures how long every phase took. Lexing is timed with an extra
+/// pass over the code, so this is only meant for benchmarking.
+pub fn compile_timed(
+    str: &str,
+    file_location: Option<&str>,
+    max_errors: usize,
+) -> Result<(String, CompilerState, Timings), CompileError> {
+    run_compile(str, file_location, max_errors, true)
+}
+
+fn run_compile(
+    str: &str,
+    file_location: Option<&str>,
+    max_errors: usize,
+    time_lexer: bool,
+) -> Result<(String, CompilerState, Timings), CompileError> {
+    let mut timings = Timings::default();
+    if time_lexer {
+        timings.lexing = time_lexing(str);
+    }
+
+    let start = Instant::now();
     let lexer = lexer::build_lexer(str);
     let mut parser = parser::build_parser(lexer, file_location.unwrap_or(""));
+    parser.max_errors = max_errors;
 
     let program = parser.parse();
+    timings.parsing = start.elapsed();
 
     if program.is_err() {
-        return Err(ExitCode::FAILURE);
+        return Err(syntax_error(parser));
     }
 
     let program = program.unwrap();
 
     let mut compiler = Compiler {
         compiled_from: str.to_string(),
+        max_errors,
         ..Compiler::default()
     };
 
-    if let Some(file) = file_location {
-        let path = Path::new(file);
-        if path.extension().is_some() {
-            compiler.base_location = path.parent().unwrap().to_str().unwrap().to_string()
-        } else {
-            compiler.base_location = file.to_string();
-        }
-    }
+    compiler.set_base_location(file_location);
+    compiler.source_locations = file_location.is_some();
 
+    let start = Instant::now();
     let compiled = compiler.compile(program);
+    timings.compiling = start.elapsed();
 
     if compiled.is_err() {
-        return Err(ExitCode::FAILURE);
+        return Err(compiler_error(compiler));
+    }
+
+    Ok((
+        compiled.unwrap().get_arc(),
+        compiler.get_compiler_state(),
+        timings,
+    ))
+}
+
+fn syntax_error(parser: parser::Parser) -> CompileError {
+    CompileError {
+        diagnostics: parser
+            .errors
+            .iter()
+            .map(|error| parser.diagnostic(error))
+            .collect(),
+        max_errors: parser.max_errors,
+        exceptions: Exceptions::Syntax(parser.errors),
     }
+}
 
-    Ok((compiled.unwrap().get_arc(), compiler.get_compiler_state()))
+fn compiler_error(compiler: Compiler) -> CompileError {
+    CompileError {
+        diagnostics: compiler.diagnostics,
+        max_errors: compiler.max_errors,
+        exceptions: Exceptions::Compiler(compiler.errors),
+    }
+}
+
+/// The lexer is driven by the parser, so lexing on its own is timed with a separate pass
+fn time_lexing(str: &str) -> Duration {
+    let start = Instant::now();
+    let mut lexer = lexer::build_lexer(str);
+
+    while lexer
+        .get_current_token()
+        .map_or(false, |token| token.token != lexer::token::TokenType::Eof)
+    {
+        lexer.next_token();
+    }
+
+    start.elapsed()
+}
+
+/// Prints how many errors were found, and whether it stopped early because there were too many.
+/// Only done for people, tools count the diagnostics themselves.
+fn print_error_summary(errors: usize, max_errors: usize) {
+    if diagnostic::format() != DiagnosticFormat::Human {
+        return;
+    }
+
+    let summary = if errors == 1 {
+        "Found 1 error".to_string()
+    } else {
+        format!("Found {} errors", errors)
+    };
+
+    if errors >= max_errors {
+        println!(
+            "{}",
+            format!(
+                "{}, stopped after reaching the limit of {}",
+                summary, max_errors
+            )
+            .red()
+        );
+    } else {
+        println!("{}", summary.red());
+    }
 }
@@ -1,4 +1,7 @@
 use crate::lexer::token::TokenType;
+use crate::span::Span;
+use std::fmt::{Display, Formatter};
+use vinci::diagnostic::Diagnostic;
 
 #[derive(Debug, Clone)]
 pub enum Parenthesis {
@@ -7,7 +10,19 @@ pub enum Parenthesis {
 }
 
 #[derive(Debug, Clone)]
-pub enum SyntaxException {
+pub struct SyntaxException {
+    pub span: Span,
+    pub exception: SyntaxExceptionCode,
+}
+
+impl SyntaxException {
+    pub fn new(span: Span, exception: SyntaxExceptionCode) -> SyntaxException {
+        SyntaxException { span, exception }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SyntaxExceptionCode {
     Unknown,
     // Title, Description
     CustomMessage(String, Option<String>),
@@ -15,3 +30,49 @@ pub enum SyntaxException {
     NoPrefixParser(TokenType),
     WrongParentheses(Parenthesis),
 }
+
+impl SyntaxException {
+    pub fn pretty_print(&self) -> String {
+        match &self.exception {
+            SyntaxExceptionCode::Unknown => "=> Unknown parser error occurred".to_string(),
+            SyntaxExceptionCode::CustomMessage(title, _) => title.clone(),
+            SyntaxExceptionCode::ExpectedToken(expected) => {
+                format!("Wrong token, expected={:?}.", expected)
+            }
+            SyntaxExceptionCode::NoPrefixParser(what) => format!("No prefix parser for {:?}", what),
+            SyntaxExceptionCode::WrongParentheses(p) => {
+                format!("Wrong parenthesis, expected={:?}.", p)
+            }
+        }
+    }
+}
+
+impl SyntaxException {
+    /// Identifies the kind of exception in diagnostics
+    pub fn code(&self) -> &'static str {
+        match &self.exception {
+            SyntaxExceptionCode::Unknown => "syntax::unknown",
+            SyntaxExceptionCode::CustomMessage(_, _) => "syntax::invalid",
+            SyntaxExceptionCode::ExpectedToken(_) => "syntax::expected-token",
+            SyntaxExceptionCode::NoPrefixParser(_) => "syntax::no-prefix-parser",
+            SyntaxExceptionCode::WrongParentheses(_) => "syntax::wrong-parentheses",
+        }
+    }
+
+    /// Creates a diagnostic for the exception, `text` is the line of code it happened on
+    pub fn diagnostic(&self, text: Option<String>) -> Diagnostic {
+        let mut span = self.span.diagnostic(text);
+
+        if let SyntaxExceptionCode::CustomMessage(_, Some(description)) = &self.exception {
+            span.label = Some(description.clone());
+        }
+
+        Diagnostic::error(self.code(), self.pretty_print()).with_span(span)
+    }
+}
+
+impl Display for SyntaxException {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pretty_print())
+    }
+}
@@ -4,17 +4,18 @@ use crate::parser::expression::Precedence::Lowest;
 use crate::parser::program::Node;
 use crate::parser::statement::expression::Expression;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 // TODO: Add type
 pub struct Array {
     pub(crate) values: Vec<Expression>,
-    // Line, colon
-    pub location: (i32, i32),
+    pub span: Span,
 }
 
 pub fn parse_expression_array(p: &mut Parser) -> Result<Node, SyntaxException> {
     let mut elements: Vec<Expression> = Vec::new();
+    let start = p.current_location();
 
     p.lexer.next_token();
 
@@ -25,6 +26,7 @@ pub fn parse_expression_array(p: &mut Parser) -> Result<Node, SyntaxException> {
 
         if let Ok(Node::Expression(exp)) = exp {
             elements.push(Expression {
+                span: exp.span(),
                 expression: Box::from(exp),
             });
         }
@@ -39,7 +41,7 @@ pub fn parse_expression_array(p: &mut Parser) -> Result<Node, SyntaxException> {
     Ok(Node::Expression(crate::parser::Expression::Array(
         Box::from(Array {
             values: elements,
-            location: (p.lexer.current_line, p.lexer.current_col),
+            span: p.span_from(start),
         }),
     )))
 }
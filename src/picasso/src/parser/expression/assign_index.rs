@@ -1,8 +1,10 @@
 use crate::parser::expression::Expression;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct AssignIndex {
     pub(crate) left: Expression,
     pub(crate) index: Expression,
     pub(crate) value: Expression,
+    pub span: Span,
 }
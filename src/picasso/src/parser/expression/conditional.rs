@@ -1,5 +1,5 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::suffix::parse_grouped_expression_without_param;
 use crate::parser::expression::Expression;
 use crate::parser::expression::Precedence::Lowest;
@@ -7,16 +7,19 @@ use crate::parser::program::Node;
 use crate::parser::statement::block::{parse_block, Block};
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Conditional {
     pub condition: Box<Expression>,
     pub body: Block,
     pub else_condition: Option<Box<Node>>,
+    pub span: Span,
 }
 
 /// Parsing of if-expressions with else blocks
 pub fn parse_conditional(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let start = p.current_location();
     p.lexer.next_token();
     let uses_parenthesis = p.current_token_is(TokenType::LeftParenthesis);
 
@@ -35,16 +38,16 @@ pub fn parse_conditional(p: &mut Parser) -> Result<Node, SyntaxException> {
             // Custom error whether if-expression has parenthesis or not
             if uses_parenthesis {
                 let message = "Syntax  -> for (<condition>) { <code> }\nExample -> if (i < 3) { println(i) }\n\nA loop can be with or without parenthesis".to_string();
-                return Err(SyntaxException::CustomMessage(
+                return Err(p.error(SyntaxExceptionCode::CustomMessage(
                     "expected: RightParenthesis".to_string(),
                     Some(message),
-                ));
+                )));
             } else {
                 let message = "Syntax  -> for <condition> { <code> }\nExample -> if i < 3 { println(i) }\n\nA loop can be with or without parenthesis".to_string();
-                return Err(SyntaxException::CustomMessage(
+                return Err(p.error(SyntaxExceptionCode::CustomMessage(
                     "expected: NoParenthesis".to_string(),
                     Some(message),
-                ));
+                )));
             }
         } else if p.lexer.current_token.clone().unwrap().token == TokenType::RightParenthesis {
             // If the if-expression has parenthesis, the lexer needs to go to the next token
@@ -64,14 +67,15 @@ pub fn parse_conditional(p: &mut Parser) -> Result<Node, SyntaxException> {
         // Parsing of else block if it exists
         if p.next_token_is(TokenType::Else) {
             p.lexer.next_token();
-            return create_conditional(Box::new(exp), body, parse_else(p)?);
+            let else_condition = parse_else(p)?;
+            return create_conditional(Box::new(exp), body, else_condition, p.span_from(start));
         }
 
         // Returning if and else body
-        return create_conditional(Box::new(exp), body, None);
+        return create_conditional(Box::new(exp), body, None, p.span_from(start));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
 
 /// Parsing of else block of an if-expression
@@ -96,12 +100,14 @@ fn create_conditional(
     condition: Box<Expression>,
     body: Block,
     else_condition: Option<Box<Node>>,
+    span: Span,
 ) -> Result<Node, SyntaxException> {
     Ok(Node::Expression(Expression::Conditional(Box::new(
         Conditional {
             condition,
             body,
             else_condition,
+            span,
         },
     ))))
 }
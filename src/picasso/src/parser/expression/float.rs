@@ -1,4 +1,7 @@
+use crate::span::Span;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Float {
     pub value: f64,
+    pub span: Span,
 }
@@ -1,11 +1,12 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::identifier::Identifier;
 use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::statement::block::{parse_block, Block};
 use crate::parser::types::Types;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Parameter {
@@ -26,12 +27,14 @@ pub struct Function {
     pub body: Block,
     pub predefined_type: Option<Types>,
     pub public: bool,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Call {
     pub identifier: Box<Expression>,
     pub parameters: Vec<Expression>,
+    pub span: Span,
 }
 
 pub fn parse_arguments(p: &mut Parser) -> Result<Vec<Parameter>, SyntaxException> {
@@ -39,14 +42,19 @@ pub fn parse_arguments(p: &mut Parser) -> Result<Vec<Parameter>, SyntaxException
 
     p.lexer.next_token();
 
-    while p.lexer.get_current_token().unwrap().token == TokenType::Identifier {
+    // Parameters declared with "var" accept a value of any type
+    while p.current_token_is(TokenType::Identifier)
+        || p.current_token_is(TokenType::VariableDeclaration)
+    {
         let old = p.lexer.get_current_token().unwrap().clone();
 
         let tp = {
             if let Some(tpe) = p.parse_type(old.clone()) {
                 tpe
             } else {
-                return Err(SyntaxException::ExpectedToken(TokenType::RightParenthesis));
+                return Err(p.error(SyntaxExceptionCode::ExpectedToken(
+                    TokenType::RightParenthesis,
+                )));
             }
         };
 
@@ -54,8 +62,7 @@ pub fn parse_arguments(p: &mut Parser) -> Result<Vec<Parameter>, SyntaxException
         arguments.push(Parameter {
             identifier: Identifier::new(
                 p.lexer.get_current_token().unwrap().literal.to_string(),
-                0,
-                0,
+                p.current_span(),
             ),
             _type: tp,
         });
@@ -99,14 +106,18 @@ pub fn parse_call(p: &mut Parser, ident: Expression) -> Result<Node, SyntaxExcep
 
     p.current_token_is_result(TokenType::RightParenthesis)?;
 
+    let span = p.span_from(ident.span().start);
+
     Ok(Node::Expression(Expression::Call(Call {
         identifier: Box::from(ident),
         parameters: arguments,
+        span,
     })))
 }
 
 pub fn parse_function(p: &mut Parser) -> Result<Node, SyntaxException> {
     let mut name = String::from("");
+    let start = p.current_location();
 
     if !p
         .lexer
@@ -116,7 +127,9 @@ pub fn parse_function(p: &mut Parser) -> Result<Node, SyntaxException> {
             name = p.lexer.current_token.as_ref().unwrap().clone().literal;
             p.lexer.next_token();
         } else {
-            return Err(SyntaxException::ExpectedToken(TokenType::LeftParenthesis));
+            return Err(p.error(SyntaxExceptionCode::ExpectedToken(
+                TokenType::LeftParenthesis,
+            )));
         }
     }
 
@@ -124,8 +137,16 @@ pub fn parse_function(p: &mut Parser) -> Result<Node, SyntaxException> {
 
     p.lexer.next_token();
 
+    // The return type can be declared after the parameters, eg: "fn double(int a) int { ... }".
+    // Without it the return type is inferred from the body.
+    let mut predefined_type = None;
+    if p.current_token_is(TokenType::Identifier) {
+        predefined_type = Some(p.expect_type()?);
+        p.lexer.next_token();
+    }
+
     if !p.lexer.next_token_and_current_is(TokenType::LeftBrace) {
-        return Err(SyntaxException::ExpectedToken(TokenType::LeftBrace));
+        return Err(p.error(SyntaxExceptionCode::ExpectedToken(TokenType::LeftBrace)));
     }
 
     let body = parse_block(p)?;
@@ -144,7 +165,8 @@ pub fn parse_function(p: &mut Parser) -> Result<Node, SyntaxException> {
         name,
         parameters: arguments,
         body,
-        predefined_type: None,
+        predefined_type,
         public,
+        span: p.span_from(start),
     })))
 }
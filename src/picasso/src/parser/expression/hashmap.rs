@@ -1,15 +1,15 @@
-use crate::parser::exception::SyntaxException;
+use crate::lexer::token::TokenType;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::boolean::Boolean;
 use crate::parser::expression::integer::Integer;
 use crate::parser::expression::string::LoopString;
-use crate::parser::expression::Expression;
+use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::Parser;
-use std::collections::HashMap;
+use crate::span::Span;
 use std::hash::Hash;
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
-#[allow(unused)]
 pub enum HashableExpression {
     Integer(Integer),
     String(LoopString),
@@ -18,9 +18,62 @@ pub enum HashableExpression {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Hashmap {
-    pub(crate) values: HashMap<HashableExpression, Expression>,
+    // Kept in the order they were written, so that the generated Arc is always the same
+    pub(crate) values: Vec<(HashableExpression, Expression)>,
+    pub span: Span,
 }
 
-pub fn parse_expression_hashmap(_: &mut Parser) -> Result<Node, SyntaxException> {
-    Err(SyntaxException::Unknown)
+/// Parses a hashmap literal, keys can only be integers, strings or booleans
+///
+/// ```loop
+/// {20: 30, false: true, "hello": {100: 20}}
+/// ```
+pub fn parse_expression_hashmap(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let mut values: Vec<(HashableExpression, Expression)> = Vec::new();
+    let start = p.current_location();
+
+    p.lexer.next_token(); // Skipping over left brace
+
+    while p.lexer.get_current_token().unwrap().token != TokenType::RightBrace
+        && p.lexer.get_current_token().unwrap().token != TokenType::Eof
+    {
+        let key = match p.parse_expression(Precedence::Lowest)? {
+            Node::Expression(key) => key.get_hash(),
+            _ => None,
+        };
+
+        let key = match key {
+            Some(key) => key,
+            None => {
+                return Err(p.error(SyntaxExceptionCode::CustomMessage(
+                    "hashmap keys can only be an integer, string or boolean".to_string(),
+                    None,
+                )))
+            }
+        };
+
+        p.expected(TokenType::Colon)?;
+        p.lexer.next_token(); // Skipping over colon
+
+        let value = p.parse_expression(Precedence::Lowest)?;
+
+        if let Node::Expression(value) = value {
+            values.push((key, value));
+        }
+
+        p.lexer.next_token();
+
+        if p.lexer.get_current_token().unwrap().token == TokenType::Comma {
+            p.lexer.next_token();
+        }
+    }
+
+    if !p.current_token_is(TokenType::RightBrace) {
+        return Err(p.error(SyntaxExceptionCode::ExpectedToken(TokenType::RightBrace)));
+    }
+
+    Ok(Node::Expression(Expression::Hashmap(Hashmap {
+        values,
+        span: p.span_from(start),
+    })))
 }
@@ -1,19 +1,23 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::assign_index::AssignIndex;
 use crate::parser::expression::function::parse_call;
 use crate::parser::expression::function::parse_expression_arguments;
 use crate::parser::expression::identifier::parse_identifier;
+use crate::parser::expression::integer::Integer;
+use crate::parser::expression::suffix::Suffix;
 use crate::parser::expression::Call;
 use crate::parser::expression::Identifier;
 use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Index {
     pub(crate) left: Expression,
     pub(crate) index: Expression,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -21,52 +25,61 @@ pub struct Slice {
     pub left: Box<Expression>,
     pub begin: Box<Expression>,
     pub end: Box<Expression>,
+    pub span: Span,
 }
 
 impl Slice {
-    pub fn new_node(left: Expression, begin: Expression, end: Expression) -> Expression {
+    pub fn new_node(
+        left: Expression,
+        begin: Expression,
+        end: Expression,
+        span: Span,
+    ) -> Expression {
         Expression::Slice(Slice {
             left: Box::new(left),
             begin: Box::new(begin),
             end: Box::new(end),
+            span,
         })
     }
 }
 
 pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node, SyntaxException> {
     let check_token = p.lexer.get_current_token().unwrap().token;
+    let start = left.span().start;
     p.lexer.next_token(); // Skipping over potential left bracket
 
     if check_token == TokenType::LeftBracket {
         // This index expression is for: Arrays OR Hashmaps
         let index_exp = p.parse_expression(Precedence::Lowest)?;
 
-        if p.lexer.peek_token.as_ref().unwrap().token == TokenType::Range {
-            // Is slice and not an index
-
-            p.expected(TokenType::Range)?; // Skipping over dotdot
-            p.lexer.next_token(); // Skipping over end expression
-
-            // 'end' is the right value of the slice
-            let end = p.parse_expression(Precedence::Lowest)?;
-
+        // A range as index is a slice, eg. arr[1..3]
+        if let Node::Expression(Expression::Range(range)) = index_exp {
             p.expected(TokenType::RightBracket)?;
 
-            let begin = if let Node::Expression(begin) = index_exp {
-                begin
+            let range = *range;
+            let end = if range.inclusive {
+                let span = range.till.span();
+
+                Expression::Suffix(Box::new(Suffix {
+                    left: range.till,
+                    operator: "+".to_string(),
+                    right: Expression::Integer(Integer {
+                        value: 1,
+                        span: span.clone(),
+                    }),
+                    span,
+                }))
             } else {
-                // TODO: Create default value so this is possible: arr[..4]
-                todo!("Create error for when begin is not expression")
+                range.till
             };
 
-            let end = if let Node::Expression(end) = end {
-                end
-            } else {
-                // TODO: Create default value so this is possible: arr[1..]
-                todo!("Create error for when end is not expression")
-            };
-
-            return Ok(Node::Expression(Slice::new_node(left, begin, end)));
+            return Ok(Node::Expression(Slice::new_node(
+                left,
+                range.from,
+                end,
+                p.span_from(start),
+            )));
         }
 
         if let Node::Expression(index) = index_exp {
@@ -86,6 +99,7 @@ pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node,
                             left,
                             index,
                             value: exp,
+                            span: p.span_from(start),
                         },
                     ))));
                 }
@@ -94,10 +108,12 @@ pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node,
             return Ok(Node::Expression(Expression::Index(Box::from(Index {
                 left,
                 index,
+                span: p.span_from(start),
             }))));
         }
     } else if p.lexer.get_current_token().unwrap().token == TokenType::Identifier {
         let identifier = p.lexer.get_current_token().unwrap().clone().literal;
+        let identifier_span = p.current_span();
 
         if p.lexer.get_peek_token().unwrap().clone().token != TokenType::LeftParenthesis {
             if p.lexer.next_token_is_and_next_token(TokenType::Assign) {
@@ -109,20 +125,26 @@ pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node,
                     return Ok(Node::Expression(Expression::AssignIndex(Box::from(
                         AssignIndex {
                             left,
-                            index: Expression::Identifier(Identifier::new(identifier, 0, 0)),
+                            index: Expression::Identifier(Identifier::new(
+                                identifier,
+                                identifier_span,
+                            )),
                             value: exp,
+                            span: p.span_from(start),
                         },
                     ))));
                 }
             }
             return Ok(Node::Expression(Expression::Index(Box::new(Index {
                 left,
-                index: Expression::Identifier(Identifier::new(identifier, 0, 0)),
+                index: Expression::Identifier(Identifier::new(identifier, identifier_span)),
+                span: p.span_from(start),
             }))));
         }
 
         p.expected(TokenType::LeftParenthesis)?;
         let arguments: Vec<Expression> = parse_expression_arguments(p)?;
+        let span = p.span_from(start);
 
         match left.clone() {
             Expression::Identifier(i) => i.value,
@@ -130,9 +152,11 @@ pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node,
                 return Ok(Node::Expression(Expression::Call(Call {
                     identifier: Box::from(Expression::Index(Box::new(Index {
                         left,
-                        index: Expression::Identifier(Identifier::new(identifier, 0, 0)),
+                        index: Expression::Identifier(Identifier::new(identifier, identifier_span)),
+                        span: span.clone(),
                     }))),
                     parameters: arguments,
+                    span,
                 })));
             }
         };
@@ -141,9 +165,11 @@ pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node,
         return Ok(Node::Expression(Expression::Call(Call {
             identifier: Box::new(Expression::Index(Box::new(Index {
                 left,
-                index: Expression::Identifier(Identifier::new(identifier, 0, 0)),
+                index: Expression::Identifier(Identifier::new(identifier, identifier_span)),
+                span: span.clone(),
             }))),
             parameters: arguments,
+            span,
         })));
     } else {
         // This index expression is for: Extension methods OR Classes
@@ -156,10 +182,11 @@ pub fn parse_index_expression(p: &mut Parser, left: Expression) -> Result<Node,
                 return Ok(Node::Expression(Expression::Index(Box::from(Index {
                     left,
                     index: val,
+                    span: p.span_from(start),
                 }))));
             }
         }
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
@@ -1,4 +1,7 @@
+use crate::span::Span;
+
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
 pub struct Integer {
     pub value: i64,
+    pub span: Span,
 }
@@ -1,24 +1,31 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::identifier::{parse_identifier, Identifier};
 use crate::parser::expression::Expression;
 use crate::parser::expression::Precedence::Lowest;
 use crate::parser::program::Node;
 use crate::parser::statement::block::{parse_block, Block};
 use crate::parser::Parser;
+use crate::span::{Location, Span};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Loop {
     pub condition: Box<Expression>,
     pub body: Block,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LoopIterator {
     pub identifier: Identifier,
     pub body: Block,
-    pub from: u32,
-    pub till: u32,
+    pub from: Box<Expression>,
+    pub till: Box<Expression>,
+    /// How much is added every iteration, counting up by one if there is no step
+    pub step: Option<Box<Expression>>,
+    /// Whether `till` itself is part of the iteration ("through" instead of "to")
+    pub inclusive: bool,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -26,9 +33,11 @@ pub struct LoopArrayIterator {
     pub identifier: Identifier,
     pub body: Block,
     pub array: Box<Expression>,
+    pub span: Span,
 }
 
 pub fn parse_loop(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let start = p.current_location();
     p.lexer.next_token();
     let uses_parenthesis = p.current_token_is(TokenType::LeftParenthesis);
     if uses_parenthesis {
@@ -41,12 +50,12 @@ pub fn parse_loop(p: &mut Parser) -> Result<Node, SyntaxException> {
 
         return if let Node::Expression(Expression::Identifier(ident)) = identifier {
             if !p.lexer.next_token_is_and_next_token(TokenType::Assign) {
-                parse_loop_array_iterator(p, ident, uses_parenthesis)
+                parse_loop_array_iterator(p, ident, uses_parenthesis, start)
             } else {
-                parse_loop_iterator(p, ident, uses_parenthesis)
+                parse_loop_iterator(p, ident, uses_parenthesis, start)
             }
         } else {
-            Err(SyntaxException::ExpectedToken(TokenType::Identifier))
+            Err(p.error(SyntaxExceptionCode::ExpectedToken(TokenType::Identifier)))
         };
     }
 
@@ -59,16 +68,16 @@ pub fn parse_loop(p: &mut Parser) -> Result<Node, SyntaxException> {
         // Custom error whether if-expression has parenthesis or not
         if uses_parenthesis {
             let message = "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }\n\nAn if expression can be with or without parenthesis".to_string();
-            return Err(SyntaxException::CustomMessage(
+            return Err(p.error(SyntaxExceptionCode::CustomMessage(
                 "expected: RightParenthesis".to_string(),
                 Some(message),
-            ));
+            )));
         } else {
             let message = "Syntax  -> for <condition> { <code> }\nExample -> for i < 3 { println(i) }\n\nAn if expression can be with or without parenthesis".to_string();
-            return Err(SyntaxException::CustomMessage(
+            return Err(p.error(SyntaxExceptionCode::CustomMessage(
                 "expected: NoParenthesis".to_string(),
                 Some(message),
-            ));
+            )));
         }
     } else if uses_parenthesis {
         // If the if-expression has parenthesis, the lexer needs to go to the next token
@@ -79,10 +88,10 @@ pub fn parse_loop(p: &mut Parser) -> Result<Node, SyntaxException> {
         let message =
             "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }"
                 .to_string();
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: LeftBrace".to_string(),
             Some(message),
-        ));
+        )));
     }
     p.lexer.next_token();
 
@@ -92,20 +101,21 @@ pub fn parse_loop(p: &mut Parser) -> Result<Node, SyntaxException> {
         let message =
             "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }"
                 .to_string();
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: RightBrace".to_string(),
             Some(message),
-        ));
+        )));
     }
 
     if let Node::Expression(exp) = condition_node {
         return Ok(Node::Expression(Expression::Loop(Loop {
             condition: Box::from(exp),
             body,
+            span: p.span_from(start),
         })));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
 
 /// Parsing the array iterator loop:
@@ -121,6 +131,7 @@ pub fn parse_loop_array_iterator(
     p: &mut Parser,
     identifier: Identifier,
     uses_parenthesis: bool,
+    start: Location,
 ) -> Result<Node, SyntaxException> {
     p.lexer.next_token();
     if p.current_token_is(TokenType::In) {
@@ -144,21 +155,26 @@ pub fn parse_loop_array_iterator(
                     identifier,
                     body,
                     array: Box::from(expression),
+                    span: p.span_from(start),
                 },
             )));
         }
     } else {
-        return Err(SyntaxException::ExpectedToken(TokenType::From));
+        return Err(p.error(SyntaxExceptionCode::ExpectedToken(TokenType::From)));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
 
-/// Parsing the iterator loop:
+/// Parsing the iterator loop, "to" excludes the end while "through" includes it:
 ///
 /// Loop iterator:
 /// ```loop
-/// for var i = 0 to 100 {
+/// for var i = 0 to items.len() {
+///     println(i)
+/// }
+///
+/// for var i = 10 through 0 step -2 {
 ///     println(i)
 /// }
 /// ```
@@ -166,59 +182,40 @@ pub fn parse_loop_iterator(
     p: &mut Parser,
     identifier: Identifier,
     uses_parenthesis: bool,
+    start: Location,
 ) -> Result<Node, SyntaxException> {
-    p.lexer.next_token();
-    if !p.current_token_is(TokenType::Integer) {
-        let message =
-            "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }"
-                .to_string();
-        return Err(SyntaxException::CustomMessage(
-            "expected: Integer".to_string(),
-            Some(message),
-        ));
-    }
+    let message =
+        "Syntax  -> for var <name> = <from> to <till> step <step> { <code> }\nExample -> for var i = 0 to 10 step 2 { println(i) }\n\n\"to\" can be replaced by \"through\" to include the end, the step is optional"
+            .to_string();
 
-    let from = p
-        .lexer
-        .current_token
-        .clone()
-        .unwrap()
-        .literal
-        .parse::<u32>()
-        .unwrap();
+    p.lexer.next_token();
+    let from = parse_loop_iterator_bound(p)?;
 
     p.lexer.next_token();
-    if !p.current_token_is(TokenType::To) {
-        let message =
-            "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }"
-                .to_string();
-        return Err(SyntaxException::CustomMessage(
+    let inclusive = if p.current_token_is(TokenType::To) {
+        false
+    } else if current_identifier_is(p, "through") {
+        true
+    } else {
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: to".to_string(),
             Some(message),
-        ));
-    }
+        )));
+    };
 
     p.lexer.next_token();
-    if !p.current_token_is(TokenType::Integer) {
-        let message =
-            "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }"
-                .to_string();
-        return Err(SyntaxException::CustomMessage(
-            "expected: Integer".to_string(),
-            Some(message),
-        ));
-    }
-
-    let till = p
-        .lexer
-        .current_token
-        .clone()
-        .unwrap()
-        .literal
-        .parse::<u32>()
-        .unwrap();
+    let till = parse_loop_iterator_bound(p)?;
 
     p.lexer.next_token();
+    let step = if current_identifier_is(p, "step") {
+        p.lexer.next_token();
+        let step = parse_loop_iterator_bound(p)?;
+        p.lexer.next_token();
+
+        Some(Box::new(step))
+    } else {
+        None
+    };
 
     // Checks if the parenthesis around the if-expression are consistent
     if (p.current_token_is(TokenType::RightParenthesis)) != uses_parenthesis {
@@ -226,17 +223,17 @@ pub fn parse_loop_iterator(
         return if uses_parenthesis {
             let message = "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }\n\nAn if expression can be with or without parenthesis".to_string();
 
-            Err(SyntaxException::CustomMessage(
+            Err(p.error(SyntaxExceptionCode::CustomMessage(
                 "expected: RightParenthesis".to_string(),
                 Some(message),
-            ))
+            )))
         } else {
             let message = "Syntax  -> for <condition> { <code> }\nExample -> for i < 3 { println(i) }\n\nAn if expression can be with or without parenthesis".to_string();
 
-            Err(SyntaxException::CustomMessage(
+            Err(p.error(SyntaxExceptionCode::CustomMessage(
                 "expected: None".to_string(),
                 Some(message),
-            ))
+            )))
         };
     } else if uses_parenthesis {
         // If the if-expression has parenthesis, the lexer needs to go to the next token
@@ -244,14 +241,10 @@ pub fn parse_loop_iterator(
     }
 
     if !p.current_token_is(TokenType::LeftBrace) {
-        let message =
-            "Syntax  -> for (<condition>) { <code> }\nExample -> for (i < 3) { println(i) }"
-                .to_string();
-
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: LeftBrace".to_string(),
             Some(message),
-        ));
+        )));
     }
     p.lexer.next_token();
     let body = parse_block(p)?;
@@ -259,7 +252,24 @@ pub fn parse_loop_iterator(
     Ok(Node::Expression(Expression::LoopIterator(LoopIterator {
         identifier,
         body,
-        from,
-        till,
+        from: Box::new(from),
+        till: Box::new(till),
+        step,
+        inclusive,
+        span: p.span_from(start),
     })))
 }
+
+fn parse_loop_iterator_bound(p: &mut Parser) -> Result<Expression, SyntaxException> {
+    match p.parse_expression(Lowest)? {
+        Node::Expression(expression) => Ok(expression),
+        _ => Err(p.error(SyntaxExceptionCode::Unknown)),
+    }
+}
+
+/// "through" and "step" are only keywords inside of an iterator loop, they can still be used as
+/// names everywhere else
+fn current_identifier_is(p: &Parser, name: &str) -> bool {
+    p.current_token_is(TokenType::Identifier)
+        && p.lexer.current_token.as_ref().unwrap().literal == name
+}
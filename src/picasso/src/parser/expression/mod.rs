@@ -11,8 +11,11 @@ use crate::parser::expression::index::{Index, Slice};
 use crate::parser::expression::integer::Integer;
 use crate::parser::expression::loops::{Loop, LoopArrayIterator, LoopIterator};
 use crate::parser::expression::null::Null;
+use crate::parser::expression::prefix::Prefix;
+use crate::parser::expression::range::Range;
 use crate::parser::expression::string::LoopString;
 use crate::parser::expression::suffix::Suffix;
+use crate::span::Span;
 
 pub mod array;
 pub mod assign_index;
@@ -27,6 +30,8 @@ pub mod integer;
 pub mod loops;
 pub mod null;
 pub mod number;
+pub mod prefix;
+pub mod range;
 pub mod string;
 pub mod suffix;
 
@@ -35,6 +40,7 @@ pub enum Expression {
     Identifier(Identifier),
     Integer(Integer),
     Suffix(Box<Suffix>),
+    Prefix(Box<Prefix>),
     Boolean(Boolean),
     Function(Function),
     Conditional(Box<Conditional>),
@@ -50,9 +56,36 @@ pub enum Expression {
     LoopArrayIterator(LoopArrayIterator),
     Hashmap(Hashmap),
     Slice(Slice),
+    Range(Box<Range>),
 }
 
 impl Expression {
+    /// Returns where in the source code this expression is written
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(identifier) => identifier.span.clone(),
+            Expression::Integer(integer) => integer.span.clone(),
+            Expression::Suffix(suffix) => suffix.span.clone(),
+            Expression::Prefix(prefix) => prefix.span.clone(),
+            Expression::Boolean(boolean) => boolean.span.clone(),
+            Expression::Function(function) => function.span.clone(),
+            Expression::Conditional(conditional) => conditional.span.clone(),
+            Expression::Null(null) => null.span.clone(),
+            Expression::Call(call) => call.span.clone(),
+            Expression::Float(float) => float.span.clone(),
+            Expression::String(string) => string.span.clone(),
+            Expression::Index(index) => index.span.clone(),
+            Expression::Array(array) => array.span.clone(),
+            Expression::AssignIndex(assign_index) => assign_index.span.clone(),
+            Expression::Loop(lp) => lp.span.clone(),
+            Expression::LoopIterator(lp) => lp.span.clone(),
+            Expression::LoopArrayIterator(lp) => lp.span.clone(),
+            Expression::Hashmap(hashmap) => hashmap.span.clone(),
+            Expression::Slice(slice) => slice.span.clone(),
+            Expression::Range(range) => range.span.clone(),
+        }
+    }
+
     pub fn into_identifier(self) -> Identifier {
         if let Expression::Identifier(ident) = self {
             ident
@@ -66,8 +99,10 @@ impl Expression {
 #[allow(dead_code)]
 pub enum Precedence {
     Lowest,
+    Logical,
     Equals,
     LessGreater,
+    Range,
     Modulo,
     Sum,
     Product,
@@ -75,7 +110,6 @@ pub enum Precedence {
     Prefix,
     Call,
     Index,
-    Logical,
     Assign,
 }
 
@@ -94,6 +128,8 @@ pub fn get_precedence(tok: TokenType) -> Precedence {
         TokenType::LessThanOrEquals => Precedence::LessGreater,
         TokenType::GreaterThanOrEquals => Precedence::LessGreater,
         TokenType::Modulo => Precedence::Modulo,
+        TokenType::Range => Precedence::Range,
+        TokenType::RangeInclusive => Precedence::Range,
         TokenType::Dot => Precedence::Index,
         TokenType::LeftBracket => Precedence::Index,
         TokenType::Assign => Precedence::Assign,
@@ -104,7 +140,7 @@ pub fn get_precedence(tok: TokenType) -> Precedence {
 }
 
 impl Expression {
-    fn _get_hash(&self) -> Option<HashableExpression> {
+    pub fn get_hash(&self) -> Option<HashableExpression> {
         match self {
             Expression::Integer(integer) => Some(HashableExpression::Integer(integer.clone())),
             Expression::String(string) => Some(HashableExpression::String(string.clone())),
@@ -2,10 +2,15 @@ use crate::parser::exception::SyntaxException;
 use crate::parser::expression::Expression;
 use crate::parser::program::Node;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Null {}
+pub struct Null {
+    pub span: Span,
+}
 
-pub fn parse_expression_null(_p: &mut Parser) -> Result<Node, SyntaxException> {
-    Ok(Node::Expression(Expression::Null(Null {})))
+pub fn parse_expression_null(p: &mut Parser) -> Result<Node, SyntaxException> {
+    Ok(Node::Expression(Expression::Null(Null {
+        span: p.current_span(),
+    })))
 }
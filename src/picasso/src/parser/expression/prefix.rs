@@ -0,0 +1,60 @@
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
+use crate::parser::expression::boolean::Boolean;
+use crate::parser::expression::float::Float;
+use crate::parser::expression::integer::Integer;
+use crate::parser::expression::Expression;
+use crate::parser::expression::Precedence;
+use crate::parser::program::Node;
+use crate::parser::Parser;
+use crate::span::Span;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Prefix {
+    pub(crate) operator: String,
+    pub(crate) right: Expression,
+    pub span: Span,
+}
+
+pub fn parse_prefix_expression(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let operator = p.lexer.get_current_token().unwrap().literal.clone();
+    let start = p.current_location();
+
+    p.lexer.next_token();
+
+    let exp = p.parse_expression(Precedence::Prefix)?;
+
+    if let Node::Expression(right) = exp {
+        let span = p.span_from(start);
+
+        // Negative numbers and inverted booleans stay a constant
+        match (operator.as_str(), &right) {
+            ("-", Expression::Integer(integer)) => {
+                return Ok(Node::Expression(Expression::Integer(Integer {
+                    value: -integer.value,
+                    span,
+                })))
+            }
+            ("-", Expression::Float(float)) => {
+                return Ok(Node::Expression(Expression::Float(Float {
+                    value: -float.value,
+                    span,
+                })))
+            }
+            ("!", Expression::Boolean(boolean)) => {
+                return Ok(Node::Expression(Expression::Boolean(Boolean {
+                    value: !boolean.value,
+                    span,
+                })))
+            }
+            _ => {}
+        }
+
+        return Ok(Node::Expression(Expression::Prefix(Box::new(Prefix {
+            operator,
+            right,
+            span,
+        }))));
+    }
+
+    Err(p.error(SyntaxExceptionCode::Unknown))
+}
@@ -0,0 +1,43 @@
+use crate::lexer::token::TokenType;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
+use crate::parser::expression::Expression;
+use crate::parser::program::Node;
+use crate::parser::Parser;
+use crate::span::Span;
+
+/// A range of integers, which is an array of every integer from `from` till `till`
+///
+/// ```loop
+/// 0..5  // [0, 1, 2, 3, 4]
+/// 0..=5 // [0, 1, 2, 3, 4, 5]
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Range {
+    pub from: Expression,
+    pub till: Expression,
+    /// Whether `till` itself is part of the range
+    pub inclusive: bool,
+    pub span: Span,
+}
+
+pub fn parse_range_expression(p: &mut Parser, left: Expression) -> Result<Node, SyntaxException> {
+    let inclusive = p.current_token_is(TokenType::RangeInclusive);
+    let start = left.span().start;
+
+    let pre = p.current_precedence();
+
+    p.lexer.next_token();
+
+    let exp = p.parse_expression(pre)?;
+
+    if let Node::Expression(till) = exp {
+        return Ok(Node::Expression(Expression::Range(Box::new(Range {
+            from: left,
+            till,
+            inclusive,
+            span: p.span_from(start),
+        }))));
+    }
+
+    Err(p.error(SyntaxExceptionCode::Unknown))
+}
@@ -1,19 +1,22 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::Expression;
 use crate::parser::expression::Precedence::Lowest;
 use crate::parser::program::Node;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Suffix {
     pub(crate) left: Expression,
     pub(crate) operator: String,
     pub(crate) right: Expression,
+    pub span: Span,
 }
 
 pub fn parse_suffix_expression(p: &mut Parser, left: Expression) -> Result<Node, SyntaxException> {
     let operator = p.lexer.get_current_token().unwrap().literal.clone();
+    let start = left.span().start;
 
     let pre = p.current_precedence();
 
@@ -26,10 +29,11 @@ pub fn parse_suffix_expression(p: &mut Parser, left: Expression) -> Result<Node,
             left,
             operator,
             right: val,
+            span: p.span_from(start),
         }))));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
 
 pub fn parse_grouped_expression(p: &mut Parser) -> Result<Node, SyntaxException> {
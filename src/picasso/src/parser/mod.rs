@@ -1,9 +1,8 @@
 //! Responsible for parsing tokens into an abstract syntax tree
-use crate::exception::Exception;
 use crate::lexer::token::{Token, TokenType};
 use crate::lexer::Lexer;
 use crate::parser::expression::array::parse_expression_array;
-use crate::parser::expression::boolean::{parse_boolean, parse_inverted_boolean};
+use crate::parser::expression::boolean::parse_boolean;
 use crate::parser::expression::conditional::parse_conditional;
 use crate::parser::expression::function::{parse_call, parse_function};
 use crate::parser::expression::hashmap::parse_expression_hashmap;
@@ -20,14 +19,19 @@ use crate::parser::statement::constant::parse_constant_declaration;
 use crate::parser::statement::expression::parse_expression_statement;
 use crate::parser::statement::return_statement::parse_return_statement;
 use crate::parser::statement::Statement;
-use colored::Colorize;
+use crate::span::{Location, Span};
+use crate::DEFAULT_MAX_ERRORS;
 use std::collections::HashMap;
+use vinci::diagnostic::Diagnostic;
 
 use self::statement::variable::parse_variable_declaration;
-use crate::parser::exception::SyntaxException;
-use crate::parser::expression::number::{parse_negative_number, parse_number_literal};
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
+use crate::parser::expression::number::parse_number_literal;
+use crate::parser::expression::prefix::parse_prefix_expression;
+use crate::parser::expression::range::parse_range_expression;
 use crate::parser::statement::break_statement::parse_break_statement;
 use crate::parser::statement::class::parse_class_statement;
+use crate::parser::statement::continue_statement::parse_continue_statement;
 use crate::parser::statement::extends::parse_extend_statement;
 use crate::parser::statement::import::parse_import_statement;
 use crate::parser::types::{BaseTypes, FunctionType, Types};
@@ -48,7 +52,9 @@ pub struct Parser {
     lexer: Lexer,
     prefix_parser: HashMap<TokenType, PrefixParseFn>,
     infix_parser: HashMap<TokenType, InfixParseFn>,
-    pub errors: Vec<Exception>,
+    pub errors: Vec<SyntaxException>,
+    // Amount of errors after which the parser stops
+    pub max_errors: usize,
     pub defined_types: Vec<String>,
     pub next_public: bool,
     current_file: String,
@@ -62,14 +68,23 @@ impl Parser {
             let tok = self.lexer.get_current_token().unwrap().clone();
             let new_statement = self.parse_statement(tok.clone());
 
-            if let Err(error) = new_statement {
-                self.print_error(error.clone());
-                return Err(error);
+            if let Err(mut error) = new_statement {
+                // Exceptions from the lexer do not know which file they are in
+                error.span = self.in_file(error.span);
+                self.errors.push(error);
+
+                if self.errors.len() >= self.max_errors {
+                    break;
+                }
+
+                self.synchronize();
+                continue;
             }
 
             match new_statement.unwrap() {
                 Node::Expression(exp) => statements.push(Statement::Expression(Box::new(
                     statement::expression::Expression {
+                        span: exp.span(),
                         expression: Box::new(exp),
                     },
                 ))),
@@ -79,76 +94,76 @@ impl Parser {
             self.lexer.next_token();
         }
 
+        if let Some(error) = self.errors.first() {
+            return Err(error.clone());
+        }
+
         Ok(Program { statements })
     }
 
-    fn print_error(&self, error: SyntaxException) {
-        let mut width = String::new();
+    /// Skips the rest of a statement that failed to parse, so parsing can continue with the
+    /// statement after it. A statement ends at a semicolon, at the closing brace of a block or at
+    /// the end of a line, as long as it is not inside of braces.
+    fn synchronize(&mut self) {
+        while !self.current_token_is(TokenType::Eof) {
+            let top_level = self.lexer.brace_depth <= 0;
+            let ends_statement = self.current_token_is(TokenType::Semicolon)
+                || self.current_token_is(TokenType::RightBrace)
+                || self.lexer.current_span().end.line < self.lexer.peek_span().start.line;
 
-        for _ in 0..self.lexer.current_line.to_string().len() {
-            width.push(' ');
-        }
+            self.lexer.next_token();
 
-        let remove_based_on_message = match error {
-            SyntaxException::NoPrefixParser(_) => 1,
-            SyntaxException::ExpectedToken(_) => 0,
-            SyntaxException::CustomMessage(_, _) => 1,
-            _ => 0,
-        };
+            if top_level && ends_statement {
+                return;
+            }
+        }
+    }
 
-        println!("{}", "SyntaxException".red());
-        println!(
-            "{} | -> {} [{}:{}]",
-            width,
-            self.current_file,
-            self.lexer.current_col - remove_based_on_message,
-            self.lexer.current_line
-        );
+    /// Creates a diagnostic for an exception of this parser, including the line it happened on
+    pub fn diagnostic(&self, error: &SyntaxException) -> Diagnostic {
+        error.diagnostic(Some(self.lexer.get_line(error.span.start.line)))
+    }
 
-        println!("{} | ", width);
-        println!(
-            "{} | {}",
-            self.lexer.current_line.to_string().red(),
-            self.lexer.get_line(self.lexer.current_line)
-        );
+    /// Creates an exception pointing at the current token
+    pub fn error(&self, exception: SyntaxExceptionCode) -> SyntaxException {
+        SyntaxException::new(self.in_file(self.lexer.current_span()), exception)
+    }
 
-        let spaces = self.lexer.current_col;
+    /// Creates an exception pointing at the peek token
+    pub fn peek_error(&self, exception: SyntaxExceptionCode) -> SyntaxException {
+        SyntaxException::new(self.in_file(self.lexer.peek_span()), exception)
+    }
 
-        let mut cursor_width = String::new();
+    /// Where the current token starts, used as the start of the span of the node that is parsed
+    pub fn current_location(&self) -> Location {
+        self.lexer.current_span().start
+    }
 
-        for _ in 0..(spaces - remove_based_on_message - (width.len() as i32)) {
-            cursor_width.push(' ');
-        }
+    /// Returns the span from `start` until the end of the current token, which is the last token
+    /// of a node once it has been parsed
+    pub fn span_from(&self, start: Location) -> Span {
+        Span::new(
+            self.current_file.as_str(),
+            start,
+            self.lexer.current_span().end,
+        )
+    }
 
-        println!("{} | {}{}", width, cursor_width, "^".red());
+    /// Returns the span of the current token
+    pub fn current_span(&self) -> Span {
+        self.in_file(self.lexer.current_span())
+    }
 
-        if let SyntaxException::CustomMessage(_, Some(message)) = error.clone() {
-            for line in message.lines() {
-                println!("{} | {}{} {}", width, cursor_width, "|".red(), line);
-            }
+    fn in_file(&self, span: Span) -> Span {
+        Span {
+            file: self.current_file.clone(),
+            ..span
         }
-
-        println!("{} | ", width);
-
-        println!(
-            "{} = {}",
-            width,
-            match error {
-                SyntaxException::Unknown => "=> Unknown parser error occurred".to_string(),
-                SyntaxException::CustomMessage(title, _) => title,
-                SyntaxException::ExpectedToken(expected) =>
-                    format!("Wrong token, expected={:?}.", expected),
-                SyntaxException::NoPrefixParser(what) => format!("No prefix parser for {:?}", what),
-                SyntaxException::WrongParentheses(p) =>
-                    format!("Wrong parenthesis, expected={:?}.", p),
-            }
-            .blue()
-        );
     }
 
     fn expected(&mut self, token: TokenType) -> Result<(), SyntaxException> {
         if !self.lexer.next_token_is_and_next_token(token) {
-            return Err(SyntaxException::ExpectedToken(token));
+            return Err(self.peek_error(SyntaxExceptionCode::ExpectedToken(token)));
         }
 
         Ok(())
@@ -229,9 +244,9 @@ impl Parser {
                         && !self.current_token_is(TokenType::Eof)
                     {
                         let next = self.lexer.current_token.as_ref().unwrap().clone();
-                        let tp = self.parse_type(next);
+                        let tp = self.parse_type(next)?;
 
-                        func_type.parameter_types.push(tp.unwrap());
+                        func_type.parameter_types.push(tp);
 
                         // Comma
                         skipped = true;
@@ -251,7 +266,7 @@ impl Parser {
                     self.lexer.next_token();
                     let cur = self.lexer.current_token.as_ref().unwrap().clone();
 
-                    func_type.return_type = Box::new(self.parse_type(cur).unwrap());
+                    func_type.return_type = Box::new(self.parse_type(cur)?);
 
                     // previous type & '>'
                     self.lexer.next_token();
@@ -276,6 +291,18 @@ impl Parser {
         }
     }
 
+    /// Parses the type at the current token, it is an error when it is not a known type
+    pub fn expect_type(&mut self) -> Result<Types, SyntaxException> {
+        let token = self.lexer.get_current_token().unwrap().clone();
+
+        self.parse_type(token.clone()).ok_or_else(|| {
+            self.error(SyntaxExceptionCode::CustomMessage(
+                format!("unknown type: {}", token.literal),
+                Some("Types are int, bool, string, float, void, func or a class".to_string()),
+            ))
+        })
+    }
+
     fn parse_statement(&mut self, token: Token) -> Result<Node, SyntaxException> {
         let r = match token.token {
             TokenType::ConstantDeclaration => parse_constant_declaration(self),
@@ -286,15 +313,11 @@ impl Parser {
                     parse_variable_declaration(self, None)
                 } else if self.peek_token_is(TokenType::Identifier) {
                     // User has explicitly typed a variable.
-                    let types = self
-                        .parse_type(self.lexer.get_current_token().unwrap().clone())
-                        .unwrap();
+                    let types = self.expect_type()?;
                     self.lexer.next_token();
                     parse_variable_declaration(self, Some(types))
                 } else if self.peek_token_is(TokenType::LeftArrow) {
-                    let types = self
-                        .parse_type(self.lexer.get_current_token().unwrap().clone())
-                        .unwrap();
+                    let types = self.expect_type()?;
                     parse_variable_declaration(self, Some(types))
                 } else {
                     parse_expression_statement(self)
@@ -304,6 +327,7 @@ impl Parser {
             //TokenType::LeftBrace => parse_block_statement(self),
             TokenType::Import => parse_import_statement(self),
             TokenType::Break => parse_break_statement(self),
+            TokenType::Continue => parse_continue_statement(self),
             TokenType::Class => parse_class_statement(self),
             TokenType::Extends => parse_extend_statement(self),
             TokenType::Public => {
@@ -318,10 +342,10 @@ impl Parser {
 
                             parse_class_statement(self)
                         }
-                        _ => Err(SyntaxException::Unknown),
+                        _ => Err(self.error(SyntaxExceptionCode::Unknown)),
                     }
                 } else {
-                    Err(SyntaxException::Unknown)
+                    Err(self.error(SyntaxExceptionCode::Unknown))
                 }
             }
             _ => self.parse_expression_statement(),
@@ -346,9 +370,9 @@ impl Parser {
             .get(&self.lexer.current_token.as_ref().unwrap().token);
 
         if prefix_parser.is_none() {
-            return Err(SyntaxException::NoPrefixParser(
+            return Err(self.error(SyntaxExceptionCode::NoPrefixParser(
                 self.lexer.current_token.as_ref().unwrap().token,
-            ));
+            )));
         }
 
         let expression_node = prefix_parser.unwrap()(self)?;
@@ -384,10 +408,10 @@ impl Parser {
             return Ok(Node::Expression(exp));
         }
 
-        Err(SyntaxException::CustomMessage(
+        Err(self.error(SyntaxExceptionCode::CustomMessage(
             "Unknown parser exception occured".to_string(),
             None,
-        ))
+        )))
     }
 
     fn add_prefix_parser(
@@ -430,13 +454,13 @@ impl Parser {
         let cur = self.lexer.get_current_token();
 
         if cur.is_none() {
-            return Err(SyntaxException::ExpectedToken(tok));
+            return Err(self.error(SyntaxExceptionCode::ExpectedToken(tok)));
         }
 
         if cur.unwrap().token == tok {
             Ok(())
         } else {
-            Err(SyntaxException::ExpectedToken(tok))
+            Err(self.error(SyntaxExceptionCode::ExpectedToken(tok)))
         }
     }
 
@@ -465,6 +489,7 @@ pub fn build_parser(lexer: Lexer, file: &str) -> Parser {
         prefix_parser: HashMap::new(),
         infix_parser: HashMap::new(),
         errors: Vec::new(),
+        max_errors: DEFAULT_MAX_ERRORS,
         defined_types: Vec::new(),
         next_public: false,
         current_file: file.to_string(),
@@ -473,12 +498,12 @@ pub fn build_parser(lexer: Lexer, file: &str) -> Parser {
     // Prefix parsers
     p.add_prefix_parser(TokenType::Integer, parse_number_literal);
     p.add_prefix_parser(TokenType::Float, parse_number_literal);
-    p.add_prefix_parser(TokenType::Minus, parse_negative_number);
+    p.add_prefix_parser(TokenType::Minus, parse_prefix_expression);
     p.add_prefix_parser(TokenType::LeftParenthesis, parse_grouped_expression);
     p.add_prefix_parser(TokenType::Identifier, parse_identifier);
     p.add_prefix_parser(TokenType::True, parse_boolean);
     p.add_prefix_parser(TokenType::False, parse_boolean);
-    p.add_prefix_parser(TokenType::InvertSign, parse_inverted_boolean);
+    p.add_prefix_parser(TokenType::InvertSign, parse_prefix_expression);
     p.add_prefix_parser(TokenType::Function, parse_function);
     p.add_prefix_parser(TokenType::If, parse_conditional);
     p.add_prefix_parser(TokenType::Null, parse_expression_null);
@@ -494,6 +519,8 @@ pub fn build_parser(lexer: Lexer, file: &str) -> Parser {
     p.add_infix_parser(TokenType::Divide, parse_suffix_expression);
     p.add_infix_parser(TokenType::Minus, parse_suffix_expression);
     p.add_infix_parser(TokenType::Modulo, parse_suffix_expression);
+    p.add_infix_parser(TokenType::Range, parse_range_expression);
+    p.add_infix_parser(TokenType::RangeInclusive, parse_range_expression);
     p.add_infix_parser(TokenType::LeftParenthesis, parse_call);
     p.add_infix_parser(TokenType::Dot, parse_index_expression);
     p.add_infix_parser(TokenType::LeftBracket, parse_index_expression);
@@ -1,15 +1,17 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::identifier::{parse_identifier, Identifier};
 use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct VariableAssign {
     pub ident: Identifier,
     pub value: Box<Expression>,
+    pub span: Span,
 }
 
 pub fn parse_variable_assignment(p: &mut Parser) -> Result<Node, SyntaxException> {
@@ -24,10 +26,11 @@ pub fn parse_variable_assignment(p: &mut Parser) -> Result<Node, SyntaxException
 
     if let Node::Expression(exp) = expr {
         return Ok(Node::Statement(Statement::VariableAssign(VariableAssign {
-            ident: Identifier::new(ident.value, ident.location.line, ident.location.colon),
+            span: p.span_from(ident.span.start.clone()),
+            ident,
             value: Box::new(exp),
         })));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
@@ -1,13 +1,15 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::hashmap::parse_expression_hashmap;
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Block {
     pub statements: Vec<Statement>,
+    pub span: Span,
 }
 
 #[allow(dead_code)]
@@ -21,16 +23,15 @@ pub fn parse_block_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
     let block = parse_block(p)?;
 
     if !p.current_token_is(TokenType::RightBrace) {
-        return Err(SyntaxException::ExpectedToken(TokenType::RightBrace));
+        return Err(p.error(SyntaxExceptionCode::ExpectedToken(TokenType::RightBrace)));
     }
 
-    Ok(Node::Statement(Statement::Block(Block {
-        statements: block.statements,
-    })))
+    Ok(Node::Statement(Statement::Block(block)))
 }
 
 pub fn parse_block(p: &mut Parser) -> Result<Block, SyntaxException> {
     let mut statements: Vec<Statement> = Vec::new();
+    let start = p.current_location();
 
     while p.lexer.get_current_token().unwrap().token != TokenType::RightBrace
         && p.lexer.get_current_token().unwrap().token != TokenType::Eof
@@ -46,5 +47,8 @@ pub fn parse_block(p: &mut Parser) -> Result<Block, SyntaxException> {
         p.lexer.next_token();
     }
 
-    Ok(Block { statements })
+    Ok(Block {
+        statements,
+        span: p.span_from(start),
+    })
 }
@@ -1,17 +1,20 @@
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::null::Null;
 use crate::parser::expression::Expression;
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct BreakStatement {
     pub expression: Box<crate::parser::expression::Expression>,
+    pub span: Span,
 }
 
-pub fn parse_break_statement(_: &mut Parser) -> Result<Node, SyntaxException> {
-    let expr = Node::Expression(Expression::Null(Null {}));
+pub fn parse_break_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let span = p.current_span();
+    let expr = Node::Expression(Expression::Null(Null { span: span.clone() }));
 
     // TODO: Allow breaking with a value
     /*
@@ -24,8 +27,9 @@ pub fn parse_break_statement(_: &mut Parser) -> Result<Node, SyntaxException> {
     if let Node::Expression(exp) = expr {
         return Ok(Node::Statement(Statement::Break(BreakStatement {
             expression: Box::new(exp),
+            span,
         })));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
@@ -1,5 +1,5 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::function::{parse_arguments, Parameter};
 use crate::parser::expression::Precedence;
 use crate::parser::program::Node;
@@ -8,6 +8,7 @@ use crate::parser::statement::expression::Expression;
 use crate::parser::statement::Statement;
 use crate::parser::types::Types;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Method {
@@ -37,6 +38,7 @@ pub struct Class {
     pub values: Vec<ClassField>,
     pub inherits: String,
     pub public: bool,
+    pub span: Span,
 }
 
 fn parse_class_item(
@@ -84,6 +86,7 @@ fn parse_class_item(
                 name,
                 ClassItem::Property(Expression {
                     expression: Box::new(exp.clone()),
+                    span: exp.span(),
                 }),
             ))
         } else {
@@ -93,6 +96,7 @@ fn parse_class_item(
 }
 
 pub fn parse_class_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let start = p.current_location();
     p.expected(TokenType::Identifier)?;
 
     let name = p.lexer.get_current_token().unwrap().literal.clone();
@@ -134,13 +138,13 @@ pub fn parse_class_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
     }
 
     if p.defined_types.contains(&name) {
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             format!(
                 "Type \"{}\" already defined! (Type definitions are always root scoped)",
                 name
             ),
             None,
-        ));
+        )));
     }
 
     p.defined_types.push(name.clone());
@@ -152,5 +156,6 @@ pub fn parse_class_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
         name,
         values,
         public: p.next_public,
+        span: p.span_from(start),
     })))
 }
@@ -1,17 +1,19 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::identifier::Identifier;
 use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::types::Types;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ConstantDeclaration {
     pub ident: Identifier,
     pub value: Box<Expression>,
     pub data_type: Types,
+    pub span: Span,
 }
 
 /// A constant looks like this:
@@ -23,23 +25,31 @@ pub struct ConstantDeclaration {
 /// `const i := 13 + 4`
 #[allow(clippy::all)]
 pub fn parse_constant_declaration(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let start = p.current_location();
+
     // This "identifier" is for the actual identifier of the constant
     if !p.next_token_is(TokenType::Identifier) {
         let message = "Syntax  -> const <datatype> <identifier> = <expression>\nExample -> const int i = 99\n\nThe identifiers can contain: letters, numbers and underscores.".to_string();
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: Identifier".to_string(),
             Some(message),
-        ));
+        )));
     }
 
     p.lexer.next_token(); // Skipping the identifier
                           // Depending on if the user has excpliciely typed the type, the current token is a identifier or a type
     let ident_or_type = p.lexer.get_current_token().unwrap().clone();
+    let ident_or_type_span = p.current_span();
 
     // Is user has typed a type, this will be the identifier, otherwise it will be a null
     let ident = if p.next_token_is(TokenType::Identifier) {
+        let data_type = p.expect_type()?;
         p.lexer.next_token();
-        Some(p.lexer.current_token.clone().unwrap())
+        Some((
+            p.lexer.current_token.clone().unwrap(),
+            p.current_span(),
+            data_type,
+        ))
     } else {
         None
     };
@@ -49,10 +59,10 @@ pub fn parse_constant_declaration(p: &mut Parser) -> Result<Node, SyntaxExceptio
         let message = "Syntax  -> const <identifier> := <expression>\nExample -> const int i := 99\n\nFor explanation go here:\nhttps://looplang.org/docs/concepts/types/primitives".to_string();
 
         //let message = "Syntax  -> const <datatype> <identifier> := <expression>\nExample -> const int i := 99\n\nFor explanation go here:\nhttps://looplang.org/docs/concepts/types/primitives".to_string();
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: ':'".to_string(),
             Some(message),
-        ));
+        )));
     }
     p.lexer.next_token(); // Skipping the ":'
 
@@ -60,10 +70,10 @@ pub fn parse_constant_declaration(p: &mut Parser) -> Result<Node, SyntaxExceptio
     if !p.next_token_is(TokenType::Assign) {
         let message = "Syntax  -> const <datatype> <identifier> = <expression>\nExample -> const int i = 99\n\nFor explanation go here:\nhttps://looplang.org/docs/concepts/types/primitives".to_string();
 
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: '='".to_string(),
             Some(message),
-        ));
+        )));
     }
 
     p.lexer.next_token(); // Skips the '='
@@ -78,25 +88,27 @@ pub fn parse_constant_declaration(p: &mut Parser) -> Result<Node, SyntaxExceptio
         if let Node::Expression(expression) = expr {
             return Ok(Node::Statement(Statement::ConstantDeclaration(
                 ConstantDeclaration {
-                    ident: Identifier::new(ident_or_type.literal, 0, 0),
+                    ident: Identifier::new(ident_or_type.literal, ident_or_type_span),
                     value: Box::new(expression),
                     data_type: Types::Auto,
+                    span: p.span_from(start),
                 },
             )));
         }
     }
     // Node being created here is for:
     // const int i := 3
-    if let Node::Expression(expression) = expr {
+    if let (Node::Expression(expression), Some(ident)) = (expr, ident) {
         return Ok(Node::Statement(Statement::ConstantDeclaration(
             ConstantDeclaration {
-                ident: Identifier::new(ident.unwrap().literal, 0, 0),
+                ident: Identifier::new(ident.0.literal, ident.1),
                 value: Box::new(expression),
-                data_type: p.parse_type(ident_or_type).unwrap(),
+                data_type: ident.2,
+                span: p.span_from(start),
             },
         )));
     }
 
     // This needs to throw a good error
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
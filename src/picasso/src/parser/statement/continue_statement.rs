@@ -0,0 +1,16 @@
+use crate::parser::exception::SyntaxException;
+use crate::parser::program::Node;
+use crate::parser::statement::Statement;
+use crate::parser::Parser;
+use crate::span::Span;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ContinueStatement {
+    pub span: Span,
+}
+
+pub fn parse_continue_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
+    Ok(Node::Statement(Statement::Continue(ContinueStatement {
+        span: p.current_span(),
+    })))
+}
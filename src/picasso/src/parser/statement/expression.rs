@@ -1,12 +1,14 @@
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::Precedence;
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Expression {
     pub expression: Box<crate::parser::expression::Expression>,
+    pub span: Span,
 }
 
 pub fn parse_expression_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
@@ -15,10 +17,11 @@ pub fn parse_expression_statement(p: &mut Parser) -> Result<Node, SyntaxExceptio
     if let Node::Expression(exp) = expr {
         return Ok(Node::Statement(Statement::Expression(Box::new(
             Expression {
+                span: exp.span(),
                 expression: Box::new(exp),
             },
         ))));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
@@ -4,11 +4,13 @@ use crate::parser::program::Node;
 use crate::parser::statement::class::{parse_class_statement, ClassField};
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ExtendStatement {
     pub identifier: Identifier,
     pub items: Vec<ClassField>,
+    pub span: Span,
 }
 
 pub fn parse_extend_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
@@ -23,7 +25,8 @@ pub fn parse_extend_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
     };
 
     Ok(Node::Statement(Statement::Extend(ExtendStatement {
-        identifier: Identifier::new(class.name, 0, 0),
+        identifier: Identifier::new(class.name, class.span.clone()),
         items: class.values,
+        span: class.span,
     })))
 }
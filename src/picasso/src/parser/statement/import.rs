@@ -3,14 +3,18 @@ use crate::parser::exception::SyntaxException;
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Import {
     pub file: String,
     pub identifier: String,
+    pub span: Span,
 }
 
 pub fn parse_import_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
+    let start = p.current_location();
+
     p.lexer
         .next_token_is_and_next_token_result(TokenType::String)?;
 
@@ -26,5 +30,6 @@ pub fn parse_import_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
     Ok(Node::Statement(Statement::Import(Import {
         file,
         identifier,
+        span: p.span_from(start),
     })))
 }
@@ -4,15 +4,18 @@ use crate::parser::statement::block::Block;
 use crate::parser::statement::break_statement::BreakStatement;
 use crate::parser::statement::class::Class;
 use crate::parser::statement::constant::ConstantDeclaration;
+use crate::parser::statement::continue_statement::ContinueStatement;
 use crate::parser::statement::expression::Expression;
 use crate::parser::statement::extends::ExtendStatement;
 use crate::parser::statement::import::Import;
 use crate::parser::statement::return_statement::ReturnStatement;
+use crate::span::Span;
 pub mod assign;
 pub mod block;
 pub mod break_statement;
 pub mod class;
 pub mod constant;
+pub mod continue_statement;
 pub mod expression;
 pub mod extends;
 pub mod import;
@@ -29,6 +32,26 @@ pub enum Statement {
     Return(ReturnStatement),
     Import(Import),
     Break(BreakStatement),
+    Continue(ContinueStatement),
     Class(Class),
     Extend(ExtendStatement),
 }
+
+impl Statement {
+    /// Returns where in the source code this statement is written
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::VariableDeclaration(declaration) => declaration.span.clone(),
+            Statement::ConstantDeclaration(declaration) => declaration.span.clone(),
+            Statement::Expression(expression) => expression.span.clone(),
+            Statement::Block(block) => block.span.clone(),
+            Statement::VariableAssign(assign) => assign.span.clone(),
+            Statement::Return(return_statement) => return_statement.span.clone(),
+            Statement::Import(import) => import.span.clone(),
+            Statement::Break(break_statement) => break_statement.span.clone(),
+            Statement::Continue(continue_statement) => continue_statement.span.clone(),
+            Statement::Class(class) => class.span.clone(),
+            Statement::Extend(extend) => extend.span.clone(),
+        }
+    }
+}
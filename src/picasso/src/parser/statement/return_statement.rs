@@ -1,18 +1,23 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::null::Null;
 use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::statement::Statement;
 use crate::parser::Parser;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ReturnStatement {
     pub expression: Box<crate::parser::expression::Expression>,
+    pub span: Span,
 }
 
 pub fn parse_return_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
-    let mut expr = Node::Expression(Expression::Null(Null {}));
+    let start = p.current_location();
+    let mut expr = Node::Expression(Expression::Null(Null {
+        span: p.current_span(),
+    }));
 
     if !p.peek_token_is(TokenType::Semicolon) {
         p.lexer.next_token();
@@ -23,8 +28,9 @@ pub fn parse_return_statement(p: &mut Parser) -> Result<Node, SyntaxException> {
     if let Node::Expression(exp) = expr {
         return Ok(Node::Statement(Statement::Return(ReturnStatement {
             expression: Box::new(exp),
+            span: p.span_from(start),
         })));
     }
 
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
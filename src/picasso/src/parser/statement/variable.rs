@@ -1,10 +1,11 @@
 use crate::lexer::token::TokenType;
-use crate::parser::exception::SyntaxException;
+use crate::parser::exception::{SyntaxException, SyntaxExceptionCode};
 use crate::parser::expression::identifier::{parse_identifier, Identifier};
 use crate::parser::expression::{Expression, Precedence};
 use crate::parser::program::Node;
 use crate::parser::types::Types;
 use crate::parser::Parser;
+use crate::span::Span;
 
 use super::Statement;
 
@@ -14,8 +15,7 @@ pub struct VariableDeclaration {
     pub ident: Identifier,
     pub value: Box<Expression>,
     pub data_type: Types,
-    // Line, colon
-    pub location: (i32, i32),
+    pub span: Span,
 }
 
 /// A variable declaration looks like this:
@@ -37,10 +37,10 @@ pub fn parse_variable_declaration(
         let message = "Syntax  -> <identifier> := <expression>\nExample -> i := 99\n\nFor explanation go here:\nhttps://looplang.org/docs/concepts/types/primitives".to_string();
 
         //let message = "Syntax  -> const <datatype> <identifier> := <expression>\nExample -> const int i := 99\n\nFor explanation go here:\nhttps://looplang.org/docs/concepts/types/primitives".to_string();
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: ':'".to_string(),
             Some(message),
-        ));
+        )));
     }
     p.lexer.next_token(); // Skipping the ":'
 
@@ -51,10 +51,10 @@ pub fn parse_variable_declaration(
         } else {
             format!("Syntax  ->  <datatype> <identifier> := <expression>\nExample -> {} i := 99\n\nFor explanation go here:\nhttps://looplang.org/docs/concepts/types/primitives", datatype.transpile())
         };
-        return Err(SyntaxException::CustomMessage(
+        return Err(p.error(SyntaxExceptionCode::CustomMessage(
             "expected: '='".to_string(),
             Some(message),
-        ));
+        )));
     }
 
     p.lexer.next_token(); // Skips the: '='
@@ -66,14 +66,14 @@ pub fn parse_variable_declaration(
     if let Node::Expression(exp) = expr {
         return Ok(Node::Statement(Statement::VariableDeclaration(
             VariableDeclaration {
-                ident: Identifier::new(ident.value, ident.location.line, ident.location.colon),
+                span: p.span_from(ident.span.start.clone()),
+                ident,
                 value: Box::new(exp),
                 data_type: datatype,
-                location: (p.lexer.current_line, p.lexer.current_col),
             },
         )));
     }
 
     // This needs to throw a good error
-    Err(SyntaxException::Unknown)
+    Err(p.error(SyntaxExceptionCode::Unknown))
 }
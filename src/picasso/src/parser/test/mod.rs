@@ -1,2 +1,4 @@
+#[allow(clippy::module_inception)]
 mod test_helper;
+#[allow(clippy::module_inception)]
 mod tests;
@@ -14,24 +14,41 @@ pub mod test_helper {
     use crate::parser::statement::variable::VariableDeclaration;
     use crate::parser::statement::Statement;
     use crate::parser::types::Types;
+    use crate::span::{Location, Span};
+
+    /// Span of code written on a single line, from column `start` up to and including `end`
+    pub fn span(line: i32, start: i32, end: i32) -> Span {
+        Span::new(
+            "",
+            Location { line, colon: start },
+            Location { line, colon: end },
+        )
+    }
+
+    /// Span of `text` when it starts at the given line and column
+    fn span_of(text: &str, line: i32, colon: i32) -> Span {
+        span(line, colon, colon + text.len() as i32 - 1)
+    }
 
     // ========================================================================
     // Everything with "v3" behind the identifier are newer functions for the refactor
     // ========================================================================
 
+    /// The declaration starts at the identifier and ends at the end of its expression
     pub fn generate_variable_declaration_v3(
         identifier: &str,
         expression: parser::expression::Expression,
         line: i32,
         colon: i32,
-        ident_line: i32,
-        ident_colon: i32,
     ) -> Statement {
+        let ident = generate_identifier_v3(identifier, line, colon);
+        let span = ident.span.to(&expression.span());
+
         Statement::VariableDeclaration(VariableDeclaration {
-            ident: Identifier::new(identifier.to_string(), ident_line, ident_colon),
+            ident,
             value: Box::new(expression),
             data_type: Types::Auto,
-            location: (line, colon),
+            span,
         })
     }
 
@@ -40,10 +57,13 @@ pub mod test_helper {
         operator: &str,
         right: parser::expression::Expression,
     ) -> crate::parser::expression::Expression {
+        let span = left.span().to(&right.span());
+
         parser::expression::Expression::Suffix(Box::new(Suffix {
             left,
             operator: operator.to_string(),
             right,
+            span,
         }))
     }
 
@@ -52,38 +72,36 @@ pub mod test_helper {
         operator: &str,
         right: parser::expression::Expression,
     ) -> Statement {
-        Statement::Expression(Box::new(Expression {
-            expression: Box::new(parser::expression::Expression::Suffix(Box::new(Suffix {
-                left,
-                operator: operator.to_string(),
-                right,
-            }))),
-        }))
+        generate_expression_statement_v3(generate_suffix_expression_v3(left, operator, right))
     }
 
     pub fn generate_function_v3_box(
         parameters: Vec<Parameter>,
         statements: Vec<Statement>,
+        body_span: Span,
+        span: Span,
     ) -> Box<crate::parser::expression::Expression> {
-        Box::new(parser::expression::Expression::Function(Function {
-            parameters,
-            body: Block { statements },
-            name: "".to_string(),
-            predefined_type: None,
-            public: false,
-        }))
+        Box::new(generate_function_v3(
+            parameters, statements, body_span, span,
+        ))
     }
 
     pub fn generate_function_v3(
         parameters: Vec<Parameter>,
         statements: Vec<Statement>,
+        body_span: Span,
+        span: Span,
     ) -> crate::parser::expression::Expression {
         parser::expression::Expression::Function(Function {
             parameters,
-            body: Block { statements },
+            body: Block {
+                statements,
+                span: body_span,
+            },
             name: "".to_string(),
             predefined_type: None,
             public: false,
+            span,
         })
     }
 
@@ -91,6 +109,7 @@ pub mod test_helper {
         expression: parser::expression::Expression,
     ) -> Statement {
         Statement::Expression(Box::new(Expression {
+            span: expression.span(),
             expression: Box::new(expression),
         }))
     }
@@ -100,21 +119,16 @@ pub mod test_helper {
         line: i32,
         colon: i32,
     ) -> crate::parser::expression::Expression {
-        parser::expression::Expression::Identifier(Identifier::new(
-            identifier.to_string(),
-            line,
-            colon,
-        ))
+        parser::expression::Expression::Identifier(generate_identifier_v3(identifier, line, colon))
     }
 
-    #[allow(dead_code)]
-    pub fn generate_identifier_v3(name: &str) -> Identifier {
-        Identifier::new(name.to_string(), 0, 0)
+    pub fn generate_identifier_v3(name: &str, line: i32, colon: i32) -> Identifier {
+        Identifier::new(name.to_string(), span_of(name, line, colon))
     }
 
-    pub fn generate_parameter_v3(name: &str, _type: Types) -> Parameter {
+    pub fn generate_parameter_v3(name: &str, _type: Types, line: i32, colon: i32) -> Parameter {
         Parameter {
-            identifier: Identifier::new(name.to_string(), 0, 0),
+            identifier: generate_identifier_v3(name, line, colon),
             _type,
         }
     }
@@ -124,87 +138,113 @@ pub mod test_helper {
     // TODO: Will be refactored
     //====================================================================
 
-    pub fn generate_boolean_expression_box(value: bool) -> Statement {
-        Statement::Expression(Box::new(Expression {
-            expression: Box::new(generate_boolean_expression(value)),
-        }))
+    pub fn generate_boolean_expression_box(value: bool, line: i32, colon: i32) -> Statement {
+        generate_expression_statement_v3(generate_boolean_expression(value, line, colon))
     }
 
-    pub fn generate_boolean_expression(value: bool) -> crate::parser::expression::Expression {
-        parser::expression::Expression::Boolean(Boolean { value })
+    pub fn generate_boolean_expression(
+        value: bool,
+        line: i32,
+        colon: i32,
+    ) -> crate::parser::expression::Expression {
+        parser::expression::Expression::Boolean(Boolean {
+            value,
+            span: span_of(&value.to_string(), line, colon),
+        })
     }
 
-    pub fn generate_integer_expression_box(value: i64) -> Statement {
-        Statement::Expression(Box::new(Expression {
-            expression: Box::new(generate_integer_expression(value)),
-        }))
+    pub fn generate_integer_expression_box(value: i64, line: i32, colon: i32) -> Statement {
+        generate_expression_statement_v3(generate_integer_expression(value, line, colon))
     }
 
-    pub fn generate_integer_expression(value: i64) -> crate::parser::expression::Expression {
-        parser::expression::Expression::Integer(Integer { value })
+    pub fn generate_integer_expression(
+        value: i64,
+        line: i32,
+        colon: i32,
+    ) -> crate::parser::expression::Expression {
+        parser::expression::Expression::Integer(Integer {
+            value,
+            span: span_of(&value.to_string(), line, colon),
+        })
     }
 
-    pub fn generate_float_expression(value: f64) -> crate::parser::expression::Expression {
-        parser::expression::Expression::Float(Float { value })
+    pub fn generate_float_expression(
+        value: f64,
+        line: i32,
+        colon: i32,
+    ) -> crate::parser::expression::Expression {
+        parser::expression::Expression::Float(Float {
+            value,
+            span: span_of(&value.to_string(), line, colon),
+        })
     }
 
+    /// A suffix expression of two integers written as "left operator right", with a space on
+    /// both sides of the operator
     pub fn generate_expression_suffix(
         left: i64,
         operator: char,
         right: i64,
+        line: i32,
+        colon: i32,
     ) -> crate::parser::expression::Expression {
-        parser::expression::Expression::Suffix(Box::new(Suffix {
-            left: parser::expression::Expression::Integer(Integer { value: left }),
-            operator: operator.to_string(),
-            right: parser::expression::Expression::Integer(Integer { value: right }),
-        }))
+        let right_colon = colon + left.to_string().len() as i32 + 3;
+
+        generate_suffix_expression_v3(
+            generate_integer_expression(left, line, colon),
+            &operator.to_string(),
+            generate_integer_expression(right, line, right_colon),
+        )
     }
 
     pub fn generate_if_expression(
-        condition: bool,
+        condition: parser::expression::Expression,
         body: Block,
         else_condition: Option<Box<Node>>,
+        span: Span,
     ) -> Statement {
-        Statement::Expression(Box::new(Expression {
-            expression: Box::new(parser::Expression::Conditional(Box::new(
-                generate_conditional(condition, body, else_condition),
-            ))),
-        }))
+        generate_expression_statement_v3(parser::Expression::Conditional(Box::new(
+            generate_conditional(condition, body, else_condition, span),
+        )))
     }
 
     pub fn generate_else_condition(
-        conditinional: bool,
+        condition: parser::expression::Expression,
         body: Block,
         else_condition: Option<Box<Node>>,
+        span: Span,
     ) -> Option<Box<Node>> {
         Some(Box::from(Node::Expression(
             parser::expression::Expression::Conditional(Box::new(generate_conditional(
-                conditinional,
+                condition,
                 body,
                 else_condition,
+                span,
             ))),
         )))
     }
 
     pub fn generate_conditional(
-        condition: bool,
+        condition: parser::expression::Expression,
         body: Block,
         else_condition: Option<Box<Node>>,
+        span: Span,
     ) -> Conditional {
         Conditional {
-            condition: Box::new(parser::Expression::Boolean(Boolean { value: condition })),
+            condition: Box::new(condition),
             body,
             else_condition,
+            span,
         }
     }
 
-    pub fn generate_else_block_box(statements: Vec<Statement>) -> Option<Box<Node>> {
+    pub fn generate_else_block_box(statements: Vec<Statement>, span: Span) -> Option<Box<Node>> {
         Some(Box::from(Node::Statement(Statement::Block(
-            generate_else_block(statements),
+            generate_else_block(statements, span),
         ))))
     }
 
-    pub fn generate_else_block(statements: Vec<Statement>) -> Block {
-        Block { statements }
+    pub fn generate_else_block(statements: Vec<Statement>, span: Span) -> Block {
+        Block { statements, span }
     }
 }
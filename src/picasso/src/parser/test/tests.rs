@@ -1,64 +1,24 @@
 #[cfg(test)]
 mod tests {
-    use crate::exception::Exception;
     use crate::lexer;
     use crate::parser;
     use crate::parser::expression::array::Array;
     use crate::parser::expression::boolean::Boolean;
     use crate::parser::expression::function::{Function, Parameter};
     use crate::parser::expression::hashmap::{HashableExpression, Hashmap};
-    use crate::parser::expression::identifier::Identifier;
     use crate::parser::expression::integer::Integer;
     use crate::parser::expression::loops::{Loop, LoopArrayIterator, LoopIterator};
-    use crate::parser::expression::null::Null;
+    use crate::parser::expression::prefix::Prefix;
+    use crate::parser::expression::range::Range;
     use crate::parser::expression::string::LoopString;
-    use crate::parser::expression::suffix::Suffix;
     use crate::parser::statement::assign::VariableAssign;
     use crate::parser::statement::block::Block;
-    use crate::parser::statement::class::{Class, ClassField, ClassItem};
     use crate::parser::statement::expression::Expression;
     use crate::parser::statement::return_statement::ReturnStatement;
-    use crate::parser::statement::variable::VariableDeclaration;
     use crate::parser::statement::Statement;
-    use crate::parser::test::test_helper::test_helper;
+    use crate::parser::test::test_helper::test_helper::{self, span};
     use crate::parser::types::{BaseTypes, Types};
-    use std::collections::HashMap;
-
-    //#[test]
-    fn classes() {
-        let input = "class Person {\
-        name = \"Jeff\"\
-        age = 42
-        }";
-
-        let expected = vec![Statement::Class(Class {
-            name: "Person".to_string(),
-            values: vec![
-                ClassField {
-                    name: "name".to_string(),
-                    index: 0,
-                    item: ClassItem::Property(Expression {
-                        expression: Box::new(parser::expression::Expression::String(LoopString {
-                            value: "Jeff".to_string(),
-                        })),
-                    }),
-                },
-                ClassField {
-                    name: "age".to_string(),
-                    index: 1,
-                    item: ClassItem::Property(Expression {
-                        expression: Box::new(parser::expression::Expression::Integer(Integer {
-                            value: 42,
-                        })),
-                    }),
-                },
-            ],
-            inherits: "".to_string(),
-            public: false,
-        })];
-
-        test_parser(input, expected)
-    }
+    use crate::span::Span;
 
     #[test]
     fn functions_return() {
@@ -66,23 +26,23 @@ mod tests {
         return 20;\
         }";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        expected.push(Statement::Expression(Box::from(Expression {
+        let expected = vec![Statement::Expression(Box::from(Expression {
             expression: Box::from(parser::expression::Expression::Function(Function {
                 parameters: vec![],
                 body: Block {
                     statements: vec![Statement::Return(ReturnStatement {
-                        expression: Box::new(parser::expression::Expression::Integer(Integer {
-                            value: 20,
-                        })),
+                        expression: Box::new(test_helper::generate_integer_expression(20, 1, 14)),
+                        span: span(1, 7, 15),
                     })],
+                    span: span(1, 7, 17),
                 },
                 name: "".to_string(),
                 predefined_type: None,
                 public: false,
+                span: span(1, 1, 17),
             })),
-        })));
+            span: span(1, 1, 17),
+        }))];
 
         test_parser(input, expected);
     }
@@ -91,47 +51,56 @@ mod tests {
     fn loop_while() {
         let input = "for(true) { }";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        expected.push(Statement::Expression(Box::new(Expression {
+        let expected = vec![Statement::Expression(Box::new(Expression {
             expression: Box::new(parser::Expression::Loop(Loop {
-                condition: Box::new(parser::Expression::Boolean(Boolean { value: true })),
-                body: Block { statements: vec![] },
+                condition: Box::new(test_helper::generate_boolean_expression(true, 1, 5)),
+                body: Block {
+                    statements: vec![],
+                    span: span(1, 13, 13),
+                },
+                span: span(1, 1, 13),
             })),
-        })));
+            span: span(1, 1, 13),
+        }))];
 
         test_parser(input, expected);
     }
 
-    //#[test]
+    #[test]
     fn hashmap() {
         let input = "{\"hello world\": 123, true: 123, 500: false}";
 
         let mut expected: Vec<Statement> = Vec::new();
-        let mut hashmap_values: HashMap<HashableExpression, parser::expression::Expression> =
-            HashMap::new();
-
-        hashmap_values.insert(
-            HashableExpression::String(LoopString {
-                value: "hello world".to_string(),
-            }),
-            parser::Expression::Integer(Integer { value: 123 }),
-        );
-
-        hashmap_values.insert(
-            HashableExpression::Boolean(Boolean { value: true }),
-            parser::Expression::Integer(Integer { value: 123 }),
-        );
-
-        hashmap_values.insert(
-            HashableExpression::Integer(Integer { value: 500 }),
-            parser::Expression::Boolean(Boolean { value: false }),
-        );
+        let hashmap_values: Vec<(HashableExpression, parser::expression::Expression)> = vec![
+            (
+                HashableExpression::String(LoopString {
+                    value: "hello world".to_string(),
+                    span: span(1, 2, 14),
+                }),
+                test_helper::generate_integer_expression(123, 1, 17),
+            ),
+            (
+                HashableExpression::Boolean(Boolean {
+                    value: true,
+                    span: span(1, 22, 25),
+                }),
+                test_helper::generate_integer_expression(123, 1, 28),
+            ),
+            (
+                HashableExpression::Integer(Integer {
+                    value: 500,
+                    span: span(1, 33, 35),
+                }),
+                test_helper::generate_boolean_expression(false, 1, 38),
+            ),
+        ];
 
         expected.push(Statement::Expression(Box::new(Expression {
             expression: Box::new(parser::Expression::Hashmap(Hashmap {
                 values: hashmap_values,
+                span: span(1, 1, 43),
             })),
+            span: span(1, 1, 43),
         })));
 
         test_parser(input, expected);
@@ -141,16 +110,80 @@ mod tests {
     fn loop_iterator() {
         let input = "for(var i = 0 to 100) { }";
 
-        let mut expected: Vec<Statement> = Vec::new();
+        let expected = vec![Statement::Expression(Box::new(Expression {
+            expression: Box::new(parser::Expression::LoopIterator(LoopIterator {
+                identifier: test_helper::generate_identifier_v3("i", 1, 9),
+                from: Box::new(test_helper::generate_integer_expression(0, 1, 13)),
+                till: Box::new(test_helper::generate_integer_expression(100, 1, 18)),
+                step: None,
+                inclusive: false,
+                body: Block {
+                    statements: vec![],
+                    span: span(1, 25, 25),
+                },
+                span: span(1, 1, 25),
+            })),
+            span: span(1, 1, 25),
+        }))];
 
-        expected.push(Statement::Expression(Box::new(Expression {
+        test_parser(input, expected);
+    }
+
+    #[test]
+    fn loop_iterator_through_step() {
+        let input = "for var i = n through -n step -2 { }";
+
+        let expected = vec![Statement::Expression(Box::new(Expression {
             expression: Box::new(parser::Expression::LoopIterator(LoopIterator {
-                identifier: Identifier::new("i".to_string(), 1, 11),
-                from: 0,
-                till: 100,
-                body: Block { statements: vec![] },
+                identifier: test_helper::generate_identifier_v3("i", 1, 9),
+                from: Box::new(test_helper::generate_identifier_expression_v3("n", 1, 13)),
+                till: Box::new(parser::Expression::Prefix(Box::new(Prefix {
+                    operator: "-".to_string(),
+                    right: test_helper::generate_identifier_expression_v3("n", 1, 24),
+                    span: span(1, 23, 24),
+                }))),
+                step: Some(Box::new(test_helper::generate_integer_expression(
+                    -2, 1, 31,
+                ))),
+                inclusive: true,
+                body: Block {
+                    statements: vec![],
+                    span: span(1, 36, 36),
+                },
+                span: span(1, 1, 36),
             })),
-        })));
+            span: span(1, 1, 36),
+        }))];
+
+        test_parser(input, expected);
+    }
+
+    #[test]
+    fn range() {
+        let input = "0..n + 1; 1..=3";
+
+        let expected = vec![
+            test_helper::generate_expression_statement_v3(parser::Expression::Range(Box::new(
+                Range {
+                    from: test_helper::generate_integer_expression(0, 1, 1),
+                    till: test_helper::generate_suffix_expression_v3(
+                        test_helper::generate_identifier_expression_v3("n", 1, 4),
+                        "+",
+                        test_helper::generate_integer_expression(1, 1, 8),
+                    ),
+                    inclusive: false,
+                    span: span(1, 1, 8),
+                },
+            ))),
+            test_helper::generate_expression_statement_v3(parser::Expression::Range(Box::new(
+                Range {
+                    from: test_helper::generate_integer_expression(1, 1, 11),
+                    till: test_helper::generate_integer_expression(3, 1, 15),
+                    inclusive: true,
+                    span: span(1, 11, 15),
+                },
+            ))),
+        ];
 
         test_parser(input, expected);
     }
@@ -159,101 +192,99 @@ mod tests {
     fn loop_array_iterator() {
         let input = "for(var value in []) { }";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        expected.push(Statement::Expression(Box::new(Expression {
+        let expected = vec![Statement::Expression(Box::new(Expression {
             expression: Box::new(parser::Expression::LoopArrayIterator(LoopArrayIterator {
-                identifier: Identifier::new("value".to_string(), 1, 16),
-                body: Block { statements: vec![] },
+                identifier: test_helper::generate_identifier_v3("value", 1, 9),
+                body: Block {
+                    statements: vec![],
+                    span: span(1, 24, 24),
+                },
                 array: Box::new(parser::Expression::Array(Box::new(Array {
                     values: vec![],
-                    location: (1, 20),
+                    span: span(1, 18, 19),
                 }))),
+                span: span(1, 1, 24),
             })),
-        })));
+            span: span(1, 1, 24),
+        }))];
 
         test_parser(input, expected);
     }
 
     /*
-        #[test]
-        fn extension_methods_chained() {
-            let input = "
-        \"123\".to_int().to_string().to_int();
-    ";
-
-            let mut expected: Vec<Statement> = Vec::new();
+            #[test]
+            fn extension_methods_chained() {
+                let input = "
+            \"123\".to_int().to_string().to_int();
+        ";
 
-            expected.push(Statement::Expression(Box::new(Expression {
-                expression: Box::new(Index(Box::new(parser::expression::index::Index {
-                    left: Index(Box::new(parser::expression::index::Index {
+                let expected = vec![Statement::Expression(Box::new(Expression {
+                    expression: Box::new(Index(Box::new(parser::expression::index::Index {
                         left: Index(Box::new(parser::expression::index::Index {
-                            left: parser::expression::Expression::String(LoopString {
-                                value: "123".to_string(),
-                            }),
+                            left: Index(Box::new(parser::expression::index::Index {
+                                left: parser::expression::Expression::String(LoopString {
+                                    value: "123".to_string(),
+                                }),
+                                index: parser::expression::Expression::Call(Call {
+                                    identifier: Box::new(parser::Expression::Identifier(Identifier {
+                                        value: "to_int".to_string(),
+                                    })),
+                                    parameters: vec![],
+                                }),
+                            })),
                             index: parser::expression::Expression::Call(Call {
                                 identifier: Box::new(parser::Expression::Identifier(Identifier {
-                                    value: "to_int".to_string(),
+                                    value: "to_string".to_string(),
                                 })),
                                 parameters: vec![],
                             }),
                         })),
                         index: parser::expression::Expression::Call(Call {
                             identifier: Box::new(parser::Expression::Identifier(Identifier {
-                                value: "to_string".to_string(),
+                                value: "to_int".to_string(),
                             })),
                             parameters: vec![],
                         }),
-                    })),
-                    index: parser::expression::Expression::Call(Call {
-                        identifier: Box::new(parser::Expression::Identifier(Identifier {
-                            value: "to_int".to_string(),
-                        })),
-                        parameters: vec![],
-                    }),
-                }))),
-            })));
+                    }))),
+                }))];
 
             test_parser(input, expected);
-        }
-
-        #[test]
-        fn extension_methods() {
-            let input = "
-        \"123\".to_int();
-        123.to_string();
-    ";
+            }
 
-            let mut expected: Vec<Statement> = Vec::new();
+            #[test]
+            fn extension_methods() {
+                let input = "
+            \"123\".to_int();
+            123.to_string();
+        ";
 
-            expected.push(Statement::Expression(Box::new(Expression {
-                expression: Box::new(Index(Box::new(parser::expression::index::Index {
-                    left: parser::expression::Expression::String(LoopString {
-                        value: "123".to_string(),
-                    }),
-                    index: parser::expression::Expression::Call(Call {
-                        identifier: Box::new(parser::Expression::Identifier(Identifier {
-                            value: "to_int".to_string(),
-                        })),
-                        parameters: vec![],
-                    }),
-                }))),
-            })));
-
-            expected.push(Statement::Expression(Box::new(Expression {
-                expression: Box::new(Index(Box::new(parser::expression::index::Index {
-                    left: parser::expression::Expression::Integer(Integer { value: 123 }),
-                    index: parser::expression::Expression::Call(Call {
-                        identifier: Box::new(parser::Expression::Identifier(Identifier {
-                            value: "to_string".to_string(),
-                        })),
-                        parameters: vec![],
-                    }),
-                }))),
-            })));
+                let expected = vec![Statement::Expression(Box::new(Expression {
+                    expression: Box::new(Index(Box::new(parser::expression::index::Index {
+                        left: parser::expression::Expression::String(LoopString {
+                            value: "123".to_string(),
+                        }),
+                        index: parser::expression::Expression::Call(Call {
+                            identifier: Box::new(parser::Expression::Identifier(Identifier {
+                                value: "to_int".to_string(),
+                            })),
+                            parameters: vec![],
+                        }),
+                    }))),
+                })),
+    Statement::Expression(Box::new(Expression {
+                    expression: Box::new(Index(Box::new(parser::expression::index::Index {
+                        left: parser::expression::Expression::Integer(Integer { value: 123 }),
+                        index: parser::expression::Expression::Call(Call {
+                            identifier: Box::new(parser::Expression::Identifier(Identifier {
+                                value: "to_string".to_string(),
+                            })),
+                            parameters: vec![],
+                        }),
+                    }))),
+                }))];
 
             test_parser(input, expected);
-        }*/
+            }*/
 
     #[test]
     fn variable_assignment() {
@@ -264,53 +295,39 @@ mod tests {
         foo = 2 ^ 3;\
         yeet = test * 2;";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        expected.push(Statement::VariableDeclaration(VariableDeclaration {
-            ident: Identifier::new("test".to_string(), 1, 6),
-            value: Box::new(parser::expression::Expression::Integer(Integer {
-                value: 0,
-            })),
-            data_type: Types::Auto,
-            location: (1, 10),
-        }));
-
-        expected.push(Statement::VariableDeclaration(VariableDeclaration {
-            ident: Identifier::new("yeet".to_string(), 1, 16),
-            value: Box::new(parser::expression::Expression::Integer(Integer {
-                value: 500,
-            })),
-            data_type: Types::Auto,
-            location: (1, 22),
-        }));
-
-        expected.push(Statement::VariableAssign(VariableAssign {
-            ident: Identifier::new("test".to_string(), 1, 28),
-            value: Box::new(parser::expression::Expression::Integer(Integer {
-                value: 1000,
-            })),
-        }));
-
-        expected.push(Statement::VariableAssign(VariableAssign {
-            ident: Identifier::new("foo".to_string(), 1, 39),
-            value: Box::new(parser::expression::Expression::Suffix(Box::from(Suffix {
-                left: parser::expression::Expression::Integer(Integer { value: 2 }),
-                operator: "^".to_string(),
-                right: parser::expression::Expression::Integer(Integer { value: 3 }),
-            }))),
-        }));
-        expected.push(Statement::VariableAssign(VariableAssign {
-            ident: Identifier::new("yeet".to_string(), 1, 52),
-            value: Box::new(parser::expression::Expression::Suffix(Box::from(Suffix {
-                left: parser::expression::Expression::Identifier(Identifier::new(
-                    "test".to_string(),
-                    1,
-                    59,
+        let expected = vec![
+            test_helper::generate_variable_declaration_v3(
+                "test",
+                test_helper::generate_integer_expression(0, 1, 9),
+                1,
+                1,
+            ),
+            test_helper::generate_variable_declaration_v3(
+                "yeet",
+                test_helper::generate_integer_expression(500, 1, 19),
+                1,
+                11,
+            ),
+            Statement::VariableAssign(VariableAssign {
+                ident: test_helper::generate_identifier_v3("test", 1, 23),
+                value: Box::new(test_helper::generate_integer_expression(1000, 1, 30)),
+                span: span(1, 23, 33),
+            }),
+            Statement::VariableAssign(VariableAssign {
+                ident: test_helper::generate_identifier_v3("foo", 1, 35),
+                value: Box::new(test_helper::generate_expression_suffix(2, '^', 3, 1, 41)),
+                span: span(1, 35, 45),
+            }),
+            Statement::VariableAssign(VariableAssign {
+                ident: test_helper::generate_identifier_v3("yeet", 1, 47),
+                value: Box::new(test_helper::generate_suffix_expression_v3(
+                    test_helper::generate_identifier_expression_v3("test", 1, 54),
+                    "*",
+                    test_helper::generate_integer_expression(2, 1, 61),
                 )),
-                operator: "*".to_string(),
-                right: parser::expression::Expression::Integer(Integer { value: 2 }),
-            }))),
-        }));
+                span: span(1, 47, 61),
+            }),
+        ];
 
         test_parser(input, expected);
     }
@@ -327,56 +344,66 @@ mod tests {
 
         // TODO: This is a hot mess. It is kind of readable but not really...
         let else_conditional = test_helper::generate_else_condition(
-            false,
-            test_helper::generate_else_block(vec![
-                test_helper::generate_boolean_expression_box(true),
-                Statement::Expression(Box::new(Expression {
-                    expression: Box::new(test_helper::generate_expression_suffix(1, '+', 1)),
-                })),
-            ]),
-            test_helper::generate_else_block_box(vec![Statement::Expression(Box::new(
-                Expression {
-                    expression: Box::new(test_helper::generate_boolean_expression(true)),
-                },
-            ))]),
+            test_helper::generate_boolean_expression(false, 2, 40),
+            test_helper::generate_else_block(
+                vec![
+                    test_helper::generate_boolean_expression_box(true, 2, 48),
+                    test_helper::generate_expression_statement_v3(
+                        test_helper::generate_expression_suffix(1, '+', 1, 2, 54),
+                    ),
+                ],
+                span(2, 48, 59),
+            ),
+            test_helper::generate_else_block_box(
+                vec![test_helper::generate_boolean_expression_box(true, 2, 68)],
+                span(2, 68, 74),
+            ),
+            span(2, 37, 74),
         );
 
         expected.push(test_helper::generate_if_expression(
-            false,
+            test_helper::generate_boolean_expression(false, 2, 12),
             Block {
                 statements: vec![
-                    test_helper::generate_integer_expression_box(1),
-                    test_helper::generate_boolean_expression_box(true),
+                    test_helper::generate_integer_expression_box(1, 2, 21),
+                    test_helper::generate_boolean_expression_box(true, 2, 24),
                 ],
+                span: span(2, 21, 30),
             },
             else_conditional,
+            span(2, 9, 74),
         ));
 
         // TODO: This is a hot mess. It is kind of readable but not really...
         let else_conditional = test_helper::generate_else_condition(
-            false,
-            test_helper::generate_else_block(vec![
-                test_helper::generate_boolean_expression_box(true),
-                Statement::Expression(Box::new(Expression {
-                    expression: Box::new(test_helper::generate_expression_suffix(1, '+', 1)),
-                })),
-            ]),
-            test_helper::generate_else_block_box(vec![Statement::Expression(Box::from(
-                Expression {
-                    expression: Box::new(test_helper::generate_boolean_expression(true)),
-                },
-            ))]),
+            test_helper::generate_boolean_expression(false, 2, 105),
+            test_helper::generate_else_block(
+                vec![
+                    test_helper::generate_boolean_expression_box(true, 2, 113),
+                    test_helper::generate_expression_statement_v3(
+                        test_helper::generate_expression_suffix(1, '+', 1, 2, 119),
+                    ),
+                ],
+                span(2, 113, 124),
+            ),
+            test_helper::generate_else_block_box(
+                vec![test_helper::generate_boolean_expression_box(true, 2, 133)],
+                span(2, 133, 139),
+            ),
+            span(2, 102, 139),
         );
 
         expected.push(test_helper::generate_if_expression(
-            false,
+            test_helper::generate_boolean_expression(false, 2, 78),
             Block {
                 statements: vec![
-                    test_helper::generate_integer_expression_box(1),
-                    test_helper::generate_boolean_expression_box(true),
+                    test_helper::generate_integer_expression_box(1, 2, 86),
+                    test_helper::generate_boolean_expression_box(true, 2, 89),
                 ],
+                span: span(2, 86, 95),
             },
             else_conditional,
+            span(2, 75, 139),
         ));
 
         test_parser(input, expected);
@@ -403,51 +430,76 @@ mod tests {
         let parameters: Vec<Parameter> = vec![];
         let statements: Vec<Statement> = vec![];
         expected.push(Statement::Expression(Box::new(Expression {
-            expression: test_helper::generate_function_v3_box(parameters, statements),
+            expression: test_helper::generate_function_v3_box(
+                parameters,
+                statements,
+                span(2, 11, 11),
+                span(2, 5, 11),
+            ),
+            span: span(2, 5, 11),
         })));
 
         // Test #2
         let parameters: Vec<Parameter> = vec![test_helper::generate_parameter_v3(
             "a",
             Types::Basic(BaseTypes::Integer),
+            2,
+            19,
         )];
         let statements: Vec<Statement> = vec![];
         expected.push(Statement::Expression(Box::new(Expression {
-            expression: test_helper::generate_function_v3_box(parameters, statements),
+            expression: test_helper::generate_function_v3_box(
+                parameters,
+                statements,
+                span(2, 23, 23),
+                span(2, 12, 23),
+            ),
+            span: span(2, 12, 23),
         })));
 
         // Test #3
         let parameters: Vec<Parameter> = vec![
-            test_helper::generate_parameter_v3("a", Types::Basic(BaseTypes::Integer)),
-            test_helper::generate_parameter_v3("b", Types::Basic(BaseTypes::Integer)),
-            test_helper::generate_parameter_v3("c", Types::Basic(BaseTypes::Integer)),
-            test_helper::generate_parameter_v3("d", Types::Basic(BaseTypes::Integer)),
+            test_helper::generate_parameter_v3("a", Types::Basic(BaseTypes::Integer), 2, 31),
+            test_helper::generate_parameter_v3("b", Types::Basic(BaseTypes::Integer), 2, 38),
+            test_helper::generate_parameter_v3("c", Types::Basic(BaseTypes::Integer), 2, 45),
+            test_helper::generate_parameter_v3("d", Types::Basic(BaseTypes::Integer), 2, 52),
         ];
         let statements: Vec<Statement> = vec![];
         expected.push(Statement::Expression(Box::new(Expression {
-            expression: test_helper::generate_function_v3_box(parameters, statements),
+            expression: test_helper::generate_function_v3_box(
+                parameters,
+                statements,
+                span(2, 56, 56),
+                span(2, 24, 56),
+            ),
+            span: span(2, 24, 56),
         })));
 
         // Test #4
         let parameters: Vec<Parameter> = vec![];
-        let statements: Vec<Statement> = vec![test_helper::generate_expression_statement_v3(
-            test_helper::generate_integer_expression(1),
-        )];
+        let statements: Vec<Statement> =
+            vec![test_helper::generate_integer_expression_box(1, 2, 63)];
         expected.push(Statement::Expression(Box::new(Expression {
-            expression: test_helper::generate_function_v3_box(parameters, statements),
+            expression: test_helper::generate_function_v3_box(
+                parameters,
+                statements,
+                span(2, 63, 64),
+                span(2, 57, 64),
+            ),
+            span: span(2, 57, 64),
         })));
 
         // Test #5
         let parameters: Vec<Parameter> = vec![
-            test_helper::generate_parameter_v3("a", Types::Basic(BaseTypes::Integer)),
-            test_helper::generate_parameter_v3("b", Types::Basic(BaseTypes::Integer)),
-            test_helper::generate_parameter_v3("c", Types::Basic(BaseTypes::Integer)),
-            test_helper::generate_parameter_v3("d", Types::Basic(BaseTypes::Integer)),
+            test_helper::generate_parameter_v3("a", Types::Basic(BaseTypes::Integer), 2, 98),
+            test_helper::generate_parameter_v3("b", Types::Basic(BaseTypes::Integer), 2, 105),
+            test_helper::generate_parameter_v3("c", Types::Basic(BaseTypes::Integer), 2, 112),
+            test_helper::generate_parameter_v3("d", Types::Basic(BaseTypes::Integer), 2, 119),
         ];
-        let left = test_helper::generate_identifier_expression_v3("a", 2, 125);
-        let right = test_helper::generate_identifier_expression_v3("b", 2, 128);
-        let left2 = test_helper::generate_identifier_expression_v3("c", 2, 136);
-        let right2 = test_helper::generate_identifier_expression_v3("d", 2, 139);
+        let left = test_helper::generate_identifier_expression_v3("a", 2, 123);
+        let right = test_helper::generate_identifier_expression_v3("b", 2, 127);
+        let left2 = test_helper::generate_identifier_expression_v3("c", 2, 134);
+        let right2 = test_helper::generate_identifier_expression_v3("d", 2, 138);
         let statements = vec![
             test_helper::generate_expression_statement_v3(
                 test_helper::generate_suffix_expression_v3(left, "+", right),
@@ -456,19 +508,20 @@ mod tests {
                 "e",
                 test_helper::generate_suffix_expression_v3(left2, "+", right2),
                 2,
-                139,
-                2,
-                131,
+                129,
             ),
         ];
-        let function = test_helper::generate_function_v3(parameters, statements);
+        let function = test_helper::generate_function_v3(
+            parameters,
+            statements,
+            span(2, 123, 140),
+            span(2, 91, 140),
+        );
         let result = test_helper::generate_variable_declaration_v3(
             "functionWithParameters",
             function,
             2,
-            141,
-            2,
-            88,
+            65,
         );
         expected.push(result);
 
@@ -479,13 +532,12 @@ mod tests {
     fn booleans() {
         let input = "true; false;";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        // Test #1
-        expected.push(test_helper::generate_boolean_expression_box(true));
-
-        // Test #2
-        expected.push(test_helper::generate_boolean_expression_box(false));
+        let expected = vec![
+            // Test #1
+            test_helper::generate_boolean_expression_box(true, 1, 1),
+            // Test #2
+            test_helper::generate_boolean_expression_box(false, 1, 7),
+        ];
 
         test_parser(input, expected);
     }
@@ -494,13 +546,23 @@ mod tests {
     fn booleans_inverted() {
         let input = "!true; !false;";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        // Test #1
-        expected.push(test_helper::generate_boolean_expression_box(false));
-
-        // Test #2
-        expected.push(test_helper::generate_boolean_expression_box(true));
+        let expected = vec![
+            // The inverted boolean spans the "!" as well
+            // Test #1
+            test_helper::generate_expression_statement_v3(parser::expression::Expression::Boolean(
+                Boolean {
+                    value: false,
+                    span: span(1, 1, 5),
+                },
+            )),
+            // Test #2
+            test_helper::generate_expression_statement_v3(parser::expression::Expression::Boolean(
+                Boolean {
+                    value: true,
+                    span: span(1, 8, 13),
+                },
+            )),
+        ];
 
         test_parser(input, expected);
     }
@@ -509,19 +571,20 @@ mod tests {
     fn strings() {
         let input = "\"hello world!\" \"hello world, from a string!\"";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::new(parser::expression::Expression::String(LoopString {
-                value: String::from("hello world!"),
-            })),
-        })));
-
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::new(parser::expression::Expression::String(LoopString {
-                value: String::from("hello world, from a string!"),
-            })),
-        })));
+        let expected = vec![
+            test_helper::generate_expression_statement_v3(parser::expression::Expression::String(
+                LoopString {
+                    value: String::from("hello world!"),
+                    span: span(1, 1, 14),
+                },
+            )),
+            test_helper::generate_expression_statement_v3(parser::expression::Expression::String(
+                LoopString {
+                    value: String::from("hello world, from a string!"),
+                    span: span(1, 16, 44),
+                },
+            )),
+        ];
 
         test_parser(input, expected);
     }
@@ -533,27 +596,27 @@ mod tests {
         let mut expected: Vec<Statement> = Vec::new();
 
         // Test #1
-        let left = test_helper::generate_boolean_expression(true);
+        let left = test_helper::generate_boolean_expression(true, 1, 1);
         let op = "==";
-        let right = test_helper::generate_boolean_expression(true);
+        let right = test_helper::generate_boolean_expression(true, 1, 9);
         expected.push(test_helper::generate_comparison_v3(left, op, right));
 
         // Test #2
-        let left = test_helper::generate_integer_expression(1);
+        let left = test_helper::generate_integer_expression(1, 1, 15);
         let op = "==";
-        let right = test_helper::generate_boolean_expression(true);
+        let right = test_helper::generate_boolean_expression(true, 1, 20);
         expected.push(test_helper::generate_comparison_v3(left, op, right));
 
         // Test #3
-        let left = test_helper::generate_integer_expression(3);
+        let left = test_helper::generate_integer_expression(3, 1, 26);
         let op = ">";
-        let right = test_helper::generate_integer_expression(4);
+        let right = test_helper::generate_integer_expression(4, 1, 30);
         expected.push(test_helper::generate_comparison_v3(left, op, right));
 
         // Test #4
-        let left = test_helper::generate_integer_expression(3);
+        let left = test_helper::generate_integer_expression(3, 1, 33);
         let op = "<";
-        let right = test_helper::generate_integer_expression(4);
+        let right = test_helper::generate_integer_expression(4, 1, 37);
         expected.push(test_helper::generate_comparison_v3(left, op, right));
 
         test_parser(input, expected);
@@ -572,92 +635,69 @@ mod tests {
         test8 := 1.1 + 1;
         ";
 
-        let mut expected: Vec<Statement> = Vec::new();
-
-        // Test #1
-        expected.push(test_helper::generate_variable_declaration_v3(
-            "test",
-            test_helper::generate_integer_expression(1),
-            2,
-            18,
-            2,
-            14,
-        ));
-
-        // Test #2
-        expected.push(test_helper::generate_variable_declaration_v3(
-            "test2",
-            test_helper::generate_integer_expression(40),
-            3,
-            20,
-            3,
-            15,
-        ));
-
-        // Test #3
-        let left = test_helper::generate_integer_expression(10);
-        let right = test_helper::generate_integer_expression(2);
-        expected.push(test_helper::generate_variable_declaration_v3(
-            "test3",
-            test_helper::generate_suffix_expression_v3(left, "*", right),
-            4,
-            24,
-            4,
-            15,
-        ));
-
-        // Test #4
-        expected.push(test_helper::generate_variable_declaration_v3(
-            "test4",
-            test_helper::generate_float_expression(1.1),
-            5,
-            21,
-            5,
-            15,
-        ));
-
-        // Test #5
-        expected.push(test_helper::generate_variable_declaration_v3(
-            "test5",
-            test_helper::generate_integer_expression(-1),
-            6,
-            20,
-            6,
-            15,
-        ));
-
-        // Test #6
-        expected.push(test_helper::generate_variable_declaration_v3(
-            "test6",
-            test_helper::generate_float_expression(-1.1),
-            7,
-            22,
-            7,
-            15,
-        ));
+        let mut expected = vec![
+            // Test #1
+            test_helper::generate_variable_declaration_v3(
+                "test",
+                test_helper::generate_integer_expression(1, 2, 17),
+                2,
+                9,
+            ),
+            // Test #2
+            test_helper::generate_variable_declaration_v3(
+                "test2",
+                test_helper::generate_integer_expression(40, 3, 18),
+                3,
+                9,
+            ),
+            // Test #3
+            test_helper::generate_variable_declaration_v3(
+                "test3",
+                test_helper::generate_expression_suffix(10, '*', 2, 4, 18),
+                4,
+                9,
+            ),
+            // Test #4
+            test_helper::generate_variable_declaration_v3(
+                "test4",
+                test_helper::generate_float_expression(1.1, 5, 18),
+                5,
+                9,
+            ),
+            // Test #5
+            test_helper::generate_variable_declaration_v3(
+                "test5",
+                test_helper::generate_integer_expression(-1, 6, 18),
+                6,
+                9,
+            ),
+            // Test #6
+            test_helper::generate_variable_declaration_v3(
+                "test6",
+                test_helper::generate_float_expression(-1.1, 7, 18),
+                7,
+                9,
+            ),
+        ];
 
         // Test #7
-        let left = test_helper::generate_float_expression(1.1);
-        let right = test_helper::generate_float_expression(1.1);
+        let left = test_helper::generate_float_expression(1.1, 8, 18);
+        let right = test_helper::generate_float_expression(1.1, 8, 24);
         expected.push(test_helper::generate_variable_declaration_v3(
             "test7",
             test_helper::generate_suffix_expression_v3(left, "+", right),
             8,
-            27,
-            8,
-            15,
+            9,
         ));
 
         // Test #8
-        let left = test_helper::generate_float_expression(1.1);
-        let right = test_helper::generate_integer_expression(1);
+        let left = test_helper::generate_float_expression(1.1, 9, 18);
+        let right = test_helper::generate_integer_expression(1, 9, 24);
         expected.push(test_helper::generate_variable_declaration_v3(
             "test8",
             test_helper::generate_suffix_expression_v3(left, "+", right),
             9,
-            25,
             9,
-            15,
         ));
 
         test_parser(input, expected);
@@ -667,14 +707,12 @@ mod tests {
     fn array() {
         let input = "[]";
 
-        let mut expected: Vec<Statement> = vec![];
-
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::new(parser::expression::Expression::Array(Box::from(Array {
+        let expected = vec![test_helper::generate_expression_statement_v3(
+            parser::expression::Expression::Array(Box::from(Array {
                 values: vec![],
-                location: (1, 3),
-            }))),
-        })));
+                span: span(1, 1, 2),
+            })),
+        )];
 
         test_parser(input, expected);
     }
@@ -683,92 +721,45 @@ mod tests {
     fn array_content() {
         let input = "[1, 2, 3]";
 
-        let mut expected: Vec<Statement> = vec![];
-
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::new(parser::expression::Expression::Array(Box::from(Array {
+        let expected = vec![test_helper::generate_expression_statement_v3(
+            parser::expression::Expression::Array(Box::from(Array {
                 values: vec![
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::Integer(Integer {
-                            value: 1,
-                        })),
-                    },
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::Integer(Integer {
-                            value: 2,
-                        })),
-                    },
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::Integer(Integer {
-                            value: 3,
-                        })),
-                    },
+                    array_value(test_helper::generate_integer_expression(1, 1, 2)),
+                    array_value(test_helper::generate_integer_expression(2, 1, 5)),
+                    array_value(test_helper::generate_integer_expression(3, 1, 8)),
                 ],
-                location: (1, 10),
-            }))),
-        })));
+                span: span(1, 1, 9),
+            })),
+        )];
 
         test_parser(input, expected);
     }
 
-    fn add_array_mixed_content(mut expected: Vec<Statement>) -> Vec<Statement> {
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::new(parser::expression::Expression::Array(Box::from(Array {
-                values: vec![
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::Integer(Integer {
-                            value: 1,
-                        })),
-                    },
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::Null(Null {})),
-                    },
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::Boolean(Boolean {
-                            value: true,
-                        })),
-                    },
-                    Expression {
-                        expression: Box::from(parser::expression::Expression::String(LoopString {
-                            value: "hello world".to_string(),
-                        })),
-                    },
-                ],
-                location: (0, 0),
-            }))),
-        })));
-
-        expected
+    /// Values of arrays are kept as expression statements
+    fn array_value(expression: parser::expression::Expression) -> Expression {
+        Expression {
+            span: expression.span(),
+            expression: Box::new(expression),
+        }
     }
 
     #[test]
     fn array_index() {
         let input = "[0, 1][0]";
 
-        let mut expected: Vec<Statement> = vec![];
-
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::from(parser::Expression::Index(Box::from(
-                parser::expression::index::Index {
-                    left: parser::Expression::Array(Box::from(Array {
-                        values: vec![
-                            Expression {
-                                expression: Box::from(parser::Expression::Integer(Integer {
-                                    value: 0,
-                                })),
-                            },
-                            Expression {
-                                expression: Box::from(parser::Expression::Integer(Integer {
-                                    value: 1,
-                                })),
-                            },
-                        ],
-                        location: (1, 7),
-                    })),
-                    index: parser::Expression::Integer(Integer { value: 0 }),
-                },
-            ))),
-        })));
+        let expected = vec![test_helper::generate_expression_statement_v3(
+            parser::Expression::Index(Box::from(parser::expression::index::Index {
+                left: parser::Expression::Array(Box::from(Array {
+                    values: vec![
+                        array_value(test_helper::generate_integer_expression(0, 1, 2)),
+                        array_value(test_helper::generate_integer_expression(1, 1, 5)),
+                    ],
+                    span: span(1, 1, 6),
+                })),
+                index: test_helper::generate_integer_expression(0, 1, 8),
+                span: span(1, 1, 9),
+            })),
+        )];
 
         test_parser(input, expected);
     }
@@ -777,31 +768,22 @@ mod tests {
     fn array_assign() {
         let input = "[0, 1][0] = 300";
 
-        let mut expected: Vec<Statement> = vec![];
-
-        expected.push(Statement::Expression(Box::from(Expression {
-            expression: Box::from(parser::Expression::AssignIndex(Box::from(
+        let expected = vec![test_helper::generate_expression_statement_v3(
+            parser::Expression::AssignIndex(Box::from(
                 parser::expression::assign_index::AssignIndex {
                     left: parser::Expression::Array(Box::from(Array {
                         values: vec![
-                            Expression {
-                                expression: Box::from(parser::Expression::Integer(Integer {
-                                    value: 0,
-                                })),
-                            },
-                            Expression {
-                                expression: Box::from(parser::Expression::Integer(Integer {
-                                    value: 1,
-                                })),
-                            },
+                            array_value(test_helper::generate_integer_expression(0, 1, 2)),
+                            array_value(test_helper::generate_integer_expression(1, 1, 5)),
                         ],
-                        location: (1, 7),
+                        span: span(1, 1, 6),
                     })),
-                    index: parser::Expression::Integer(Integer { value: 0 }),
-                    value: parser::Expression::Integer(Integer { value: 300 }),
+                    index: test_helper::generate_integer_expression(0, 1, 8),
+                    value: test_helper::generate_integer_expression(300, 1, 13),
+                    span: span(1, 1, 15),
                 },
-            ))),
-        })));
+            )),
+        )];
 
         test_parser(input, expected);
     }
@@ -823,6 +805,85 @@ mod tests {
         test_parser(input, expected);
     }
 
+    #[test]
+    fn spans() {
+        let l = lexer::build_lexer("x := 10 + foo;\n  bar(1, 2)");
+        let mut parser = parser::build_parser(l, "main.loop");
+
+        let program = parser.parse().unwrap();
+
+        let declaration = &program.statements[0];
+        assert_span(&declaration.span(), (1, 1), (1, 13));
+        assert_eq!(declaration.span().file, "main.loop");
+
+        if let Statement::VariableDeclaration(declaration) = declaration {
+            assert_span(&declaration.ident.span, (1, 1), (1, 1));
+            assert_span(&declaration.value.span(), (1, 6), (1, 13));
+
+            if let parser::Expression::Suffix(suffix) = &*declaration.value {
+                assert_span(&suffix.left.span(), (1, 6), (1, 7));
+                assert_span(&suffix.right.span(), (1, 11), (1, 13));
+            } else {
+                panic!("expected a suffix expression")
+            }
+        } else {
+            panic!("expected a variable declaration")
+        }
+
+        assert_span(&program.statements[1].span(), (2, 3), (2, 11));
+    }
+
+    #[test]
+    fn syntax_exception_span() {
+        let l = lexer::build_lexer("x := 1\ny := )");
+        let mut parser = parser::build_parser(l, "");
+
+        let error = parser.parse().err().unwrap();
+
+        assert_span(&error.span, (2, 6), (2, 6));
+    }
+
+    #[test]
+    fn syntax_exception_recovery() {
+        let l = lexer::build_lexer("x := )\ny := 1\nf := fn() {\n  a := ;\n}\nz := (1 +;\nw := 2");
+        let mut parser = parser::build_parser(l, "");
+
+        let error = parser.parse().err().unwrap();
+
+        assert_span(&error.span, (1, 6), (1, 6));
+        assert_eq!(parser.errors.len(), 3);
+        assert_span(&parser.errors[1].span, (4, 8), (4, 8));
+        assert_span(&parser.errors[2].span, (6, 10), (6, 10));
+    }
+
+    #[test]
+    fn syntax_exception_unknown_type() {
+        let l = lexer::build_lexer("foo bar\nconst baz qux := 1");
+        let mut parser = parser::build_parser(l, "");
+
+        let error = parser.parse().err().unwrap();
+
+        assert_eq!(error.pretty_print(), "unknown type: foo");
+        assert_span(&error.span, (1, 1), (1, 3));
+        assert_eq!(parser.errors.len(), 2);
+        assert_eq!(parser.errors[1].pretty_print(), "unknown type: baz");
+    }
+
+    #[test]
+    fn syntax_exception_max_errors() {
+        let l = lexer::build_lexer("a := )\nb := )\nc := )");
+        let mut parser = parser::build_parser(l, "");
+        parser.max_errors = 2;
+
+        assert!(parser.parse().is_err());
+        assert_eq!(parser.errors.len(), 2);
+    }
+
+    fn assert_span(span: &Span, start: (i32, i32), end: (i32, i32)) {
+        assert_eq!((span.start.line, span.start.colon), start);
+        assert_eq!((span.end.line, span.end.colon), end);
+    }
+
     fn test_parser(input: &str, expected: Vec<Statement>) {
         let l = lexer::build_lexer(input);
         let mut parser = parser::build_parser(l, "");
@@ -831,19 +892,17 @@ mod tests {
 
         if !parser.errors.is_empty() {
             for err in parser.errors {
-                if let Exception::Syntax(err) = err {
-                    println!("ParserException: {}", err);
-                }
+                println!("ParserException: {}", err);
             }
 
             panic!("Parser exceptions occurred!")
         }
 
-        let mut i = 0;
-        for statement in program.statements {
-            assert_eq!(statement, expected[i]);
+        // Spans are part of the comparison, so every node has to be at the expected position
+        assert_eq!(program.statements.len(), expected.len());
 
-            i += 1;
+        for (i, statement) in program.statements.into_iter().enumerate() {
+            assert_eq!(statement, expected[i]);
         }
     }
 }
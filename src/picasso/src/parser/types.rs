@@ -57,6 +57,8 @@ pub enum Types {
     Library(Library),
     Compound(Compound),
     Module(String),
+    // Key & Value types
+    Hashmap(Box<Types>, Box<Types>),
     Void,
     Auto,
 }
@@ -87,8 +89,9 @@ impl Display for Types {
                     Types::Library(lib) => format!("LIBRARY {{{:?}}}", lib.methods),
                     Types::Compound(Compound(tp, _)) => tp,
                     Types::Module(m) => m,
+                    Types::Hashmap(key, value) => format!("{{{}: {}}}[]", key, value),
                 },
-                Types::Auto => "Variant".to_string(),
+                Types::Auto => "var".to_string(),
                 // TODO: Should probably be different now we know types
                 Types::Function(func) => {
                     let mut args = String::new();
@@ -96,7 +99,7 @@ impl Display for Types {
                     let mut index = 0;
                     for parameter_type in &func.parameter_types {
                         index += 1;
-                        args.push_str(&*parameter_type.transpile());
+                        args.push_str(&parameter_type.transpile());
 
                         if index != func.parameter_types.len() {
                             args.push(',');
@@ -109,12 +112,23 @@ impl Display for Types {
                 Types::Void => "void".to_string(),
                 Types::Library(lib) => format!("LIBRARY {{{:?}}}", lib.methods),
                 Types::Module(m) => m.clone(),
+                Types::Hashmap(key, value) => format!("{{{}: {}}}", key, value),
             }
         )
     }
 }
 
 impl Types {
+    /// Whether values of this type hold floats, also when they are in an array or hashmap
+    pub fn contains_float(&self) -> bool {
+        match self {
+            Types::Basic(BaseTypes::Float) => true,
+            Types::Array(tp) => tp.contains_float(),
+            Types::Hashmap(key, value) => key.contains_float() || value.contains_float(),
+            _ => false,
+        }
+    }
+
     pub fn transpile(&self) -> String {
         match self {
             Types::Basic(basic) => match basic {
@@ -125,8 +139,9 @@ impl Types {
                 BaseTypes::UserDefined(s) => s.to_string(),
             },
             Types::Array(array) => match *array.clone() {
+                // Strings are arrays of characters themselves
                 Types::Basic(basic) => {
-                    format!("{}[]", basic.transpile())
+                    format!("{}[]", Types::Basic(basic).transpile())
                 }
                 Types::Array(array) => {
                     format!("{}[][]", array.transpile())
@@ -137,14 +152,21 @@ impl Types {
                 Types::Library(lib) => format!("LIBRARY {{{:?}}}", lib.methods),
                 Types::Compound(Compound(tp, _)) => tp,
                 Types::Module(m) => m,
+                Types::Hashmap(key, value) => {
+                    format!("HASHMAP[{},{}][]", key.transpile(), value.transpile())
+                }
             },
-            Types::Auto => "Variant".to_string(),
+            // The backend does not need to know the type of values that are only known at runtime
+            Types::Auto => "VOID".to_string(),
             // TODO: Should probably be different now we know types
             Types::Function(_) => "VOID".to_string(),
             Types::Void => "VOID".to_string(),
             Types::Library(lib) => format!("LIBRARY {{{:?}}}", lib.methods),
             Types::Compound(Compound(tp, _)) => tp.clone(),
             Types::Module(m) => m.to_string(),
+            Types::Hashmap(key, value) => {
+                format!("HASHMAP[{},{}]", key.transpile(), value.transpile())
+            }
         }
     }
 }
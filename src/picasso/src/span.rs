@@ -0,0 +1,63 @@
+//! Positions in the source code, used to point at the code that caused an error
+use std::fmt::{Display, Formatter};
+use vinci::diagnostic::DiagnosticSpan;
+
+/// A position in a file, both the line and the column start counting at 1
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+pub struct Location {
+    pub line: i32,
+    pub colon: i32,
+}
+
+/// The part of a file a token or node was parsed from, `end` is the last character that is part
+/// of it
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+pub struct Span {
+    pub file: String,
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Span {
+    pub fn new(file: &str, start: Location, end: Location) -> Span {
+        Span {
+            file: file.to_string(),
+            start,
+            end,
+        }
+    }
+
+    /// Returns a span starting at the start of this span and ending at the end of `other`
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            file: self.file.clone(),
+            start: self.start.clone(),
+            end: other.end.clone(),
+        }
+    }
+
+    /// Whether the span points to an actual place in a file, nodes created by the compiler itself
+    /// have a span with line 0
+    pub fn is_known(&self) -> bool {
+        self.start.line > 0
+    }
+
+    /// Converts the span into the span of a diagnostic, `text` is the line of code it starts on
+    pub fn diagnostic(&self, text: Option<String>) -> DiagnosticSpan {
+        DiagnosticSpan {
+            file: self.file.clone(),
+            line_start: self.start.line,
+            column_start: self.start.colon,
+            line_end: self.end.line,
+            column_end: self.end.colon,
+            label: None,
+            text,
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.start.line, self.start.colon)
+    }
+}
@@ -0,0 +1,59 @@
+//! The standard library of Loop, written in Loop and compiled into the binary. It is imported with
+//! `import "std" as std` from any directory.
+//!
+//! Functions that can not be written in Loop itself call an intrinsic, eg. `intrinsic.println(value)`,
+//! which is provided by the backend. Intrinsics can only be called from inside of the standard
+//! library.
+use crate::parser::types::{BaseTypes, Types};
+
+/// The path that imports the standard library
+pub const STD_PATH: &str = "std";
+
+/// The location the standard library is compiled in, it can not be a path to an actual file
+pub const STD_LOCATION: &str = "<std>";
+
+/// Every file of the standard library, with their name used in errors
+pub const STD_FILES: [(&str, &str); 5] = [
+    ("<std>/io.loop", include_str!("io.loop")),
+    ("<std>/strings.loop", include_str!("strings.loop")),
+    ("<std>/math.loop", include_str!("math.loop")),
+    ("<std>/arrays.loop", include_str!("arrays.loop")),
+    ("<std>/time.loop", include_str!("time.loop")),
+];
+
+/// The parameter types and return type of an intrinsic, [None] if it does not exist
+pub fn intrinsic(name: &str) -> Option<(Vec<Types>, Types)> {
+    let int = || Types::Basic(BaseTypes::Integer);
+    let float = || Types::Basic(BaseTypes::Float);
+    let string = || Types::Basic(BaseTypes::String);
+    let boolean = || Types::Basic(BaseTypes::Boolean);
+
+    let signature = match name {
+        // io
+        "print" | "println" => (vec![Types::Auto], Types::Void),
+        "read_line" => (vec![], string()),
+        // strings
+        "to_string" => (vec![Types::Auto], string()),
+        "parse_int" => (vec![string()], int()),
+        "parse_float" => (vec![string()], float()),
+        "is_int" | "is_float" => (vec![string()], boolean()),
+        "upper" | "lower" | "trim" => (vec![string()], string()),
+        "find" => (vec![string(), string()], int()),
+        "replace" => (vec![string(), string(), string()], string()),
+        "split" => (vec![string(), string()], Types::Array(Box::new(string()))),
+        "repeat" => (vec![string(), int()], string()),
+        // arrays
+        "join" => (vec![Types::Array(Box::new(string())), string()], string()),
+        // math
+        "sqrt" | "sin" | "cos" | "tan" | "log" => (vec![float()], float()),
+        "pow" => (vec![float(), float()], float()),
+        "floor" | "ceil" | "round" => (vec![float()], int()),
+        "random" => (vec![], float()),
+        // time
+        "now" => (vec![], float()),
+        "sleep" => (vec![int()], Types::Void),
+        _ => return None,
+    };
+
+    Some(signature)
+}
@@ -0,0 +1,62 @@
+//! The methods built into arrays, each is a Lua function that gets the array as its first argument.
+//! They loop over the array by position, functions that are given are called once per element.
+
+/// Metatable that lets values be pushed to an array, it is defined once as `loop_array_metatable`
+/// before the methods
+pub const ARRAY_METATABLE: &str = "{ __concat = function(a, b) return table.insert(a, b) end }";
+
+/// An empty array that can be pushed to, like the ones the compiled code creates
+pub const ARRAY: &str = "setmetatable({}, loop_array_metatable)";
+
+/// The Lua function implementing a method, [None] if arrays do not have it
+pub fn array_method(name: &str) -> Option<String> {
+    let function = match name {
+        "map" => format!(
+            "function(a, f) local r = {} for i = 1, #a do r[i] = f(a[i]) end return r end",
+            ARRAY
+        ),
+        "filter" => format!(
+            "function(a, f) local r, n = {}, 0 \
+            for i = 1, #a do local v = a[i] if f(v) then n = n + 1 r[n] = v end end return r end",
+            ARRAY
+        ),
+        // Without a starting value the first element is used
+        "reduce" | "fold" => "function(a, f, acc) local start = 1 \
+            if acc == nil then acc, start = a[1], 2 end \
+            for i = start, #a do acc = f(acc, a[i]) end return acc end"
+            .to_string(),
+        // A copy is sorted, without a comparator the elements are sorted from low to high
+        "sort" => format!(
+            "function(a, f) local r = {} for i = 1, #a do r[i] = a[i] end table.sort(r, f) return r end",
+            ARRAY
+        ),
+        "reverse" => format!(
+            "function(a) local r, n = {}, #a for i = 1, n do r[i] = a[n - i + 1] end return r end",
+            ARRAY
+        ),
+        "find" => "function(a, f) for i = 1, #a do if f(a[i]) then return a[i] end end return nil end"
+            .to_string(),
+        "index_of" => "function(a, v) for i = 1, #a do if a[i] == v then return i - 1 end end return -1 end"
+            .to_string(),
+        "contains" => "function(a, v) for i = 1, #a do if a[i] == v then return true end end return false end"
+            .to_string(),
+        "any" => "function(a, f) for i = 1, #a do if f(a[i]) then return true end end return false end"
+            .to_string(),
+        "all" => "function(a, f) for i = 1, #a do if not f(a[i]) then return false end end return true end"
+            .to_string(),
+        "concat" => format!(
+            "function(a, b) local r, n = {}, #a \
+            for i = 1, n do r[i] = a[i] end for i = 1, #b do r[n + i] = b[i] end return r end",
+            ARRAY
+        ),
+        "flatten" => format!(
+            "function(a) local r, n = {}, 0 \
+            for i = 1, #a do local inner = a[i] for j = 1, #inner do n = n + 1 r[n] = inner[j] end end return r end",
+            ARRAY
+        ),
+        "join" => "function(a, s) return table.concat(a, s) end".to_string(),
+        _ => return None,
+    };
+
+    Some(function)
+}
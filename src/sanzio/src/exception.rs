@@ -1,19 +1,79 @@
-use colored::*;
-
-pub fn throw_runtime_exception(message: String, extra_message: Option<String>) {
-    println!("{}", "RuntimeError -->".bright_red());
-    println!(
-        "\t{}: {}",
-        "Message".bright_blue(),
-        format!("{}\n", message).bright_white()
-    );
-
-    if extra_message.is_some() {
-        println!(
-            "\t{}: {}",
-            "Note".bright_blue(),
-            format!("{}\n", extra_message.unwrap()).bright_white()
-        );
+use std::fmt::{Display, Formatter};
+use vinci::ast::instructions::location::Location;
+use vinci::diagnostic::Diagnostic;
+
+/// Why a program could not be run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeError {
+    /// The Arc could not be turned into Lua, eg. a method or library that does not exist
+    Generation(String),
+    /// Lua failed while running, positions in the message and traceback point at the Loop code
+    Lua {
+        message: String,
+        traceback: Option<String>,
+        // Where in the Loop code it failed, when the source map knows
+        location: Option<Location>,
+    },
+    /// The interpreter failed while running, the message and traceback point at the Loop code
+    Interpreter {
+        message: String,
+        traceback: Option<String>,
+        location: Option<Location>,
+    },
+}
+
+impl RuntimeError {
+    /// Prints the error as a diagnostic, without stopping the program
+    pub fn report(&self) {
+        match self {
+            RuntimeError::Generation(message) => report_runtime_exception(message.clone(), None),
+            RuntimeError::Lua {
+                message,
+                traceback,
+                location,
+            }
+            | RuntimeError::Interpreter {
+                message,
+                traceback,
+                location,
+            } => {
+                let mut diagnostic = runtime_diagnostic(message.clone(), traceback.clone());
+
+                if let Some(location) = location {
+                    diagnostic =
+                        diagnostic.with_span(location.diagnostic_span(location.source_line()));
+                }
+
+                diagnostic.emit();
+            }
+        }
+    }
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeError::Generation(message) => write!(f, "{}", message),
+            RuntimeError::Lua { message, .. } | RuntimeError::Interpreter { message, .. } => {
+                write!(f, "{}", message)
+            }
+        }
     }
-    std::process::exit(1)
+}
+
+pub fn report_runtime_exception(message: String, extra_message: Option<String>) {
+    runtime_diagnostic(message, extra_message).emit();
+}
+
+fn runtime_diagnostic(message: String, extra_message: Option<String>) -> Diagnostic {
+    let diagnostic = Diagnostic::error("runtime::error", message);
+
+    match extra_message {
+        Some(extra_message) => diagnostic.with_note(extra_message),
+        None => diagnostic,
+    }
+}
+
+pub fn throw_runtime_warning(message: String) {
+    Diagnostic::warning("runtime::warning", message).emit();
 }
@@ -0,0 +1,171 @@
+//! The methods built into arrays, the array is the first argument. They behave like the ones the
+//! Lua backend generates, functions that are given are called once per element.
+use crate::interpreter::operations;
+use crate::interpreter::strings::{chars, join};
+use crate::interpreter::value::Value;
+use crate::interpreter::{argument, Interpreter, Result};
+use std::cmp::Ordering;
+
+pub fn call(interpreter: &mut Interpreter, method: &str, arguments: &[Value]) -> Result<Value> {
+    let array = interpreter.array_argument(method, arguments, 0)?;
+    let function = argument(arguments, 1);
+
+    let value = match method {
+        "map" => Value::array(
+            array
+                .into_iter()
+                .map(|value| interpreter.call(&function, vec![value]))
+                .collect::<Result<Vec<Value>>>()?,
+        ),
+        "filter" => {
+            let mut filtered = vec![];
+            for value in array {
+                if interpreter
+                    .call(&function, vec![value.clone()])?
+                    .is_truthy()
+                {
+                    filtered.push(value);
+                }
+            }
+
+            Value::array(filtered)
+        }
+        // Without a starting value the first element is used
+        "reduce" | "fold" => {
+            let mut values = array.into_iter();
+            let mut accumulator = match argument(arguments, 2) {
+                Value::Null => values.next().unwrap_or(Value::Null),
+                accumulator => accumulator,
+            };
+
+            for value in values {
+                accumulator = interpreter.call(&function, vec![accumulator, value])?;
+            }
+
+            accumulator
+        }
+        // A copy is sorted, without a comparator the elements are sorted from low to high
+        "sort" => Value::array(sort(interpreter, array, &function)?),
+        "reverse" => Value::array(array.into_iter().rev().collect()),
+        "find" => {
+            for value in array {
+                if interpreter
+                    .call(&function, vec![value.clone()])?
+                    .is_truthy()
+                {
+                    return Ok(value);
+                }
+            }
+
+            Value::Null
+        }
+        "index_of" => Value::Integer(
+            array
+                .iter()
+                .position(|value| value.equals(&function))
+                .map_or(-1, |index| index as i64),
+        ),
+        "contains" => Value::Boolean(array.iter().any(|value| value.equals(&function))),
+        "any" => {
+            for value in array {
+                if interpreter.call(&function, vec![value])?.is_truthy() {
+                    return Ok(Value::Boolean(true));
+                }
+            }
+
+            Value::Boolean(false)
+        }
+        "all" => {
+            for value in array {
+                if !interpreter.call(&function, vec![value])?.is_truthy() {
+                    return Ok(Value::Boolean(false));
+                }
+            }
+
+            Value::Boolean(true)
+        }
+        "concat" => {
+            let mut concatenated = array;
+            concatenated.extend(interpreter.array_argument(method, arguments, 1)?);
+
+            Value::array(concatenated)
+        }
+        "flatten" => {
+            let mut flattened = vec![];
+            for value in array {
+                match value {
+                    Value::Array(inner) => flattened.extend(inner.borrow().iter().cloned()),
+                    Value::String(string) => {
+                        if let Value::Array(inner) = chars(&string) {
+                            flattened.extend(inner.borrow().iter().cloned())
+                        }
+                    }
+                    value => {
+                        return Err(
+                            interpreter.error(format!("can not flatten {}", value.describe()))
+                        )
+                    }
+                }
+            }
+
+            Value::array(flattened)
+        }
+        "join" => {
+            let separator = interpreter.string_argument(method, arguments, 1)?;
+            Value::string(&join(interpreter, &array, &separator)?)
+        }
+        _ => {
+            return Err(
+                interpreter.error(format!("arrays do not have a method called \"{}\"", method))
+            )
+        }
+    };
+
+    Ok(value)
+}
+
+/// A merge sort, so a comparator that does not give a consistent order can not make it fail
+fn sort(
+    interpreter: &mut Interpreter,
+    values: Vec<Value>,
+    comparator: &Value,
+) -> Result<Vec<Value>> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+
+    let mut left = values;
+    let right = left.split_off(left.len() / 2);
+    let left = sort(interpreter, left, comparator)?;
+    let right = sort(interpreter, right, comparator)?;
+
+    let mut sorted = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // Elements of the left half go first when they are equal, which keeps the sort stable
+        if less(interpreter, b, a, comparator)? {
+            sorted.extend(right.next());
+        } else {
+            sorted.extend(left.next());
+        }
+    }
+
+    sorted.extend(left);
+    sorted.extend(right);
+
+    Ok(sorted)
+}
+
+fn less(interpreter: &mut Interpreter, a: &Value, b: &Value, comparator: &Value) -> Result<bool> {
+    if let Value::Null = comparator {
+        return operations::compare(a, b)
+            .map(|ordering| ordering == Some(Ordering::Less))
+            .map_err(|e| interpreter.error(e));
+    }
+
+    Ok(interpreter
+        .call(comparator, vec![a.clone(), b.clone()])?
+        .is_truthy())
+}
@@ -0,0 +1,468 @@
+//! Calls the functions of C libraries. The declarations a library gives are parsed to know the
+//! types of the parameters and the return value of every function, like "ffi.cdef" does for the Lua
+//! backend.
+//!
+//! Unlike LuaJIT there is no code generated for every signature, functions are called through a
+//! fixed set of function pointer types. Libraries with functions it can not call are rejected when
+//! they are loaded, which are functions with:
+//! - more than [MAX_PARAMETERS] parameters
+//! - "float" parameters, "double" is supported
+//! - a variable amount of parameters, eg: "int printf(const char *format, ...)"
+use crate::interpreter::value::Value;
+use std::collections::HashMap;
+use std::ffi::CString;
+
+/// The most parameters a function of a library can have
+pub const MAX_PARAMETERS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CType {
+    Integer { size: usize, signed: bool },
+    Bool,
+    Float,
+    Double,
+    // "char*", which is converted from and to a string
+    String,
+    Pointer,
+    Void,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Signature {
+    pub parameters: Vec<CType>,
+    pub returns: CType,
+}
+
+pub struct Library {
+    #[cfg(feature = "libloading")]
+    library: libloading::Library,
+    functions: HashMap<String, Signature>,
+}
+
+impl Library {
+    pub fn load(path: &str) -> Result<Library, String> {
+        #[cfg(feature = "libloading")]
+        let (library, signatures) = crate::library::load_library(path)?;
+        #[cfg(not(feature = "libloading"))]
+        let signatures = crate::library::library_signatures(path)?;
+
+        Ok(Library {
+            #[cfg(feature = "libloading")]
+            library,
+            functions: parse_signatures(&signatures)?,
+        })
+    }
+
+    pub fn call(&self, name: &str, arguments: &[Value]) -> Result<Value, String> {
+        let signature = match self.functions.get(name) {
+            Some(signature) => signature,
+            None => {
+                return Err(format!(
+                    "the library does not have a function called \"{}\"",
+                    name
+                ))
+            }
+        };
+
+        // The strings have to live until the function returns
+        let mut strings = Vec::new();
+        let mut converted = Vec::with_capacity(signature.parameters.len());
+
+        for (index, parameter) in signature.parameters.iter().enumerate() {
+            let value = arguments.get(index).cloned().unwrap_or(Value::Null);
+
+            let argument = match (parameter, &value) {
+                (CType::Double, value) if value.as_number().is_some() => {
+                    Argument::Double(value.as_number().unwrap_or_default())
+                }
+                (CType::Integer { .. } | CType::Pointer, Value::Integer(integer)) => {
+                    Argument::Integer(*integer)
+                }
+                (CType::Integer { .. }, Value::Float(float)) => Argument::Integer(*float as i64),
+                (CType::Bool, value) => Argument::Integer(value.is_truthy() as i64),
+                (CType::String | CType::Pointer, Value::Null) => Argument::Integer(0),
+                (CType::String, Value::String(string)) => {
+                    let string = CString::new(string.as_bytes()).map_err(|e| format!("{}", e))?;
+                    let argument = Argument::Integer(string.as_ptr() as i64);
+                    strings.push(string);
+
+                    argument
+                }
+                (parameter, value) => {
+                    return Err(format!(
+                        "bad argument #{} to \"{}\" (expected {}, got {})",
+                        index + 1,
+                        name,
+                        parameter.describe(),
+                        value.describe()
+                    ))
+                }
+            };
+
+            converted.push(argument);
+        }
+
+        #[cfg(feature = "libloading")]
+        let result = self.invoke(name, signature.returns, &converted);
+        #[cfg(not(feature = "libloading"))]
+        let result = Err(String::from("Loading of dynamic libaries is not enabled"));
+
+        drop(strings);
+
+        result
+    }
+
+    #[cfg(feature = "libloading")]
+    fn invoke(&self, name: &str, returns: CType, arguments: &[Argument]) -> Result<Value, String> {
+        use std::ffi::CStr;
+        use std::os::raw::{c_char, c_void};
+
+        let address = unsafe {
+            *self
+                .library
+                .get::<*mut c_void>(name.as_bytes())
+                .map_err(|e| format!("{}", e))?
+        };
+
+        let value = unsafe {
+            match returns {
+                CType::Double => Value::Float(call::<f64>(address, arguments)?),
+                CType::Float => Value::Float(call::<f32>(address, arguments)? as f64),
+                CType::Void => {
+                    call::<i64>(address, arguments)?;
+                    Value::Null
+                }
+                returns => {
+                    let result = call::<i64>(address, arguments)?;
+
+                    match returns {
+                        CType::Bool => Value::Boolean(result as u8 != 0),
+                        CType::Integer { size, signed } => {
+                            Value::Integer(truncate(result, size, signed))
+                        }
+                        CType::String if result == 0 => Value::Null,
+                        CType::String => Value::string(
+                            &CStr::from_ptr(result as *const c_char).to_string_lossy(),
+                        ),
+                        _ => Value::Integer(result),
+                    }
+                }
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+impl CType {
+    fn describe(&self) -> &str {
+        match self {
+            CType::Integer { .. } => "an int",
+            CType::Bool => "a bool",
+            CType::Float | CType::Double => "a float",
+            CType::String => "a string",
+            CType::Pointer => "a pointer",
+            CType::Void => "nothing",
+        }
+    }
+}
+
+/// Parses C declarations like "int add(int a, int b);" into the signatures of the functions
+pub fn parse_signatures(declarations: &str) -> Result<HashMap<String, Signature>, String> {
+    let mut functions = HashMap::new();
+
+    for declaration in declarations.split(';').map(str::trim) {
+        if declaration.is_empty() {
+            continue;
+        }
+
+        let invalid = || format!("can not parse the declaration \"{}\"", declaration);
+
+        let (head, parameters) = declaration.split_once('(').ok_or_else(invalid)?;
+        let parameters = parameters
+            .trim_end()
+            .strip_suffix(')')
+            .ok_or_else(invalid)?;
+
+        let head = head.replace('*', " * ");
+        let mut words: Vec<&str> = head.split_whitespace().collect();
+        let name = words.pop().ok_or_else(invalid)?;
+        let returns = parse_type(&words).ok_or_else(invalid)?;
+
+        let parameters = match parameters.trim() {
+            "" | "void" => vec![],
+            parameters => parameters
+                .split(',')
+                .map(|parameter| parse_parameter(parameter, name))
+                .collect::<Result<Vec<CType>, String>>()?,
+        };
+
+        if parameters.len() > MAX_PARAMETERS {
+            return Err(format!(
+                "\"{}\" has {} parameters, functions of libraries can have at most {}",
+                name,
+                parameters.len(),
+                MAX_PARAMETERS
+            ));
+        }
+
+        functions.insert(
+            name.to_string(),
+            Signature {
+                parameters,
+                returns,
+            },
+        );
+    }
+
+    Ok(functions)
+}
+
+fn parse_parameter(parameter: &str, function: &str) -> Result<CType, String> {
+    let parameter = parameter.replace('*', " * ");
+    let mut words: Vec<&str> = parameter.split_whitespace().collect();
+
+    if words == ["..."] {
+        return Err(format!(
+            "\"{}\" has a variable amount of parameters, which is not supported",
+            function
+        ));
+    }
+
+    // The name of a parameter is optional
+    if words.len() > 1 && !is_type_word(words[words.len() - 1]) {
+        words.pop();
+    }
+
+    match parse_type(&words) {
+        Some(CType::Float) => Err(format!(
+            "\"{}\" has a float parameter, which is not supported",
+            function
+        )),
+        Some(CType::Void) | None => Err(format!(
+            "can not parse the parameter \"{}\" of \"{}\"",
+            parameter.trim(),
+            function
+        )),
+        Some(parameter) => Ok(parameter),
+    }
+}
+
+fn is_type_word(word: &str) -> bool {
+    matches!(
+        word,
+        "*" | "void"
+            | "bool"
+            | "_Bool"
+            | "char"
+            | "short"
+            | "int"
+            | "long"
+            | "float"
+            | "double"
+            | "signed"
+            | "unsigned"
+    ) || word.ends_with("_t")
+}
+
+fn parse_type(words: &[&str]) -> Option<CType> {
+    let words: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|word| !matches!(*word, "const" | "volatile" | "extern" | "static" | "inline"))
+        .collect();
+
+    let pointers = words.iter().filter(|word| **word == "*").count();
+    let words: Vec<&str> = words.into_iter().filter(|word| *word != "*").collect();
+
+    if pointers > 0 {
+        return Some(if pointers == 1 && words.contains(&"char") {
+            CType::String
+        } else {
+            CType::Pointer
+        });
+    }
+
+    let signed = !words.contains(&"unsigned");
+    let longs = words.iter().filter(|word| **word == "long").count();
+
+    let ctype = match words
+        .iter()
+        .find(|word| !matches!(**word, "signed" | "unsigned" | "long"))
+    {
+        Some(&"void") => CType::Void,
+        Some(&"bool") | Some(&"_Bool") => CType::Bool,
+        Some(&"float") => CType::Float,
+        Some(&"double") => CType::Double,
+        Some(&"char") | Some(&"int8_t") => CType::Integer { size: 1, signed },
+        Some(&"uint8_t") => CType::Integer {
+            size: 1,
+            signed: false,
+        },
+        Some(&"short") | Some(&"int16_t") => CType::Integer { size: 2, signed },
+        Some(&"uint16_t") => CType::Integer {
+            size: 2,
+            signed: false,
+        },
+        Some(&"int32_t") => CType::Integer { size: 4, signed },
+        Some(&"uint32_t") => CType::Integer {
+            size: 4,
+            signed: false,
+        },
+        Some(&"int64_t") | Some(&"intptr_t") | Some(&"ssize_t") => {
+            CType::Integer { size: 8, signed }
+        }
+        Some(&"uint64_t") | Some(&"uintptr_t") | Some(&"size_t") => CType::Integer {
+            size: 8,
+            signed: false,
+        },
+        // "long" is as big as an int on Windows
+        Some(&"int") | None if longs == 1 && !cfg!(windows) => CType::Integer { size: 8, signed },
+        Some(&"int") | None if longs >= 1 => CType::Integer {
+            size: if longs == 1 { 4 } else { 8 },
+            signed,
+        },
+        Some(&"int") if longs == 0 => CType::Integer { size: 4, signed },
+        None if words.is_empty() => return None,
+        None => CType::Integer { size: 4, signed },
+        _ => return None,
+    };
+
+    Some(ctype)
+}
+
+/// A value returned in a register, of which only the lower bytes belong to a smaller type
+#[cfg(feature = "libloading")]
+fn truncate(value: i64, size: usize, signed: bool) -> i64 {
+    match (size, signed) {
+        (1, true) => value as i8 as i64,
+        (1, false) => value as u8 as i64,
+        (2, true) => value as i16 as i64,
+        (2, false) => value as u16 as i64,
+        (4, true) => value as i32 as i64,
+        (4, false) => value as u32 as i64,
+        _ => value,
+    }
+}
+
+/// Ints, bools and pointers are passed in the same registers, so only doubles are different
+#[cfg_attr(not(feature = "libloading"), allow(dead_code))]
+enum Argument {
+    Integer(i64),
+    Double(f64),
+}
+
+/// Calls a function with any combination of up to [MAX_PARAMETERS] arguments
+#[cfg(feature = "libloading")]
+unsafe fn call<R>(address: *mut std::os::raw::c_void, arguments: &[Argument]) -> Result<R, String> {
+    use std::mem::transmute;
+    use std::os::raw::c_void;
+    use Argument::{Double as D, Integer as I};
+
+    let result =
+        match arguments {
+            [] => transmute::<*mut c_void, extern "C" fn() -> R>(address)(),
+            [I(a)] => transmute::<*mut c_void, extern "C" fn(i64) -> R>(address)(*a),
+            [D(a)] => transmute::<*mut c_void, extern "C" fn(f64) -> R>(address)(*a),
+            [I(a), I(b)] => transmute::<*mut c_void, extern "C" fn(i64, i64) -> R>(address)(*a, *b),
+            [I(a), D(b)] => transmute::<*mut c_void, extern "C" fn(i64, f64) -> R>(address)(*a, *b),
+            [D(a), I(b)] => transmute::<*mut c_void, extern "C" fn(f64, i64) -> R>(address)(*a, *b),
+            [D(a), D(b)] => transmute::<*mut c_void, extern "C" fn(f64, f64) -> R>(address)(*a, *b),
+            [I(a), I(b), I(c)] => {
+                transmute::<*mut c_void, extern "C" fn(i64, i64, i64) -> R>(address)(*a, *b, *c)
+            }
+            [I(a), I(b), D(c)] => {
+                transmute::<*mut c_void, extern "C" fn(i64, i64, f64) -> R>(address)(*a, *b, *c)
+            }
+            [I(a), D(b), I(c)] => {
+                transmute::<*mut c_void, extern "C" fn(i64, f64, i64) -> R>(address)(*a, *b, *c)
+            }
+            [I(a), D(b), D(c)] => {
+                transmute::<*mut c_void, extern "C" fn(i64, f64, f64) -> R>(address)(*a, *b, *c)
+            }
+            [D(a), I(b), I(c)] => {
+                transmute::<*mut c_void, extern "C" fn(f64, i64, i64) -> R>(address)(*a, *b, *c)
+            }
+            [D(a), I(b), D(c)] => {
+                transmute::<*mut c_void, extern "C" fn(f64, i64, f64) -> R>(address)(*a, *b, *c)
+            }
+            [D(a), D(b), I(c)] => {
+                transmute::<*mut c_void, extern "C" fn(f64, f64, i64) -> R>(address)(*a, *b, *c)
+            }
+            [D(a), D(b), D(c)] => {
+                transmute::<*mut c_void, extern "C" fn(f64, f64, f64) -> R>(address)(*a, *b, *c)
+            }
+            [I(a), I(b), I(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, i64, i64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), I(b), I(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, i64, i64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), I(b), D(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, i64, f64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), I(b), D(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, i64, f64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), D(b), I(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, f64, i64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), D(b), I(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, f64, i64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), D(b), D(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, f64, f64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [I(a), D(b), D(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(i64, f64, f64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), I(b), I(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, i64, i64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), I(b), I(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, i64, i64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), I(b), D(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, i64, f64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), I(b), D(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, i64, f64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), D(b), I(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, f64, i64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), D(b), I(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, f64, i64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), D(b), D(c), I(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, f64, f64, i64) -> R,
+            >(address)(*a, *b, *c, *d),
+            [D(a), D(b), D(c), D(d)] => transmute::<
+                *mut c_void,
+                extern "C" fn(f64, f64, f64, f64) -> R,
+            >(address)(*a, *b, *c, *d),
+            _ => {
+                return Err(format!(
+                    "functions of libraries can have at most {} parameters",
+                    MAX_PARAMETERS
+                ))
+            }
+        };
+
+    Ok(result)
+}
@@ -0,0 +1,142 @@
+//! The functions the standard library of Loop can not write in Loop itself, called like
+//! `intrinsic::println`. They behave like the ones registered in Lua.
+use crate::interpreter::strings::{chars, join};
+use crate::interpreter::value::Value;
+use crate::interpreter::{argument, Interpreter, Result};
+use crate::{format_with_type, write_output};
+use std::io::{self, BufRead};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub fn call(interpreter: &mut Interpreter, name: &str, arguments: &[Value]) -> Result<Value> {
+    let value = match name {
+        // io
+        "print" => {
+            write_output(&interpreter.format_value(&argument(arguments, 0)));
+
+            Value::Null
+        }
+        "println" => {
+            write_output(&format!(
+                "{}\n",
+                interpreter.format_value(&argument(arguments, 0))
+            ));
+
+            Value::Null
+        }
+        // Used by the compiler for the value a program returns when it holds floats
+        "show" => {
+            let value = interpreter.value_type(&argument(arguments, 0));
+            let tp = interpreter.string_argument(name, arguments, 1)?;
+
+            Value::string(&format_with_type(value, &tp))
+        }
+        "read_line" => {
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line).ok();
+
+            Value::string(line.trim_end_matches(&['\r', '\n'][..]))
+        }
+
+        // strings
+        "to_string" => Value::string(&interpreter.format_value(&argument(arguments, 0))),
+        // A string that is not a number becomes 0, "is_int" tells whether it is one
+        "parse_int" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            Value::Integer(text.trim().parse::<i64>().unwrap_or(0))
+        }
+        "parse_float" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            Value::Float(text.trim().parse::<f64>().unwrap_or(0.0))
+        }
+        "is_int" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            Value::Boolean(text.trim().parse::<i64>().is_ok())
+        }
+        "is_float" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            Value::Boolean(text.trim().parse::<f64>().is_ok())
+        }
+        "upper" => Value::string(
+            &interpreter
+                .string_argument(name, arguments, 0)?
+                .to_uppercase(),
+        ),
+        "lower" => Value::string(
+            &interpreter
+                .string_argument(name, arguments, 0)?
+                .to_lowercase(),
+        ),
+        "trim" => Value::string(interpreter.string_argument(name, arguments, 0)?.trim()),
+        "find" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            let part = interpreter.string_argument(name, arguments, 1)?;
+
+            Value::Integer(text.find(&*part).map_or(-1, |index| index as i64))
+        }
+        "replace" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            let from = interpreter.string_argument(name, arguments, 1)?;
+            let to = interpreter.string_argument(name, arguments, 2)?;
+
+            Value::string(&text.replace(&*from, &to))
+        }
+        "repeat" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            let count = interpreter.integer_argument(name, arguments, 1)?;
+
+            Value::string(&text.repeat(count.max(0) as usize))
+        }
+        "split" => {
+            let text = interpreter.string_argument(name, arguments, 0)?;
+            let separator = interpreter.string_argument(name, arguments, 1)?;
+
+            if separator.is_empty() {
+                chars(&text)
+            } else {
+                Value::array(text.split(&*separator).map(Value::string).collect())
+            }
+        }
+
+        // arrays
+        "join" => {
+            let values = interpreter.array_argument(name, arguments, 0)?;
+            let separator = interpreter.string_argument(name, arguments, 1)?;
+
+            Value::string(&join(interpreter, &values, &separator)?)
+        }
+
+        // math
+        "sqrt" => Value::Float(interpreter.number_argument(name, arguments, 0)?.sqrt()),
+        "sin" => Value::Float(interpreter.number_argument(name, arguments, 0)?.sin()),
+        "cos" => Value::Float(interpreter.number_argument(name, arguments, 0)?.cos()),
+        "tan" => Value::Float(interpreter.number_argument(name, arguments, 0)?.tan()),
+        "log" => Value::Float(interpreter.number_argument(name, arguments, 0)?.ln()),
+        "floor" => Value::Integer(interpreter.number_argument(name, arguments, 0)?.floor() as i64),
+        "ceil" => Value::Integer(interpreter.number_argument(name, arguments, 0)?.ceil() as i64),
+        "round" => Value::Integer(interpreter.number_argument(name, arguments, 0)?.round() as i64),
+        "pow" => {
+            let base = interpreter.number_argument(name, arguments, 0)?;
+            let exponent = interpreter.number_argument(name, arguments, 1)?;
+
+            Value::Float(base.powf(exponent))
+        }
+        "random" => Value::Float(interpreter.random()),
+
+        // time
+        "now" => Value::Float(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs_f64())
+                .unwrap_or(0.0),
+        ),
+        "sleep" => {
+            let milliseconds = interpreter.integer_argument(name, arguments, 0)?;
+            std::thread::sleep(Duration::from_millis(milliseconds.max(0) as u64));
+
+            Value::Null
+        }
+        _ => return Err(interpreter.error(format!("there is no intrinsic called \"{}\"", name))),
+    };
+
+    Ok(value)
+}
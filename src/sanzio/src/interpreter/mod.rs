@@ -0,0 +1,1049 @@
+//! Runs the AST directly, instead of compiling it to Lua first. It follows the semantics of the Lua
+//! backend, so a program gives the same result on both, and it does not need LuaJIT to be built.
+mod arrays;
+mod ffi;
+mod intrinsics;
+mod operations;
+mod strings;
+mod tests;
+mod value;
+
+use crate::exception::throw_runtime_warning;
+use crate::{Execution, LuaBackend, RuntimeError};
+use ffi::Library;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::iter;
+use std::rc::Rc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use value::{format_number, Class, Frame, Function, Hashmap, Value};
+use vinci::ast::instructions::location::Location;
+use vinci::ast::instructions::memory::{CompoundType, LoadType};
+use vinci::ast::instructions::suffix::{BinaryOperation, Suffix};
+use vinci::ast::instructions::{function, memory, Node};
+use vinci::ast::AST;
+use vinci::types::{Type, ValueType};
+
+/// Every call uses the stack of Rust, calls nested deeper than this are an error instead of
+/// running out of it. LuaJIT allows about as many.
+const MAX_CALL_DEPTH: usize = 20_000;
+
+/// Calls shown at the start and at the end of a traceback, the ones in between are left out
+const TRACEBACK_CALLS: usize = 10;
+
+/// Why running a block stopped before its end
+pub(crate) enum Flow {
+    Return(Value),
+    Break,
+    Continue,
+    Error(RuntimeError),
+}
+
+type Result<T> = std::result::Result<T, Flow>;
+
+pub struct Interpreter {
+    // Variables are global, like they are in the generated Lua
+    variables: Vec<Value>,
+    classes: HashMap<String, Rc<Class>>,
+    libraries: HashMap<String, Library>,
+    library_paths: Vec<String>,
+    // The parameters of the function that is running, and those of the functions around it
+    frame: Option<Rc<Frame>>,
+    // Where in the Loop code the interpreter is, and where every running function was called from
+    location: Option<Location>,
+    calls: Vec<Option<Location>>,
+    // Every program that was run is kept, so the bodies of its functions are only copied once
+    programs: Vec<Rc<AST>>,
+    bodies: HashMap<*const Vec<Node>, Rc<Vec<Node>>>,
+    // State of "intrinsic::random", seeded with the time the interpreter was created
+    random: u64,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        unsafe { Interpreter::new() }
+    }
+}
+
+impl Interpreter {
+    /// The stack a thread needs to nest calls as deep as is allowed, debug builds use about 13KB
+    /// for every call
+    pub const STACK_SIZE: usize = 512 * 1024 * 1024;
+
+    /// # Safety
+    /// Programs can call functions of C libraries, which are unsafe like every other use of FFI.
+    pub unsafe fn new() -> Interpreter {
+        Interpreter {
+            variables: Vec::new(),
+            classes: HashMap::new(),
+            libraries: HashMap::new(),
+            library_paths: Vec::new(),
+            frame: None,
+            location: None,
+            calls: Vec::new(),
+            programs: Vec::new(),
+            bodies: HashMap::new(),
+            random: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or(0)
+                | 1,
+        }
+    }
+
+    /// Runs a program and returns the value of its last expression. What it defined is kept, so a
+    /// following run can use it.
+    pub fn run(&mut self, ast: AST) -> std::result::Result<ValueType, RuntimeError> {
+        let program = Rc::new(ast);
+        self.programs.push(program.clone());
+
+        let result = match self.run_global(&program.nodes) {
+            Ok(value) | Err(Flow::Return(value)) => Ok(value),
+            Err(Flow::Break) => Err(self.error("break is not allowed outside of a loop")),
+            Err(Flow::Continue) => Err(self.error("continue is not allowed outside of a loop")),
+            Err(Flow::Error(error)) => Err(Flow::Error(error)),
+        };
+
+        // An error can stop the program inside of a function
+        self.frame = None;
+        self.calls.clear();
+
+        match result {
+            Ok(value) => Ok(self.value_type(&value)),
+            Err(Flow::Error(error)) => Err(error),
+            Err(_) => unreachable!("only errors are left"),
+        }
+    }
+
+    /// Same as [Interpreter::run], but also measures how long it took. Memory is not measured,
+    /// so there is no peak memory.
+    pub fn run_measured(
+        &mut self,
+        ast: AST,
+    ) -> std::result::Result<(ValueType, Execution), RuntimeError> {
+        let start = Instant::now();
+        let result = self.run(ast);
+
+        let execution = Execution {
+            duration: start.elapsed(),
+            peak_memory: None,
+        };
+
+        result.map(|result| (result, execution))
+    }
+
+    /// Only the last expression has a value, like the chunk the Lua backend generates
+    fn run_global(&mut self, nodes: &[Node]) -> Result<Value> {
+        let last = nodes
+            .iter()
+            .rposition(|node| !matches!(node, Node::LOCATION(_)));
+
+        for (index, node) in nodes.iter().enumerate() {
+            if Some(index) == last && LuaBackend::has_printable_value(node) {
+                return self.evaluate(node);
+            }
+
+            self.execute(node)?;
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn run_block(&mut self, nodes: &[Node]) -> Result<()> {
+        for node in nodes {
+            self.execute(node)?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs a node as a statement. A "return" in a conditional or loop returns from the function
+    /// around it.
+    fn execute(&mut self, node: &Node) -> Result<()> {
+        match node {
+            Node::CONDITIONAL(conditional) => {
+                if self.evaluate(&conditional.condition)?.is_truthy() {
+                    self.run_block(&conditional.body)
+                } else {
+                    self.run_block(&conditional.alternative)
+                }
+            }
+            Node::WHILE(whi) => {
+                while self.evaluate(&whi.condition)?.is_truthy() {
+                    match self.run_block(&whi.body) {
+                        Ok(()) | Err(Flow::Continue) => {}
+                        Err(Flow::Break) => break,
+                        Err(flow) => return Err(flow),
+                    }
+                }
+
+                Ok(())
+            }
+            Node::LOCATION(location) => {
+                self.locate(location);
+                Ok(())
+            }
+            node => self.evaluate(node).map(|_| ()),
+        }
+    }
+
+    /// Every node has a value. Nodes that need more than a few lines are evaluated by functions
+    /// of their own, so a call in the Loop program uses as little of the stack as possible.
+    fn evaluate(&mut self, node: &Node) -> Result<Value> {
+        match node {
+            Node::CONSTANT(constant) => Ok(self.constant(constant)),
+            Node::LOAD(load) => Ok(self.load(load)),
+            Node::STORE(store) => {
+                let value = self.evaluate(&store.value)?;
+                self.store(store.index, value);
+
+                Ok(Value::Null)
+            }
+            Node::SUFFIX(suffix) => self.suffix(suffix),
+            Node::NOT(value) => Ok(Value::Boolean(!self.evaluate(value)?.is_truthy())),
+            Node::NEGATE(value) => {
+                let value = self.evaluate(value)?;
+                operations::negate(&value).map_err(|e| self.error(e))
+            }
+            // Used as a value, a "return" inside of them gives the value
+            Node::CONDITIONAL(_) | Node::WHILE(_) => match self.execute(node) {
+                Ok(()) => Ok(Value::Null),
+                Err(Flow::Return(value)) => Ok(value),
+                Err(flow) => Err(flow),
+            },
+            Node::FUNCTION(function) => self.function(function),
+            Node::CALL(call) => self.evaluate_call(call),
+            Node::RETURN(value) => Err(Flow::Return(self.evaluate(value)?)),
+            Node::BREAK => Err(Flow::Break),
+            Node::CONTINUE => Err(Flow::Continue),
+            Node::INDEX(index) => self.index(index),
+            Node::SLICE(slice) => self.slice(slice),
+            Node::PUSH(push) => self.push(push),
+            Node::COPY(copy) => self.copy(copy),
+            Node::LOADLIB(library) => self.load_library(library),
+            Node::ASSIGN(to, value) => self.assign(to, value).map(|_| Value::Null),
+            Node::POP(from, position) => self.pop(from, position),
+            Node::LENGTH(value) => self.length(value),
+            Node::AND(left, right) => self.logical(left, right, true),
+            Node::OR(left, right) => self.logical(left, right, false),
+            Node::COMPOUND(compound) => {
+                self.define_class(compound);
+                Ok(Value::Null)
+            }
+            Node::HASHMAP(hashmap) => self.hashmap(hashmap),
+            Node::KEYS(value) => self.keys(value),
+            Node::RANGE(range) => self.range(range),
+            Node::LOCATION(location) => {
+                self.locate(location);
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    fn load(&self, load: &memory::Load) -> Value {
+        match load.load_type {
+            LoadType::VARIABLE => self
+                .variables
+                .get(load.index as usize)
+                .cloned()
+                .unwrap_or(Value::Null),
+            LoadType::PARAMETER(unique_identifier) => self
+                .frame
+                .as_ref()
+                .and_then(|frame| Frame::find(frame, unique_identifier as i64))
+                .and_then(|frame| frame.parameters.borrow().get(load.index as usize).cloned())
+                .unwrap_or(Value::Null),
+        }
+    }
+
+    fn suffix(&mut self, suffix: &Suffix) -> Result<Value> {
+        let left = self.evaluate(&suffix.left)?;
+        let right = self.evaluate(&suffix.right)?;
+
+        operations::binary(&suffix.operation, &left, &right).map_err(|e| self.error(e))
+    }
+
+    fn function(&mut self, function: &function::Function) -> Result<Value> {
+        let value = Value::Function(Rc::new(Function {
+            unique_identifier: function.unique_identifier as i64,
+            parameters: function.parameters.len(),
+            body: self.body(&function.body),
+            frame: self.frame.clone(),
+        }));
+
+        if function.name.is_empty() {
+            return Ok(value);
+        }
+
+        // Named functions are variables, "var_<index>"
+        match function.name.strip_prefix("var_").map(str::parse) {
+            Some(Ok(index)) => self.store(index, value),
+            _ => return Err(self.error(format!("invalid function name \"{}\"", function.name))),
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn evaluate_call(&mut self, call: &function::Call) -> Result<Value> {
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| self.evaluate(argument))
+            .collect::<Result<Vec<Value>>>()?;
+
+        // Methods, intrinsics and functions of libraries are called by their name
+        match &call.call {
+            Node::CONSTANT(name @ ValueType::Array(_)) => {
+                self.call_namespace(&name.clone().char_arr_to_string(), arguments)
+            }
+            function => {
+                let function = self.evaluate(function)?;
+                self.call(&function, arguments)
+            }
+        }
+    }
+
+    /// "and" and "or" give one of their operands, the right one is only evaluated when needed
+    fn logical(&mut self, left: &Node, right: &Node, and: bool) -> Result<Value> {
+        let left = self.evaluate(left)?;
+
+        if left.is_truthy() == and {
+            self.evaluate(right)
+        } else {
+            Ok(left)
+        }
+    }
+
+    /// A shallow copy, the values inside are shared with the original
+    fn copy(&mut self, copy: &memory::Copy) -> Result<Value> {
+        let copy = match self.evaluate(&copy.object)? {
+            Value::Array(array) => Value::array(array.borrow().clone()),
+            Value::Instance(class, values) => {
+                Value::Instance(class, Rc::new(RefCell::new(values.borrow().clone())))
+            }
+            Value::Hashmap(hashmap) => {
+                let mut copy = Hashmap::default();
+                for (key, value) in hashmap.borrow().entries() {
+                    copy.set(key.clone(), value.clone())
+                        .map_err(|e| self.error(e))?;
+                }
+
+                Value::Hashmap(Rc::new(RefCell::new(copy)))
+            }
+            value => value,
+        };
+
+        Ok(copy)
+    }
+
+    fn pop(&mut self, from: &Node, position: &Node) -> Result<Value> {
+        let from = self.evaluate(from)?;
+        let position = self.key(position, false)?;
+
+        match from {
+            Value::Array(array) => {
+                let position = position_of(&position, array.borrow().len());
+                Ok(position.map_or(Value::Null, |position| array.borrow_mut().remove(position)))
+            }
+            from => Err(self.error(format!("attempt to remove from {}", from.describe()))),
+        }
+    }
+
+    fn length(&mut self, value: &Node) -> Result<Value> {
+        match self.evaluate(value)? {
+            Value::String(string) => Ok(Value::Integer(string.len() as i64)),
+            Value::Array(array) | Value::Instance(_, array) => {
+                Ok(Value::Integer(array.borrow().len() as i64))
+            }
+            Value::Hashmap(hashmap) => Ok(Value::Integer(hashmap.borrow().len() as i64)),
+            value => Err(self.error(format!("attempt to get the length of {}", value.describe()))),
+        }
+    }
+
+    fn hashmap(&mut self, hashmap: &memory::Hashmap) -> Result<Value> {
+        let mut values = Hashmap::default();
+        for (key, value) in &hashmap.values {
+            let key = self.evaluate(key)?;
+            let value = self.evaluate(value)?;
+            values.set(key, value).map_err(|e| self.error(e))?;
+        }
+
+        Ok(Value::Hashmap(Rc::new(RefCell::new(values))))
+    }
+
+    fn keys(&mut self, value: &Node) -> Result<Value> {
+        match self.evaluate(value)? {
+            Value::Hashmap(hashmap) => Ok(Value::array(
+                hashmap
+                    .borrow()
+                    .entries()
+                    .iter()
+                    .map(|(key, _)| key.clone())
+                    .collect(),
+            )),
+            Value::Array(array) | Value::Instance(_, array) => Ok(Value::array(
+                (1..=array.borrow().len() as i64)
+                    .map(Value::Integer)
+                    .collect(),
+            )),
+            value => Err(self.error(format!("{} does not have keys", value.describe()))),
+        }
+    }
+
+    fn constant(&mut self, constant: &ValueType) -> Value {
+        match constant {
+            ValueType::Void => Value::Null,
+            ValueType::Integer(integer) => Value::Integer(*integer),
+            ValueType::Float(float) => Value::Float(*float),
+            ValueType::Boolean(boolean) => Value::Boolean(*boolean),
+            ValueType::Character(character) => Value::string(&character.to_string()),
+            ValueType::Function(_, parameters, unique_identifier, body) => {
+                Value::Function(Rc::new(Function {
+                    unique_identifier: *unique_identifier as i64,
+                    parameters: parameters.len(),
+                    body: self.body(body),
+                    frame: self.frame.clone(),
+                }))
+            }
+            ValueType::Compound(name, values) => {
+                let values = values.iter().map(|value| self.constant(value)).collect();
+
+                // Compounds without fields are not classes, they are arrays like in the Lua backend
+                match self.classes.get(name) {
+                    Some(class) => Value::Instance(class.clone(), Rc::new(RefCell::new(values))),
+                    None => Value::array(values),
+                }
+            }
+            ValueType::Instance(_, properties) => Value::array(
+                properties
+                    .iter()
+                    .map(|(_, value)| self.constant(value))
+                    .collect(),
+            ),
+            ValueType::Hashmap(values) => {
+                let mut hashmap = Hashmap::default();
+                for (key, value) in values.iter() {
+                    let key = self.constant(key);
+                    let value = self.constant(value);
+
+                    // Constants can not have null keys, the parser does not allow them
+                    hashmap.set(key, value).ok();
+                }
+
+                Value::Hashmap(Rc::new(RefCell::new(hashmap)))
+            }
+            // Arrays of characters are strings
+            ValueType::Array(values) => match values.first() {
+                Some(ValueType::Character(_)) => Value::string(
+                    &values
+                        .iter()
+                        .filter_map(|value| match value {
+                            ValueType::Character(character) => Some(*character),
+                            _ => None,
+                        })
+                        .collect::<String>(),
+                ),
+                _ => Value::array(values.iter().map(|value| self.constant(value)).collect()),
+            },
+        }
+    }
+
+    /// The body of a function, which is shared by every time the function is created
+    fn body(&mut self, nodes: &Vec<Node>) -> Rc<Vec<Node>> {
+        self.bodies
+            .entry(nodes as *const Vec<Node>)
+            .or_insert_with(|| Rc::new(nodes.clone()))
+            .clone()
+    }
+
+    fn store(&mut self, index: u64, value: Value) {
+        let index = index as usize;
+        if index >= self.variables.len() {
+            self.variables.resize(index + 1, Value::Null);
+        }
+
+        self.variables[index] = value;
+    }
+
+    fn locate(&mut self, location: &Location) {
+        match &mut self.location {
+            Some(current) if current.file == location.file => current.line = location.line,
+            current => *current = Some(location.clone()),
+        }
+    }
+
+    /// An error at the current location, the traceback shows where every running function was
+    /// called from
+    pub(crate) fn error(&self, message: impl Into<String>) -> Flow {
+        let message = match &self.location {
+            Some(location) => format!("{}: {}", location, message.into()),
+            None => message.into(),
+        };
+
+        // Like in Lua only the innermost and outermost calls are shown when there are many, a
+        // stack overflow would show every one of them otherwise
+        let calls: Vec<&Location> = self.calls.iter().rev().flatten().collect();
+        let frame = |location: &&Location| format!("\t{}: in function call", location);
+        let frames: Vec<String> = if calls.len() > TRACEBACK_CALLS * 2 {
+            calls[..TRACEBACK_CALLS]
+                .iter()
+                .map(frame)
+                .chain(iter::once("\t...".to_string()))
+                .chain(calls[calls.len() - TRACEBACK_CALLS..].iter().map(frame))
+                .collect()
+        } else {
+            calls.iter().map(frame).collect()
+        };
+        let traceback = if frames.is_empty() {
+            None
+        } else {
+            Some(format!("stack traceback:\n{}", frames.join("\n")))
+        };
+
+        Flow::Error(RuntimeError::Interpreter {
+            message,
+            traceback,
+            location: self.location.clone(),
+        })
+    }
+
+    pub(crate) fn call(&mut self, function: &Value, mut arguments: Vec<Value>) -> Result<Value> {
+        let function = match function {
+            Value::Function(function) => function.clone(),
+            value => return Err(self.error(format!("attempt to call {}", value.describe()))),
+        };
+
+        if self.calls.len() >= MAX_CALL_DEPTH {
+            return Err(self.error("stack overflow"));
+        }
+
+        // Missing arguments are null and extra ones are ignored, like in Lua
+        arguments.resize(function.parameters, Value::Null);
+
+        let frame = Rc::new(Frame {
+            unique_identifier: function.unique_identifier,
+            parameters: RefCell::new(arguments),
+            parent: function.frame.clone(),
+        });
+        let caller = self.frame.replace(frame);
+        self.calls.push(self.location.clone());
+
+        let result = self.run_block(&function.body);
+
+        let returned = match result {
+            Ok(()) => Ok(Value::Null),
+            Err(Flow::Return(value)) => Ok(value),
+            Err(Flow::Break) => Err(self.error("break is not allowed outside of a loop")),
+            Err(Flow::Continue) => Err(self.error("continue is not allowed outside of a loop")),
+            Err(flow) => Err(flow),
+        };
+
+        self.location = self.calls.pop().flatten();
+        self.frame = caller;
+
+        returned
+    }
+
+    /// Calls a method of strings or arrays, an intrinsic or a function of a library
+    fn call_namespace(&mut self, name: &str, arguments: Vec<Value>) -> Result<Value> {
+        let (namespace, function) = name.split_once("::").unwrap_or((name, ""));
+
+        match namespace {
+            "string" => strings::call(self, function, &arguments),
+            "array" => arrays::call(self, function, &arguments),
+            "intrinsic" => intrinsics::call(self, function, &arguments),
+            namespace => {
+                let result = match self.libraries.get(namespace) {
+                    Some(library) => library.call(function, &arguments),
+                    None => Err(format!("there is no library called \"{}\"", namespace)),
+                };
+
+                result.map_err(|e| self.error(e))
+            }
+        }
+    }
+
+    fn load_library(&mut self, library: &memory::LoadLib) -> Result<Value> {
+        let namespace = &library.namespace;
+        let path = match self.evaluate(&library.path)? {
+            Value::String(path) => path.to_string(),
+            path => {
+                return Err(self.error(format!(
+                    "the path of a library has to be a string, got {}",
+                    path.describe()
+                )))
+            }
+        };
+
+        if self.library_paths.contains(&path) {
+            throw_runtime_warning("Library is already loaded".to_string());
+        }
+        if self.libraries.contains_key(namespace) {
+            return Err(self.error(format!("The libary alias {}, is already in use", namespace)));
+        }
+
+        let loaded = Library::load(&path).map_err(|e| self.error(e))?;
+        self.libraries.insert(namespace.to_string(), loaded);
+        self.library_paths.push(path);
+
+        Ok(Value::Null)
+    }
+
+    fn define_class(&mut self, compound: &CompoundType) {
+        if let Some((name, fields)) = &compound.fields {
+            let class = Class {
+                name: name.clone(),
+                fields: fields.clone(),
+            };
+
+            self.classes.insert(compound.name.clone(), Rc::new(class));
+        }
+    }
+
+    /// The key an index refers to, positions in arrays start at 1 like they do in Lua
+    fn key(&mut self, index: &Node, hashmap: bool) -> Result<Value> {
+        let key = self.evaluate(index)?;
+
+        if hashmap {
+            return Ok(key);
+        }
+
+        operations::binary(&BinaryOperation::ADD, &key, &Value::Integer(1))
+            .map_err(|e| self.error(e))
+    }
+
+    fn index(&mut self, index: &memory::Index) -> Result<Value> {
+        let to_index = &self.evaluate(&index.to_index)?;
+        let key = &self.key(&index.index, index.hashmap)?;
+
+        let value = match to_index {
+            Value::String(string) => match key.as_number() {
+                Some(position) => Value::string(&substring(string, position, position)),
+                None => {
+                    return Err(
+                        self.error(format!("can not index a string with {}", key.describe()))
+                    )
+                }
+            },
+            Value::Array(array) | Value::Instance(_, array) => {
+                let array = array.borrow();
+                position_of(key, array.len())
+                    .map_or(Value::Null, |position| array[position].clone())
+            }
+            Value::Hashmap(hashmap) => hashmap.borrow().get(key),
+            value => return Err(self.error(format!("attempt to index {}", value.describe()))),
+        };
+
+        Ok(value)
+    }
+
+    fn assign(&mut self, to: &Node, value: &Node) -> Result<()> {
+        match to {
+            Node::LOAD(load) => {
+                let value = self.evaluate(value)?;
+
+                match load.load_type {
+                    LoadType::VARIABLE => self.store(load.index, value),
+                    LoadType::PARAMETER(unique_identifier) => {
+                        let frame = self
+                            .frame
+                            .as_ref()
+                            .and_then(|frame| Frame::find(frame, unique_identifier as i64));
+
+                        if let Some(frame) = frame {
+                            if let Some(parameter) =
+                                frame.parameters.borrow_mut().get_mut(load.index as usize)
+                            {
+                                *parameter = value;
+                            }
+                        }
+                    }
+                }
+            }
+            Node::INDEX(index) => {
+                let to_index = self.evaluate(&index.to_index)?;
+                let key = self.key(&index.index, index.hashmap)?;
+                let value = self.evaluate(value)?;
+
+                self.set_index(&to_index, key, value)?;
+            }
+            _ => return Err(self.error(format!("can not assign to {}", to))),
+        }
+
+        Ok(())
+    }
+
+    fn set_index(&mut self, to_index: &Value, key: Value, value: Value) -> Result<()> {
+        match to_index {
+            Value::Array(array) | Value::Instance(_, array) => {
+                let mut array = array.borrow_mut();
+                let length = array.len();
+
+                // Arrays can grow by assigning right after their last value
+                match position_of(&key, length + 1) {
+                    Some(position) if position < length => array[position] = value,
+                    Some(_) if matches!(to_index, Value::Array(_)) => {
+                        if !matches!(value, Value::Null) {
+                            array.push(value);
+                        }
+                    }
+                    _ => {
+                        drop(array);
+                        let position = operations::binary(
+                            &BinaryOperation::SUBTRACT,
+                            &key,
+                            &Value::Integer(1),
+                        )
+                        .unwrap_or(key);
+
+                        let message = format!(
+                            "can not assign to position {} of {} with {} values",
+                            self.show(&position)?,
+                            to_index.describe(),
+                            length
+                        );
+
+                        return Err(self.error(message));
+                    }
+                }
+            }
+            Value::Hashmap(hashmap) => {
+                let result = hashmap.borrow_mut().set(key, value);
+                result.map_err(|e| self.error(e))?;
+            }
+            value => return Err(self.error(format!("attempt to index {}", value.describe()))),
+        }
+
+        Ok(())
+    }
+
+    /// A slice of a string or an array, both the start and the end are included
+    fn slice(&mut self, slice: &memory::Slice) -> Result<Value> {
+        let to_slice = &self.evaluate(&slice.to_slice)?;
+        let from = &self.evaluate(&slice.from)?;
+        let to = &self.evaluate(&slice.to)?;
+
+        let (from, to) = match (from.as_number(), to.as_number()) {
+            (Some(from), Some(to)) => (from + 1.0, to + 1.0),
+            _ => {
+                return Err(self.error(format!(
+                    "can not slice from {} to {}",
+                    from.describe(),
+                    to.describe()
+                )))
+            }
+        };
+
+        let value = match to_slice {
+            Value::String(string) => Value::string(&substring(string, from, to)),
+            Value::Array(array) | Value::Instance(_, array) => {
+                let array = array.borrow();
+
+                // Positions that are not in the array are left out
+                let values = if from.fract() == 0.0 {
+                    let start = from.max(1.0) as usize;
+                    let end = to.min(array.len() as f64);
+                    let end = if end < 1.0 { 0 } else { end as usize };
+
+                    array
+                        .get(start - 1..end.max(start - 1))
+                        .map(<[Value]>::to_vec)
+                        .unwrap_or_default()
+                } else {
+                    vec![]
+                };
+
+                Value::array(values)
+            }
+            value => return Err(self.error(format!("attempt to slice {}", value.describe()))),
+        };
+
+        Ok(value)
+    }
+
+    /// Strings and numbers are joined into a string, pushing to an array adds to its end
+    fn push(&mut self, push: &memory::Push) -> Result<Value> {
+        let to_push = &self.evaluate(&push.to_push)?;
+        let item = self.evaluate(&push.item)?;
+
+        if let Value::Array(array) = to_push {
+            if !matches!(item, Value::Null) {
+                array.borrow_mut().push(item);
+            }
+
+            return Ok(Value::Null);
+        }
+
+        match (concatenable(to_push), concatenable(&item)) {
+            (Some(left), Some(right)) => Ok(Value::string(&(left + &right))),
+            (None, _) => Err(self.error(format!("attempt to concatenate {}", to_push.describe()))),
+            (_, None) => Err(self.error(format!("attempt to concatenate {}", item.describe()))),
+        }
+    }
+
+    /// The end is excluded, and a step of 0 is an empty range
+    fn range(&mut self, range: &memory::Range) -> Result<Value> {
+        let from = &self.evaluate(&range.from)?;
+        let till = &self.evaluate(&range.till)?;
+        let step = &self.evaluate(&range.step)?;
+
+        let mut values = vec![];
+
+        match (from, till, step) {
+            (Value::Integer(from), Value::Integer(till), Value::Integer(step)) => {
+                let mut value = *from;
+                while (*step > 0 && value < *till) || (*step < 0 && value > *till) {
+                    values.push(Value::Integer(value));
+
+                    value = match value.checked_add(*step) {
+                        Some(value) => value,
+                        None => break,
+                    };
+                }
+            }
+            _ => match (from.as_number(), till.as_number(), step.as_number()) {
+                (Some(from), Some(till), Some(step)) => {
+                    let mut value = from;
+                    while (step > 0.0 && value < till) || (step < 0.0 && value > till) {
+                        values.push(Value::Float(value));
+                        value += step;
+                    }
+                }
+                _ => return Err(self.error("the bounds and step of a range have to be numbers")),
+            },
+        }
+
+        Ok(Value::array(values))
+    }
+
+    /// Xorshift, the upper 53 bits fit exactly in the fraction of a float
+    pub(crate) fn random(&mut self) -> f64 {
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 7;
+        self.random ^= self.random << 17;
+
+        (self.random >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// An argument of a built-in function that has to be a string, numbers are converted like
+    /// they are in Lua
+    pub(crate) fn string_argument(
+        &self,
+        function: &str,
+        arguments: &[Value],
+        position: usize,
+    ) -> Result<Rc<str>> {
+        match argument(arguments, position) {
+            Value::String(string) => Ok(string),
+            value => match concatenable(&value) {
+                Some(string) => Ok(Rc::from(string)),
+                None => Err(self.bad_argument(function, position, "a string", &value)),
+            },
+        }
+    }
+
+    pub(crate) fn number_argument(
+        &self,
+        function: &str,
+        arguments: &[Value],
+        position: usize,
+    ) -> Result<f64> {
+        let value = argument(arguments, position);
+
+        value
+            .as_number()
+            .ok_or_else(|| self.bad_argument(function, position, "a number", &value))
+    }
+
+    /// Floats are rounded down to an int
+    pub(crate) fn integer_argument(
+        &self,
+        function: &str,
+        arguments: &[Value],
+        position: usize,
+    ) -> Result<i64> {
+        match argument(arguments, position) {
+            Value::Integer(integer) => Ok(integer),
+            Value::Float(float) => Ok(float.floor() as i64),
+            value => Err(self.bad_argument(function, position, "an int", &value)),
+        }
+    }
+
+    pub(crate) fn array_argument(
+        &self,
+        function: &str,
+        arguments: &[Value],
+        position: usize,
+    ) -> Result<Vec<Value>> {
+        match argument(arguments, position) {
+            Value::Array(array) => Ok(array.borrow().clone()),
+            value => Err(self.bad_argument(function, position, "an array", &value)),
+        }
+    }
+
+    fn bad_argument(&self, function: &str, position: usize, expected: &str, got: &Value) -> Flow {
+        self.error(format!(
+            "bad argument #{} to \"{}\" (expected {}, got {})",
+            position + 1,
+            function,
+            expected,
+            got.describe()
+        ))
+    }
+
+    /// Shows a value like "tostring" in Lua does, instances use their "to_string" method
+    pub(crate) fn show(&mut self, value: &Value) -> Result<String> {
+        let shown = match value {
+            Value::Null => "nil".to_string(),
+            Value::Boolean(boolean) => boolean.to_string(),
+            Value::Integer(integer) => integer.to_string(),
+            Value::Float(float) => format_number(*float),
+            Value::String(string) => string.to_string(),
+            Value::Array(array) => format!("table: {:p}", Rc::as_ptr(array)),
+            Value::Hashmap(hashmap) => format!("table: {:p}", Rc::as_ptr(hashmap)),
+            Value::Instance(class, values) => match class.to_string_method() {
+                Some(index) => {
+                    let method = values.borrow().get(index).cloned().unwrap_or(Value::Null);
+
+                    match self.call(&method, vec![value.clone()])? {
+                        Value::String(string) => string.to_string(),
+                        _ => return Err(self.error("\"to_string\" has to return a string")),
+                    }
+                }
+                None => format!("table: {:p}", Rc::as_ptr(values)),
+            },
+            Value::Function(function) => format!("function: {:p}", Rc::as_ptr(function)),
+        };
+
+        Ok(shown)
+    }
+
+    /// Formats a value the way Loop shows it, strings are shown as they are
+    pub(crate) fn format_value(&mut self, value: &Value) -> String {
+        match value {
+            Value::String(string) => string.to_string(),
+            value => self.value_type(value).format(),
+        }
+    }
+
+    /// Converts a value into the value it is in Loop, the same way the Lua backend does
+    pub(crate) fn value_type(&mut self, value: &Value) -> ValueType {
+        match value {
+            Value::Null => ValueType::Void,
+            Value::Boolean(boolean) => ValueType::Boolean(*boolean),
+            Value::Integer(integer) => ValueType::Integer(*integer),
+            // Whole floats are ints like in LuaJIT, "intrinsic::show" gives them their type back
+            Value::Float(float) if float.fract() == 0.0 && float.abs() < 1e15 => {
+                ValueType::Integer(*float as i64)
+            }
+            Value::Float(float) => ValueType::Float(*float),
+            Value::String(string) => string_value(string),
+            Value::Array(array) => {
+                let values = array.borrow().clone();
+                ValueType::Array(Box::new(
+                    values.iter().map(|value| self.value_type(value)).collect(),
+                ))
+            }
+            Value::Hashmap(hashmap) => {
+                let entries = hashmap.borrow().entries().to_vec();
+                let mut values: Vec<(ValueType, ValueType)> = entries
+                    .iter()
+                    .map(|(key, value)| (self.value_type(key), self.value_type(value)))
+                    .collect();
+                values.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+                ValueType::Hashmap(Box::new(values))
+            }
+            Value::Instance(class, values) => {
+                if class.to_string_method().is_some() {
+                    if let Ok(string) = self.show(value) {
+                        return string_value(&string);
+                    }
+                }
+
+                let values = values.borrow().clone();
+                let properties = class
+                    .fields
+                    .iter()
+                    .zip(values.iter())
+                    .filter(|(_, value)| !matches!(value, Value::Function(_)))
+                    .map(|(field, value)| (field.clone(), self.value_type(value)))
+                    .collect();
+
+                ValueType::Instance(class.name.clone(), Box::new(properties))
+            }
+            // The signature of a function is not known anymore while running
+            Value::Function(_) => {
+                ValueType::Function(Box::new(Type::VOID), Box::default(), 0, Box::default())
+            }
+        }
+    }
+}
+
+/// An argument of a call, arguments that were not given are null
+pub(crate) fn argument(arguments: &[Value], position: usize) -> Value {
+    arguments.get(position).cloned().unwrap_or(Value::Null)
+}
+
+/// The position in a list of this length a key refers to, keys start at 1
+fn position_of(key: &Value, length: usize) -> Option<usize> {
+    let key = match key {
+        Value::Integer(integer) => *integer,
+        Value::Float(float) if float.fract() == 0.0 => *float as i64,
+        _ => return None,
+    };
+
+    if key >= 1 && key as usize <= length {
+        Some(key as usize - 1)
+    } else {
+        None
+    }
+}
+
+/// The characters from "from" till "to", both included and starting at 1, like "string.sub" in
+/// Lua. Negative positions count from the end.
+pub(crate) fn substring(string: &str, from: f64, to: f64) -> String {
+    let length = string.len() as i64;
+    let position = |position: f64| {
+        let position = position.floor() as i64;
+        if position < 0 {
+            (length + position + 1).max(0)
+        } else {
+            position
+        }
+    };
+
+    let from = position(from).max(1);
+    let to = position(to).min(length);
+
+    if from > to {
+        return String::new();
+    }
+
+    String::from_utf8_lossy(&string.as_bytes()[from as usize - 1..to as usize]).into_owned()
+}
+
+/// Strings and numbers can be joined into a string
+fn concatenable(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string.to_string()),
+        Value::Integer(integer) => Some(integer.to_string()),
+        Value::Float(float) => Some(format_number(*float)),
+        _ => None,
+    }
+}
+
+fn string_value(string: &str) -> ValueType {
+    ValueType::Array(Box::new(string.chars().map(ValueType::Character).collect()))
+}
+
+/// Keys of hashmaps are sorted the same way the Lua backend sorts them
+fn compare_keys(a: &ValueType, b: &ValueType) -> std::cmp::Ordering {
+    match (a, b) {
+        (ValueType::Integer(a), ValueType::Integer(b)) => a.cmp(b),
+        (ValueType::Float(a), ValueType::Float(b)) => {
+            a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+        }
+        _ => a.format().cmp(&b.format()),
+    }
+}
@@ -0,0 +1,98 @@
+//! Arithmetic and comparisons. They follow Lua, so both backends give the same results: dividing
+//! always results in a float, and ints that would overflow become floats.
+use crate::interpreter::value::Value;
+use std::cmp::Ordering;
+use vinci::ast::instructions::suffix::BinaryOperation;
+
+pub fn binary(operation: &BinaryOperation, left: &Value, right: &Value) -> Result<Value, String> {
+    match operation {
+        BinaryOperation::EQUALS => Ok(left.equals(right)),
+        BinaryOperation::NOTEQUALS => Ok(!left.equals(right)),
+        BinaryOperation::GREATERTHAN => {
+            compare(left, right).map(|o| matches!(o, Some(Ordering::Greater)))
+        }
+        BinaryOperation::LESSTHAN => {
+            compare(left, right).map(|o| matches!(o, Some(Ordering::Less)))
+        }
+        BinaryOperation::GREATERTHANOREQUAL => {
+            compare(left, right).map(|o| matches!(o, Some(Ordering::Greater | Ordering::Equal)))
+        }
+        BinaryOperation::LESSTHANOREQUAL => {
+            compare(left, right).map(|o| matches!(o, Some(Ordering::Less | Ordering::Equal)))
+        }
+        _ => return arithmetic(operation, left, right),
+    }
+    .map(Value::Boolean)
+}
+
+fn arithmetic(operation: &BinaryOperation, left: &Value, right: &Value) -> Result<Value, String> {
+    if let (Value::Integer(a), Value::Integer(b)) = (left, right) {
+        let result = match operation {
+            BinaryOperation::ADD => a.checked_add(*b),
+            BinaryOperation::SUBTRACT => a.checked_sub(*b),
+            BinaryOperation::MULTIPLY => a.checked_mul(*b),
+            // The remainder has the sign of the divisor, like in Lua
+            BinaryOperation::MODULO if *b != 0 => a.checked_rem(*b).map(|r| {
+                if r != 0 && (r < 0) != (*b < 0) {
+                    r + b
+                } else {
+                    r
+                }
+            }),
+            _ => None,
+        };
+
+        if let Some(result) = result {
+            return Ok(Value::Integer(result));
+        }
+    }
+
+    let (a, b) = match (left.as_number(), right.as_number()) {
+        (Some(a), Some(b)) => (a, b),
+        (None, _) => return Err(format!("attempt to do arithmetic on {}", left.describe())),
+        (_, None) => return Err(format!("attempt to do arithmetic on {}", right.describe())),
+    };
+
+    let result = match operation {
+        BinaryOperation::ADD => a + b,
+        BinaryOperation::SUBTRACT => a - b,
+        BinaryOperation::MULTIPLY => a * b,
+        BinaryOperation::DIVIDE => a / b,
+        BinaryOperation::POWER => a.powf(b),
+        BinaryOperation::MODULO => a - (a / b).floor() * b,
+        _ => unreachable!("comparisons are not arithmetic"),
+    };
+
+    Ok(Value::Float(result))
+}
+
+/// Numbers are compared with numbers, and strings with strings. NaN is neither smaller, equal nor
+/// bigger than anything, so it has no ordering.
+pub fn compare(left: &Value, right: &Value) -> Result<Option<Ordering>, String> {
+    let ordering = match (left, right) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (a, b) => match (a.as_number(), b.as_number()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => {
+                return Err(format!(
+                    "attempt to compare {} with {}",
+                    left.describe(),
+                    right.describe()
+                ))
+            }
+        },
+    };
+
+    Ok(ordering)
+}
+
+pub fn negate(value: &Value) -> Result<Value, String> {
+    match value {
+        Value::Integer(integer) => Ok(integer
+            .checked_neg()
+            .map_or(Value::Float(-(*integer as f64)), Value::Integer)),
+        Value::Float(float) => Ok(Value::Float(-float)),
+        value => Err(format!("attempt to do arithmetic on {}", value.describe())),
+    }
+}
@@ -0,0 +1,169 @@
+//! The methods built into strings, the string is the first argument. They behave like the ones the
+//! Lua backend generates, positions start at 0.
+use crate::interpreter::value::Value;
+use crate::interpreter::{argument, Interpreter, Result};
+
+pub fn call(interpreter: &mut Interpreter, method: &str, arguments: &[Value]) -> Result<Value> {
+    let string = interpreter.string_argument(method, arguments, 0)?;
+
+    let value = match method {
+        "split" => {
+            let separator = interpreter.string_argument(method, arguments, 1)?;
+
+            if separator.is_empty() {
+                chars(&string)
+            } else {
+                Value::array(string.split(&*separator).map(Value::string).collect())
+            }
+        }
+        "join" => {
+            let values = interpreter.array_argument(method, arguments, 1)?;
+            Value::string(&join(interpreter, &values, &string)?)
+        }
+        "trim" => Value::string(string.trim()),
+        "to_upper" => Value::string(&string.to_uppercase()),
+        "to_lower" => Value::string(&string.to_lowercase()),
+        "replace" => {
+            let from = interpreter.string_argument(method, arguments, 1)?;
+            let to = interpreter.string_argument(method, arguments, 2)?;
+
+            Value::string(&string.replace(&*from, &to))
+        }
+        "contains" => {
+            let part = interpreter.string_argument(method, arguments, 1)?;
+            Value::Boolean(string.contains(&*part))
+        }
+        "starts_with" => {
+            let part = interpreter.string_argument(method, arguments, 1)?;
+            Value::Boolean(string.starts_with(&*part))
+        }
+        "ends_with" => {
+            let part = interpreter.string_argument(method, arguments, 1)?;
+            Value::Boolean(string.ends_with(&*part))
+        }
+        "find" => {
+            let part = interpreter.string_argument(method, arguments, 1)?;
+            Value::Integer(string.find(&*part).map_or(-1, |index| index as i64))
+        }
+        "repeat" => {
+            let count = interpreter.integer_argument(method, arguments, 1)?;
+            Value::string(&string.repeat(count.max(0) as usize))
+        }
+        "chars" => chars(&string),
+        "to_int" => match to_number(&string) {
+            Some(number) if number.fract() == 0.0 => number_value(number),
+            _ => fallback(interpreter, &string, arguments, "an int")?,
+        },
+        "to_float" => match to_number(&string) {
+            Some(number) => Value::Float(number),
+            None => fallback(interpreter, &string, arguments, "a float")?,
+        },
+        // Every "{}" is replaced by the next value
+        "format" => {
+            let mut formatted = String::new();
+            let mut parts = string.split("{}");
+            formatted.push_str(parts.next().unwrap_or_default());
+
+            for (index, part) in parts.enumerate() {
+                let value = argument(arguments, index + 1);
+                formatted.push_str(&interpreter.show(&value)?);
+                formatted.push_str(part);
+            }
+
+            Value::string(&formatted)
+        }
+        _ => {
+            return Err(interpreter.error(format!(
+                "strings do not have a method called \"{}\"",
+                method
+            )))
+        }
+    };
+
+    Ok(value)
+}
+
+/// Joins strings and numbers, like "table.concat" in Lua
+pub fn join(interpreter: &Interpreter, values: &[Value], separator: &str) -> Result<String> {
+    let mut strings = Vec::with_capacity(values.len());
+
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            Value::String(_) | Value::Integer(_) | Value::Float(_) => strings.push(
+                interpreter
+                    .string_argument("join", values, index)?
+                    .to_string(),
+            ),
+            value => {
+                return Err(interpreter.error(format!(
+                    "can not join {} at position {}",
+                    value.describe(),
+                    index
+                )))
+            }
+        }
+    }
+
+    Ok(strings.join(separator))
+}
+
+/// Every character of a string, as an array of strings
+pub fn chars(string: &str) -> Value {
+    Value::array(
+        string
+            .chars()
+            .map(|char| Value::string(char.encode_utf8(&mut [0; 4])))
+            .collect(),
+    )
+}
+
+/// Parses a number like "tonumber" in Lua, which also allows hexadecimal numbers
+pub fn to_number(string: &str) -> Option<f64> {
+    let string = string.trim();
+    let (negative, digits) = match string.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, string.strip_prefix('+').unwrap_or(string)),
+    };
+
+    if let Some(hexadecimal) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        let number = i64::from_str_radix(hexadecimal, 16).ok()? as f64;
+        return Some(if negative { -number } else { number });
+    }
+
+    // Rust also parses words like "inf" and "NaN", Lua does not
+    if !digits
+        .chars()
+        .all(|char| char.is_ascii_digit() || matches!(char, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return None;
+    }
+
+    string.parse().ok()
+}
+
+/// Whole numbers are ints
+pub fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < 9.2e18 {
+        Value::Integer(number as i64)
+    } else {
+        Value::Float(number)
+    }
+}
+
+/// The value given to use when a string is not a number, an error if there is none
+fn fallback(
+    interpreter: &Interpreter,
+    string: &str,
+    arguments: &[Value],
+    expected: &str,
+) -> Result<Value> {
+    match argument(arguments, 1) {
+        Value::Null => {
+            Err(interpreter.error(format!("can not convert \"{}\" to {}", string, expected)))
+        }
+        value => Ok(value),
+    }
+}
@@ -0,0 +1,377 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::ffi::{parse_signatures, CType, Signature};
+    use crate::interpreter::Interpreter;
+    use crate::RuntimeError;
+    use vinci::types::ValueType;
+
+    fn run_with(interpreter: &mut Interpreter, arc: &str) -> Result<ValueType, RuntimeError> {
+        interpreter.run(vinci::parse(arc).unwrap())
+    }
+
+    /// The value of the program, formatted like Loop prints it
+    fn run(arc: &str) -> String {
+        match run_with(&mut Interpreter::default(), arc) {
+            Ok(value) => value.format(),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    fn error(arc: &str) -> RuntimeError {
+        match run_with(&mut Interpreter::default(), arc) {
+            Ok(value) => panic!("expected an error, got {}", value.format()),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            run(".ADD { .CONSTANT INT 1; .MULTIPLY { .CONSTANT INT 2; .CONSTANT INT 3; }; };"),
+            "7"
+        );
+        assert_eq!(
+            run(".DIVIDE { .CONSTANT INT 10; .CONSTANT INT 4; };"),
+            "2.5"
+        );
+        assert_eq!(run(".DIVIDE { .CONSTANT INT 10; .CONSTANT INT 5; };"), "2");
+        assert_eq!(run(".MODULO { .CONSTANT INT 7; .CONSTANT INT -3; };"), "-2");
+        assert_eq!(
+            run(".MODULO { .CONSTANT FLOAT 5.5; .CONSTANT INT 1; };"),
+            "0.5"
+        );
+        assert_eq!(
+            run(".POWER { .CONSTANT INT 2; .CONSTANT INT 10; };"),
+            "1024"
+        );
+        assert_eq!(run(".NEGATE { .CONSTANT FLOAT 1.25; };"), "-1.25");
+        assert_eq!(
+            run(".LESSTHAN { .CONSTANT CHAR[] \"abc\"; .CONSTANT CHAR[] \"abd\"; };"),
+            "true"
+        );
+        // "and" and "or" give one of their operands
+        assert_eq!(run(".OR { .CONSTANT VOID; .CONSTANT INT 3; };"), "3");
+        assert_eq!(
+            run(".AND { .CONSTANT BOOL false; .CONSTANT INT 3; };"),
+            "false"
+        );
+    }
+
+    #[test]
+    fn closures() {
+        assert_eq!(
+            run(".STORE 0 { .CONSTANT INT 200; };
+                .STORE 1 { .FUNCTION \"\" 1 VOID ARGUMENTS { INT; } FREE { } THEN {
+                    .RETURN { .FUNCTION \"\" 2 INT ARGUMENTS { INT; } FREE { } THEN {
+                        .RETURN { .ADD { .ADD { .LOAD PARAMETER 1 0; .LOAD PARAMETER 2 0; }; .LOAD VARIABLE 0; }; };
+                    }; };
+                }; };
+                .STORE 2 { .CALL { .LOAD VARIABLE 1; } { .CONSTANT INT 20; }; };
+                .STORE 0 { .CONSTANT INT 1000; };
+                .CALL { .LOAD VARIABLE 2; } { .CONSTANT INT 3; };"),
+            "1023"
+        );
+    }
+
+    #[test]
+    fn recursion() {
+        assert_eq!(
+            run(".FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; } FREE { } THEN {
+                    .IF CONDITION { .LESSTHAN { .LOAD PARAMETER 0 0; .CONSTANT INT 2; }; } THEN {
+                        .RETURN { .LOAD PARAMETER 0 0; };
+                    };
+                    .RETURN { .ADD {
+                        .CALL { .LOAD VARIABLE 0; } { .SUBTRACT { .LOAD PARAMETER 0 0; .CONSTANT INT 1; }; };
+                        .CALL { .LOAD VARIABLE 0; } { .SUBTRACT { .LOAD PARAMETER 0 0; .CONSTANT INT 2; }; };
+                    }; };
+                };
+                .CALL { .LOAD VARIABLE 0; } { .CONSTANT INT 15; };"),
+            "610"
+        );
+    }
+
+    #[test]
+    fn conditionals_and_loops_as_values() {
+        assert_eq!(
+            run(".STORE 0 { .IF CONDITION { .CONSTANT BOOL false; } THEN {
+                    .RETURN { .CONSTANT INT 1; };
+                } ELSE {
+                    .RETURN { .CONSTANT INT 2; };
+                }; };
+                .STORE 1 { .WHILE CONDITION { .CONSTANT BOOL true; } THEN { .RETURN { .CONSTANT INT 40; }; }; };
+                .ADD { .LOAD VARIABLE 0; .LOAD VARIABLE 1; };"),
+            "42"
+        );
+        assert_eq!(
+            run(".STORE 0 { .CONSTANT INT 0; };
+                .STORE 1 { .CONSTANT INT 0; };
+                .WHILE CONDITION { .LESSTHAN { .LOAD VARIABLE 0; .CONSTANT INT 10; }; } THEN {
+                    .STORE 0 { .ADD { .LOAD VARIABLE 0; .CONSTANT INT 1; }; };
+                    .IF CONDITION { .EQUALS { .LOAD VARIABLE 0; .CONSTANT INT 3; }; } THEN { .CONTINUE; };
+                    .IF CONDITION { .EQUALS { .LOAD VARIABLE 0; .CONSTANT INT 6; }; } THEN { .BREAK; };
+                    .STORE 1 { .ADD { .LOAD VARIABLE 1; .LOAD VARIABLE 0; }; };
+                };
+                .LOAD VARIABLE 1;"),
+            "12"
+        );
+    }
+
+    #[test]
+    fn classes() {
+        let class =
+            ".COMPOUND \"class_0\" { INT; CHAR[]; } FIELDS \"Named\" { \"x\"; \"to_string\"; };
+            .COMPOUND \"class_1\" { INT; INT; } FIELDS \"Point\" { \"x\"; \"y\"; };";
+
+        assert_eq!(
+            run(&format!(
+                "{} .STORE 0 {{ .CONSTANT class_1 {{ .CONSTANT INT 1; .CONSTANT INT 2; }}; }};
+                .ASSIGN {{ .INDEX {{ .LOAD VARIABLE 0; }} {{ .CONSTANT INT 1; }}; }} {{ .CONSTANT INT 5; }};
+                .LOAD VARIABLE 0;",
+                class
+            )),
+            "Point { x: 1, y: 5 }"
+        );
+        assert_eq!(
+            run(&format!(
+                "{} .CONSTANT class_0 {{ .CONSTANT INT 7;
+                    .FUNCTION \"\" 0 CHAR[] ARGUMENTS {{ class_0; }} FREE {{ }} THEN {{
+                        .RETURN {{ .PUSH {{ .CONSTANT CHAR[] \"x=\"; }} {{
+                            .INDEX {{ .LOAD PARAMETER 0 0; }} {{ .CONSTANT INT 0; }};
+                        }}; }};
+                    }};
+                }};",
+                class
+            )),
+            "x=7"
+        );
+    }
+
+    #[test]
+    fn hashmaps() {
+        assert_eq!(
+            run(".STORE 0 { .HASHMAP CHAR[] INT {
+                    .CONSTANT CHAR[] \"b\"; .CONSTANT INT 2;
+                    .CONSTANT CHAR[] \"a\"; .CONSTANT INT 1;
+                    .CONSTANT CHAR[] \"c\"; .CONSTANT INT 3;
+                }; };
+                .ASSIGN { .INDEX HASHMAP { .LOAD VARIABLE 0; } { .CONSTANT CHAR[] \"c\"; }; } { .CONSTANT VOID; };
+                .ASSIGN { .INDEX HASHMAP { .LOAD VARIABLE 0; } { .CONSTANT CHAR[] \"d\"; }; } { .CONSTANT INT 4; };
+                .LOAD VARIABLE 0;"),
+            "{\"a\": 1, \"b\": 2, \"d\": 4}"
+        );
+        // Keys 1 till the length do not make it an array
+        assert_eq!(
+            run(".HASHMAP INT INT { .CONSTANT INT 2; .CONSTANT INT 20; .CONSTANT INT 1; .CONSTANT INT 10; };"),
+            "{1: 10, 2: 20}"
+        );
+        assert_eq!(
+            run(".LENGTH { .KEYS { .HASHMAP INT INT { .CONSTANT INT 5; .CONSTANT INT 1; }; }; };"),
+            "1"
+        );
+    }
+
+    #[test]
+    fn show_floats() {
+        assert_eq!(run(".CONSTANT FLOAT 2;"), "2");
+        assert_eq!(
+            run(".CALL intrinsic::show { .CONSTANT FLOAT[] [ .CONSTANT FLOAT 1; .CONSTANT FLOAT 2.5; ]; .CONSTANT CHAR[] \"FLOAT[]\"; };"),
+            "[1.0, 2.5]"
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let array = ".STORE 0 { .CONSTANT INT[] [ .CONSTANT INT 5; .CONSTANT INT 3; .CONSTANT INT 8; .CONSTANT INT 1; ]; };";
+
+        assert_eq!(
+            run(&format!(
+                "{} .SLICE {{ .LOAD VARIABLE 0; }} {{ .CONSTANT INT 1; }} {{ .CONSTANT INT 2; }};",
+                array
+            )),
+            "[3, 8]"
+        );
+        assert_eq!(
+            run(&format!(
+                "{} .PUSH {{ .LOAD VARIABLE 0; }} {{ .CONSTANT INT 9; }};
+                .POP {{ .LOAD VARIABLE 0; }} {{ .CONSTANT INT 0; }};
+                .LOAD VARIABLE 0;",
+                array
+            )),
+            "[3, 8, 1, 9]"
+        );
+        assert_eq!(
+            run(&format!(
+                "{} .CALL array::join {{ .CALL array::sort {{ .LOAD VARIABLE 0; }}; .CONSTANT CHAR[] \",\"; }};",
+                array
+            )),
+            "1,3,5,8"
+        );
+        assert_eq!(
+            run(".RANGE { .CONSTANT INT 3; } { .CONSTANT INT 0; } { .CONSTANT INT -1; };"),
+            "[3, 2, 1]"
+        );
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(
+            run(".CALL string::format { .CONSTANT CHAR[] \"{}-{}\";
+                    .CALL string::to_upper { .CONSTANT CHAR[] \"ab\"; };
+                    .CALL string::to_int { .CONSTANT CHAR[] \"x\"; .CONSTANT INT -1; };
+                };"),
+            "AB--1"
+        );
+        assert_eq!(
+            run(".INDEX { .CONSTANT CHAR[] \"loop\"; } { .CONSTANT INT 2; };"),
+            "o"
+        );
+        assert_eq!(
+            run(".LENGTH { .CALL string::split { .CONSTANT CHAR[] \"a,b,,c\"; .CONSTANT CHAR[] \",\"; }; };"),
+            "4"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let failure = error(
+            ".LOCATION \"main.loop\" 1;
+            .FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN {
+                .LOCATION \"main.loop\" 2;
+                .RETURN { .ADD { .CONSTANT INT 1; .CONSTANT VOID; }; };
+            };
+            .LOCATION \"main.loop\" 4;
+            .CALL { .LOAD VARIABLE 0; } { };",
+        );
+
+        match failure {
+            RuntimeError::Interpreter {
+                message,
+                traceback,
+                location,
+            } => {
+                assert_eq!(message, "main.loop:2: attempt to do arithmetic on null");
+                assert_eq!(location.map(|location| location.line), Some(2));
+                assert_eq!(
+                    traceback.as_deref(),
+                    Some("stack traceback:\n\tmain.loop:4: in function call")
+                );
+            }
+            error => panic!("unexpected error {}", error),
+        }
+
+        assert_eq!(
+            error(".CALL { .CONSTANT INT 1; } { };").to_string(),
+            "attempt to call an int"
+        );
+    }
+
+    #[test]
+    fn stack_overflow() {
+        // Like the binary does, the program runs on a thread with a stack big enough for it
+        let failure = std::thread::Builder::new()
+            .stack_size(Interpreter::STACK_SIZE)
+            .spawn(|| {
+                error(
+                    ".FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN {
+                        .LOCATION \"main.loop\" 2;
+                        .RETURN { .CALL { .LOAD VARIABLE 0; } { }; };
+                    };
+                    .LOCATION \"main.loop\" 4;
+                    .CALL { .LOAD VARIABLE 0; } { };",
+                )
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+
+        match failure {
+            RuntimeError::Interpreter {
+                message, traceback, ..
+            } => {
+                assert_eq!(message, "main.loop:2: stack overflow");
+
+                // Only the first and last calls are shown, with "..." in between
+                let traceback = traceback.unwrap();
+                assert_eq!(traceback.lines().count(), 22);
+                assert!(traceback.len() < 1000);
+                assert!(traceback.contains("\n\t...\n"));
+                assert!(traceback.ends_with("\tmain.loop:4: in function call"));
+            }
+            error => panic!("unexpected error {}", error),
+        }
+    }
+
+    #[test]
+    fn state_is_kept_between_runs() {
+        let mut interpreter = Interpreter::default();
+
+        run_with(&mut interpreter, ".STORE 0 { .CONSTANT INT 20; };").unwrap();
+        run_with(
+            &mut interpreter,
+            ".FUNCTION \"var_1\" 0 INT ARGUMENTS { INT; } FREE { } THEN {
+                .RETURN { .ADD { .LOAD VARIABLE 0; .LOAD PARAMETER 0 0; }; };
+            };",
+        )
+        .unwrap();
+
+        assert!(run_with(&mut interpreter, ".CALL { .CONSTANT INT 1; } { };").is_err());
+        assert_eq!(
+            run_with(
+                &mut interpreter,
+                ".CALL { .LOAD VARIABLE 1; } { .CONSTANT INT 22; };"
+            )
+            .unwrap(),
+            ValueType::Integer(42)
+        );
+    }
+
+    #[test]
+    fn library_signatures() {
+        let signatures = parse_signatures(
+            "int add(int a, int b); const char* greet(const char *name);
+            double scale(double, unsigned char); void reset(void);",
+        )
+        .unwrap();
+
+        assert_eq!(
+            signatures["add"],
+            Signature {
+                parameters: vec![
+                    CType::Integer {
+                        size: 4,
+                        signed: true
+                    },
+                    CType::Integer {
+                        size: 4,
+                        signed: true
+                    }
+                ],
+                returns: CType::Integer {
+                    size: 4,
+                    signed: true
+                },
+            }
+        );
+        assert_eq!(signatures["greet"].parameters, vec![CType::String]);
+        assert_eq!(signatures["greet"].returns, CType::String);
+        assert_eq!(
+            signatures["scale"].parameters,
+            vec![
+                CType::Double,
+                CType::Integer {
+                    size: 1,
+                    signed: false
+                }
+            ]
+        );
+        assert_eq!(signatures["reset"].parameters, vec![]);
+        assert_eq!(signatures["reset"].returns, CType::Void);
+
+        assert!(parse_signatures("int printf(const char *format, ...);").is_err());
+        assert!(parse_signatures("float half(float value);").is_err());
+        assert_eq!(
+            parse_signatures("int sum(int a, int b, int c, int d, int e);").err(),
+            Some("\"sum\" has 5 parameters, functions of libraries can have at most 4".to_string())
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! The values the interpreter works with. Arrays, hashmaps and instances are shared like the tables
+//! of Lua are, changing one changes it everywhere it is stored.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use vinci::ast::instructions::Node;
+
+#[derive(Clone)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    // Characters are strings of a single character
+    String(Rc<str>),
+    Array(Rc<RefCell<Vec<Value>>>),
+    Hashmap(Rc<RefCell<Hashmap>>),
+    // The values of an instance are in the order of the fields of its class
+    Instance(Rc<Class>, Rc<RefCell<Vec<Value>>>),
+    Function(Rc<Function>),
+}
+
+impl Value {
+    pub fn string(string: &str) -> Value {
+        Value::String(Rc::from(string))
+    }
+
+    pub fn array(values: Vec<Value>) -> Value {
+        Value::Array(Rc::new(RefCell::new(values)))
+    }
+
+    /// Only null and false are false, like in Lua
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Null | Value::Boolean(false))
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Integer(integer) => Some(*integer as f64),
+            Value::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+
+    /// Numbers and strings are equal when their values are, everything else only when it is the
+    /// same value
+    pub fn equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => Rc::ptr_eq(a, b),
+            (Value::Hashmap(a), Value::Hashmap(b)) => Rc::ptr_eq(a, b),
+            (Value::Instance(_, a), Value::Instance(_, b)) => Rc::ptr_eq(a, b),
+            (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+            (a, b) => match (a.as_number(), b.as_number()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+
+    /// What the value is, used in errors like "attempt to call an int"
+    pub fn describe(&self) -> String {
+        let description = match self {
+            Value::Null => "null",
+            Value::Boolean(_) => "a bool",
+            Value::Integer(_) => "an int",
+            Value::Float(_) => "a float",
+            Value::String(_) => "a string",
+            Value::Array(_) => "an array",
+            Value::Hashmap(_) => "a hashmap",
+            Value::Instance(class, _) => return format!("an instance of {}", class.name),
+            Value::Function(_) => "a function",
+        };
+
+        description.to_string()
+    }
+}
+
+/// A class, instances use it to know the names of their fields
+pub struct Class {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+impl Class {
+    /// Position of the "to_string" method, which is used to show instances
+    pub fn to_string_method(&self) -> Option<usize> {
+        self.fields.iter().position(|field| field == "to_string")
+    }
+}
+
+pub struct Function {
+    pub unique_identifier: i64,
+    pub parameters: usize,
+    pub body: Rc<Vec<Node>>,
+    // The parameters of the functions this one was created in, which it can still use
+    pub frame: Option<Rc<Frame>>,
+}
+
+/// The parameters of a single call
+pub struct Frame {
+    pub unique_identifier: i64,
+    pub parameters: RefCell<Vec<Value>>,
+    pub parent: Option<Rc<Frame>>,
+}
+
+impl Frame {
+    /// The innermost frame of the function with this identifier
+    pub fn find(frame: &Rc<Frame>, unique_identifier: i64) -> Option<&Rc<Frame>> {
+        let mut current = Some(frame);
+
+        while let Some(frame) = current {
+            if frame.unique_identifier == unique_identifier {
+                return Some(frame);
+            }
+
+            current = frame.parent.as_ref();
+        }
+
+        None
+    }
+}
+
+/// Keys of a hashmap, whole numbers are the same key whether they are an int or a float
+#[derive(PartialEq, Eq, Hash)]
+enum Key {
+    Boolean(bool),
+    Integer(i64),
+    Float(u64),
+    String(Rc<str>),
+    Reference(usize),
+}
+
+impl Key {
+    /// [None] for values that can not be a key, which are null and NaN
+    fn from_value(value: &Value) -> Option<Key> {
+        let key = match value {
+            Value::Null => return None,
+            Value::Boolean(boolean) => Key::Boolean(*boolean),
+            Value::Integer(integer) => Key::Integer(*integer),
+            Value::Float(float) if float.is_nan() => return None,
+            Value::Float(float) if float.fract() == 0.0 && float.abs() < 9.2e18 => {
+                Key::Integer(*float as i64)
+            }
+            Value::Float(float) => Key::Float(float.to_bits()),
+            Value::String(string) => Key::String(string.clone()),
+            Value::Array(array) => Key::Reference(Rc::as_ptr(array) as *const u8 as usize),
+            Value::Hashmap(hashmap) => Key::Reference(Rc::as_ptr(hashmap) as *const u8 as usize),
+            Value::Instance(_, values) => Key::Reference(Rc::as_ptr(values) as *const u8 as usize),
+            Value::Function(function) => Key::Reference(Rc::as_ptr(function) as *const u8 as usize),
+        };
+
+        Some(key)
+    }
+}
+
+/// The entries of a hashmap in the order they were added, removing one moves the last entry into
+/// its place
+#[derive(Default)]
+pub struct Hashmap {
+    entries: Vec<(Value, Value)>,
+    positions: HashMap<Key, usize>,
+}
+
+impl Hashmap {
+    pub fn get(&self, key: &Value) -> Value {
+        Key::from_value(key)
+            .and_then(|key| self.positions.get(&key))
+            .map_or(Value::Null, |position| self.entries[*position].1.clone())
+    }
+
+    /// Setting a key to null removes it
+    pub fn set(&mut self, key: Value, value: Value) -> Result<(), String> {
+        let hashed = match Key::from_value(&key) {
+            Some(hashed) => hashed,
+            None => return Err(format!("{} can not be used as a key", key.describe())),
+        };
+
+        match (self.positions.get(&hashed).copied(), value) {
+            (Some(position), Value::Null) => {
+                self.positions.remove(&hashed);
+                self.entries.swap_remove(position);
+
+                if let Some((moved, _)) = self.entries.get(position) {
+                    if let Some(moved) = Key::from_value(moved) {
+                        self.positions.insert(moved, position);
+                    }
+                }
+            }
+            (Some(position), value) => self.entries[position].1 = value,
+            (None, Value::Null) => {}
+            (None, value) => {
+                self.positions.insert(hashed, self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[(Value, Value)] {
+        &self.entries
+    }
+
+    /// The length of a hashmap is the amount of keys from 1 onwards that it has, like in Lua
+    pub fn len(&self) -> usize {
+        let mut length = 0;
+        while self
+            .positions
+            .contains_key(&Key::Integer(length as i64 + 1))
+        {
+            length += 1;
+        }
+
+        length
+    }
+}
+
+/// Formats a number the way Lua does, with up to 14 significant digits
+pub fn format_number(number: f64) -> String {
+    if number.is_nan() {
+        return "nan".to_string();
+    }
+    if number.is_infinite() {
+        return if number > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+
+    // Rounding to 14 digits first tells which notation is used, like "%.14g" does
+    let scientific = format!("{:.13e}", number);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+
+    if !(-4..14).contains(&exponent) {
+        let mantissa = trim_zeros(mantissa);
+        let sign = if exponent < 0 { '-' } else { '+' };
+
+        return format!("{}e{}{:02}", mantissa, sign, exponent.abs());
+    }
+
+    let decimals = (13 - exponent).max(0) as usize;
+    trim_zeros(&format!("{:.*}", decimals, number)).to_string()
+}
+
+fn trim_zeros(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
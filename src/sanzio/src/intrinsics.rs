@@ -0,0 +1,214 @@
+//! The functions the standard library of Loop can not write in Loop itself. They are stored in the
+//! global "intrinsic" table of Lua, and are called like `intrinsic.println(value)`.
+use crate::values::to_value_type;
+use crate::{format_with_type, write_output};
+use mlua::{Function, Lua, Result, Value};
+use std::io::{self, BufRead};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the metatable arrays created by intrinsics get, in the registry of Lua
+const ARRAY_METATABLE: &str = "loop_array_metatable";
+
+type FloatFunction = fn(f64) -> f64;
+
+/// Makes every intrinsic available to the code that is run afterwards
+pub fn register(lua: &Lua) -> Result<()> {
+    let intrinsic = lua.create_table()?;
+
+    // io
+    intrinsic.set(
+        "print",
+        lua.create_function(|_, value: Value| {
+            write_output(&format_value(&value));
+            Ok(())
+        })?,
+    )?;
+    intrinsic.set(
+        "println",
+        lua.create_function(|_, value: Value| {
+            write_output(&format!("{}\n", format_value(&value)));
+            Ok(())
+        })?,
+    )?;
+    // Used by the compiler for the value a program returns when it holds floats
+    intrinsic.set(
+        "show",
+        lua.create_function(|_, (value, tp): (Value, String)| {
+            Ok(format_with_type(to_value_type(&value), &tp))
+        })?,
+    )?;
+    intrinsic.set(
+        "read_line",
+        lua.create_function(|_, ()| {
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line).ok();
+
+            Ok(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+        })?,
+    )?;
+
+    // strings
+    intrinsic.set(
+        "to_string",
+        lua.create_function(|_, value: Value| Ok(format_value(&value)))?,
+    )?;
+    // A string that is not a number becomes 0, "is_int" tells whether it is one
+    intrinsic.set(
+        "parse_int",
+        lua.create_function(|_, text: String| Ok(text.trim().parse::<i64>().unwrap_or(0)))?,
+    )?;
+    intrinsic.set(
+        "parse_float",
+        lua.create_function(|_, text: String| Ok(text.trim().parse::<f64>().unwrap_or(0.0)))?,
+    )?;
+    intrinsic.set(
+        "is_int",
+        lua.create_function(|_, text: String| Ok(text.trim().parse::<i64>().is_ok()))?,
+    )?;
+    intrinsic.set(
+        "is_float",
+        lua.create_function(|_, text: String| Ok(text.trim().parse::<f64>().is_ok()))?,
+    )?;
+    intrinsic.set(
+        "upper",
+        lua.create_function(|_, text: String| Ok(text.to_uppercase()))?,
+    )?;
+    intrinsic.set(
+        "lower",
+        lua.create_function(|_, text: String| Ok(text.to_lowercase()))?,
+    )?;
+    intrinsic.set(
+        "trim",
+        lua.create_function(|_, text: String| Ok(text.trim().to_string()))?,
+    )?;
+    intrinsic.set(
+        "find",
+        lua.create_function(|_, (text, part): (String, String)| {
+            Ok(text.find(&part).map(|index| index as i64).unwrap_or(-1))
+        })?,
+    )?;
+    intrinsic.set(
+        "replace",
+        lua.create_function(|_, (text, from, to): (String, String, String)| {
+            Ok(text.replace(&from, &to))
+        })?,
+    )?;
+    intrinsic.set(
+        "repeat",
+        lua.create_function(|_, (text, count): (String, i64)| {
+            Ok(text.repeat(count.max(0) as usize))
+        })?,
+    )?;
+
+    // Arrays need the same metatable as the ones created by the compiled code, so they can be
+    // pushed to
+    let concat: Function = lua
+        .load("return function(a, b) return table.insert(a, b) end")
+        .eval()?;
+    let array_metatable = lua.create_table()?;
+    array_metatable.set("__concat", concat)?;
+    lua.set_named_registry_value(ARRAY_METATABLE, array_metatable)?;
+
+    intrinsic.set(
+        "split",
+        lua.create_function(|lua, (text, separator): (String, String)| {
+            let parts: Vec<String> = if separator.is_empty() {
+                text.chars().map(String::from).collect()
+            } else {
+                text.split(separator.as_str()).map(String::from).collect()
+            };
+
+            let array = lua.create_sequence_from(parts)?;
+            array.set_metatable(Some(lua.named_registry_value(ARRAY_METATABLE)?));
+
+            Ok(array)
+        })?,
+    )?;
+
+    // arrays
+    intrinsic.set(
+        "join",
+        lua.create_function(|_, (values, separator): (Vec<String>, String)| {
+            Ok(values.join(&separator))
+        })?,
+    )?;
+
+    // math
+    let float_functions: [(&str, FloatFunction); 5] = [
+        ("sqrt", f64::sqrt),
+        ("sin", f64::sin),
+        ("cos", f64::cos),
+        ("tan", f64::tan),
+        ("log", f64::ln),
+    ];
+    for (name, function) in float_functions {
+        intrinsic.set(
+            name,
+            lua.create_function(move |_, value: f64| Ok(function(value)))?,
+        )?;
+    }
+
+    let rounding_functions: [(&str, FloatFunction); 3] = [
+        ("floor", f64::floor),
+        ("ceil", f64::ceil),
+        ("round", f64::round),
+    ];
+    for (name, function) in rounding_functions {
+        intrinsic.set(
+            name,
+            lua.create_function(move |_, value: f64| Ok(function(value) as i64))?,
+        )?;
+    }
+
+    intrinsic.set(
+        "pow",
+        lua.create_function(|_, (base, exponent): (f64, f64)| Ok(base.powf(exponent)))?,
+    )?;
+
+    // Xorshift, seeded with the time the program started
+    let mut state = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_nanos() as u64)
+        .unwrap_or(0)
+        | 1;
+    intrinsic.set(
+        "random",
+        lua.create_function_mut(move |_, ()| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            // The upper 53 bits fit exactly in the fraction of a float
+            Ok((state >> 11) as f64 / (1u64 << 53) as f64)
+        })?,
+    )?;
+
+    // time
+    intrinsic.set(
+        "now",
+        lua.create_function(|_, ()| {
+            Ok(SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs_f64())
+                .unwrap_or(0.0))
+        })?,
+    )?;
+    intrinsic.set(
+        "sleep",
+        lua.create_function(|_, milliseconds: i64| {
+            std::thread::sleep(Duration::from_millis(milliseconds.max(0) as u64));
+            Ok(())
+        })?,
+    )?;
+
+    lua.globals().set("intrinsic", intrinsic)
+}
+
+/// Formats a value the way Loop shows it, strings are shown as they are
+fn format_value(value: &Value) -> String {
+    match value {
+        // An empty string would be shown as an empty array
+        Value::String(string) => string.to_string_lossy().to_string(),
+        value => to_value_type(value).format(),
+    }
+}
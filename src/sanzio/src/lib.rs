@@ -1,42 +1,80 @@
 #[cfg(feature = "mlua")]
-use mlua::{Lua, MultiValue, Value};
+use mlua::{HookTriggers, Lua, MultiValue, Value};
 
+use std::collections::BTreeMap;
 use std::ops::Deref;
 
 use std::str;
-use vinci::ast::instructions::memory::LoadType;
+use std::sync::atomic::{AtomicBool, Ordering};
+use vinci::ast::instructions::memory::{CompoundType, LoadType};
 use vinci::ast::instructions::suffix::BinaryOperation;
 use vinci::ast::instructions::Node;
 use vinci::types::ValueType;
+mod arrays;
 mod exception;
-use exception::throw_runtime_exception;
+pub mod interpreter;
+#[cfg(feature = "mlua")]
+mod intrinsics;
+pub mod library;
+pub mod source_map;
+mod strings;
+#[cfg(feature = "mlua")]
+mod values;
+use arrays::{array_method, ARRAY_METATABLE};
+use exception::throw_runtime_warning;
+pub use exception::RuntimeError;
+pub use interpreter::Interpreter;
+use library::{library_file, library_signatures};
+use source_map::SourceMap;
+use strings::string_method;
+
+/// How long running a program took, and the most memory in bytes it used while doing so
+#[derive(Debug, Clone, Copy)]
+pub struct Execution {
+    pub duration: std::time::Duration,
+    // None when the backend can not measure memory
+    pub peak_memory: Option<usize>,
+}
+
+/// Whether "print" and "println" write anything, they are silenced when a program is run again
+/// for a benchmark
+static OUTPUT_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enables or disables what programs print, for both backends
+pub fn set_output_enabled(enabled: bool) {
+    OUTPUT_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Prints for "print" and "println", unless output is disabled
+fn write_output(text: &str) {
+    use std::io::Write;
+
+    if OUTPUT_ENABLED.load(Ordering::Relaxed) {
+        print!("{}", text);
+        std::io::stdout().flush().ok();
+    }
+}
+
+/// Formats the value of a program with the type the compiler gave it, whole floats can not be
+/// told apart from ints anymore otherwise
+fn format_with_type(value: ValueType, tp: &str) -> String {
+    match vinci::parse_type(tp) {
+        Ok(tp) => value.with_type(&tp).format(),
+        Err(_) => value.format(),
+    }
+}
 
 pub struct Sanzio {
     #[cfg(feature = "mlua")]
     lua: Lua,
 }
 
+/// Converts the value a program returned into the value it was in Loop
 #[cfg(feature = "mlua")]
 pub fn parse_multivalue(m: MultiValue) -> vinci::types::ValueType {
     let vec = m.into_vec();
-    let value = vec.first().unwrap_or(&Value::Nil);
-
-    match value {
-        Value::Boolean(b) => ValueType::Boolean(*b),
-        Value::Integer(n) => ValueType::Integer(*n as i64),
-        Value::Number(n) => ValueType::Integer(n.round() as i64),
-        Value::String(str) => {
-            let chars: Vec<ValueType> = str
-                .to_str()
-                .unwrap()
-                .chars()
-                .map(ValueType::Character)
-                .collect();
-
-            ValueType::Array(Box::new(chars))
-        }
-        _ => ValueType::Void,
-    }
+
+    values::to_value_type(vec.first().unwrap_or(&Value::Nil))
 }
 
 impl Default for Sanzio {
@@ -50,35 +88,115 @@ impl Sanzio {
     /// This function is unsafe due to Sanzio allowing C FFI. And FFI is inherintly unsafe due to
     /// the fact that C code is unsafe.
     pub unsafe fn new() -> Sanzio {
+        #[cfg(feature = "mlua")]
+        let lua = Lua::unsafe_new();
+
+        // The standard library calls into these, they have to exist before anything runs
+        #[cfg(feature = "mlua")]
+        intrinsics::register(&lua).expect("unable to register the intrinsics");
+
         Sanzio {
             #[cfg(feature = "mlua")]
-            lua: Lua::unsafe_new(),
+            lua,
         }
     }
 
+    /// Runs a program, the Lua state is kept so a following run can use what this one defined
     #[cfg(feature = "mlua")]
-    pub fn run(&mut self, ast: vinci::ast::AST) -> MultiValue {
-        use std::process::exit;
+    pub fn run(&mut self, ast: vinci::ast::AST) -> Result<MultiValue<'_>, RuntimeError> {
+        let (code, source_map) = Sanzio::compile_to_lua_with_source_map(&ast)?;
 
-        let mut backend = LuaBackend::new();
-
-        backend.compile_nodes_global(&ast.nodes);
+        self.run_lua(&code, &source_map)
+    }
 
-        let result = self.lua.load(backend.code.as_str()).eval::<MultiValue>();
+    /// Runs Lua code that was generated by [Sanzio::compile_to_lua_with_source_map], errors point
+    /// at the Loop code using the source map
+    #[cfg(feature = "mlua")]
+    pub fn run_lua(
+        &self,
+        code: &str,
+        source_map: &SourceMap,
+    ) -> Result<MultiValue<'_>, RuntimeError> {
+        let result = self
+            .lua
+            .load(code)
+            .set_name(format!("={}", source_map::CHUNK_NAME))
+            .and_then(|chunk| chunk.eval::<MultiValue>());
+
+        result.map_err(|error| {
+            let error = error.to_string();
+            let location = source_map.locate(&error).cloned();
+            let error = source_map.rewrite(&error);
+
+            // The first line is the error itself, the rest is the traceback
+            match error.split_once('\n') {
+                Some((message, traceback)) => RuntimeError::Lua {
+                    message: message.to_string(),
+                    traceback: Some(traceback.trim().to_string()),
+                    location,
+                },
+                None => RuntimeError::Lua {
+                    message: error,
+                    traceback: None,
+                    location,
+                },
+            }
+        })
+    }
 
-        if let Err(result) = result {
-            println!("{}", result);
-            exit(1);
+    /// Same as [Sanzio::run_lua], but also measures how long it took and the most memory Lua used
+    #[cfg(feature = "mlua")]
+    pub fn run_lua_measured(
+        &self,
+        code: &str,
+        source_map: &SourceMap,
+    ) -> Result<(MultiValue<'_>, Execution), RuntimeError> {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+        use std::time::Instant;
+
+        // Memory is sampled while running, code compiled by the JIT does not call the hook so the
+        // actual peak can be a bit higher
+        let peak_memory = Arc::new(AtomicUsize::new(self.lua.used_memory()));
+        let sampled = peak_memory.clone();
+        self.lua
+            .set_hook(HookTriggers::every_nth_instruction(1000), move |lua, _| {
+                sampled.fetch_max(lua.used_memory(), Ordering::Relaxed);
+                Ok(())
+            })
+            .expect("unable to measure the memory of Lua");
+
+        let start = Instant::now();
+        let result = self.run_lua(code, source_map);
+        let duration = start.elapsed();
+
+        self.lua.remove_hook();
+        peak_memory.fetch_max(self.lua.used_memory(), Ordering::Relaxed);
+
+        let execution = Execution {
+            duration,
+            peak_memory: Some(peak_memory.load(Ordering::Relaxed)),
         };
 
-        result.unwrap()
+        result.map(|result| (result, execution))
+    }
+
+    pub fn compile_to_lua(ast: &vinci::ast::AST) -> Result<String, RuntimeError> {
+        Ok(Sanzio::compile_to_lua_with_source_map(ast)?.0)
     }
 
-    pub fn compile_to_lua(ast: &vinci::ast::AST) -> String {
+    /// Compiles to Lua, together with where every line of it came from in the Loop code
+    pub fn compile_to_lua_with_source_map(
+        ast: &vinci::ast::AST,
+    ) -> Result<(String, SourceMap), RuntimeError> {
         let mut backend = LuaBackend::new();
         backend.compile_nodes_global(&ast.nodes);
+        backend.define_methods();
 
-        backend.code
+        match backend.error {
+            Some(error) => Err(error),
+            None => Ok((backend.code, backend.source_map)),
+        }
     }
 }
 
@@ -87,6 +205,18 @@ struct LuaBackend {
     doing_statement: bool,
     library_paths: Vec<String>,
     library_names: Vec<String>,
+    // Unique identifier of every loop that is being compiled, the last one is the innermost loop
+    loops: Vec<u32>,
+    loop_count: u32,
+    source_map: SourceMap,
+    // The first error while generating, generating continues so the code stays complete
+    error: Option<RuntimeError>,
+    // Line of the Lua code that is being added to, starting at 1
+    line: usize,
+    // Local name and Lua function of every string and array method that is called
+    methods: BTreeMap<String, String>,
+    // Where the last line of the prelude ends, before its newline
+    prelude_end: usize,
 }
 
 impl LuaBackend {
@@ -97,29 +227,69 @@ impl LuaBackend {
     ///      print(clib.dub(2))",
     ///  ),
     pub fn new() -> LuaBackend {
-        LuaBackend {
+        let mut backend = LuaBackend {
             #[cfg(feature = "libloading")]
             code: String::from("\
 ffi = require(\"ffi\")
 getmetatable('').__index = function(str,i) return string.sub(str,i,i) end
 getmetatable('').__call = function(str,i,j) if type(i)~='table' then return string.sub(str,i,j) end end
+loop_classes = loop_classes or {}
+loop_hashmap_metatable = { __hashmap = true }
 "),
             #[cfg(not(feature = "libloading"))]
             code: String::from("\
 getmetatable('').__index = function(str,i) return string.sub(str,i,i) end
 getmetatable('').__call = function(str,i,j) if type(i)~='table' then return string.sub(str,i,j) end end
+loop_classes = loop_classes or {}
+loop_hashmap_metatable = { __hashmap = true }
 "),
             doing_statement: false,
             library_paths: vec![],
             library_names: vec![],
+            loops: vec![],
+            loop_count: 0,
+            source_map: SourceMap::default(),
+            error: None,
+            line: 1,
+            methods: BTreeMap::new(),
+            prelude_end: 0,
+        };
+
+        backend.line += backend.code.matches('\n').count();
+        backend.prelude_end = backend.code.len() - 1;
+        backend
+    }
+
+    /// Defines the string and array methods that were called as locals, so a function is only
+    /// created once instead of on every call. They are put on the last line of the prelude, which
+    /// keeps the lines in the source map the same.
+    fn define_methods(&mut self) {
+        if self.methods.is_empty() {
+            return;
+        }
+
+        let mut definitions = format!(" local loop_array_metatable = {}", ARRAY_METATABLE);
+
+        for (name, function) in &self.methods {
+            definitions.push_str(&format!(" local {} = {}", name, function));
+        }
+
+        self.code.insert_str(self.prelude_end, &definitions);
+    }
+
+    /// Remembers an error, only the first one is returned
+    fn add_error(&mut self, message: String) {
+        if self.error.is_none() {
+            self.error = Some(RuntimeError::Generation(message));
         }
     }
 
     fn add_code(&mut self, code: String) {
-        self.code.push_str(code.as_str());
+        self.add_code_str(code.as_str());
     }
 
     fn add_code_str(&mut self, code: &str) {
+        self.line += code.matches('\n').count();
         self.code.push_str(code);
     }
 
@@ -151,8 +321,9 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
 
                 self.add_code_str("end)");
             }
-            ValueType::Compound(_, values) => {
-                self.add_code_str("({");
+            // Instances get the metatable of their class, which is how they are shown
+            ValueType::Compound(name, values) => {
+                self.add_code_str("setmetatable({");
 
                 for (key, value) in values.iter().enumerate() {
                     self.add_code(format!("[{} + 1] = ", key));
@@ -163,8 +334,40 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
                     }
                 }
 
+                self.add_code(format!("}}, loop_classes[\"{}\"])", name));
+            }
+            // The class of an instance read back from Lua is unknown, its properties are stored in
+            // order like a compound without one
+            ValueType::Instance(_, properties) => {
+                self.add_code_str("({");
+
+                for (key, (_, value)) in properties.iter().enumerate() {
+                    self.add_code(format!("[{} + 1] = ", key));
+                    self.add_constant_value(value);
+
+                    if key + 1 != properties.len() {
+                        self.add_code_str(",");
+                    }
+                }
+
                 self.add_code_str("})");
             }
+            ValueType::Hashmap(values) => {
+                self.add_code_str("{");
+
+                for (index, (key, value)) in values.iter().enumerate() {
+                    self.add_code_str("[");
+                    self.add_constant_value(key);
+                    self.add_code_str("] = ");
+                    self.add_constant_value(value);
+
+                    if index + 1 != values.len() {
+                        self.add_code_str(",");
+                    }
+                }
+
+                self.add_code_str("}");
+            }
             ValueType::Array(a) => {
                 let items = a.deref();
 
@@ -234,19 +437,34 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
 
                 match suffix.operation {
                     BinaryOperation::ADD => self.add_code_str("+"),
-                    BinaryOperation::SUBTRACT => self.add_code_str("-"),
+                    // Subtracting a negative constant would otherwise start a comment with "--"
+                    BinaryOperation::SUBTRACT => self.add_code_str("- "),
                     BinaryOperation::MULTIPLY => self.add_code_str("*"),
                     BinaryOperation::DIVIDE => self.add_code_str("/"),
                     BinaryOperation::POWER => self.add_code_str("^"),
                     BinaryOperation::GREATERTHAN => self.add_code_str(">"),
+                    BinaryOperation::LESSTHAN => self.add_code_str("<"),
+                    BinaryOperation::GREATERTHANOREQUAL => self.add_code_str(">="),
+                    BinaryOperation::LESSTHANOREQUAL => self.add_code_str("<="),
                     BinaryOperation::EQUALS => self.add_code_str("=="),
-                    BinaryOperation::NOTEQUALS => self.add_code_str("!="),
+                    BinaryOperation::NOTEQUALS => self.add_code_str("~="),
                     BinaryOperation::MODULO => self.add_code_str("%"),
                 };
 
                 self.compile_node(&suffix.right);
                 self.add_code_str(")");
             }
+            Node::NOT(right) => {
+                self.add_code_str("(not ");
+                self.compile_node(right);
+                self.add_code_str(")");
+            }
+            Node::NEGATE(right) => {
+                // The space prevents "--" when negating a negative number, which is a comment in Lua
+                self.add_code_str("(- ");
+                self.compile_node(right);
+                self.add_code_str(")");
+            }
             Node::CONDITIONAL(cond) => {
                 let stmt = self.doing_statement;
 
@@ -324,24 +542,45 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
 
                     let str = e.clone().char_arr_to_string();
                     let parts: Vec<&str> = str.split("::").collect();
-                    if parts[1] == "println" || parts[1] == "print" {
-                        self.add_code(format!("{}.{}(", parts[0], parts[1]))
+
+                    // Methods built into strings and arrays are implemented in Lua itself
+                    if parts[0] == "string" || parts[0] == "array" {
+                        let function = if parts[0] == "string" {
+                            string_method(parts[1])
+                        } else {
+                            array_method(parts[1])
+                        };
+
+                        match function {
+                            Some(function) => {
+                                let name = format!("loop_{}_{}", parts[0], parts[1]);
+                                self.add_code(format!("{}(", name));
+                                self.methods.insert(name, function);
+                            }
+                            None => self.add_error(format!(
+                                "{}s do not have a method called \"{}\"",
+                                parts[0], parts[1]
+                            )),
+                        }
+
+                        self.compile_arguments(&call.arguments);
+                        self.add_code_str(")");
+                        return;
+                    }
+
+                    // Intrinsics are Lua functions, so their result does not have to be converted. Their
+                    // names can be keywords of Lua, like "repeat".
+                    let intrinsic = parts[0] == "intrinsic";
+                    if intrinsic {
+                        self.add_code(format!("{}[\"{}\"](", parts[0], parts[1]))
                     } else {
                         self.add_code(format!(
                             "(function() local res = {}.{}(",
                             parts[0], parts[1]
                         ));
                     }
-                    let mut index = 0;
-                    for argument in &call.arguments {
-                        index += 1;
-                        self.compile_node(argument);
-
-                        if index != call.arguments.len() {
-                            self.add_code_str(",");
-                        }
-                    }
-                    if parts[1] == "println" || parts[1] == "print" {
+                    self.compile_arguments(&call.arguments);
+                    if intrinsic {
                         self.add_code_str(")");
                     } else {
                         self.add_code(") if type(res) == \"cdata\" then return ffi.string(res) else return res end end)()".to_string());
@@ -352,32 +591,59 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
 
                     self.add_code_str("(");
 
-                    let mut index = 0;
-                    for argument in &call.arguments {
-                        index += 1;
-                        self.compile_node(argument);
-
-                        if index != call.arguments.len() {
-                            self.add_code_str(",");
-                        }
-                    }
+                    self.compile_arguments(&call.arguments);
 
                     self.add_code_str(")");
                 }
             }
             Node::WHILE(whi) => {
-                self.add_code_str("(function() while ");
+                let stmt = self.doing_statement;
+                let label = self.loop_count;
+                self.loop_count += 1;
+                self.loops.push(label);
+
+                // Only a loop used as a value is wrapped, as "return" would otherwise return from the
+                // wrapper instead of the surrounding function
+                if stmt {
+                    self.add_code_str("(function() while ");
+                } else {
+                    self.add_code_str("while ");
+                }
+
                 self.compile_node(&whi.condition);
-                self.add_code_str(" do ");
+                self.add_code_str(" do do ");
 
+                self.doing_statement = false;
                 self.compile_nodes(&whi.body);
-                self.add_code_str(" end end)()");
+                self.doing_statement = stmt;
+
+                // Lua has no "continue", so it jumps past the body instead. The body is in its own
+                // block, as a "return" or "break" has to be the last statement of a block
+                self.add_code(format!(" end ::continue_{}:: end", label));
+
+                if stmt {
+                    self.add_code_str(" end)()");
+                }
+
+                self.loops.pop();
             }
+            // Same as "return", it has to be the last statement of a block
+            Node::BREAK => self.add_code_str("do break end"),
+            Node::CONTINUE => match self.loops.last() {
+                Some(label) => self.add_code(format!("goto continue_{}", label)),
+                None => self.add_error("continue is not allowed outside of a loop".to_string()),
+            },
             Node::INDEX(idx) => {
                 self.compile_node(&idx.to_index);
                 self.add_code_str("[");
                 self.compile_node(&idx.index);
-                self.add_code_str(" + 1]");
+
+                // Only arrays and strings are indexed by position, which starts at 1 in Lua
+                if idx.hashmap {
+                    self.add_code_str("]");
+                } else {
+                    self.add_code_str(" + 1]");
+                }
             }
             Node::SLICE(slice) => {
                 self.add_code_str("(function() if type(");
@@ -404,24 +670,18 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
                 self.compile_node(&*push.item);
             }
             Node::COPY(_) => {}
-            Node::LOADLIB(lib) => match self.get_lib_signiture(lib.clone().get_path()) {
+            Node::LOADLIB(lib) => match library_signatures(&lib.clone().get_path()) {
                 Ok(str) => {
-                    let extension = if cfg!(windows) { "dll" } else { "so" };
-
                     self.add_library_path(lib.clone().get_path());
                     self.add_library_namespace(lib.clone().namespace);
                     self.add_code(format!("ffi.cdef[[ {} ]] ", str.as_str()));
                     self.add_code(format!(
-                        "{} = ffi.load(\"./{}.{}\")",
+                        "{} = ffi.load(\"./{}\")",
                         lib.namespace,
-                        lib.clone().get_path(),
-                        extension
+                        library_file(&lib.clone().get_path())
                     ))
                 }
-                Err(str) => {
-                    throw_runtime_exception(str, None);
-                    unreachable!("Loadlib should crash at this point")
-                }
+                Err(str) => self.add_error(str),
             },
             Node::RETURN(rt) => {
                 self.doing_statement = true;
@@ -459,42 +719,124 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
                 self.compile_node(b);
                 self.add_code_str(")");
             }
-            Node::COMPOUND(_) => (),
+            Node::COMPOUND(compound) => self.compile_compound(compound),
+            Node::HASHMAP(hashmap) => {
+                // The metatable tells hashmaps apart from arrays, which are tables as well
+                self.add_code_str("setmetatable({");
+
+                for (index, (key, value)) in hashmap.values.iter().enumerate() {
+                    self.add_code_str("[");
+                    self.compile_node(key);
+                    self.add_code_str("] = ");
+                    self.compile_node(value);
+
+                    if index + 1 != hashmap.values.len() {
+                        self.add_code_str(",");
+                    }
+                }
+
+                self.add_code_str("}, loop_hashmap_metatable)");
+            }
+            Node::KEYS(hashmap) => {
+                // The keys are returned as an array, so they behave like every other array
+                self.add_code_str("(function(hashmap) local keys = {} for key in pairs(hashmap) do keys[#keys + 1] = key end return setmetatable(keys, { __concat = function(a, b) return table.insert(a, b) end }) end)(");
+                self.compile_node(hashmap);
+                self.add_code_str(")");
+            }
+            Node::RANGE(range) => {
+                // A step of zero would never reach the end, it is an empty range instead
+                self.add_code_str("(function(from, till, step) local values = {} if step > 0 then for value = from, till - 1, step do values[#values + 1] = value end elseif step < 0 then for value = from, till + 1, step do values[#values + 1] = value end end return setmetatable(values, { __concat = function(a, b) return table.insert(a, b) end }) end)(");
+                self.compile_node(&range.from);
+                self.add_code_str(", ");
+                self.compile_node(&range.till);
+                self.add_code_str(", ");
+                self.compile_node(&range.step);
+                self.add_code_str(")");
+            }
+            Node::LOCATION(location) => {
+                // Every location starts a line of its own, so errors on it can be traced back
+                self.add_code_str("\n");
+                self.source_map.add(self.line, location.clone());
+            }
+        }
+    }
+
+    /// Registers the metatable of a class, which knows the names of its fields and shows instances
+    /// using "to_string" if the class defines it
+    fn compile_compound(&mut self, compound: &CompoundType) {
+        let (class, fields) = match &compound.fields {
+            Some(fields) => fields,
+            None => return,
+        };
+
+        self.add_code(format!(
+            "loop_classes[\"{}\"] = {{__name = \"{}\", __fields = {{",
+            compound.name, class
+        ));
+
+        let names: Vec<String> = fields
+            .iter()
+            .map(|field| format!("\"{}\"", field))
+            .collect();
+        self.add_code(names.join(","));
+        self.add_code_str("}");
+
+        if let Some(index) = fields.iter().position(|field| field == "to_string") {
+            self.add_code(format!(
+                ", __tostring = function(self) return self[{} + 1](self) end",
+                index
+            ));
+        }
+
+        self.add_code_str("}");
+    }
+
+    /// Compiles the arguments of a call, separated by commas
+    fn compile_arguments(&mut self, arguments: &[Node]) {
+        for (index, argument) in arguments.iter().enumerate() {
+            if index != 0 {
+                self.add_code_str(",");
+            }
+
+            self.compile_node(argument);
         }
     }
 
     fn compile_nodes(&mut self, nodes: &[Node]) {
         for node in nodes {
             self.compile_node(node);
-            self.add_code_str(";")
+
+            // Lua does not allow empty statements
+            if !matches!(node, Node::LOCATION(_)) {
+                self.add_code_str(";")
+            }
         }
     }
 
     fn compile_nodes_global(&mut self, nodes: &[Node]) {
+        // Locations are not statements, the last statement comes before them
+        let last = nodes
+            .iter()
+            .rposition(|node| !matches!(node, Node::LOCATION(_)))
+            .map_or(0, |index| index + 1);
+
         let mut index = 0;
         for node in nodes {
             index += 1;
 
-            if index == nodes.len() {
-                match node.clone() {
-                    Node::STORE(_) => {}
-                    Node::ASSIGN(_, _) => {}
-                    _ => self.add_code_str("print("),
-                };
+            // Only the last expression is returned to be shown, definitions have no value to show
+            if index == last && Self::has_printable_value(node) {
+                self.add_code_str("return ");
             }
 
             self.compile_node(node);
 
-            if index == nodes.len() {
-                match node.clone() {
-                    Node::STORE(_) => {}
-                    Node::ASSIGN(_, _) => {}
-                    _ => self.add_code_str(")"),
-                };
-            }
-
-            if index != nodes.len() {
-                let add_colon = !matches!(node, Node::COMPOUND(_));
+            if index < last {
+                // Compounds without fields and locations do not result in a statement
+                let add_colon = !matches!(
+                    node,
+                    Node::COMPOUND(CompoundType { fields: None, .. }) | Node::LOCATION(_)
+                );
 
                 if add_colon {
                     self.add_code_str(";")
@@ -503,59 +845,33 @@ getmetatable('').__call = function(str,i,j) if type(i)~='table' then return stri
         }
     }
 
+    fn has_printable_value(node: &Node) -> bool {
+        match node {
+            Node::STORE(_)
+            | Node::ASSIGN(_, _)
+            | Node::COMPOUND(_)
+            | Node::LOADLIB(_)
+            | Node::WHILE(_)
+            | Node::BREAK
+            | Node::CONTINUE
+            | Node::LOCATION(_) => false,
+            Node::FUNCTION(func) => func.name.is_empty(),
+            _ => true,
+        }
+    }
+
     fn add_library_path(&mut self, lib_path: String) {
         if self.library_paths.contains(&lib_path) {
-            println!("RuntimeWarning: Library is already loaded");
+            throw_runtime_warning("Library is already loaded".to_string());
         }
         self.library_paths.push(lib_path);
     }
 
     fn add_library_namespace(&mut self, lib_name: String) {
         if self.library_names.contains(&lib_name) {
-            throw_runtime_exception(
-                format!("The libary alias {}, is already in use", lib_name),
-                None,
-            );
+            self.add_error(format!("The libary alias {}, is already in use", lib_name));
         }
 
         self.library_names.push(lib_name);
     }
-
-    fn get_lib_signiture(&self, _path: String) -> Result<String, String> {
-        #[cfg(feature = "libloading")]
-        {
-            use std::ffi::CStr;
-            use std::os::raw::c_char;
-
-            let full_path = if std::env::consts::OS == "windows" {
-                format!("{}.dll", _path)
-            } else {
-                format!("{}.so", _path)
-            };
-
-            let lib = libloading::Library::new(full_path.clone());
-            match lib {
-                Ok(l) => unsafe {
-                    let signature = l.get(b"library_signatures");
-                    if let Ok(sym) = signature {
-                        let func: libloading::Symbol<unsafe extern "C" fn() -> *const c_char> = sym;
-                        let str = CStr::from_ptr(func()).to_str().unwrap().to_owned();
-                        return Ok(str);
-                    } else if let Err(_) = signature {
-                        return Err(String::from(
-                            "Could not call the 'library_signatures' function",
-                        ));
-                    } else {
-                        unreachable!("get_lib_signatures is not supposed to get here")
-                    }
-                },
-
-                Err(e) => {
-                    return Err(format!("{}", e));
-                }
-            }
-        }
-        #[allow(unreachable_code)]
-        Err(String::from("Loading of dynamic libaries is not enabled"))
-    }
 }
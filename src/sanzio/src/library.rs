@@ -0,0 +1,39 @@
+//! Loads the C libraries that Loop code imports, both backends use the declarations a library gives
+//! to know which functions it has
+
+/// The file a library is loaded from, "<path>.dll" on Windows and "<path>.so" everywhere else
+pub fn library_file(path: &str) -> String {
+    let extension = if cfg!(windows) { "dll" } else { "so" };
+
+    format!("{}.{}", path, extension)
+}
+
+/// Loads a library together with the C declarations of its functions, which are returned by the
+/// "library_signatures" function every library exports
+#[cfg(feature = "libloading")]
+pub fn load_library(path: &str) -> Result<(libloading::Library, String), String> {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+
+    let library = libloading::Library::new(library_file(path)).map_err(|e| format!("{}", e))?;
+
+    let signatures = unsafe {
+        let function: libloading::Symbol<unsafe extern "C" fn() -> *const c_char> = library
+            .get(b"library_signatures")
+            .map_err(|_| String::from("Could not call the 'library_signatures' function"))?;
+
+        CStr::from_ptr(function()).to_string_lossy().into_owned()
+    };
+
+    Ok((library, signatures))
+}
+
+/// The C declarations of the functions in a library, without keeping it loaded
+pub fn library_signatures(_path: &str) -> Result<String, String> {
+    #[cfg(feature = "libloading")]
+    {
+        load_library(_path).map(|(_, signatures)| signatures)
+    }
+    #[cfg(not(feature = "libloading"))]
+    Err(String::from("Loading of dynamic libaries is not enabled"))
+}
@@ -0,0 +1,76 @@
+//! Maps the lines of the generated Lua back to the Loop code they were compiled from
+use vinci::ast::instructions::location::Location;
+
+/// Name the generated Lua is loaded as, errors refer to its lines as `[loop]:<line>`
+pub const CHUNK_NAME: &str = "[loop]";
+
+#[derive(Debug, Default, Clone)]
+pub struct SourceMap {
+    // Line in the Lua and the Loop code it was compiled from, ordered by line
+    lines: Vec<(usize, Location)>,
+}
+
+impl SourceMap {
+    pub fn add(&mut self, lua_line: usize, location: Location) {
+        self.lines.push((lua_line, location));
+    }
+
+    /// Where a line of Lua came from, lines without a location belong to the last one before them
+    pub fn lookup(&self, lua_line: usize) -> Option<&Location> {
+        let index = self.lines.partition_point(|(line, _)| *line <= lua_line);
+
+        index.checked_sub(1).map(|index| &self.lines[index].1)
+    }
+
+    /// The Loop code the first `[loop]:<line>` in an error points at, which is where it happened.
+    /// Lines of the prelude, like the methods of strings and arrays, are skipped so errors in them
+    /// point at where they were called.
+    pub fn locate(&self, message: &str) -> Option<&Location> {
+        let prefix = format!("{}:", CHUNK_NAME);
+        let mut rest = message;
+
+        while let Some(start) = rest.find(&prefix) {
+            rest = &rest[start + prefix.len()..];
+            let digits = rest.chars().take_while(char::is_ascii_digit).count();
+
+            if let Some(location) = rest[..digits]
+                .parse()
+                .ok()
+                .and_then(|line| self.lookup(line))
+            {
+                return Some(location);
+            }
+        }
+
+        None
+    }
+
+    /// Replaces every `[loop]:<line>` in an error or traceback by the Loop file and line, lines
+    /// that can not be found are kept as they are
+    pub fn rewrite(&self, message: &str) -> String {
+        let prefix = format!("{}:", CHUNK_NAME);
+        let mut rewritten = String::new();
+        let mut rest = message;
+
+        while let Some(start) = rest.find(&prefix) {
+            rewritten.push_str(&rest[..start]);
+
+            let after = &rest[start + prefix.len()..];
+            let digits = after.chars().take_while(char::is_ascii_digit).count();
+            let location = after[..digits]
+                .parse::<usize>()
+                .ok()
+                .and_then(|line| self.lookup(line));
+
+            match location {
+                Some(location) => rewritten.push_str(&location.to_string()),
+                None => rewritten.push_str(&rest[start..start + prefix.len() + digits]),
+            }
+
+            rest = &after[digits..];
+        }
+
+        rewritten.push_str(rest);
+        rewritten
+    }
+}
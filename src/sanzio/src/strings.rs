@@ -0,0 +1,54 @@
+//! The methods built into strings, each is a Lua function that gets the string as its first
+//! argument. Positions start at 0, like they do in Loop.
+use crate::arrays::ARRAY;
+
+/// Lua pattern of a single UTF-8 encoded character, strings in Lua are bytes
+const CHARACTER: &str = "\"[%z\\1-\\127\\194-\\244][\\128-\\191]*\"";
+
+/// The Lua function implementing a method, [None] if strings do not have it
+pub fn string_method(name: &str) -> Option<String> {
+    let function = match name {
+        "split" => format!(
+            "function(s, separator) local values = {} \
+            if separator == \"\" then for c in string.gmatch(s, {}) do values[#values + 1] = c end return values end \
+            local start = 1 \
+            while true do local i, j = string.find(s, separator, start, true) \
+            if i == nil then break end \
+            values[#values + 1] = string.sub(s, start, i - 1) start = j + 1 end \
+            values[#values + 1] = string.sub(s, start) return values end",
+            ARRAY, CHARACTER
+        ),
+        "join" => "function(s, values) return table.concat(values, s) end".to_string(),
+        "trim" => "function(s) return (string.match(s, \"^%s*(.-)%s*$\")) end".to_string(),
+        "to_upper" => "string.upper".to_string(),
+        "to_lower" => "string.lower".to_string(),
+        // The text that is replaced and its replacement are not patterns
+        "replace" => "function(s, from, to) return (string.gsub(s, string.gsub(from, \"%p\", \"%%%0\"), (string.gsub(to, \"%%\", \"%%%%\")))) end".to_string(),
+        "contains" => "function(s, part) return string.find(s, part, 1, true) ~= nil end".to_string(),
+        "starts_with" => "function(s, part) return string.sub(s, 1, #part) == part end".to_string(),
+        "ends_with" => "function(s, part) return #part == 0 or string.sub(s, -#part) == part end".to_string(),
+        "find" => "function(s, part) return (string.find(s, part, 1, true) or 0) - 1 end".to_string(),
+        "repeat" => "string.rep".to_string(),
+        "chars" => format!(
+            "function(s) local values = {} for c in string.gmatch(s, {}) do values[#values + 1] = c end return values end",
+            ARRAY, CHARACTER
+        ),
+        "to_int" => "function(s, fallback) local n = tonumber(s) \
+            if n == nil or n % 1 ~= 0 then \
+            if fallback == nil then error(\"can not convert \\\"\" .. s .. \"\\\" to an int\", 0) end \
+            return fallback end return n end"
+            .to_string(),
+        "to_float" => "function(s, fallback) local n = tonumber(s) \
+            if n == nil then \
+            if fallback == nil then error(\"can not convert \\\"\" .. s .. \"\\\" to a float\", 0) end \
+            return fallback end return n end"
+            .to_string(),
+        // Every "{}" is replaced by the next value
+        "format" => "function(s, ...) local values = { ... } local i = 0 \
+            return (string.gsub(s, \"{}\", function() i = i + 1 return tostring(values[i]) end)) end"
+            .to_string(),
+        _ => return None,
+    };
+
+    Some(function)
+}
@@ -0,0 +1,105 @@
+//! Converts the values of Lua back into the values of Loop, so they can be shown to the user
+use mlua::{Function, Table, Value};
+use std::cmp::Ordering;
+use vinci::types::{Type, ValueType};
+
+/// Converts a value of Lua into the value it was in Loop
+pub fn to_value_type(value: &Value) -> ValueType {
+    match value {
+        Value::Nil => ValueType::Void,
+        Value::Boolean(boolean) => ValueType::Boolean(*boolean),
+        Value::Integer(integer) => ValueType::Integer(*integer),
+        // LuaJIT only has floats, so whole numbers are integers
+        Value::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => {
+            ValueType::Integer(*number as i64)
+        }
+        Value::Number(number) => ValueType::Float(*number),
+        Value::String(string) => string_value(&string.to_string_lossy()),
+        Value::Table(table) => table_value(table),
+        // The signature of a function is not known anymore at runtime
+        Value::Function(_) => {
+            ValueType::Function(Box::new(Type::VOID), Box::default(), 0, Box::default())
+        }
+        _ => ValueType::Void,
+    }
+}
+
+fn string_value(string: &str) -> ValueType {
+    ValueType::Array(Box::new(string.chars().map(ValueType::Character).collect()))
+}
+
+/// Tables are instances of a class when they have its metatable and hashmaps when they have the
+/// one of hashmaps. Other tables that only have the keys 1 till their length are arrays.
+fn table_value(table: &Table) -> ValueType {
+    let mut hashmap = false;
+
+    if let Some(metatable) = table.get_metatable() {
+        if let Ok(name) = metatable.raw_get::<_, String>("__name") {
+            return instance_value(table, &metatable, name);
+        }
+
+        hashmap = metatable.raw_get::<_, bool>("__hashmap").unwrap_or(false);
+    }
+
+    let length = table.raw_len();
+    let pairs: Vec<(Value, Value)> = table
+        .clone()
+        .pairs::<Value, Value>()
+        .filter_map(|pair| pair.ok())
+        .collect();
+
+    if !hashmap && pairs.len() as i64 == length {
+        let values = (1..=length)
+            .map(|index| to_value_type(&table.raw_get(index).unwrap_or(Value::Nil)))
+            .collect();
+
+        return ValueType::Array(Box::new(values));
+    }
+
+    // Lua does not keep the order keys were added in, so they are sorted instead
+    let mut values: Vec<(ValueType, ValueType)> = pairs
+        .iter()
+        .map(|(key, value)| (to_value_type(key), to_value_type(value)))
+        .collect();
+    values.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+    ValueType::Hashmap(Box::new(values))
+}
+
+/// Instances of classes that define "to_string" are shown as what it returns, otherwise every
+/// property that is not a method is shown
+fn instance_value(table: &Table, class: &Table, name: String) -> ValueType {
+    if let Ok(to_string) = class.raw_get::<_, Function>("__tostring") {
+        if let Ok(string) = to_string.call::<_, String>(table.clone()) {
+            return string_value(&string);
+        }
+    }
+
+    let fields: Vec<String> = class
+        .raw_get::<_, Vec<String>>("__fields")
+        .unwrap_or_default();
+
+    let properties = fields
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, field)| {
+            let value = table.raw_get(index as i64 + 1).unwrap_or(Value::Nil);
+
+            if let Value::Function(_) = value {
+                None
+            } else {
+                Some((field, to_value_type(&value)))
+            }
+        })
+        .collect();
+
+    ValueType::Instance(name, Box::new(properties))
+}
+
+fn compare_keys(a: &ValueType, b: &ValueType) -> Ordering {
+    match (a, b) {
+        (ValueType::Integer(a), ValueType::Integer(b)) => a.cmp(b),
+        (ValueType::Float(a), ValueType::Float(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        _ => a.format().cmp(&b.format()),
+    }
+}
@@ -0,0 +1,48 @@
+use crate::diagnostic::DiagnosticSpan;
+use std::fmt::{Display, Formatter};
+
+/// The file and line in the Loop source code the statements after it were written on
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Location {
+    pub file: String,
+    pub line: u32,
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+impl Location {
+    /// The code written on the line, if the file can still be read
+    pub fn source_line(&self) -> Option<String> {
+        let content = std::fs::read_to_string(&self.file).ok()?;
+
+        content
+            .lines()
+            .nth((self.line as usize).checked_sub(1)?)
+            .map(str::to_string)
+    }
+
+    /// A span of the code on the line without its indentation, `text` is the code written on it
+    pub fn diagnostic_span(&self, text: Option<String>) -> DiagnosticSpan {
+        let (indentation, length) = match &text {
+            Some(text) => (
+                text.chars().take_while(|ch| ch.is_whitespace()).count(),
+                text.trim_end().chars().count(),
+            ),
+            None => (0, 1),
+        };
+
+        DiagnosticSpan {
+            file: self.file.clone(),
+            line_start: self.line as i32,
+            column_start: indentation as i32 + 1,
+            line_end: self.line as i32,
+            column_end: length.max(indentation + 1) as i32,
+            label: None,
+            text,
+        }
+    }
+}
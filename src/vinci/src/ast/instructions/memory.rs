@@ -14,6 +14,9 @@ pub enum LoadType {
 pub struct CompoundType {
     pub name: String,
     pub values: Box<Vec<Type>>,
+    // The name the class has in the source and the names of its fields by index, only used to
+    // show values of this type
+    pub fields: Option<(String, Vec<String>)>,
 }
 
 impl Display for LoadType {
@@ -65,11 +68,34 @@ impl Display for Store {
 pub struct Index {
     pub to_index: Box<Node>,
     pub index: Box<Node>,
+    // Hashmaps are indexed by key, arrays and strings by position
+    pub hashmap: bool,
 }
 
 impl Display for Index {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "INDEX {} {}", self.to_index, self.index)
+        if self.hashmap {
+            write!(f, "INDEX HASHMAP {} {}", self.to_index, self.index)
+        } else {
+            write!(f, "INDEX {} {}", self.to_index, self.index)
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Hashmap {
+    pub key_type: Type,
+    pub value_type: Type,
+    pub values: Vec<(Node, Node)>,
+}
+
+impl Display for Hashmap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HASHMAP {:?} {:?} {:?}",
+            self.key_type, self.value_type, self.values
+        )
     }
 }
 
@@ -86,6 +112,20 @@ impl Display for Slice {
     }
 }
 
+/// An array of integers, starting at `from` and counting by `step` until it reaches `till`
+#[derive(PartialEq, Clone)]
+pub struct Range {
+    pub from: Box<Node>,
+    pub till: Box<Node>,
+    pub step: Box<Node>,
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RANGE {} {} {}", self.from, self.till, self.step)
+    }
+}
+
 #[derive(PartialEq, Clone)]
 pub struct Push {
     pub to_push: Box<Node>,
@@ -1,7 +1,8 @@
 use crate::ast::instructions::conditional::Conditional;
 use crate::ast::instructions::function::{Call, Function};
+use crate::ast::instructions::location::Location;
 use crate::ast::instructions::memory::{
-    CompoundType, Copy, Index, Load, LoadLib, Push, Slice, Store,
+    CompoundType, Copy, Hashmap, Index, Load, LoadLib, Push, Range, Slice, Store,
 };
 use crate::ast::instructions::suffix::Suffix;
 use crate::ast::instructions::while_loop::While;
@@ -10,6 +11,7 @@ use std::fmt::{Debug, Display, Formatter};
 
 pub mod conditional;
 pub mod function;
+pub mod location;
 pub mod memory;
 pub mod suffix;
 pub mod while_loop;
@@ -39,6 +41,16 @@ pub enum Node {
     /// .ADD { .CONSTANT INT 10; } { .LOAD VARIABLE 10; }
     /// ```
     SUFFIX(Box<Suffix>),
+    /// Inverting a boolean
+    /// ```txt
+    /// .NOT { .CONSTANT BOOL true; };
+    /// ```
+    NOT(Box<Node>),
+    /// Negating an integer or float
+    /// ```txt
+    /// .NEGATE { .LOAD VARIABLE 0; };
+    /// ```
+    NEGATE(Box<Node>),
     /// A if-expression
     /// ```txt
     /// .IF
@@ -71,7 +83,17 @@ pub enum Node {
     /// };
     /// ```
     WHILE(Box<While>),
-    /// Instruction to index a array or string. Starting from 0
+    /// Stops the loop it is in
+    /// ```txt
+    /// .BREAK;
+    /// ```
+    BREAK,
+    /// Skips the rest of the body of the loop it is in, going to the next iteration
+    /// ```txt
+    /// .CONTINUE;
+    /// ```
+    CONTINUE,
+    /// Instruction to index a array or string. Starting from 0. Hashmaps are indexed by key
     /// ```txt
     /// .INDEX
     /// {
@@ -80,6 +102,7 @@ pub enum Node {
     /// {
     ///     .CONSTANT INT 0;
     /// };
+    /// .INDEX HASHMAP { .LOAD VARIABLE 0; } { .CONSTANT CHAR[] "key"; };
     /// ```
     INDEX(Index),
     /// Instruction to slice an array or string. First is inclusive, end is exclusive. Starting from 0
@@ -124,7 +147,34 @@ pub enum Node {
     /// ```txt
     /// .COMPOUND { INT; STRING; }
     /// ```
+    /// Classes also name themselves and their fields, so their values can be shown
+    /// ```txt
+    /// .COMPOUND "class_0" { INT; CHAR[]; } FIELDS "Person" { "age"; "name"; };
+    /// ```
     COMPOUND(CompoundType),
+    /// Creating a hashmap with the key and value type, followed by every key and its value
+    /// ```txt
+    /// .HASHMAP INT CHAR[] { .CONSTANT INT 10; .CONSTANT CHAR[] "ten"; };
+    /// ```
+    HASHMAP(Box<Hashmap>),
+    /// All keys of a hashmap as an array
+    /// ```txt
+    /// .KEYS { .LOAD VARIABLE 0; };
+    /// ```
+    KEYS(Box<Node>),
+    /// An array of integers, from the first value (inclusive) till the second value (exclusive),
+    /// counting by the third value. A negative step counts down, a step of zero is empty
+    /// ```txt
+    /// .RANGE { .CONSTANT INT 10; } { .CONSTANT INT 0; } { .CONSTANT INT -2; };
+    /// // Resulting in [10, 8, 6, 4, 2]
+    /// ```
+    RANGE(Range),
+    /// Where the statements after it were written in the Loop source code, so errors while running
+    /// can point at the Loop code instead of the generated code. It only ever is a statement
+    /// ```txt
+    /// .LOCATION "main.loop" 3;
+    /// ```
+    LOCATION(Location),
 }
 
 impl Display for Node {
@@ -132,12 +182,16 @@ impl Display for Node {
         match self {
             Node::LOAD(load) => write!(f, "{}", load),
             Node::SUFFIX(suffix) => write!(f, "{}", suffix),
+            Node::NOT(a) => write!(f, "NOT {}", a),
+            Node::NEGATE(a) => write!(f, "NEGATE {}", a),
             Node::STORE(store) => write!(f, "{}", store),
             Node::CONSTANT(value_type) => write!(f, "{}", value_type),
             Node::CONDITIONAL(conditional) => write!(f, "{}", conditional),
             Node::FUNCTION(func) => write!(f, "{}", func),
             Node::CALL(call) => write!(f, "{}", call),
             Node::WHILE(wh) => write!(f, "{}", wh),
+            Node::BREAK => write!(f, "BREAK;"),
+            Node::CONTINUE => write!(f, "CONTINUE;"),
             Node::PUSH(wh) => write!(f, "{}", wh),
             Node::SLICE(wh) => write!(f, "{}", wh),
             Node::INDEX(wh) => write!(f, "{}", wh),
@@ -150,6 +204,10 @@ impl Display for Node {
             Node::AND(a, b) => write!(f, "{}, {}", a, b),
             Node::OR(a, b) => write!(f, "{}, {}", a, b),
             Node::COMPOUND(cmp) => write!(f, "{:?}", cmp),
+            Node::HASHMAP(hashmap) => write!(f, "{}", hashmap),
+            Node::KEYS(a) => write!(f, "KEYS {}", a),
+            Node::RANGE(range) => write!(f, "{}", range),
+            Node::LOCATION(location) => write!(f, "LOCATION {};", location),
         }
     }
 }
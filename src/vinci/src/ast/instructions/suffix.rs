@@ -9,6 +9,9 @@ pub enum BinaryOperation {
     DIVIDE,
     POWER,
     GREATERTHAN,
+    LESSTHAN,
+    GREATERTHANOREQUAL,
+    LESSTHANOREQUAL,
     EQUALS,
     NOTEQUALS,
     MODULO,
@@ -30,6 +33,11 @@ impl Display for Suffix {
             BinaryOperation::DIVIDE => write!(f, "({} / {})", self.left, self.right),
             BinaryOperation::POWER => write!(f, "({} ^ {})", self.left, self.right),
             BinaryOperation::GREATERTHAN => write!(f, "({} > {})", self.left, self.right),
+            BinaryOperation::LESSTHAN => write!(f, "({} < {})", self.left, self.right),
+            BinaryOperation::GREATERTHANOREQUAL => {
+                write!(f, "({} >= {})", self.left, self.right)
+            }
+            BinaryOperation::LESSTHANOREQUAL => write!(f, "({} <= {})", self.left, self.right),
             BinaryOperation::EQUALS => write!(f, "({} == {})", self.left, self.right),
             BinaryOperation::NOTEQUALS => write!(f, "({} != {})", self.left, self.right),
             BinaryOperation::MODULO => write!(f, "({} % {})", self.left, self.right),
@@ -0,0 +1,350 @@
+use crate::ast::instructions::conditional::Conditional;
+use crate::ast::instructions::function::{Call, Function};
+use crate::ast::instructions::location::Location;
+use crate::ast::instructions::memory::{
+    CompoundType, Copy, Hashmap, Index, Load, LoadLib, LoadType, Push, Range, Slice, Store,
+};
+use crate::ast::instructions::suffix::{BinaryOperation, Suffix};
+use crate::ast::instructions::while_loop::While;
+use crate::ast::instructions::Node;
+use crate::binary::{DecodeError, Header, FORMAT_VERSION, MAGIC};
+use crate::types::{Type, ValueType};
+
+/// Reads what the encoder wrote, every read fails instead of panicking on bytes that are cut off
+/// or corrupted
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Decoder<'a> {
+        Decoder { bytes, position: 0 }
+    }
+
+    pub fn header(&mut self) -> Result<Header, DecodeError> {
+        if self.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+            return Err(DecodeError::NotArcb);
+        }
+
+        let format_version = self.u16()?;
+        if format_version != FORMAT_VERSION {
+            return Err(DecodeError::FormatVersion(format_version));
+        }
+
+        Ok(Header {
+            format_version,
+            compiler_version: self.string()?,
+            source_hash: self.u64()?,
+        })
+    }
+
+    pub fn end(&self) -> Result<(), DecodeError> {
+        if self.position == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingBytes)
+        }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(DecodeError::UnexpectedEnd)?;
+
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, DecodeError> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        Ok(f64::from_bits(self.u64()?))
+    }
+
+    fn bool(&mut self) -> Result<bool, DecodeError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(DecodeError::UnknownTag("boolean", tag)),
+        }
+    }
+
+    fn length(&mut self) -> Result<usize, DecodeError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let length = self.length()?;
+        let bytes = self.take(length)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidString)
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, DecodeError> {
+        let mut strings = Vec::new();
+        for _ in 0..self.length()? {
+            strings.push(self.string()?);
+        }
+
+        Ok(strings)
+    }
+
+    pub fn nodes(&mut self) -> Result<Vec<Node>, DecodeError> {
+        // Lists are not allocated up front, a corrupted length would otherwise allocate too much
+        let mut nodes = Vec::new();
+        for _ in 0..self.length()? {
+            nodes.push(self.node()?);
+        }
+
+        Ok(nodes)
+    }
+
+    fn boxed(&mut self) -> Result<Box<Node>, DecodeError> {
+        Ok(Box::new(self.node()?))
+    }
+
+    fn node(&mut self) -> Result<Node, DecodeError> {
+        let node = match self.u8()? {
+            0 => Node::CONSTANT(self.value()?),
+            1 => Node::LOAD(Load {
+                load_type: self.load_type()?,
+                index: self.u64()?,
+            }),
+            2 => Node::STORE(Store {
+                index: self.u64()?,
+                value: self.boxed()?,
+            }),
+            3 => Node::SUFFIX(Box::new(Suffix {
+                operation: self.operation()?,
+                left: self.node()?,
+                right: self.node()?,
+            })),
+            4 => Node::NOT(self.boxed()?),
+            5 => Node::NEGATE(self.boxed()?),
+            6 => Node::CONDITIONAL(Box::new(Conditional {
+                condition: self.node()?,
+                body: self.nodes()?,
+                alternative: self.nodes()?,
+            })),
+            7 => Node::FUNCTION(Box::new(Function {
+                name: self.string()?,
+                unique_identifier: self.i64()? as i32,
+                return_type: self.tp()?,
+                parameters: self.types()?,
+                free: self.types()?,
+                body: self.nodes()?,
+            })),
+            8 => Node::CALL(Box::new(Call {
+                call: self.node()?,
+                arguments: self.nodes()?,
+            })),
+            9 => Node::RETURN(self.boxed()?),
+            10 => Node::WHILE(Box::new(While {
+                condition: self.node()?,
+                body: self.nodes()?,
+            })),
+            11 => Node::BREAK,
+            12 => Node::CONTINUE,
+            13 => Node::INDEX(Index {
+                to_index: self.boxed()?,
+                index: self.boxed()?,
+                hashmap: self.bool()?,
+            }),
+            14 => Node::SLICE(Slice {
+                to_slice: self.boxed()?,
+                from: self.boxed()?,
+                to: self.boxed()?,
+            }),
+            15 => Node::PUSH(Push {
+                to_push: self.boxed()?,
+                item: self.boxed()?,
+            }),
+            16 => Node::COPY(Copy {
+                object: self.boxed()?,
+            }),
+            17 => Node::LOADLIB(LoadLib {
+                path: self.boxed()?,
+                namespace: self.string()?,
+            }),
+            18 => Node::ASSIGN(self.boxed()?, self.boxed()?),
+            19 => Node::POP(self.boxed()?, self.boxed()?),
+            20 => Node::LENGTH(self.boxed()?),
+            21 => Node::AND(self.boxed()?, self.boxed()?),
+            22 => Node::OR(self.boxed()?, self.boxed()?),
+            23 => Node::COMPOUND(CompoundType {
+                name: self.string()?,
+                values: Box::new(self.types()?),
+                fields: if self.bool()? {
+                    Some((self.string()?, self.strings()?))
+                } else {
+                    None
+                },
+            }),
+            24 => {
+                let key_type = self.tp()?;
+                let value_type = self.tp()?;
+
+                let mut values = Vec::new();
+                for _ in 0..self.length()? {
+                    values.push((self.node()?, self.node()?));
+                }
+
+                Node::HASHMAP(Box::new(Hashmap {
+                    key_type,
+                    value_type,
+                    values,
+                }))
+            }
+            25 => Node::KEYS(self.boxed()?),
+            26 => Node::RANGE(Range {
+                from: self.boxed()?,
+                till: self.boxed()?,
+                step: self.boxed()?,
+            }),
+            27 => Node::LOCATION(Location {
+                file: self.string()?,
+                line: self.u32()?,
+            }),
+            tag => return Err(DecodeError::UnknownTag("node", tag)),
+        };
+
+        Ok(node)
+    }
+
+    fn load_type(&mut self) -> Result<LoadType, DecodeError> {
+        match self.u8()? {
+            0 => Ok(LoadType::VARIABLE),
+            1 => Ok(LoadType::PARAMETER(self.u64()?)),
+            tag => Err(DecodeError::UnknownTag("load type", tag)),
+        }
+    }
+
+    fn operation(&mut self) -> Result<BinaryOperation, DecodeError> {
+        let operation = match self.u8()? {
+            0 => BinaryOperation::ADD,
+            1 => BinaryOperation::SUBTRACT,
+            2 => BinaryOperation::MULTIPLY,
+            3 => BinaryOperation::DIVIDE,
+            4 => BinaryOperation::POWER,
+            5 => BinaryOperation::GREATERTHAN,
+            6 => BinaryOperation::LESSTHAN,
+            7 => BinaryOperation::GREATERTHANOREQUAL,
+            8 => BinaryOperation::LESSTHANOREQUAL,
+            9 => BinaryOperation::EQUALS,
+            10 => BinaryOperation::NOTEQUALS,
+            11 => BinaryOperation::MODULO,
+            tag => return Err(DecodeError::UnknownTag("operation", tag)),
+        };
+
+        Ok(operation)
+    }
+
+    fn value(&mut self) -> Result<ValueType, DecodeError> {
+        let value = match self.u8()? {
+            0 => ValueType::Integer(self.i64()?),
+            1 => ValueType::Float(self.f64()?),
+            2 => ValueType::Boolean(self.bool()?),
+            3 => {
+                let char = self.u32()?;
+                ValueType::Character(
+                    char::from_u32(char).ok_or(DecodeError::InvalidCharacter(char))?,
+                )
+            }
+            4 => ValueType::Array(Box::new(self.values()?)),
+            5 => ValueType::Void,
+            6 => ValueType::Compound(self.string()?, Box::new(self.values()?)),
+            7 => {
+                let mut values = Vec::new();
+                for _ in 0..self.length()? {
+                    values.push((self.value()?, self.value()?));
+                }
+
+                ValueType::Hashmap(Box::new(values))
+            }
+            8 => ValueType::Function(
+                Box::new(self.tp()?),
+                Box::new(self.types()?),
+                self.u32()?,
+                Box::new(self.nodes()?),
+            ),
+            9 => {
+                let name = self.string()?;
+
+                let mut properties = Vec::new();
+                for _ in 0..self.length()? {
+                    properties.push((self.string()?, self.value()?));
+                }
+
+                ValueType::Instance(name, Box::new(properties))
+            }
+            tag => return Err(DecodeError::UnknownTag("value", tag)),
+        };
+
+        Ok(value)
+    }
+
+    fn values(&mut self) -> Result<Vec<ValueType>, DecodeError> {
+        let mut values = Vec::new();
+        for _ in 0..self.length()? {
+            values.push(self.value()?);
+        }
+
+        Ok(values)
+    }
+
+    fn tp(&mut self) -> Result<Type, DecodeError> {
+        let tp = match self.u8()? {
+            0 => Type::INT,
+            1 => Type::FLOAT,
+            2 => Type::BOOL,
+            3 => Type::CHAR,
+            4 => Type::ARRAY(Box::new(self.tp()?)),
+            5 => Type::VOID,
+            6 => Type::Compound(self.string()?, Box::new(self.types()?)),
+            7 => Type::Function(Box::new(self.tp()?), Box::new(self.types()?)),
+            8 => Type::HASHMAP(Box::new(self.tp()?), Box::new(self.tp()?)),
+            tag => return Err(DecodeError::UnknownTag("type", tag)),
+        };
+
+        Ok(tp)
+    }
+
+    fn types(&mut self) -> Result<Vec<Type>, DecodeError> {
+        let mut types = Vec::new();
+        for _ in 0..self.length()? {
+            types.push(self.tp()?);
+        }
+
+        Ok(types)
+    }
+}
@@ -0,0 +1,344 @@
+use crate::ast::instructions::memory::LoadType;
+use crate::ast::instructions::suffix::BinaryOperation;
+use crate::ast::instructions::Node;
+use crate::types::{Type, ValueType};
+
+/// Writes every node as a tag followed by its fields, the tags have to match the decoder
+#[derive(Default)]
+pub struct Encoder {
+    pub bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.u64(value.to_bits());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    fn length(&mut self, length: usize) {
+        self.u32(length as u32);
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.length(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    pub fn nodes(&mut self, nodes: &[Node]) {
+        self.length(nodes.len());
+
+        for node in nodes {
+            self.node(node);
+        }
+    }
+
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::CONSTANT(value) => {
+                self.u8(0);
+                self.value(value);
+            }
+            Node::LOAD(load) => {
+                self.u8(1);
+                self.load_type(&load.load_type);
+                self.u64(load.index);
+            }
+            Node::STORE(store) => {
+                self.u8(2);
+                self.u64(store.index);
+                self.node(&store.value);
+            }
+            Node::SUFFIX(suffix) => {
+                self.u8(3);
+                self.operation(&suffix.operation);
+                self.node(&suffix.left);
+                self.node(&suffix.right);
+            }
+            Node::NOT(value) => {
+                self.u8(4);
+                self.node(value);
+            }
+            Node::NEGATE(value) => {
+                self.u8(5);
+                self.node(value);
+            }
+            Node::CONDITIONAL(conditional) => {
+                self.u8(6);
+                self.node(&conditional.condition);
+                self.nodes(&conditional.body);
+                self.nodes(&conditional.alternative);
+            }
+            Node::FUNCTION(function) => {
+                self.u8(7);
+                self.string(&function.name);
+                self.i64(function.unique_identifier as i64);
+                self.tp(&function.return_type);
+                self.types(&function.parameters);
+                self.types(&function.free);
+                self.nodes(&function.body);
+            }
+            Node::CALL(call) => {
+                self.u8(8);
+                self.node(&call.call);
+                self.nodes(&call.arguments);
+            }
+            Node::RETURN(value) => {
+                self.u8(9);
+                self.node(value);
+            }
+            Node::WHILE(while_loop) => {
+                self.u8(10);
+                self.node(&while_loop.condition);
+                self.nodes(&while_loop.body);
+            }
+            Node::BREAK => self.u8(11),
+            Node::CONTINUE => self.u8(12),
+            Node::INDEX(index) => {
+                self.u8(13);
+                self.node(&index.to_index);
+                self.node(&index.index);
+                self.bool(index.hashmap);
+            }
+            Node::SLICE(slice) => {
+                self.u8(14);
+                self.node(&slice.to_slice);
+                self.node(&slice.from);
+                self.node(&slice.to);
+            }
+            Node::PUSH(push) => {
+                self.u8(15);
+                self.node(&push.to_push);
+                self.node(&push.item);
+            }
+            Node::COPY(copy) => {
+                self.u8(16);
+                self.node(&copy.object);
+            }
+            Node::LOADLIB(library) => {
+                self.u8(17);
+                self.node(&library.path);
+                self.string(&library.namespace);
+            }
+            Node::ASSIGN(a, b) => {
+                self.u8(18);
+                self.node(a);
+                self.node(b);
+            }
+            Node::POP(a, b) => {
+                self.u8(19);
+                self.node(a);
+                self.node(b);
+            }
+            Node::LENGTH(value) => {
+                self.u8(20);
+                self.node(value);
+            }
+            Node::AND(a, b) => {
+                self.u8(21);
+                self.node(a);
+                self.node(b);
+            }
+            Node::OR(a, b) => {
+                self.u8(22);
+                self.node(a);
+                self.node(b);
+            }
+            Node::COMPOUND(compound) => {
+                self.u8(23);
+                self.string(&compound.name);
+                self.types(&compound.values);
+
+                match &compound.fields {
+                    Some((class, fields)) => {
+                        self.bool(true);
+                        self.string(class);
+                        self.length(fields.len());
+
+                        for field in fields {
+                            self.string(field);
+                        }
+                    }
+                    None => self.bool(false),
+                }
+            }
+            Node::HASHMAP(hashmap) => {
+                self.u8(24);
+                self.tp(&hashmap.key_type);
+                self.tp(&hashmap.value_type);
+                self.length(hashmap.values.len());
+
+                for (key, value) in &hashmap.values {
+                    self.node(key);
+                    self.node(value);
+                }
+            }
+            Node::KEYS(value) => {
+                self.u8(25);
+                self.node(value);
+            }
+            Node::RANGE(range) => {
+                self.u8(26);
+                self.node(&range.from);
+                self.node(&range.till);
+                self.node(&range.step);
+            }
+            Node::LOCATION(location) => {
+                self.u8(27);
+                self.string(&location.file);
+                self.u32(location.line);
+            }
+        }
+    }
+
+    fn load_type(&mut self, load_type: &LoadType) {
+        match load_type {
+            LoadType::VARIABLE => self.u8(0),
+            LoadType::PARAMETER(unique_identifier) => {
+                self.u8(1);
+                self.u64(*unique_identifier);
+            }
+        }
+    }
+
+    fn operation(&mut self, operation: &BinaryOperation) {
+        self.u8(match operation {
+            BinaryOperation::ADD => 0,
+            BinaryOperation::SUBTRACT => 1,
+            BinaryOperation::MULTIPLY => 2,
+            BinaryOperation::DIVIDE => 3,
+            BinaryOperation::POWER => 4,
+            BinaryOperation::GREATERTHAN => 5,
+            BinaryOperation::LESSTHAN => 6,
+            BinaryOperation::GREATERTHANOREQUAL => 7,
+            BinaryOperation::LESSTHANOREQUAL => 8,
+            BinaryOperation::EQUALS => 9,
+            BinaryOperation::NOTEQUALS => 10,
+            BinaryOperation::MODULO => 11,
+        });
+    }
+
+    fn value(&mut self, value: &ValueType) {
+        match value {
+            ValueType::Integer(int) => {
+                self.u8(0);
+                self.i64(*int);
+            }
+            ValueType::Float(float) => {
+                self.u8(1);
+                self.f64(*float);
+            }
+            ValueType::Boolean(bool) => {
+                self.u8(2);
+                self.bool(*bool);
+            }
+            ValueType::Character(char) => {
+                self.u8(3);
+                self.u32(*char as u32);
+            }
+            ValueType::Array(values) => {
+                self.u8(4);
+                self.values(values);
+            }
+            ValueType::Void => self.u8(5),
+            ValueType::Compound(name, values) => {
+                self.u8(6);
+                self.string(name);
+                self.values(values);
+            }
+            ValueType::Hashmap(values) => {
+                self.u8(7);
+                self.length(values.len());
+
+                for (key, value) in values.iter() {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            ValueType::Function(return_type, parameters, unique_identifier, body) => {
+                self.u8(8);
+                self.tp(return_type);
+                self.types(parameters);
+                self.u32(*unique_identifier);
+                self.nodes(body);
+            }
+            ValueType::Instance(name, properties) => {
+                self.u8(9);
+                self.string(name);
+                self.length(properties.len());
+
+                for (property, value) in properties.iter() {
+                    self.string(property);
+                    self.value(value);
+                }
+            }
+        }
+    }
+
+    fn values(&mut self, values: &[ValueType]) {
+        self.length(values.len());
+
+        for value in values {
+            self.value(value);
+        }
+    }
+
+    fn tp(&mut self, tp: &Type) {
+        match tp {
+            Type::INT => self.u8(0),
+            Type::FLOAT => self.u8(1),
+            Type::BOOL => self.u8(2),
+            Type::CHAR => self.u8(3),
+            Type::ARRAY(inner) => {
+                self.u8(4);
+                self.tp(inner);
+            }
+            Type::VOID => self.u8(5),
+            Type::Compound(name, types) => {
+                self.u8(6);
+                self.string(name);
+                self.types(types);
+            }
+            Type::Function(return_type, parameters) => {
+                self.u8(7);
+                self.tp(return_type);
+                self.types(parameters);
+            }
+            Type::HASHMAP(key, value) => {
+                self.u8(8);
+                self.tp(key);
+                self.tp(value);
+            }
+        }
+    }
+
+    fn types(&mut self, types: &[Type]) {
+        self.length(types.len());
+
+        for tp in types {
+            self.tp(tp);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! A compact binary encoding of the AST, written to `.arcb` files so a compiled program can be run
+//! without lexing and parsing it again. Every file starts with a header:
+//! ```txt
+//! "ARCB" | format version (u16) | compiler version (string) | source hash (u64)
+//! ```
+//! followed by the nodes of the AST. Numbers are little endian, strings and lists are prefixed by
+//! their length as an u32.
+use crate::ast::AST;
+use std::fmt::{Display, Formatter};
+
+mod decode;
+mod encode;
+mod tests;
+
+const MAGIC: &[u8; 4] = b"ARCB";
+
+/// Changes whenever the encoding changes, files of another version can not be read
+pub const FORMAT_VERSION: u16 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub format_version: u16,
+    // Version of the compiler that wrote the file, as the AST it creates can differ per version
+    pub compiler_version: String,
+    // Hash of the source code the AST was compiled from, see `source_hash`
+    pub source_hash: u64,
+}
+
+impl Header {
+    pub fn new(compiler_version: &str, source_hash: u64) -> Header {
+        Header {
+            format_version: FORMAT_VERSION,
+            compiler_version: compiler_version.to_string(),
+            source_hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes do not start with "ARCB"
+    NotArcb,
+    /// The file was written with another version of the format
+    FormatVersion(u16),
+    /// The bytes end in the middle of a node
+    UnexpectedEnd,
+    /// A tag that does not belong to any node, value or type, with what was being read
+    UnknownTag(&'static str, u8),
+    InvalidString,
+    InvalidCharacter(u32),
+    /// There are bytes left after the last node
+    TrailingBytes,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::NotArcb => write!(f, "not an Arc binary, it does not start with \"ARCB\""),
+            DecodeError::FormatVersion(version) => write!(
+                f,
+                "written with format version {}, but only version {} can be read",
+                version, FORMAT_VERSION
+            ),
+            DecodeError::UnexpectedEnd => write!(f, "the file ends unexpectedly"),
+            DecodeError::UnknownTag(kind, tag) => write!(f, "unknown {} with tag {}", kind, tag),
+            DecodeError::InvalidString => write!(f, "a string is not valid UTF-8"),
+            DecodeError::InvalidCharacter(char) => {
+                write!(f, "{} is not a valid character", char)
+            }
+            DecodeError::TrailingBytes => write!(f, "there are bytes after the last node"),
+        }
+    }
+}
+
+/// Encodes the header and the AST
+pub fn encode(header: &Header, ast: &AST) -> Vec<u8> {
+    let mut encoder = encode::Encoder::default();
+
+    encoder.bytes.extend_from_slice(MAGIC);
+    encoder.u16(header.format_version);
+    encoder.string(&header.compiler_version);
+    encoder.u64(header.source_hash);
+    encoder.nodes(&ast.nodes);
+
+    encoder.bytes
+}
+
+/// Decodes only the header, so it can be checked before the rest is read
+pub fn decode_header(bytes: &[u8]) -> Result<Header, DecodeError> {
+    decode::Decoder::new(bytes).header()
+}
+
+pub fn decode(bytes: &[u8]) -> Result<(Header, AST), DecodeError> {
+    let mut decoder = decode::Decoder::new(bytes);
+
+    let header = decoder.header()?;
+    let nodes = decoder.nodes()?;
+    decoder.end()?;
+
+    Ok((header, AST { nodes }))
+}
+
+/// FNV-1a hash of the source code, which unlike the hashers of the standard library is the same
+/// for every build of Loop
+pub fn source_hash(source: &str) -> u64 {
+    hash(source.as_bytes())
+}
+
+/// FNV-1a hash of any bytes, see [source_hash]
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::ast::instructions::Node;
+    use crate::ast::AST;
+    use crate::binary::{
+        decode, decode_header, encode, source_hash, DecodeError, Header, FORMAT_VERSION,
+    };
+    use crate::parse;
+    use crate::types::{Type, ValueType};
+
+    fn header() -> Header {
+        Header::new("0.2.0", source_hash("println(1)"))
+    }
+
+    fn round_trip(ast: AST) {
+        let bytes = encode(&header(), &ast);
+
+        assert_eq!(decode(&bytes), Ok((header(), ast)));
+    }
+
+    #[test]
+    fn every_instruction() {
+        round_trip(
+            parse(
+                ".COMPOUND \"class_0\" { INT; VOID; } FIELDS \"Person\" { \"age\"; \"greet\"; };
+                .CONSTANT class_0 { .CONSTANT INT 10; .FUNCTION \"\" 3 INT ARGUMENTS { INT; } FREE { }
+                    THEN { .RETURN { .LOAD PARAMETER 3 0; }; }; };
+                .CONSTANT INT -50; .CONSTANT FLOAT 2.5; .CONSTANT BOOL true; .CONSTANT CHAR 'a';
+                .CONSTANT CHAR[] \"hello\"; .CONSTANT VOID; .CONSTANT INT[] [.CONSTANT INT 1;];
+                .STORE 0 { .FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; HASHMAP[CHAR[],INT[]]; }
+                    FREE { BOOL; } THEN { .RETURN { .LOAD PARAMETER 0 0; }; }; };
+                .ADD { .CONSTANT INT 1; .LOAD VARIABLE 0; }; .SUBTRACT { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .MULTIPLY { .CONSTANT INT 1; .CONSTANT INT 1; }; .DIVIDE { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .POWER { .CONSTANT INT 1; .CONSTANT INT 1; }; .MODULO { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .GREATERTHAN { .CONSTANT INT 1; .CONSTANT INT 1; }; .LESSTHAN { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .GREATERTHANOREQUAL { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .LESSTHANOREQUAL { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .EQUALS { .CONSTANT INT 1; .CONSTANT INT 1; }; .NOTEQUALS { .CONSTANT INT 1; .CONSTANT INT 1; };
+                .NOT { .CONSTANT BOOL true; }; .NEGATE { .CONSTANT INT 1; };
+                .AND { .CONSTANT BOOL true; .OR { .CONSTANT BOOL false; .CONSTANT BOOL true; }; };
+                .IF CONDITION { .CONSTANT BOOL true; } THEN { .CONSTANT INT 1; } ELSE { .CONSTANT INT 2; };
+                .WHILE CONDITION { .CONSTANT BOOL true; } THEN { .BREAK; .CONTINUE; };
+                .CALL { .LOAD VARIABLE 0; } { .CONSTANT INT 10; }; .CALL std::println { };
+                .INDEX { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };
+                .INDEX HASHMAP { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };
+                .SLICE { .LOAD VARIABLE 0; } { .CONSTANT INT 0; } { .CONSTANT INT 1; };
+                .PUSH { .LOAD VARIABLE 0; } { .CONSTANT INT 1; }; .COPY { .LOAD VARIABLE 0; };
+                .LOADLIB { .CONSTANT CHAR[] \"./lib.so\"; } \"lib\";
+                .ASSIGN { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };
+                .POP { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };
+                .LENGTH { .LOAD VARIABLE 0; }; .KEYS { .LOAD VARIABLE 0; };
+                .HASHMAP INT BOOL { .CONSTANT INT 10; .CONSTANT BOOL true; };
+                .RANGE { .CONSTANT INT 0; } { .CONSTANT INT 10; } { .CONSTANT INT 2; };
+                .LOCATION \"src/main.loop\" 3;",
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn backend_values() {
+        // Values that have no Arc syntax can still be encoded
+        round_trip(AST::new_mock(vec![
+            Node::CONSTANT(ValueType::Hashmap(Box::new(vec![(
+                ValueType::Character('a'),
+                ValueType::Float(-0.5),
+            )]))),
+            Node::CONSTANT(ValueType::Instance(
+                "Person".to_string(),
+                Box::new(vec![("age".to_string(), ValueType::Integer(10))]),
+            )),
+            Node::CONSTANT(ValueType::Function(
+                Box::new(Type::Function(Box::new(Type::INT), Box::default())),
+                Box::new(vec![Type::Compound("class_0".to_string(), Box::default())]),
+                u32::MAX,
+                Box::new(vec![Node::BREAK]),
+            )),
+            Node::CONSTANT(ValueType::Float(f64::INFINITY)),
+        ]));
+    }
+
+    #[test]
+    fn header_only() {
+        let bytes = encode(&header(), &parse(".CONSTANT INT 1;").unwrap());
+
+        assert_eq!(decode_header(&bytes), Ok(header()));
+        assert_eq!(header().format_version, FORMAT_VERSION);
+    }
+
+    #[test]
+    fn invalid_files() {
+        let mut bytes = encode(&header(), &parse(".CONSTANT INT 1;").unwrap());
+
+        assert_eq!(decode(b".CONSTANT INT 1;"), Err(DecodeError::NotArcb));
+        assert_eq!(decode(b"AR"), Err(DecodeError::NotArcb));
+        assert_eq!(
+            decode(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(decode(&trailing), Err(DecodeError::TrailingBytes));
+
+        // The tag of the first node comes after the amount of nodes
+        let tag = bytes.len() - 10;
+        bytes[tag] = 200;
+        assert_eq!(decode(&bytes), Err(DecodeError::UnknownTag("node", 200)));
+
+        bytes[4] = 99;
+        assert_eq!(decode(&bytes), Err(DecodeError::FormatVersion(99)));
+    }
+
+    #[test]
+    fn stable_source_hash() {
+        assert_eq!(source_hash(""), 0xcbf29ce484222325);
+        assert_eq!(source_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(source_hash("println(1)"), source_hash("println(2)"));
+    }
+}
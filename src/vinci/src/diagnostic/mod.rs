@@ -0,0 +1,269 @@
+//! Diagnostics reported by every stage of Loop, they can be rendered for humans or as JSON lines
+//! for editors and CI
+use colored::Colorize;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+mod tests;
+
+static FORMAT: AtomicU8 = AtomicU8::new(DiagnosticFormat::Human as u8);
+
+/// How diagnostics are printed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DiagnosticFormat {
+    Human,
+    /// One JSON object per line
+    Json,
+}
+
+/// Sets how diagnostics are printed from now on, for the whole program
+pub fn set_format(format: DiagnosticFormat) {
+    FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+pub fn format() -> DiagnosticFormat {
+    if FORMAT.load(Ordering::Relaxed) == DiagnosticFormat::Json as u8 {
+        DiagnosticFormat::Json
+    } else {
+        DiagnosticFormat::Human
+    }
+}
+
+/// Whether colors are used in the human format, `Auto` leaves it to the environment (eg. `NO_COLOR`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+pub fn set_color(choice: ColorChoice) {
+    match choice {
+        ColorChoice::Auto => colored::control::unset_override(),
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// A piece of a file a diagnostic points at, lines and columns start counting at 1 and the end is
+/// the last character that is part of it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DiagnosticSpan {
+    pub file: String,
+    pub line_start: i32,
+    pub column_start: i32,
+    pub line_end: i32,
+    pub column_end: i32,
+    /// Text shown underneath the marked code
+    pub label: Option<String>,
+    /// The line of code the span starts on
+    pub text: Option<String>,
+}
+
+impl DiagnosticSpan {
+    fn marker(&self) -> String {
+        if self.line_end == self.line_start && self.column_end > self.column_start {
+            "^".repeat((self.column_end - self.column_start + 1) as usize)
+        } else {
+            "^".to_string()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Identifies the kind of diagnostic, eg. "syntax::expected-token"
+    pub code: String,
+    pub message: String,
+    pub spans: Vec<DiagnosticSpan>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &str, message: String) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code: code.to_string(),
+            message,
+            spans: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error(code: &str, message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &str, message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    pub fn with_span(mut self, span: DiagnosticSpan) -> Diagnostic {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn with_note(mut self, note: String) -> Diagnostic {
+        self.notes.push(note);
+        self
+    }
+
+    /// Prints the diagnostic in the format chosen with [set_format]
+    pub fn emit(&self) {
+        match format() {
+            DiagnosticFormat::Human => print!("{}", self.render_human()),
+            DiagnosticFormat::Json => println!("{}", self.render_json()),
+        }
+    }
+
+    /// Renders the diagnostic with the code it points at, eg:
+    ///
+    /// <pre>
+    /// error[syntax::no-prefix-parser]
+    ///   | -> main.loop [2:6]
+    ///   |
+    /// 2 | y := ;
+    ///   |      ^
+    ///   |
+    ///   = No prefix parser for Semicolon
+    /// </pre>
+    pub fn render_human(&self) -> String {
+        let mut out = String::new();
+
+        let header = format!("{}[{}]", self.severity, self.code);
+        let header = match self.severity {
+            Severity::Error => header.red(),
+            Severity::Warning => header.yellow(),
+            Severity::Note => header.blue(),
+        };
+        out.push_str(&format!("{}\n", header));
+
+        let line_width = self
+            .spans
+            .iter()
+            .map(|span| span.line_start.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let width = " ".repeat(line_width);
+
+        for span in &self.spans {
+            out.push_str(&format!(
+                "{} | -> {} [{}:{}]\n",
+                width, span.file, span.line_start, span.column_start
+            ));
+
+            if let Some(text) = &span.text {
+                let cursor_width = " ".repeat(span.column_start.max(1) as usize - 1);
+
+                out.push_str(&format!("{} | \n", width));
+                let line = span.line_start.to_string();
+                let padding = " ".repeat(line_width - line.len());
+
+                out.push_str(&format!("{}{} | {}\n", padding, line.red(), text));
+                out.push_str(&format!(
+                    "{} | {}{}\n",
+                    width,
+                    cursor_width,
+                    span.marker().red()
+                ));
+
+                if let Some(label) = &span.label {
+                    for line in label.lines() {
+                        out.push_str(&format!(
+                            "{} | {}{} {}\n",
+                            width,
+                            cursor_width,
+                            "|".red(),
+                            line
+                        ));
+                    }
+                }
+            }
+
+            out.push_str(&format!("{} | \n", width));
+        }
+
+        out.push_str(&format!("{} = {}\n", width, self.message.blue()));
+
+        for note in &self.notes {
+            out.push_str(&format!("{} = {} {}\n", width, "note:".bold(), note));
+        }
+
+        out
+    }
+
+    /// Renders the diagnostic as a single line of JSON
+    pub fn render_json(&self) -> String {
+        let spans: Vec<String> = self
+            .spans
+            .iter()
+            .map(|span| {
+                format!(
+                    "{{\"file\":{},\"line_start\":{},\"column_start\":{},\"line_end\":{},\"column_end\":{},\"label\":{},\"text\":{}}}",
+                    json_string(&span.file),
+                    span.line_start,
+                    span.column_start,
+                    span.line_end,
+                    span.column_end,
+                    json_option(&span.label),
+                    json_option(&span.text)
+                )
+            })
+            .collect();
+
+        let notes: Vec<String> = self.notes.iter().map(|note| json_string(note)).collect();
+
+        format!(
+            "{{\"severity\":{},\"code\":{},\"message\":{},\"spans\":[{}],\"notes\":[{}]}}",
+            json_string(&self.severity.to_string()),
+            json_string(&self.code),
+            json_string(&self.message),
+            spans.join(","),
+            notes.join(",")
+        )
+    }
+}
+
+fn json_option(value: &Option<String>) -> String {
+    match value {
+        Some(value) => json_string(value),
+        None => "null".to_string(),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+
+    out.push('"');
+    out
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use crate::diagnostic::{Diagnostic, DiagnosticSpan};
+
+    fn diagnostic() -> Diagnostic {
+        Diagnostic::error(
+            "syntax::no-prefix-parser",
+            "No prefix parser for \"Semicolon\"".to_string(),
+        )
+        .with_span(DiagnosticSpan {
+            file: "main.loop".to_string(),
+            line_start: 2,
+            column_start: 6,
+            line_end: 2,
+            column_end: 7,
+            label: None,
+            text: Some("y := ;;".to_string()),
+        })
+        .with_note("statements end with a semicolon".to_string())
+    }
+
+    #[test]
+    fn render_json() {
+        assert_eq!(
+            diagnostic().render_json(),
+            "{\"severity\":\"error\",\"code\":\"syntax::no-prefix-parser\",\"message\":\"No prefix parser for \\\"Semicolon\\\"\",\"spans\":[{\"file\":\"main.loop\",\"line_start\":2,\"column_start\":6,\"line_end\":2,\"column_end\":7,\"label\":null,\"text\":\"y := ;;\"}],\"notes\":[\"statements end with a semicolon\"]}"
+        );
+    }
+
+    #[test]
+    fn render_human() {
+        colored::control::set_override(false);
+
+        assert_eq!(
+            diagnostic().render_human(),
+            "error[syntax::no-prefix-parser]\n  | -> main.loop [2:6]\n  | \n2 | y := ;;\n  |      ^^\n  | \n  = No prefix parser for \"Semicolon\"\n  = note: statements end with a semicolon\n"
+        );
+    }
+}
@@ -20,6 +20,9 @@ pub enum Instruction {
     CALL,
     WHILE,
     GREATERTHAN,
+    LESSTHAN,
+    GREATERTHANOREQUAL,
+    LESSTHANOREQUAL,
     EQUALS,
     NOTEQUALS,
     PUSH,
@@ -35,6 +38,14 @@ pub enum Instruction {
     OR,
     MODULO,
     COMPOUND,
+    HASHMAP,
+    KEYS,
+    RANGE,
+    BREAK,
+    CONTINUE,
+    NOT,
+    NEGATE,
+    LOCATION,
 }
 
 impl Instruction {
@@ -108,7 +119,7 @@ pub enum Token {
     #[error]
     #[regex(r"[ \t\n\f]+", logos::skip)]
     Error,
-    #[regex(r"\.\S+", instruction)]
+    #[regex(r"\.[A-Z]+", instruction)]
     Instruction(Instruction),
     #[regex(r"PARAMETER|VARIABLE", load_type_lex)]
     LoadType(LoadType),
@@ -152,5 +163,9 @@ pub enum Token {
     Identifier(String),
     #[token("WHILE")]
     While,
+    #[token("HASHMAP")]
+    Hashmap,
+    #[token("FIELDS")]
+    Fields,
     End,
 }
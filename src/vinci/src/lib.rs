@@ -1,16 +1,46 @@
 use crate::ast::AST;
 use crate::lexer::token::Token;
 use crate::parser::Parser;
+use crate::types::Type;
 use logos::Logos;
 
 pub mod ast;
+pub mod binary;
+pub mod diagnostic;
 mod lexer;
+pub mod optimizer;
 mod parser;
+pub mod printer;
 pub mod types;
+pub mod validator;
 
-pub fn parse(arc: &str) -> AST {
+pub use crate::parser::error::{ParseError, ParseErrorKind};
+pub use crate::parser::ParserState;
+
+/// Parses Arc into an AST. Nothing is printed, every error that is found is returned and can be
+/// shown using `ParseError::diagnostic`.
+pub fn parse(arc: &str) -> Result<AST, Vec<ParseError>> {
     let lexer = Token::lexer(arc);
     let mut parser = Parser::new(lexer);
 
     parser.parse()
 }
+
+/// Parses a type the way it is written in Arc, eg: `HASHMAP[CHAR[],FLOAT[]]`
+pub fn parse_type(tp: &str) -> Result<Type, ParseError> {
+    Parser::new(Token::lexer(tp)).parse_type()
+}
+
+/// Parses Arc while keeping the types defined by an earlier call to this function. When it fails
+/// the state from before is still valid.
+pub fn parse_with_state(
+    arc: &str,
+    state: ParserState,
+) -> Result<(AST, ParserState), Vec<ParseError>> {
+    let lexer = Token::lexer(arc);
+    let mut parser = Parser::new_with_state(lexer, state);
+
+    let ast = parser.parse()?;
+
+    Ok((ast, parser.get_parser_state()))
+}
@@ -0,0 +1,122 @@
+//! Calculates operations on constants while compiling, eg. `.ADD { .CONSTANT INT 1; .CONSTANT INT
+//! 2; };` becomes `.CONSTANT INT 3;`
+use crate::ast::instructions::suffix::BinaryOperation;
+use crate::ast::instructions::Node;
+use crate::optimizer::visit_nodes;
+use crate::types::ValueType;
+
+pub fn fold_constants(nodes: &mut [Node]) -> usize {
+    let mut changes = 0;
+
+    visit_nodes(nodes, &mut |node| {
+        if let Some(folded) = fold(node) {
+            *node = Node::CONSTANT(folded);
+            changes += 1;
+        }
+    });
+
+    changes
+}
+
+fn fold(node: &Node) -> Option<ValueType> {
+    match node {
+        Node::SUFFIX(suffix) => match (&suffix.left, &suffix.right) {
+            (Node::CONSTANT(left), Node::CONSTANT(right)) => {
+                fold_operation(&suffix.operation, left, right)
+            }
+            _ => None,
+        },
+        Node::NOT(value) => match value.as_ref() {
+            Node::CONSTANT(ValueType::Boolean(value)) => Some(ValueType::Boolean(!value)),
+            _ => None,
+        },
+        Node::NEGATE(value) => match value.as_ref() {
+            Node::CONSTANT(ValueType::Integer(value)) => {
+                value.checked_neg().map(ValueType::Integer)
+            }
+            Node::CONSTANT(ValueType::Float(value)) => Some(ValueType::Float(-value)),
+            _ => None,
+        },
+        Node::AND(a, b) | Node::OR(a, b) => match (a.as_ref(), b.as_ref()) {
+            (Node::CONSTANT(ValueType::Boolean(a)), Node::CONSTANT(ValueType::Boolean(b))) => {
+                Some(ValueType::Boolean(if let Node::AND(..) = node {
+                    *a && *b
+                } else {
+                    *a || *b
+                }))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Division, powers and modulo are left to the backend, as they depend on how it rounds
+fn fold_operation(
+    operation: &BinaryOperation,
+    left: &ValueType,
+    right: &ValueType,
+) -> Option<ValueType> {
+    use BinaryOperation::*;
+
+    match (left, right) {
+        (ValueType::Integer(a), ValueType::Integer(b)) => match operation {
+            ADD => a.checked_add(*b).map(ValueType::Integer),
+            SUBTRACT => a.checked_sub(*b).map(ValueType::Integer),
+            MULTIPLY => a.checked_mul(*b).map(ValueType::Integer),
+            _ => compare(operation, a, b),
+        },
+        (
+            ValueType::Integer(_) | ValueType::Float(_),
+            ValueType::Integer(_) | ValueType::Float(_),
+        ) => {
+            let (a, b) = (number(left)?, number(right)?);
+            let result = match operation {
+                ADD => a + b,
+                SUBTRACT => a - b,
+                MULTIPLY => a * b,
+                _ => return compare(operation, &a, &b),
+            };
+
+            // The backend can not write infinity or NaN as a constant
+            if result.is_finite() {
+                Some(ValueType::Float(result))
+            } else {
+                None
+            }
+        }
+        (ValueType::Boolean(a), ValueType::Boolean(b)) => match operation {
+            EQUALS | NOTEQUALS => compare(operation, a, b),
+            _ => None,
+        },
+        (ValueType::Character(a), ValueType::Character(b)) => match operation {
+            EQUALS | NOTEQUALS => compare(operation, a, b),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn compare<T: PartialOrd>(operation: &BinaryOperation, a: &T, b: &T) -> Option<ValueType> {
+    use BinaryOperation::*;
+
+    let result = match operation {
+        GREATERTHAN => a > b,
+        LESSTHAN => a < b,
+        GREATERTHANOREQUAL => a >= b,
+        LESSTHANOREQUAL => a <= b,
+        EQUALS => a == b,
+        NOTEQUALS => a != b,
+        _ => return None,
+    };
+
+    Some(ValueType::Boolean(result))
+}
+
+fn number(value: &ValueType) -> Option<f64> {
+    match value {
+        ValueType::Integer(int) => Some(*int as f64),
+        ValueType::Float(float) => Some(*float),
+        _ => None,
+    }
+}
@@ -0,0 +1,60 @@
+//! Removes code that never runs
+use crate::ast::instructions::Node;
+use crate::optimizer::visit_blocks;
+use crate::types::ValueType;
+
+/// Replaces `.IF` statements with a constant condition by the branch that is taken
+pub fn remove_constant_conditions(nodes: &mut Vec<Node>) -> usize {
+    let mut changes = 0;
+
+    visit_blocks(nodes, &mut |block| {
+        let mut index = 0;
+
+        while index < block.len() {
+            let branch = match &block[index] {
+                Node::CONDITIONAL(conditional) => match conditional.condition {
+                    Node::CONSTANT(ValueType::Boolean(true)) => Some(conditional.body.clone()),
+                    Node::CONSTANT(ValueType::Boolean(false)) => {
+                        Some(conditional.alternative.clone())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some(branch) = branch {
+                let length = branch.len();
+                block.splice(index..=index, branch);
+
+                index += length;
+                changes += 1;
+            } else {
+                index += 1;
+            }
+        }
+    });
+
+    changes
+}
+
+/// Removes the statements after a `.RETURN`, `.BREAK` or `.CONTINUE`, as they are never reached
+pub fn remove_unreachable_code(nodes: &mut Vec<Node>) -> usize {
+    let mut changes = 0;
+
+    visit_blocks(nodes, &mut |block| {
+        let end = block
+            .iter()
+            .position(|node| matches!(node, Node::RETURN(_) | Node::BREAK | Node::CONTINUE));
+
+        if let Some(end) = end {
+            // Locations are not code that runs
+            changes += block[end + 1..]
+                .iter()
+                .filter(|node| !matches!(node, Node::LOCATION(_)))
+                .count();
+            block.truncate(end + 1);
+        }
+    });
+
+    changes
+}
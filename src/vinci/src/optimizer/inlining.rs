@@ -0,0 +1,213 @@
+//! Replaces calls to small functions by what they return. Only functions which consist of a single
+//! `.RETURN` are inlined, eg. calling
+//! ```txt
+//! .FUNCTION "var_0" 0 INT ARGUMENTS { INT; } FREE { } THEN { .RETURN { .ADD { .LOAD PARAMETER 0 0; .CONSTANT INT 1; }; }; };
+//! ```
+//! with `.CONSTANT INT 2;` becomes `.ADD { .CONSTANT INT 2; .CONSTANT INT 1; };`
+use crate::ast::instructions::function::Function;
+use crate::ast::instructions::memory::LoadType;
+use crate::ast::instructions::Node;
+use crate::optimizer::{children, is_pure, visit_node, visit_nodes};
+use crate::types::ValueType;
+use std::collections::{HashMap, HashSet};
+
+/// The most nodes a function can return to be inlined
+const MAX_SIZE: usize = 16;
+
+/// A function that can be inlined, with the unique identifier its parameters are loaded by
+struct Inlinable {
+    unique_identifier: u64,
+    parameters: usize,
+    value: Node,
+}
+
+pub fn inline_functions(nodes: &mut [Node]) -> usize {
+    let functions = inlinable_functions(nodes);
+
+    if functions.is_empty() {
+        return 0;
+    }
+
+    let mut changes = 0;
+    for node in nodes.iter_mut() {
+        changes += inline_calls(node, &functions);
+    }
+
+    changes
+}
+
+/// Named functions that return a small value, which are defined once and never assigned to
+fn inlinable_functions(nodes: &mut [Node]) -> HashMap<u64, Inlinable> {
+    let mut functions = HashMap::new();
+    let mut excluded = HashSet::new();
+
+    visit_nodes(nodes, &mut |node| match node {
+        Node::FUNCTION(function) => {
+            let index = match function.name.strip_prefix("var_") {
+                Some(index) => index.parse::<u64>().ok(),
+                None => None,
+            };
+
+            if let Some(index) = index {
+                if functions.contains_key(&index) {
+                    excluded.insert(index);
+                }
+
+                if let Some(inlinable) = inlinable(index, function) {
+                    functions.insert(index, inlinable);
+                } else {
+                    excluded.insert(index);
+                }
+            }
+        }
+        Node::STORE(store) => {
+            excluded.insert(store.index);
+        }
+        Node::ASSIGN(to, _) => {
+            if let Node::LOAD(load) = to.as_ref() {
+                if load.load_type == LoadType::VARIABLE {
+                    excluded.insert(load.index);
+                }
+            }
+        }
+        _ => (),
+    });
+
+    functions.retain(|index, _| !excluded.contains(index));
+    functions
+}
+
+fn inlinable(index: u64, function: &Function) -> Option<Inlinable> {
+    if !function.free.is_empty() {
+        return None;
+    }
+
+    let body: Vec<&Node> = function
+        .body
+        .iter()
+        .filter(|node| !matches!(node, Node::LOCATION(_)))
+        .collect();
+
+    let value = match body.as_slice() {
+        [Node::RETURN(value)] => value.as_ref().clone(),
+        _ => return None,
+    };
+
+    let unique_identifier = function.unique_identifier as u64;
+    let mut size = 0;
+    let mut allowed = true;
+
+    // Functions inside of the value, calls to itself and parameters of other functions could
+    // behave differently where the function is called
+    visit_node(&mut value.clone(), &mut |node| {
+        size += 1;
+
+        match node {
+            Node::FUNCTION(_) | Node::CONSTANT(ValueType::Function(..)) => allowed = false,
+            Node::LOAD(load) => match load.load_type {
+                LoadType::VARIABLE if load.index == index => allowed = false,
+                LoadType::PARAMETER(identifier) if identifier != unique_identifier => {
+                    allowed = false
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+    });
+
+    if !allowed || size > MAX_SIZE {
+        return None;
+    }
+
+    Some(Inlinable {
+        unique_identifier,
+        parameters: function.parameters.len(),
+        value,
+    })
+}
+
+/// Inlines the calls used as a value inside of the node, calls that are statements are kept as
+/// their value might not be a valid statement
+fn inline_calls(node: &mut Node, functions: &HashMap<u64, Inlinable>) -> usize {
+    let (nodes, blocks) = children(node);
+    let mut changes = 0;
+
+    for child in nodes {
+        changes += inline_calls(child, functions);
+
+        if let Some(inlined) = inline_call(child, functions) {
+            *child = inlined;
+            changes += 1;
+        }
+    }
+
+    for block in blocks {
+        for node in block.iter_mut() {
+            changes += inline_calls(node, functions);
+        }
+    }
+
+    changes
+}
+
+fn inline_call(node: &Node, functions: &HashMap<u64, Inlinable>) -> Option<Node> {
+    let call = match node {
+        Node::CALL(call) => call,
+        _ => return None,
+    };
+
+    let function = match &call.call {
+        Node::LOAD(load) if load.load_type == LoadType::VARIABLE => functions.get(&load.index)?,
+        _ => return None,
+    };
+
+    if call.arguments.len() != function.parameters {
+        return None;
+    }
+
+    let mut value = function.value.clone();
+    let mut uses = vec![0; function.parameters];
+
+    visit_node(&mut value.clone(), &mut |node| {
+        if let Node::LOAD(load) = node {
+            if load.load_type == LoadType::PARAMETER(function.unique_identifier) {
+                if let Some(uses) = uses.get_mut(load.index as usize) {
+                    *uses += 1;
+                }
+            }
+        }
+    });
+
+    // Arguments can only be left out or evaluated more than once when that does not change
+    // what the program does
+    for (argument, uses) in call.arguments.iter().zip(&uses) {
+        if !is_pure(argument) || (*uses > 1 && !is_trivial(argument)) {
+            return None;
+        }
+    }
+
+    visit_node(&mut value, &mut |node| {
+        if let Node::LOAD(load) = node {
+            if load.load_type == LoadType::PARAMETER(function.unique_identifier) {
+                *node = call.arguments[load.index as usize].clone();
+            }
+        }
+    });
+
+    Some(value)
+}
+
+/// Values that are the same every time they are evaluated, arrays and hashmaps are new ones
+fn is_trivial(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::LOAD(_)
+            | Node::CONSTANT(
+                ValueType::Integer(_)
+                    | ValueType::Float(_)
+                    | ValueType::Boolean(_)
+                    | ValueType::Character(_)
+                    | ValueType::Void
+            )
+    )
+}
@@ -0,0 +1,215 @@
+//! Optimizations on the AST of Arc, enabled with the "--optimize" flag. Every pass rewrites the
+//! AST in place and tells how many changes it made.
+use crate::ast::instructions::conditional::Conditional;
+use crate::ast::instructions::function::Call;
+use crate::ast::instructions::memory::{Index, Push, Range, Slice};
+use crate::ast::instructions::suffix::Suffix;
+use crate::ast::instructions::while_loop::While;
+use crate::ast::instructions::Node;
+use crate::ast::AST;
+use crate::types::ValueType;
+use std::fmt::{Display, Formatter};
+
+mod constant_folding;
+mod dead_code;
+mod inlining;
+mod tests;
+mod unused_stores;
+
+type Pass = fn(&mut Vec<Node>) -> usize;
+
+/// Every pass in the order they run in, functions are inlined first so the constants they return
+/// can be folded
+const PASSES: [(&str, Pass); 5] = [
+    ("inlining", |nodes| inlining::inline_functions(nodes)),
+    ("constant folding", |nodes| {
+        constant_folding::fold_constants(nodes)
+    }),
+    ("constant conditions", dead_code::remove_constant_conditions),
+    ("unreachable code", dead_code::remove_unreachable_code),
+    ("unused stores", unused_stores::remove_unused_stores),
+];
+
+/// How many changes a pass made
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Statistics {
+    pub pass: &'static str,
+    pub changes: usize,
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} changes", self.pass, self.changes)
+    }
+}
+
+/// Runs every optimization over the AST. Only whole programs can be optimized, as stores that are
+/// not used yet might be used by code that is parsed later.
+pub fn optimize(ast: &mut AST) -> Vec<Statistics> {
+    PASSES
+        .iter()
+        .map(|(pass, run)| Statistics {
+            pass,
+            changes: run(&mut ast.nodes),
+        })
+        .collect()
+}
+
+/// The nodes used as values by a node, and the lists of statements inside of it
+fn children(node: &mut Node) -> (Vec<&mut Node>, Vec<&mut Vec<Node>>) {
+    let mut nodes: Vec<&mut Node> = Vec::new();
+    let mut blocks: Vec<&mut Vec<Node>> = Vec::new();
+
+    match node {
+        Node::CONSTANT(value) => constant_blocks(value, &mut blocks),
+        Node::STORE(store) => nodes.push(&mut store.value),
+        Node::SUFFIX(suffix) => {
+            let Suffix { left, right, .. } = suffix.as_mut();
+            nodes.push(left);
+            nodes.push(right);
+        }
+        Node::NOT(value)
+        | Node::NEGATE(value)
+        | Node::RETURN(value)
+        | Node::LENGTH(value)
+        | Node::KEYS(value) => nodes.push(value),
+        Node::CONDITIONAL(conditional) => {
+            let Conditional {
+                condition,
+                body,
+                alternative,
+            } = conditional.as_mut();
+            nodes.push(condition);
+            blocks.push(body);
+            blocks.push(alternative);
+        }
+        Node::FUNCTION(function) => blocks.push(&mut function.body),
+        Node::CALL(call) => {
+            let Call { call, arguments } = call.as_mut();
+            nodes.push(call);
+            nodes.extend(arguments.iter_mut());
+        }
+        Node::WHILE(while_loop) => {
+            let While { condition, body } = while_loop.as_mut();
+            nodes.push(condition);
+            blocks.push(body);
+        }
+        Node::INDEX(Index {
+            to_index, index, ..
+        }) => {
+            nodes.push(to_index);
+            nodes.push(index);
+        }
+        Node::SLICE(Slice { to_slice, from, to }) => {
+            nodes.push(to_slice);
+            nodes.push(from);
+            nodes.push(to);
+        }
+        Node::PUSH(Push { to_push, item }) => {
+            nodes.push(to_push);
+            nodes.push(item);
+        }
+        Node::COPY(copy) => nodes.push(&mut copy.object),
+        Node::LOADLIB(library) => nodes.push(&mut library.path),
+        Node::ASSIGN(a, b) | Node::POP(a, b) | Node::AND(a, b) | Node::OR(a, b) => {
+            nodes.push(a);
+            nodes.push(b);
+        }
+        Node::HASHMAP(hashmap) => {
+            for (key, value) in hashmap.values.iter_mut() {
+                nodes.push(key);
+                nodes.push(value);
+            }
+        }
+        Node::RANGE(Range { from, till, step }) => {
+            nodes.push(from);
+            nodes.push(till);
+            nodes.push(step);
+        }
+        Node::LOAD(_) | Node::BREAK | Node::CONTINUE | Node::COMPOUND(_) | Node::LOCATION(_) => (),
+    }
+
+    (nodes, blocks)
+}
+
+/// Functions can be constants as well, eg. the methods of a class
+fn constant_blocks<'a>(value: &'a mut ValueType, blocks: &mut Vec<&'a mut Vec<Node>>) {
+    match value {
+        ValueType::Function(_, _, _, body) => blocks.push(body),
+        ValueType::Array(values) | ValueType::Compound(_, values) => {
+            for value in values.iter_mut() {
+                constant_blocks(value, blocks);
+            }
+        }
+        ValueType::Hashmap(values) => {
+            for (key, value) in values.iter_mut() {
+                constant_blocks(key, blocks);
+                constant_blocks(value, blocks);
+            }
+        }
+        ValueType::Instance(_, properties) => {
+            for (_, value) in properties.iter_mut() {
+                constant_blocks(value, blocks);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Calls `visit` on every node, after it was called on the nodes inside of it
+fn visit_nodes(nodes: &mut [Node], visit: &mut dyn FnMut(&mut Node)) {
+    for node in nodes {
+        visit_node(node, visit);
+    }
+}
+
+fn visit_node(node: &mut Node, visit: &mut dyn FnMut(&mut Node)) {
+    let (nodes, blocks) = children(node);
+
+    for child in nodes {
+        visit_node(child, visit);
+    }
+
+    for block in blocks {
+        visit_nodes(block, visit);
+    }
+
+    visit(node);
+}
+
+/// Calls `visit` on every list of statements, after it was called on the lists inside of it
+fn visit_blocks(nodes: &mut Vec<Node>, visit: &mut dyn FnMut(&mut Vec<Node>)) {
+    for node in nodes.iter_mut() {
+        visit_node_blocks(node, visit);
+    }
+
+    visit(nodes);
+}
+
+fn visit_node_blocks(node: &mut Node, visit: &mut dyn FnMut(&mut Vec<Node>)) {
+    let (nodes, blocks) = children(node);
+
+    for child in nodes {
+        visit_node_blocks(child, visit);
+    }
+
+    for block in blocks {
+        visit_blocks(block, visit);
+    }
+}
+
+/// Whether evaluating the node does nothing besides resulting in a value, so it can be left out
+fn is_pure(node: &Node) -> bool {
+    match node {
+        Node::CONSTANT(_) | Node::LOAD(_) => true,
+        Node::FUNCTION(function) => function.name.is_empty(),
+        Node::SUFFIX(suffix) => is_pure(&suffix.left) && is_pure(&suffix.right),
+        Node::NOT(value) | Node::NEGATE(value) | Node::LENGTH(value) => is_pure(value),
+        Node::AND(a, b) | Node::OR(a, b) => is_pure(a) && is_pure(b),
+        Node::HASHMAP(hashmap) => hashmap
+            .values
+            .iter()
+            .all(|(key, value)| is_pure(key) && is_pure(value)),
+        _ => false,
+    }
+}
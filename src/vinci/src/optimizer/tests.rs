@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use crate::optimizer::{optimize, Statistics};
+    use crate::parse;
+
+    /// Optimizes the Arc and compares it to the AST of the expected Arc
+    fn optimizer_test(arc: &str, expected: &str) -> Vec<Statistics> {
+        let mut ast = parse(arc).unwrap();
+        assert!(!ast.nodes.is_empty());
+
+        let statistics = optimize(&mut ast);
+
+        assert_eq!(parse(expected).unwrap(), ast);
+
+        statistics
+    }
+
+    fn changes(statistics: &[Statistics], pass: &str) -> usize {
+        statistics
+            .iter()
+            .find(|statistics| statistics.pass == pass)
+            .unwrap()
+            .changes
+    }
+
+    #[test]
+    fn constant_folding() {
+        let statistics = optimizer_test(
+            ".ADD { .MULTIPLY { .CONSTANT INT 2; .CONSTANT INT 3; }; .CONSTANT INT 4; };",
+            ".CONSTANT INT 10;",
+        );
+
+        assert_eq!(changes(&statistics, "constant folding"), 2);
+    }
+
+    #[test]
+    fn constant_folding_comparisons() {
+        optimizer_test(
+            ".NOT { .GREATERTHAN { .CONSTANT FLOAT 2.5; .CONSTANT INT 2; }; };",
+            ".CONSTANT BOOL false;",
+        );
+        optimizer_test(
+            ".AND { .EQUALS { .CONSTANT INT 1; .CONSTANT INT 1; }; .CONSTANT BOOL true; };",
+            ".CONSTANT BOOL true;",
+        );
+    }
+
+    #[test]
+    fn constant_folding_keeps_division_and_variables() {
+        optimizer_test(
+            ".DIVIDE { .CONSTANT INT 7; .CONSTANT INT 2; };",
+            ".DIVIDE { .CONSTANT INT 7; .CONSTANT INT 2; };",
+        );
+        optimizer_test(
+            ".STORE 0 { .CONSTANT INT 1; }; .ADD { .LOAD VARIABLE 0; .CONSTANT INT 2; };",
+            ".STORE 0 { .CONSTANT INT 1; }; .ADD { .LOAD VARIABLE 0; .CONSTANT INT 2; };",
+        );
+    }
+
+    #[test]
+    fn constant_conditions() {
+        let statistics = optimizer_test(
+            ".STORE 0 { .CONSTANT INT 0; };\
+            .IF CONDITION { .GREATERTHAN { .CONSTANT INT 2; .CONSTANT INT 1; }; } \
+            THEN { .ASSIGN { .LOAD VARIABLE 0; } { .CONSTANT INT 1; }; } \
+            ELSE { .ASSIGN { .LOAD VARIABLE 0; } { .CONSTANT INT 2; }; };",
+            ".STORE 0 { .CONSTANT INT 0; }; .ASSIGN { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };",
+        );
+
+        assert_eq!(changes(&statistics, "constant conditions"), 1);
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let statistics = optimizer_test(
+            ".FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN { \
+            .RETURN { .CONSTANT INT 1; }; .CALL { .LOAD VARIABLE 0; } { }; .CONSTANT INT 2; };",
+            ".FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN { .RETURN { .CONSTANT INT 1; }; };",
+        );
+
+        assert_eq!(changes(&statistics, "unreachable code"), 2);
+    }
+
+    #[test]
+    fn unused_stores() {
+        let statistics = optimizer_test(
+            ".STORE 0 { .CONSTANT INT 1; };\
+            .STORE 1 { .CONSTANT INT 2; };\
+            .STORE 2 { .CALL { .LOAD VARIABLE 1; } { }; };\
+            .LOAD VARIABLE 1;",
+            ".STORE 1 { .CONSTANT INT 2; }; .STORE 2 { .CALL { .LOAD VARIABLE 1; } { }; }; .LOAD VARIABLE 1;",
+        );
+
+        assert_eq!(changes(&statistics, "unused stores"), 1);
+    }
+
+    #[test]
+    fn inlining() {
+        let statistics = optimizer_test(
+            ".FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; } FREE { } THEN { \
+            .RETURN { .MULTIPLY { .LOAD PARAMETER 0 0; .LOAD PARAMETER 0 0; }; }; };\
+            .STORE 1 { .CALL { .LOAD VARIABLE 0; } { .CONSTANT INT 3; }; };\
+            .LOAD VARIABLE 1;",
+            ".FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; } FREE { } THEN { \
+            .RETURN { .MULTIPLY { .LOAD PARAMETER 0 0; .LOAD PARAMETER 0 0; }; }; };\
+            .STORE 1 { .CONSTANT INT 9; };\
+            .LOAD VARIABLE 1;",
+        );
+
+        assert_eq!(changes(&statistics, "inlining"), 1);
+        assert_eq!(changes(&statistics, "constant folding"), 1);
+    }
+
+    #[test]
+    fn inlining_keeps_recursion_and_side_effects() {
+        // Calls itself
+        let recursive = ".FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; } FREE { } THEN { \
+            .RETURN { .CALL { .LOAD VARIABLE 0; } { .LOAD PARAMETER 0 0; }; }; };\
+            .STORE 1 { .CALL { .LOAD VARIABLE 0; } { .CONSTANT INT 3; }; };\
+            .LOAD VARIABLE 1;";
+        optimizer_test(recursive, recursive);
+
+        // The argument would be called twice
+        let twice = ".FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; } FREE { } THEN { \
+            .RETURN { .ADD { .LOAD PARAMETER 0 0; .LOAD PARAMETER 0 0; }; }; };\
+            .STORE 1 { .CALL { .LOAD VARIABLE 0; } { .CALL { .LOAD VARIABLE 2; } { }; }; };\
+            .LOAD VARIABLE 1;";
+        optimizer_test(twice, twice);
+
+        // Calls as a statement are kept
+        let statement = ".FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN { \
+            .RETURN { .CONSTANT INT 1; }; };\
+            .CALL { .LOAD VARIABLE 0; } { };";
+        optimizer_test(statement, statement);
+    }
+
+    #[test]
+    fn inlining_ignores_locations() {
+        let statistics = optimizer_test(
+            ".LOCATION \"main.loop\" 1;\
+            .FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN { \
+            .LOCATION \"main.loop\" 2; .RETURN { .CONSTANT INT 1; }; };\
+            .LOCATION \"main.loop\" 4;\
+            .STORE 1 { .ADD { .CALL { .LOAD VARIABLE 0; } { }; .CONSTANT INT 1; }; };\
+            .LOAD VARIABLE 1;",
+            ".LOCATION \"main.loop\" 1;\
+            .FUNCTION \"var_0\" 0 INT ARGUMENTS { } FREE { } THEN { \
+            .LOCATION \"main.loop\" 2; .RETURN { .CONSTANT INT 1; }; };\
+            .LOCATION \"main.loop\" 4;\
+            .STORE 1 { .CONSTANT INT 2; };\
+            .LOAD VARIABLE 1;",
+        );
+
+        assert_eq!(changes(&statistics, "inlining"), 1);
+    }
+}
@@ -0,0 +1,35 @@
+//! Removes variables that are never loaded, eg. `.STORE 1 { .CONSTANT INT 10; };` when there is no
+//! `.LOAD VARIABLE 1;` anywhere in the program
+use crate::ast::instructions::memory::LoadType;
+use crate::ast::instructions::Node;
+use crate::optimizer::{is_pure, visit_blocks, visit_nodes};
+use std::collections::HashSet;
+
+pub fn remove_unused_stores(nodes: &mut Vec<Node>) -> usize {
+    let mut loaded = HashSet::new();
+
+    // Variables are global in the backend, so a load anywhere keeps the store
+    visit_nodes(nodes, &mut |node| {
+        if let Node::LOAD(load) = node {
+            if load.load_type == LoadType::VARIABLE {
+                loaded.insert(load.index);
+            }
+        }
+    });
+
+    let mut changes = 0;
+
+    visit_blocks(nodes, &mut |block| {
+        let length = block.len();
+
+        // Stores are kept when their value does more than resulting in a value
+        block.retain(|node| match node {
+            Node::STORE(store) => loaded.contains(&store.index) || !is_pure(&store.value),
+            _ => true,
+        });
+
+        changes += length - block.len();
+    });
+
+    changes
+}
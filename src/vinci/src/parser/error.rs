@@ -1,26 +1,86 @@
+use crate::diagnostic::{Diagnostic, DiagnosticSpan};
 use crate::lexer::token::Token;
 use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
 
-#[derive(Debug)]
-pub enum ParseError {
-    Unknown,
+/// An error together with the bytes of the Arc it was found on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
     // (Expected, Got)
     UnexpectedToken(Token, Token),
+    /// A constant whose value does not fit its type, eg: `.CONSTANT INT ;`
+    InvalidValue(Token),
+    /// Arrays can only contain constants
+    NotConstant,
+    /// A key of a hashmap that is not followed by a value
+    MissingValue,
     TypeAlreadyExists(String),
     UnknownType(String),
 }
 
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, span: Range<usize>) -> ParseError {
+        ParseError { kind, span }
+    }
+
+    /// Turns the error into a diagnostic that shows the line of the Arc it was found on
+    pub fn diagnostic(&self, arc: &str) -> Diagnostic {
+        let start = self.span.start.min(arc.len());
+        let end = self.span.end.clamp(start, arc.len());
+
+        // Positions in the Arc are in bytes, diagnostics count lines and characters
+        let before = &arc[..start];
+        let line_start = before.matches('\n').count() + 1;
+        let line_offset = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column_start = arc[line_offset..start].chars().count() + 1;
+        let length = arc[start..end].chars().count().max(1);
+        let text = arc[line_offset..].lines().next().unwrap_or("");
+
+        Diagnostic::error("arc::parse", self.to_string())
+            .with_span(DiagnosticSpan {
+                file: "<arc>".to_string(),
+                line_start: line_start as i32,
+                column_start: column_start as i32,
+                line_end: line_start as i32,
+                column_end: (column_start + length - 1) as i32,
+                label: None,
+                text: Some(text.to_string()),
+            })
+            .with_note(format!("near: {}", &arc[start..end]))
+    }
+}
+
 impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Display for ParseErrorKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ParseError::Unknown => write!(f, "{:?}", self),
-            ParseError::UnexpectedToken(expected, got) => {
-                write!(f, "expected={:?}. got={:?}", expected, got)
+            ParseErrorKind::UnexpectedToken(expected, got) => {
+                write!(f, "expected {}. got {}", describe(expected), describe(got))
             }
-            ParseError::TypeAlreadyExists(name) => {
+            ParseErrorKind::InvalidValue(got) => {
+                write!(
+                    f,
+                    "expected the value of the constant. got {}",
+                    describe(got)
+                )
+            }
+            ParseErrorKind::NotConstant => write!(f, "arrays can only contain constants"),
+            ParseErrorKind::MissingValue => write!(f, "the last key of the hashmap has no value"),
+            ParseErrorKind::TypeAlreadyExists(name) => {
                 write!(f, "Compound type's name already in use. got=\"{}\"", name)
             }
-            ParseError::UnknownType(name) => write!(
+            ParseErrorKind::UnknownType(name) => write!(
                 f,
                 "Type is unknown, did you define it using .COMPOUND? got=\"{}\"",
                 name
@@ -28,3 +88,35 @@ impl Display for ParseError {
         }
     }
 }
+
+/// What kind of token it is, values are left out as the diagnostic shows the Arc itself
+fn describe(token: &Token) -> &'static str {
+    match token {
+        Token::Error => "an unknown token",
+        Token::Instruction(_) => "an instruction",
+        Token::LoadType(_) => "VARIABLE or PARAMETER",
+        Token::Semicolon => "\";\"",
+        Token::LeftBracket => "\"[\"",
+        Token::RightBracket => "\"]\"",
+        Token::Comma => "\",\"",
+        Token::LeftCurly => "\"{\"",
+        Token::RightCurly => "\"}\"",
+        Token::String(_) => "a string",
+        Token::Character(_) => "a character",
+        Token::Condition => "CONDITION",
+        Token::Then => "THEN",
+        Token::Else => "ELSE",
+        Token::Free => "FREE",
+        Token::Type(_) => "a type",
+        Token::Float(_) => "a float",
+        Token::Number(_) => "a number",
+        Token::Boolean(_) => "a boolean",
+        Token::Arguments => "ARGUMENTS",
+        Token::Namespace(_) => "a namespace",
+        Token::Identifier(_) => "an identifier",
+        Token::While => "WHILE",
+        Token::Hashmap => "HASHMAP",
+        Token::Fields => "FIELDS",
+        Token::End => "the end of the Arc",
+    }
+}
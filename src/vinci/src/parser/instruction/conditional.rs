@@ -12,7 +12,7 @@ use crate::parser::Parser;
 /// use vinci::parse;
 /// use vinci::types::ValueType;
 /// let mut input = ".IF CONDITION { .CONSTANT BOOL true; } THEN { .CONSTANT INT 10; } ELSE { .CONSTANT INT 20; };";
-/// let result = parse(input);
+/// let result = parse(input).unwrap();
 ///
 /// assert_eq!(result, AST {nodes: vec![
 ///     Node::CONDITIONAL(
@@ -1,10 +1,9 @@
 use crate::ast::instructions::function::{Call, Function};
 use crate::ast::instructions::Node;
 use crate::lexer::token::Token;
-use crate::parser::error::ParseError;
+use crate::parser::error::{ParseError, ParseErrorKind};
 use crate::parser::Parser;
 use crate::types::{Type, ValueType};
-use std::borrow::Borrow;
 
 /// ```
 /// use vinci::ast::AST;
@@ -16,7 +15,7 @@ use std::borrow::Borrow;
 /// use vinci::parse;
 /// use vinci::types::{Type, ValueType};
 /// let mut input = ".FUNCTION \"named_function\" 0 INT ARGUMENTS {INT;} FREE { } THEN { .LOAD PARAMETER 0 0; };";
-/// let result = parse(input);
+/// let result = parse(input).unwrap();
 ///
 /// assert_eq!(result, AST {nodes: vec![
 ///     Node::FUNCTION(
@@ -38,19 +37,19 @@ pub fn parse_function_instruction(parser: &mut Parser) -> Result<Node, ParseErro
     let name = if let Token::String(value) = parser.next_token() {
         value
     } else {
-        return Err(ParseError::UnexpectedToken(
-            Token::Type(Type::ARRAY(Box::from(Type::CHAR))),
+        return Err(parser.error(ParseErrorKind::UnexpectedToken(
+            Token::String(vec![]),
             parser.current_token(),
-        ));
+        )));
     };
 
     let unique_identifier = if let Token::Number(tp) = parser.next_token() {
         tp
     } else {
-        return Err(ParseError::UnexpectedToken(
-            Token::Type(Type::INT),
+        return Err(parser.error(ParseErrorKind::UnexpectedToken(
+            Token::Number(0),
             parser.current_token(),
-        ));
+        )));
     };
 
     let return_type = parser.parse_type()?;
@@ -97,7 +96,7 @@ pub fn parse_function_instruction(parser: &mut Parser) -> Result<Node, ParseErro
 /// use vinci::parse;
 /// use vinci::types::{Type, ValueType};
 /// let mut input = ".CALL { .CONSTANT INT 0; } { .CONSTANT INT 10; .CONSTANT INT 30; };";
-/// let result = parse(input);
+/// let result = parse(input).unwrap();
 ///
 /// assert_eq!(result, AST {nodes: vec![
 ///     Node::CALL(Box::new(
@@ -146,12 +145,9 @@ pub fn parse_call_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
 
 pub fn parse_type_arguments(parser: &mut Parser) -> Result<Vec<Type>, ParseError> {
     let mut parameters: Vec<Type> = Vec::new();
-    let mut next = parser.lexer.borrow().clone().next().unwrap();
-
-    while next != Token::RightCurly {
+    while parser.peek_token() != Token::RightCurly {
         parameters.push(parser.parse_type()?);
         parser.expected(Token::Semicolon)?;
-        next = parser.lexer.borrow().clone().next().unwrap();
     }
 
     Ok(parameters)
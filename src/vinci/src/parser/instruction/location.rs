@@ -0,0 +1,42 @@
+use crate::ast::instructions::location::Location;
+use crate::ast::instructions::Node;
+use crate::lexer::token::Token;
+use crate::parser::error::{ParseError, ParseErrorKind};
+use crate::parser::Parser;
+
+/// ```
+/// use vinci::ast::AST;
+/// use vinci::ast::instructions::Node;
+/// use vinci::ast::instructions::location::Location;
+/// use vinci::parse;
+/// let mut input = ".LOCATION \"main.loop\" 3;";
+/// let result = parse(input).unwrap();
+///
+/// assert_eq!(result, AST {nodes: vec![
+///     Node::LOCATION(Location { file: "main.loop".to_string(), line: 3 })
+/// ]});
+/// ```
+pub fn parse_location_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    let file = if let Token::String(value) = parser.next_token() {
+        value.iter().collect()
+    } else {
+        return Err(parser.error(ParseErrorKind::UnexpectedToken(
+            Token::String(vec![]),
+            parser.current_token(),
+        )));
+    };
+
+    let line = match parser.next_token() {
+        Token::Number(line) if line >= 0 => line as u32,
+        _ => {
+            return Err(parser.error(ParseErrorKind::UnexpectedToken(
+                Token::Number(0),
+                parser.current_token(),
+            )))
+        }
+    };
+
+    parser.expected(Token::Semicolon)?;
+
+    Ok(Node::LOCATION(Location { file, line }))
+}
@@ -1,13 +1,12 @@
 use crate::ast::instructions::memory::{
-    CompoundType, Copy, Index, Load, LoadLib, LoadType, Push, Slice, Store,
+    CompoundType, Copy, Hashmap, Index, Load, LoadLib, LoadType, Push, Range, Slice, Store,
 };
 use crate::ast::instructions::Node;
-use crate::lexer::token::Token;
-use crate::parser::error::ParseError;
+use crate::lexer::token::{Instruction, Token};
+use crate::parser::error::{ParseError, ParseErrorKind};
 use crate::parser::instruction::function::{parse_function_instruction, parse_type_arguments};
 use crate::parser::Parser;
 use crate::types::{Type, ValueType};
-use std::borrow::Borrow;
 
 pub fn parse_constant_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
     let type_def = parser.parse_type()?;
@@ -17,16 +16,17 @@ pub fn parse_constant_instruction(parser: &mut Parser) -> Result<Node, ParseErro
 
         parser.expected(Token::LeftCurly)?;
 
-        while parser.next_token() != Token::RightCurly {
-            match parser.lexer.borrow().slice() {
-                ".CONSTANT" => {
+        loop {
+            match parser.next_token() {
+                Token::RightCurly => break,
+                Token::Instruction(Instruction::CONSTANT) => {
                     let constant = parse_constant_instruction(parser)?;
 
                     if let Node::CONSTANT(v) = constant {
                         values.push(v);
                     }
                 }
-                ".FUNCTION" => {
+                Token::Instruction(Instruction::FUNCTION) => {
                     let function = parse_function_instruction(parser)?;
 
                     if let Node::FUNCTION(func) = function {
@@ -39,11 +39,19 @@ pub fn parse_constant_instruction(parser: &mut Parser) -> Result<Node, ParseErro
                         ))
                     }
                 }
-                _ => (),
+                token => {
+                    return Err(parser.error(ParseErrorKind::UnexpectedToken(
+                        Token::Instruction(Instruction::CONSTANT),
+                        token,
+                    )))
+                }
             }
         }
 
         ValueType::Compound(name, Box::new(values))
+    } else if type_def == Type::VOID {
+        // Void has no value, eg: ".CONSTANT VOID;"
+        ValueType::Void
     } else {
         // Second argument is the value
         let next = parser.next_token();
@@ -59,9 +67,7 @@ pub fn parse_constant_instruction(parser: &mut Parser) -> Result<Node, ParseErro
                 ValueType::Array(Box::new(mapped))
             }
             Token::Character(char) => ValueType::Character(char),
-            a => {
-                return Err(ParseError::UnexpectedToken(Token::Type(Type::INT), a));
-            }
+            token => return Err(parser.error(ParseErrorKind::InvalidValue(token))),
         }
     };
 
@@ -81,7 +87,7 @@ fn parse_array(parser: &mut Parser) -> Result<ValueType, ParseError> {
         if let Node::CONSTANT(c) = cst {
             values.push(c);
         } else {
-            return Err(ParseError::Unknown);
+            return Err(parser.error(ParseErrorKind::NotConstant));
         }
 
         current = parser.next_token();
@@ -91,6 +97,8 @@ fn parse_array(parser: &mut Parser) -> Result<ValueType, ParseError> {
 }
 
 pub fn parse_index_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    let hashmap = parser.expected_maybe(Token::Hashmap);
+
     parser.expected(Token::LeftCurly)?;
 
     let next = &parser.next_token();
@@ -102,7 +110,70 @@ pub fn parse_index_instruction(parser: &mut Parser) -> Result<Node, ParseError>
     parser.expected(Token::RightCurly)?;
     parser.expected(Token::Semicolon)?;
 
-    Ok(Node::INDEX(Index { to_index, index }))
+    Ok(Node::INDEX(Index {
+        to_index,
+        index,
+        hashmap,
+    }))
+}
+
+/// ```
+/// use vinci::ast::AST;
+/// use vinci::ast::instructions::memory::Hashmap;
+/// use vinci::ast::instructions::Node;
+/// use vinci::parse;
+/// use vinci::types::{Type, ValueType};
+/// let mut input = ".HASHMAP INT BOOL { .CONSTANT INT 10; .CONSTANT BOOL true; };";
+/// let result = parse(input).unwrap();
+///
+/// assert_eq!(result, AST {nodes: vec![
+///     Node::HASHMAP(Box::new(Hashmap {
+///         key_type: Type::INT,
+///         value_type: Type::BOOL,
+///         values: vec![(
+///             Node::CONSTANT(ValueType::Integer(10)),
+///             Node::CONSTANT(ValueType::Boolean(true))
+///         )]
+///     }))]
+/// });
+/// ```
+pub fn parse_hashmap_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    let key_type = parser.parse_type()?;
+    let value_type = parser.parse_type()?;
+
+    parser.expected(Token::LeftCurly)?;
+
+    let mut nodes = parser.parse_nodes()?.into_iter();
+    let mut values: Vec<(Node, Node)> = Vec::new();
+
+    // Keys and values follow each other
+    while let Some(key) = nodes.next() {
+        if let Some(value) = nodes.next() {
+            values.push((key, value));
+        } else {
+            return Err(parser.error(ParseErrorKind::MissingValue));
+        }
+    }
+
+    parser.expected(Token::Semicolon)?;
+
+    Ok(Node::HASHMAP(Box::new(Hashmap {
+        key_type,
+        value_type,
+        values,
+    })))
+}
+
+pub fn parse_keys_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    parser.expected(Token::LeftCurly)?;
+
+    let current = parser.next_token();
+    let ret = Node::KEYS(Box::new(parser.parse_node(&current)?));
+
+    parser.expected(Token::RightCurly)?;
+    parser.expected(Token::Semicolon)?;
+
+    Ok(ret)
 }
 
 pub fn parse_assign_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
@@ -184,6 +255,27 @@ pub fn parse_slice_instruction(parser: &mut Parser) -> Result<Node, ParseError>
     Ok(Node::SLICE(Slice { to_slice, from, to }))
 }
 
+pub fn parse_range_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    parser.expected(Token::LeftCurly)?;
+
+    let next = &parser.next_token();
+    let from = Box::new(parser.parse_node(next)?);
+    parser.expected(Token::RightCurly)?;
+    parser.expected(Token::LeftCurly)?;
+
+    let next = &parser.next_token();
+    let till = Box::new(parser.parse_node(next)?);
+    parser.expected(Token::RightCurly)?;
+    parser.expected(Token::LeftCurly)?;
+
+    let next = &parser.next_token();
+    let step = Box::new(parser.parse_node(next)?);
+    parser.expected(Token::RightCurly)?;
+    parser.expected(Token::Semicolon)?;
+
+    Ok(Node::RANGE(Range { from, till, step }))
+}
+
 pub fn parse_load_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
     // Check if the next argument is the proper one
 
@@ -202,13 +294,18 @@ pub fn parse_load_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
                 }
             }
         }
-        _ => return Err(ParseError::Unknown),
+        token => {
+            return Err(parser.error(ParseErrorKind::UnexpectedToken(
+                Token::LoadType(LoadType::VARIABLE),
+                token,
+            )))
+        }
     }
 
     let load_type = node.load_type.clone();
     let next_token = parser.next_token();
 
-    parse_load_type(&mut node, next_token, load_type)?;
+    parse_load_type(&mut node, next_token, load_type).map_err(|kind| parser.error(kind))?;
 
     parser.expected(Token::Semicolon)?;
 
@@ -219,7 +316,7 @@ fn parse_load_type(
     node: &mut Load,
     current_token: Token,
     load_type: LoadType,
-) -> Result<(), ParseError> {
+) -> Result<(), ParseErrorKind> {
     match current_token {
         Token::Number(int) => match load_type {
             LoadType::VARIABLE => {
@@ -230,7 +327,7 @@ fn parse_load_type(
             }
         },
         // Expected an argument, nothing else is possible as index
-        _ => return Err(ParseError::Unknown),
+        token => return Err(ParseErrorKind::UnexpectedToken(Token::Number(0), token)),
     }
 
     Ok(())
@@ -243,7 +340,7 @@ fn parse_load_type(
 /// use vinci::parse;
 /// use vinci::types::ValueType;
 /// let mut input = ".STORE 0 { .CONSTANT INT 10; };";
-/// let result = parse(input);
+/// let result = parse(input).unwrap();
 ///
 /// assert_eq!(result, AST {nodes: vec![
 ///     Node::STORE(Store {
@@ -258,10 +355,9 @@ pub fn parse_store_instruction(parser: &mut Parser) -> Result<Node, ParseError>
         value: Box::new(Node::CONSTANT(ValueType::Integer(0))),
     };
 
-    if let Token::Number(value) = parser.next_token() {
-        node.index = value as u64;
-    } else {
-        return Err(ParseError::Unknown);
+    match parser.next_token() {
+        Token::Number(value) => node.index = value as u64,
+        token => return Err(parser.error(ParseErrorKind::UnexpectedToken(Token::Number(0), token))),
     }
 
     parser.expected(Token::LeftCurly)?;
@@ -316,26 +412,72 @@ pub fn parse_copy_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
 }
 
 pub fn parse_compound_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
-    let name = {
-        if let Token::String(n) = parser.next_token() {
-            let s: String = n.into_iter().collect();
-            s
-        } else {
-            return Err(ParseError::Unknown);
+    let name = match parser.next_token() {
+        Token::String(n) => n.into_iter().collect::<String>(),
+        token => {
+            return Err(parser.error(ParseErrorKind::UnexpectedToken(
+                Token::String(vec![]),
+                token,
+            )))
         }
     };
+    // Reported on the name when it is already in use
+    let name_span = parser.span();
 
     parser.expected(Token::LeftCurly)?;
 
     let types = parse_type_arguments(parser)?;
     parser.expected(Token::RightCurly)?;
 
+    // Optionally followed by the name of the class and the names of its fields, eg:
+    // FIELDS "Person" { "age"; "name"; }
+    let fields = if parser.expected_maybe(Token::Fields) {
+        Some(parse_compound_fields(parser)?)
+    } else {
+        None
+    };
+
     parser.expected(Token::Semicolon)?;
 
-    parser.add_custom_type(name.clone(), types.clone())?;
+    parser
+        .add_custom_type(name.clone(), types.clone())
+        .map_err(|error| ParseError::new(error.kind, name_span))?;
 
     Ok(Node::COMPOUND(CompoundType {
         name,
         values: Box::new(types),
+        fields,
     }))
 }
+
+fn parse_compound_fields(parser: &mut Parser) -> Result<(String, Vec<String>), ParseError> {
+    let class = match parser.next_token() {
+        Token::String(class) => class.into_iter().collect(),
+        token => {
+            return Err(parser.error(ParseErrorKind::UnexpectedToken(
+                Token::String(vec![]),
+                token,
+            )))
+        }
+    };
+
+    parser.expected(Token::LeftCurly)?;
+
+    let mut fields = Vec::new();
+    loop {
+        match parser.next_token() {
+            Token::String(field) => fields.push(field.into_iter().collect()),
+            Token::RightCurly => break,
+            token => {
+                return Err(parser.error(ParseErrorKind::UnexpectedToken(
+                    Token::String(vec![]),
+                    token,
+                )))
+            }
+        }
+
+        parser.expected(Token::Semicolon)?;
+    }
+
+    Ok((class, fields))
+}
@@ -8,18 +8,25 @@ use crate::parser::instruction::conditional::{
 use crate::parser::instruction::function::{
     parse_call_instruction, parse_function_instruction, parse_return_instruction,
 };
+use crate::parser::instruction::location::parse_location_instruction;
 use crate::parser::instruction::memory::{
     parse_assign_instruction, parse_compound_instruction, parse_constant_instruction,
-    parse_copy_instruction, parse_index_instruction, parse_length_instruction,
-    parse_load_instruction, parse_loadlib_instruction, parse_pop_instruction,
-    parse_push_instruction, parse_slice_instruction, parse_store_instruction,
+    parse_copy_instruction, parse_hashmap_instruction, parse_index_instruction,
+    parse_keys_instruction, parse_length_instruction, parse_load_instruction,
+    parse_loadlib_instruction, parse_pop_instruction, parse_push_instruction,
+    parse_range_instruction, parse_slice_instruction, parse_store_instruction,
+};
+use crate::parser::instruction::suffix::{
+    parse_math_instruction, parse_negate_instruction, parse_not_instruction,
+};
+use crate::parser::instruction::while_loop::{
+    parse_break_instruction, parse_continue_instruction, parse_while_instruction,
 };
-use crate::parser::instruction::suffix::parse_math_instruction;
-use crate::parser::instruction::while_loop::parse_while_instruction;
 use crate::parser::Parser;
 
 mod conditional;
 mod function;
+mod location;
 mod memory;
 mod suffix;
 mod while_loop;
@@ -35,6 +42,13 @@ pub fn parse_instruction(parser: &mut Parser, ins: Instruction) -> Result<Node,
         Instruction::DIVIDE => parse_math_instruction(parser, BinaryOperation::DIVIDE),
         Instruction::POWER => parse_math_instruction(parser, BinaryOperation::POWER),
         Instruction::GREATERTHAN => parse_math_instruction(parser, BinaryOperation::GREATERTHAN),
+        Instruction::LESSTHAN => parse_math_instruction(parser, BinaryOperation::LESSTHAN),
+        Instruction::GREATERTHANOREQUAL => {
+            parse_math_instruction(parser, BinaryOperation::GREATERTHANOREQUAL)
+        }
+        Instruction::LESSTHANOREQUAL => {
+            parse_math_instruction(parser, BinaryOperation::LESSTHANOREQUAL)
+        }
         Instruction::EQUALS => parse_math_instruction(parser, BinaryOperation::EQUALS),
         Instruction::NOTEQUALS => parse_math_instruction(parser, BinaryOperation::NOTEQUALS),
         Instruction::IF => parse_conditional_instruction(parser),
@@ -54,5 +68,13 @@ pub fn parse_instruction(parser: &mut Parser, ins: Instruction) -> Result<Node,
         Instruction::OR => parse_or_instruction(parser),
         Instruction::MODULO => parse_math_instruction(parser, BinaryOperation::MODULO),
         Instruction::COMPOUND => parse_compound_instruction(parser),
+        Instruction::HASHMAP => parse_hashmap_instruction(parser),
+        Instruction::KEYS => parse_keys_instruction(parser),
+        Instruction::RANGE => parse_range_instruction(parser),
+        Instruction::BREAK => parse_break_instruction(parser),
+        Instruction::CONTINUE => parse_continue_instruction(parser),
+        Instruction::NOT => parse_not_instruction(parser),
+        Instruction::NEGATE => parse_negate_instruction(parser),
+        Instruction::LOCATION => parse_location_instruction(parser),
     }
 }
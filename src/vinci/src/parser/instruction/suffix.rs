@@ -12,7 +12,7 @@ use crate::parser::Parser;
 /// use vinci::parse;
 /// use vinci::types::ValueType;
 /// let mut input = ".ADD { .CONSTANT INT 10; .CONSTANT INT 20; };";
-/// let result = parse(input);
+/// let result = parse(input).unwrap();
 ///
 /// assert_eq!(result, AST {nodes: vec![
 ///     Node::SUFFIX(
@@ -47,3 +47,36 @@ pub fn parse_math_instruction(
         right,
     })))
 }
+
+/// Input is .NOT { right; }
+/// ```
+/// use vinci::ast::AST;
+/// use vinci::ast::instructions::Node;
+/// use vinci::parse;
+/// use vinci::types::ValueType;
+/// let mut input = ".NOT { .CONSTANT BOOL true; };";
+/// let result = parse(input).unwrap();
+///
+/// assert_eq!(result, AST {nodes: vec![
+///     Node::NOT(Box::new(Node::CONSTANT(ValueType::Boolean(true))))
+/// ]});
+/// ```
+pub fn parse_not_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    Ok(Node::NOT(Box::new(parse_prefix_operand(parser)?)))
+}
+
+pub fn parse_negate_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    Ok(Node::NEGATE(Box::new(parse_prefix_operand(parser)?)))
+}
+
+fn parse_prefix_operand(parser: &mut Parser) -> Result<Node, ParseError> {
+    parser.expected(Token::LeftCurly)?;
+
+    let next = parser.next_token();
+    let right = parser.parse_node(&next)?;
+
+    parser.expected(Token::RightCurly)?;
+    parser.expected(Token::Semicolon)?;
+
+    Ok(right)
+}
@@ -12,7 +12,7 @@ use crate::parser::Parser;
 /// use vinci::parse;
 /// use vinci::types::ValueType;
 /// let mut input = ".WHILE CONDITION { .CONSTANT BOOL true; } THEN { .CONSTANT INT 10; };";
-/// let result = parse(input);
+/// let result = parse(input).unwrap();
 ///
 /// assert_eq!(result, AST {nodes: vec![
 ///     Node::WHILE(
@@ -40,3 +40,33 @@ pub fn parse_while_instruction(parser: &mut Parser) -> Result<Node, ParseError>
 
     Ok(Node::WHILE(Box::new(While { condition, body })))
 }
+
+/// ```
+/// use vinci::ast::AST;
+/// use vinci::ast::instructions::Node;
+/// use vinci::ast::instructions::while_loop::While;
+/// use vinci::parse;
+/// use vinci::types::ValueType;
+/// let mut input = ".WHILE CONDITION { .CONSTANT BOOL true; } THEN { .BREAK; };";
+/// let result = parse(input).unwrap();
+///
+/// assert_eq!(result, AST {nodes: vec![
+///     Node::WHILE(
+///         Box::new(While {
+///             condition: Node::CONSTANT(ValueType::Boolean(true)),
+///             body: vec![Node::BREAK],
+///         })
+///     )]
+/// });
+/// ```
+pub fn parse_break_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    parser.expected(Token::Semicolon)?;
+
+    Ok(Node::BREAK)
+}
+
+pub fn parse_continue_instruction(parser: &mut Parser) -> Result<Node, ParseError> {
+    parser.expected(Token::Semicolon)?;
+
+    Ok(Node::CONTINUE)
+}
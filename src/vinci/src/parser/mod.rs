@@ -1,59 +1,73 @@
 use crate::ast::instructions::Node;
 use crate::ast::AST;
-use crate::lexer::token::Token;
-use crate::parser::error::ParseError;
+use crate::lexer::token::{Instruction, Token};
+use crate::parser::error::{ParseError, ParseErrorKind};
 use crate::types::Type;
-use std::borrow::{Borrow, BorrowMut};
 use std::collections::HashMap;
+use std::ops::Range;
 
 pub mod error;
 mod instruction;
 mod tests;
 
+/// Types defined using .COMPOUND that should stay known to a following parse, used by the REPL
+#[derive(Default, Clone)]
+pub struct ParserState {
+    pub custom_types: HashMap<String, Vec<Type>>,
+}
+
 pub struct Parser<'a> {
     lexer: logos::Lexer<'a, Token>,
+    current: Token,
+    span: Range<usize>,
     custom_types: HashMap<String, Vec<Type>>,
 }
 
 impl Parser<'_> {
-    pub fn parse(&mut self) -> AST {
+    /// Parses until the end. After an error it continues with the next instruction, so every
+    /// error is returned instead of only the first one.
+    pub fn parse(&mut self) -> Result<AST, Vec<ParseError>> {
         let mut ast = AST::new();
+        let mut errors = Vec::new();
 
-        // Parse until at end
-        while let Some(current) = self.lexer.borrow_mut().next() {
-            let node = self.parse_node(&current);
-
-            if let Ok(node) = node {
-                ast.add_node(node);
-            } else {
-                // TODO: Clean this up a little
-                let lexer = self.lexer.borrow();
-                let source: &str = lexer.source();
-                let span = lexer.span();
-                println!("{}", source);
-                let mut amount = String::from("");
-                let mut spaces = String::from("");
-
-                for _ in 0..span.start {
-                    spaces.push(' ');
-                }
+        loop {
+            let current = self.next_token();
+            if current == Token::End {
+                break;
+            }
 
-                for _ in 0..(span.end - span.start) {
-                    amount.push('^');
-                }
+            let start = self.lexer.clone();
+            match self.parse_node(&current) {
+                Ok(node) => ast.add_node(node),
+                Err(error) => {
+                    errors.push(error);
 
-                let source_str = String::from(source);
+                    self.lexer = start;
+                    self.skip_instruction();
+                }
+            }
+        }
 
-                let t = &source_str[span.start..span.end];
+        if errors.is_empty() {
+            Ok(ast)
+        } else {
+            Err(errors)
+        }
+    }
 
-                println!("{}{}", spaces, amount);
-                println!("Error: {}\n", node.expect_err("?"));
-                println!("Near: {}\n", t);
-                break;
+    /// Skips to the semicolon that ends the instruction, ignoring those of nested instructions
+    fn skip_instruction(&mut self) {
+        let mut depth = 0;
+
+        loop {
+            match self.next_token() {
+                Token::LeftCurly | Token::LeftBracket => depth += 1,
+                Token::RightCurly | Token::RightBracket => depth -= 1,
+                Token::Semicolon if depth <= 0 => return,
+                Token::End => return,
+                _ => (),
             }
         }
-
-        ast
     }
 
     /// Will parse nodes until a RightCurly token is found
@@ -79,6 +93,24 @@ impl Parser<'_> {
             return Ok(tp);
         }
 
+        // HASHMAP[KEY,VALUE], which can be an array as well: HASHMAP[KEY,VALUE][]
+        if tp == Token::Hashmap {
+            self.expected(Token::LeftBracket)?;
+            let key = self.parse_type()?;
+            self.expected(Token::Comma)?;
+            let value = self.parse_type()?;
+            self.expected(Token::RightBracket)?;
+
+            let mut hashmap = Type::HASHMAP(Box::new(key), Box::new(value));
+
+            while self.expected_maybe(Token::LeftBracket) {
+                self.expected(Token::RightBracket)?;
+                hashmap = Type::ARRAY(Box::new(hashmap));
+            }
+
+            return Ok(hashmap);
+        }
+
         if let Token::Identifier(custom_type) = tp {
             if let Some(compound_types) = self.custom_types.get(&custom_type) {
                 return Ok(Type::Compound(
@@ -88,17 +120,24 @@ impl Parser<'_> {
             }
         }
 
-        Err(ParseError::UnknownType(
-            self.lexer.borrow().slice().to_string(),
-        ))
+        Err(self.error(ParseErrorKind::UnknownType(self.lexer.slice().to_string())))
     }
 
+    /// Goes to the next token, which is `Token::End` after the last one
     pub fn next_token(&mut self) -> Token {
-        if let Some(token) = self.lexer.borrow_mut().next() {
-            token
-        } else {
-            Token::Error
-        }
+        self.current = match self.lexer.next() {
+            Some(token) => {
+                self.span = self.lexer.span();
+                token
+            }
+            None => {
+                let end = self.lexer.source().len();
+                self.span = end..end;
+                Token::End
+            }
+        };
+
+        self.current.clone()
     }
 
     pub fn parse_node(&mut self, start: &Token) -> Result<Node, ParseError> {
@@ -107,13 +146,16 @@ impl Parser<'_> {
                 // Skip over the instruction, then parse it
                 instruction::parse_instruction(self, ins.clone())
             }
-            _ => Err(ParseError::Unknown),
+            _ => Err(self.error(ParseErrorKind::UnexpectedToken(
+                Token::Instruction(Instruction::CONSTANT),
+                start.clone(),
+            ))),
         }
     }
 
     pub fn add_custom_type(&mut self, name: String, tp: Vec<Type>) -> Result<(), ParseError> {
         if self.custom_types.contains_key(&name) {
-            return Err(ParseError::TypeAlreadyExists(name));
+            return Err(self.error(ParseErrorKind::TypeAlreadyExists(name)));
         }
 
         self.custom_types.insert(name, tp);
@@ -121,16 +163,31 @@ impl Parser<'_> {
         Ok(())
     }
 
-    /// Get the current token, wrapper for lexer
+    /// The token that was returned last by `next_token`
     pub fn current_token(&self) -> Token {
-        Token::End
+        self.current.clone()
+    }
+
+    /// The bytes of the current token in the Arc
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// An error on the current token
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, self.span())
+    }
+
+    /// The token after the current one, without going to it
+    pub fn peek_token(&self) -> Token {
+        self.lexer.clone().next().unwrap_or(Token::End)
     }
 
     /// Parser goes to next token, expects it to be the same as the parameter. If not it returns an exception
     pub fn expected(&mut self, token: Token) -> Result<Token, ParseError> {
         let next = self.next_token();
         if next != token {
-            Err(ParseError::UnexpectedToken(token, self.current_token()))
+            Err(self.error(ParseErrorKind::UnexpectedToken(token, next)))
         } else {
             Ok(next)
         }
@@ -138,10 +195,8 @@ impl Parser<'_> {
 
     /// Same as [expected] but never throws an error
     pub fn expected_maybe(&mut self, token: Token) -> bool {
-        let last = self.lexer.borrow().clone().next();
-
-        if last.unwrap() == token {
-            self.lexer.next();
+        if self.peek_token() == token {
+            self.next_token();
             return true;
         }
 
@@ -149,9 +204,21 @@ impl Parser<'_> {
     }
 
     pub fn new(lexer: logos::Lexer<Token>) -> Parser {
+        Parser::new_with_state(lexer, ParserState::default())
+    }
+
+    pub fn new_with_state(lexer: logos::Lexer<Token>, state: ParserState) -> Parser {
         Parser {
             lexer,
-            custom_types: HashMap::new(),
+            current: Token::End,
+            span: 0..0,
+            custom_types: state.custom_types,
+        }
+    }
+
+    pub fn get_parser_state(&self) -> ParserState {
+        ParserState {
+            custom_types: self.custom_types.clone(),
         }
     }
 }
@@ -1,14 +1,17 @@
 #[cfg(test)]
 mod tests {
+    use crate::ast::instructions::conditional::Conditional;
     use crate::ast::instructions::function::{Call, Function};
+    use crate::ast::instructions::location::Location;
     use crate::ast::instructions::memory::{
-        CompoundType, Index, Load, LoadType, Push, Slice, Store,
+        CompoundType, Hashmap, Index, Load, LoadType, Push, Range, Slice, Store,
     };
     use crate::ast::instructions::suffix::{BinaryOperation, Suffix};
     use crate::ast::instructions::while_loop::While;
     use crate::ast::instructions::Node;
     use crate::ast::AST;
-    use crate::lexer::token::Token;
+    use crate::lexer::token::{Instruction, Token};
+    use crate::parser::error::{ParseError, ParseErrorKind};
     use crate::parser::Parser;
     use crate::types::{Type, ValueType};
     use logos::Logos;
@@ -19,7 +22,7 @@ mod tests {
         let lexer = Token::lexer(".CONSTANT INT 50;.CONSTANT BOOL false;");
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![
             Node::CONSTANT(ValueType::Integer(50)),
@@ -37,7 +40,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![
             Node::CONSTANT(ValueType::Integer(50)),
@@ -57,7 +60,7 @@ mod tests {
         let lexer = Token::lexer(".COMPOUND \"TEST\" { INT; INT; CHAR[]; };");
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::COMPOUND(CompoundType {
             name: "TEST".to_string(),
@@ -66,6 +69,7 @@ mod tests {
                 Type::INT,
                 Type::ARRAY(Box::new(Type::CHAR)),
             ]),
+            fields: None,
         })]);
 
         assert!(parser.custom_types.get("TEST").is_some());
@@ -78,6 +82,28 @@ mod tests {
         assert_eq!(expected, result)
     }
 
+    #[test]
+    fn test_parser_compound_fields() {
+        let lexer = Token::lexer(
+            ".COMPOUND \"class_0\" { INT; CHAR[]; } FIELDS \"Person\" { \"age\"; \"name\"; };",
+        );
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![Node::COMPOUND(CompoundType {
+            name: "class_0".to_string(),
+            values: Box::new(vec![Type::INT, Type::ARRAY(Box::new(Type::CHAR))]),
+            fields: Some((
+                "Person".to_string(),
+                vec!["age".to_string(), "name".to_string()],
+            )),
+        })]);
+
+        assert!(parser.custom_types.contains_key("class_0"));
+        assert_eq!(expected, result)
+    }
+
     #[test]
     fn test_parser_compound_functions() {
         let lexer = Token::lexer(
@@ -86,7 +112,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![
             Node::COMPOUND(CompoundType {
@@ -96,6 +122,7 @@ mod tests {
                     Type::INT,
                     Type::ARRAY(Box::new(Type::CHAR)),
                 ]),
+                fields: None,
             }),
             Node::FUNCTION(Box::new(Function {
                 name: "".to_string(),
@@ -117,6 +144,35 @@ mod tests {
         assert_eq!(expected, result)
     }
 
+    #[test]
+    fn test_parser_compound_with_state() {
+        let lexer = Token::lexer(".COMPOUND \"TEST\" { INT; CHAR[]; };");
+        let mut parser = Parser::new(lexer);
+        parser.parse().unwrap();
+
+        let state = parser.get_parser_state();
+
+        // The type was defined during an earlier parse, but should still be known
+        let lexer = Token::lexer(".CONSTANT TEST { .CONSTANT INT 10; .CONSTANT CHAR[] \"Hi\"; };");
+        let mut parser = Parser::new_with_state(lexer, state);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![Node::CONSTANT(ValueType::Compound(
+            "TEST".to_string(),
+            Box::new(vec![
+                ValueType::Integer(10),
+                ValueType::Array(Box::new(vec![
+                    ValueType::Character('H'),
+                    ValueType::Character('i'),
+                ])),
+            ]),
+        ))]);
+
+        assert_eq!(expected, result);
+        assert!(parser.custom_types.contains_key("TEST"));
+    }
+
     #[test]
     fn test_parser_compound_use() {
         let lexer = Token::lexer(
@@ -127,7 +183,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![
             Node::COMPOUND(CompoundType {
@@ -137,6 +193,7 @@ mod tests {
                     Type::INT,
                     Type::ARRAY(Box::new(Type::CHAR)),
                 ]),
+                fields: None,
             }),
             Node::COMPOUND(CompoundType {
                 name: "TEST_SECOND".to_string(),
@@ -152,6 +209,7 @@ mod tests {
                     ),
                     Type::INT,
                 ]),
+                fields: None,
             }),
             Node::CONSTANT(ValueType::Compound(
                 "TEST".to_string(),
@@ -193,7 +251,7 @@ mod tests {
         let lexer = Token::lexer(".ADD { .CONSTANT INT 10; .CONSTANT INT 20; };");
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::SUFFIX(Box::new(Suffix {
             operation: BinaryOperation::ADD,
@@ -204,6 +262,57 @@ mod tests {
         assert_eq!(expected, result)
     }
 
+    #[test]
+    fn test_parser_comparisons() {
+        // 1 < 2; 1 >= 2; 1 <= 2
+        let lexer = Token::lexer(
+            ".LESSTHAN { .CONSTANT INT 1; .CONSTANT INT 2; }; \
+            .GREATERTHANOREQUAL { .CONSTANT INT 1; .CONSTANT INT 2; }; \
+            .LESSTHANOREQUAL { .CONSTANT INT 1; .CONSTANT INT 2; };",
+        );
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let comparison = |operation: BinaryOperation| {
+            Node::SUFFIX(Box::new(Suffix {
+                operation,
+                left: Node::CONSTANT(ValueType::Integer(1)),
+                right: Node::CONSTANT(ValueType::Integer(2)),
+            }))
+        };
+
+        let expected = AST::new_mock(vec![
+            comparison(BinaryOperation::LESSTHAN),
+            comparison(BinaryOperation::GREATERTHANOREQUAL),
+            comparison(BinaryOperation::LESSTHANOREQUAL),
+        ]);
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_parser_prefix() {
+        // !(!true); -x
+        let lexer =
+            Token::lexer(".NOT { .NOT { .CONSTANT BOOL true; }; }; .NEGATE { .LOAD VARIABLE 0; };");
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![
+            Node::NOT(Box::new(Node::NOT(Box::new(Node::CONSTANT(
+                ValueType::Boolean(true),
+            ))))),
+            Node::NEGATE(Box::new(Node::LOAD(Load {
+                load_type: LoadType::VARIABLE,
+                index: 0,
+            }))),
+        ]);
+
+        assert_eq!(expected, result)
+    }
+
     #[test]
     fn test_parser_math_advanced_1() {
         let lexer = Token::lexer(
@@ -211,7 +320,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::SUFFIX(Box::new(Suffix {
             operation: BinaryOperation::ADD,
@@ -247,7 +356,7 @@ mod tests {
 
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::SUFFIX(Box::new(Suffix {
             operation: BinaryOperation::ADD,
@@ -279,7 +388,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::STORE(Store {
             index: 0,
@@ -295,7 +404,7 @@ mod tests {
 
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::CALL(Box::new(Call {
             call: Node::FUNCTION(Box::new(Function {
@@ -330,7 +439,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::INDEX(Index {
             to_index: Box::new(Node::CONSTANT(ValueType::Array(Box::new(vec![
@@ -338,11 +447,75 @@ mod tests {
                 ValueType::Integer(20),
             ])))),
             index: Box::new(Node::CONSTANT(ValueType::Integer(0))),
+            hashmap: false,
+        })]);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_parser_index_hashmap() {
+        // {"a": 10}["a"]
+        let lexer = Token::lexer(
+            ".INDEX HASHMAP {.HASHMAP CHAR[] INT {.CONSTANT CHAR[] \"a\";.CONSTANT INT 10;};} {.CONSTANT CHAR[] \"a\";};",
+        );
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![Node::INDEX(Index {
+            to_index: Box::new(Node::HASHMAP(Box::new(Hashmap {
+                key_type: Type::ARRAY(Box::new(Type::CHAR)),
+                value_type: Type::INT,
+                values: vec![(
+                    Node::CONSTANT(ValueType::Array(Box::new(vec![ValueType::Character('a')]))),
+                    Node::CONSTANT(ValueType::Integer(10)),
+                )],
+            }))),
+            index: Box::new(Node::CONSTANT(ValueType::Array(Box::new(vec![
+                ValueType::Character('a'),
+            ])))),
+            hashmap: true,
         })]);
 
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn test_parser_hashmap_nested() {
+        let lexer = Token::lexer(
+            ".HASHMAP BOOL HASHMAP[INT,INT[]] {.CONSTANT BOOL true;.HASHMAP INT INT[] {};}; \
+            .KEYS {.LOAD VARIABLE 0;};",
+        );
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![
+            Node::HASHMAP(Box::new(Hashmap {
+                key_type: Type::BOOL,
+                value_type: Type::HASHMAP(
+                    Box::new(Type::INT),
+                    Box::new(Type::ARRAY(Box::new(Type::INT))),
+                ),
+                values: vec![(
+                    Node::CONSTANT(ValueType::Boolean(true)),
+                    Node::HASHMAP(Box::new(Hashmap {
+                        key_type: Type::INT,
+                        value_type: Type::ARRAY(Box::new(Type::INT)),
+                        values: vec![],
+                    })),
+                )],
+            })),
+            Node::KEYS(Box::new(Node::LOAD(Load {
+                load_type: LoadType::VARIABLE,
+                index: 0,
+            }))),
+        ]);
+
+        assert_eq!(expected, result);
+    }
+
     #[test]
     fn test_parser_push() {
         // [10, 20][0]
@@ -351,7 +524,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::PUSH(Push {
             to_push: Box::new(Node::CONSTANT(ValueType::Array(Box::new(vec![
@@ -372,7 +545,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::SLICE(Slice {
             to_slice: Box::new(Node::CONSTANT(ValueType::Array(Box::new(vec![
@@ -392,7 +565,7 @@ mod tests {
         let lexer = Token::lexer(".CONSTANT INT[] [.CONSTANT INT 10;.CONSTANT INT 20;];");
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::CONSTANT(ValueType::Array(Box::new(vec![
             ValueType::Integer(10),
@@ -408,7 +581,7 @@ mod tests {
         let lexer = Token::lexer(".CONSTANT INT[][] [.CONSTANT INT[] [.CONSTANT INT 10;.CONSTANT INT 20;];.CONSTANT INT[] [.CONSTANT INT 30;.CONSTANT INT 40;];];");
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![Node::CONSTANT(ValueType::Array(Box::new(vec![
             ValueType::Array(Box::new(vec![
@@ -434,7 +607,7 @@ mod tests {
         );
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![
             Node::STORE(Store {
@@ -463,7 +636,7 @@ mod tests {
 
         let mut parser = Parser::new(lexer);
 
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
 
         let expected = AST::new_mock(vec![
             Node::STORE(Store {
@@ -499,4 +672,181 @@ mod tests {
 
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn test_while_break_continue() {
+        let lexer = Token::lexer(".WHILE CONDITION { .CONSTANT BOOL true; } THEN { .IF CONDITION { .CONSTANT BOOL false; } THEN { .CONTINUE; } ELSE { }; .BREAK; };");
+
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![Node::WHILE(Box::new(While {
+            condition: Node::CONSTANT(ValueType::Boolean(true)),
+            body: vec![
+                Node::CONDITIONAL(Box::new(Conditional {
+                    condition: Node::CONSTANT(ValueType::Boolean(false)),
+                    body: vec![Node::CONTINUE],
+                    alternative: vec![],
+                })),
+                Node::BREAK,
+            ],
+        }))]);
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_parser_range() {
+        let lexer = Token::lexer(
+            ".RANGE { .CONSTANT INT 10; } { .LOAD VARIABLE 0; } { .CONSTANT INT -2; };",
+        );
+
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![Node::RANGE(Range {
+            from: Box::new(Node::CONSTANT(ValueType::Integer(10))),
+            till: Box::new(Node::LOAD(Load {
+                load_type: LoadType::VARIABLE,
+                index: 0,
+            })),
+            step: Box::new(Node::CONSTANT(ValueType::Integer(-2))),
+        })]);
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_parser_location() {
+        let lexer =
+            Token::lexer(".LOCATION \"examples/main.loop\" 12; .STORE 0 { .CONSTANT INT 1; };");
+
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse().unwrap();
+
+        let expected = AST::new_mock(vec![
+            Node::LOCATION(Location {
+                file: "examples/main.loop".to_string(),
+                line: 12,
+            }),
+            Node::STORE(Store {
+                index: 0,
+                value: Box::new(Node::CONSTANT(ValueType::Integer(1))),
+            }),
+        ]);
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_parser_error() {
+        let lexer = Token::lexer(".CONSTANT INT 1; .CONSTANT UNKNOWN 2;");
+
+        let mut parser = Parser::new(lexer);
+
+        let result = parser.parse();
+
+        assert_eq!(
+            result,
+            Err(vec![ParseError::new(
+                ParseErrorKind::UnknownType("UNKNOWN".to_string()),
+                27..34
+            )])
+        )
+    }
+
+    #[test]
+    fn test_parser_error_tokens() {
+        let lexer = Token::lexer(".LOAD 0; .STORE 1 { .CONSTANT INT 1; } .CONSTANT INT 2");
+
+        let mut parser = Parser::new(lexer);
+
+        assert_eq!(
+            parser.parse(),
+            Err(vec![
+                ParseError::new(
+                    ParseErrorKind::UnexpectedToken(
+                        Token::LoadType(LoadType::VARIABLE),
+                        Token::Number(0)
+                    ),
+                    6..7
+                ),
+                // The semicolon of the constant inside the store does not end the store
+                ParseError::new(
+                    ParseErrorKind::UnexpectedToken(
+                        Token::Semicolon,
+                        Token::Instruction(Instruction::CONSTANT)
+                    ),
+                    39..48
+                ),
+            ])
+        )
+    }
+
+    #[test]
+    fn test_parser_error_recovery() {
+        let lexer = Token::lexer(
+            ".CONSTANT INT ;\n.IF CONDITION { .CONSTANT BOOL true; } THEN { ; };\n.CONSTANT INT 1",
+        );
+
+        let mut parser = Parser::new(lexer);
+
+        assert_eq!(
+            parser.parse(),
+            Err(vec![
+                ParseError::new(ParseErrorKind::InvalidValue(Token::Semicolon), 14..15),
+                ParseError::new(
+                    ParseErrorKind::UnexpectedToken(
+                        Token::Instruction(Instruction::CONSTANT),
+                        Token::Semicolon
+                    ),
+                    62..63
+                ),
+                ParseError::new(
+                    ParseErrorKind::UnexpectedToken(Token::Semicolon, Token::End),
+                    82..82
+                ),
+            ])
+        )
+    }
+
+    #[test]
+    fn test_parser_error_diagnostic() {
+        let arc = ".CONSTANT INT 1;\n.COMPOUND \"a\" { INT; };\n.COMPOUND \"a\" { };";
+
+        let errors = crate::parse(arc).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ParseError::new(
+                ParseErrorKind::TypeAlreadyExists("a".to_string()),
+                51..54
+            )]
+        );
+
+        let diagnostic = errors[0].diagnostic(arc);
+        assert_eq!(diagnostic.spans[0].line_start, 3);
+        assert_eq!(diagnostic.spans[0].column_start, 11);
+        assert_eq!(diagnostic.spans[0].column_end, 13);
+        assert_eq!(
+            diagnostic.spans[0].text.as_deref(),
+            Some(".COMPOUND \"a\" { };")
+        );
+        assert_eq!(diagnostic.notes, vec!["near: \"a\"".to_string()]);
+    }
+
+    #[test]
+    fn test_parser_type_of_value() {
+        let tp = crate::parse_type("HASHMAP[CHAR[],FLOAT[]]").unwrap();
+        let value = ValueType::Hashmap(Box::new(vec![(
+            ValueType::Character('a'),
+            ValueType::Array(Box::new(vec![ValueType::Integer(1), ValueType::Float(2.5)])),
+        )]));
+
+        assert_eq!(value.with_type(&tp).format(), "{'a': [1.0, 2.5]}");
+        assert_eq!(ValueType::Integer(2).with_type(&Type::INT).format(), "2");
+        assert!(crate::parse_type("HASHMAP[INT").is_err());
+    }
 }
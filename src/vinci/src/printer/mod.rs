@@ -0,0 +1,575 @@
+//! Writes an AST back as Arc, which parses into the same AST again. Either as a single line, or
+//! with every statement on its own line and blocks indented, eg:
+//! ```txt
+//! .FUNCTION "var_0" 0 INT ARGUMENTS { INT; } FREE { } THEN {
+//!     .RETURN { .ADD { .LOAD PARAMETER 0 0; .CONSTANT INT 1; }; };
+//! };
+//! ```
+use crate::ast::instructions::memory::{CompoundType, LoadType};
+use crate::ast::instructions::suffix::BinaryOperation;
+use crate::ast::instructions::Node;
+use crate::ast::AST;
+use crate::types::{Type, ValueType};
+use std::fmt::{Display, Formatter};
+
+mod tests;
+
+/// Parts of an AST that Arc has no way of writing down
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrintError {
+    /// Values that are only created by a backend, eg. hashmaps, instances of classes and
+    /// functions outside of a class
+    Value(ValueType),
+    /// Types without a name in Arc, eg. functions or arrays of classes
+    Type(Type),
+    /// Strings in Arc can not contain quotes or backslashes
+    String(String),
+}
+
+impl Display for PrintError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrintError::Value(value) => {
+                write!(
+                    f,
+                    "the value \"{}\" can not be written as Arc",
+                    value.format()
+                )
+            }
+            PrintError::Type(tp) => write!(f, "the type \"{:?}\" can not be written as Arc", tp),
+            PrintError::String(string) => {
+                write!(f, "the string {:?} can not be written as Arc", string)
+            }
+        }
+    }
+}
+
+/// Writes the AST as Arc on a single line
+pub fn print(ast: &AST) -> Result<String, PrintError> {
+    Printer::new(None).print(ast)
+}
+
+/// Writes the AST as Arc with every statement on its own line, the statements inside of blocks are
+/// indented by the given amount of spaces
+pub fn print_indented(ast: &AST, indent: usize) -> Result<String, PrintError> {
+    Printer::new(Some(indent)).print(ast)
+}
+
+struct Printer {
+    arc: String,
+    indent: Option<usize>,
+    depth: usize,
+}
+
+impl Printer {
+    fn new(indent: Option<usize>) -> Printer {
+        Printer {
+            arc: String::new(),
+            indent,
+            depth: 0,
+        }
+    }
+
+    fn print(mut self, ast: &AST) -> Result<String, PrintError> {
+        for (i, node) in ast.nodes.iter().enumerate() {
+            if i > 0 {
+                self.separator();
+            }
+
+            self.node(node)?;
+        }
+
+        if self.indent.is_some() && !ast.nodes.is_empty() {
+            self.arc.push('\n');
+        }
+
+        Ok(self.arc)
+    }
+
+    fn push(&mut self, arc: &str) {
+        self.arc.push_str(arc);
+    }
+
+    /// Goes to the next statement, which is a new line when indenting
+    fn separator(&mut self) {
+        match self.indent {
+            Some(indent) => {
+                self.arc.push('\n');
+                self.arc.push_str(&" ".repeat(indent * self.depth));
+            }
+            None => self.arc.push(' '),
+        }
+    }
+
+    /// A list of statements, eg. the body of a function
+    fn block(&mut self, nodes: &[Node]) -> Result<(), PrintError> {
+        if nodes.is_empty() {
+            self.push("{ }");
+            return Ok(());
+        }
+
+        self.push("{");
+        self.depth += 1;
+
+        for node in nodes {
+            self.separator();
+            self.node(node)?;
+        }
+
+        self.depth -= 1;
+        self.separator();
+        self.push("}");
+
+        Ok(())
+    }
+
+    /// A single node used as a value, eg. `{ .CONSTANT INT 1; }`
+    fn operand(&mut self, node: &Node) -> Result<(), PrintError> {
+        self.push("{ ");
+        self.node(node)?;
+        self.push(" }");
+
+        Ok(())
+    }
+
+    /// Nodes used as values which are not separated by braces, eg. `{ .CONSTANT INT 1; .CONSTANT
+    /// INT 2; }`
+    fn operands(&mut self, nodes: &[&Node]) -> Result<(), PrintError> {
+        self.push("{");
+
+        for node in nodes {
+            self.push(" ");
+            self.node(node)?;
+        }
+
+        self.push(" }");
+
+        Ok(())
+    }
+
+    fn node(&mut self, node: &Node) -> Result<(), PrintError> {
+        match node {
+            Node::CONSTANT(value) => {
+                self.push(".CONSTANT ");
+                self.constant(value)?;
+            }
+            Node::LOAD(load) => match load.load_type {
+                LoadType::VARIABLE => self.push(&format!(".LOAD VARIABLE {}", load.index)),
+                LoadType::PARAMETER(unique_identifier) => self.push(&format!(
+                    ".LOAD PARAMETER {} {}",
+                    unique_identifier, load.index
+                )),
+            },
+            Node::STORE(store) => {
+                self.push(&format!(".STORE {} ", store.index));
+                self.operand(&store.value)?;
+            }
+            Node::SUFFIX(suffix) => {
+                self.push(&format!(".{} ", operation_name(&suffix.operation)));
+                self.operands(&[&suffix.left, &suffix.right])?;
+            }
+            Node::NOT(value) => {
+                self.push(".NOT ");
+                self.operand(value)?;
+            }
+            Node::NEGATE(value) => {
+                self.push(".NEGATE ");
+                self.operand(value)?;
+            }
+            Node::CONDITIONAL(conditional) => {
+                self.push(".IF CONDITION ");
+                self.operand(&conditional.condition)?;
+                self.push(" THEN ");
+                self.block(&conditional.body)?;
+
+                // An empty alternative is parsed the same as leaving it out
+                if !conditional.alternative.is_empty() {
+                    self.push(" ELSE ");
+                    self.block(&conditional.alternative)?;
+                }
+            }
+            Node::FUNCTION(function) => self.function(
+                &function.name,
+                function.unique_identifier as i64,
+                &function.return_type,
+                &function.parameters,
+                &function.free,
+                &function.body,
+            )?,
+            Node::CALL(call) => {
+                // Calls to libraries, eg. `.CALL std::print { ... };`, are parsed as a call to a
+                // string and written the same way again
+                match namespace(&call.call) {
+                    Some(namespace) => self.push(&format!(".CALL {} ", namespace)),
+                    None => {
+                        self.push(".CALL ");
+                        self.operand(&call.call)?;
+                        self.push(" ");
+                    }
+                }
+
+                let arguments: Vec<&Node> = call.arguments.iter().collect();
+                self.operands(&arguments)?;
+            }
+            Node::RETURN(value) => {
+                self.push(".RETURN ");
+                self.operand(value)?;
+            }
+            Node::WHILE(while_loop) => {
+                self.push(".WHILE CONDITION ");
+                self.operand(&while_loop.condition)?;
+                self.push(" THEN ");
+                self.block(&while_loop.body)?;
+            }
+            Node::INDEX(index) => {
+                self.push(if index.hashmap {
+                    ".INDEX HASHMAP "
+                } else {
+                    ".INDEX "
+                });
+                self.operand(&index.to_index)?;
+                self.push(" ");
+                self.operand(&index.index)?;
+            }
+            Node::SLICE(slice) => {
+                self.push(".SLICE ");
+                self.operand(&slice.to_slice)?;
+                self.push(" ");
+                self.operand(&slice.from)?;
+                self.push(" ");
+                self.operand(&slice.to)?;
+            }
+            Node::PUSH(push) => {
+                self.push(".PUSH ");
+                self.operand(&push.to_push)?;
+                self.push(" ");
+                self.operand(&push.item)?;
+            }
+            Node::COPY(copy) => {
+                self.push(".COPY ");
+                self.operand(&copy.object)?;
+            }
+            Node::LOADLIB(library) => {
+                self.push(".LOADLIB ");
+                self.operand(&library.path)?;
+                self.push(&format!(" {}", string(&library.namespace)?));
+            }
+            Node::ASSIGN(a, b) => {
+                self.push(".ASSIGN ");
+                self.operand(a)?;
+                self.push(" ");
+                self.operand(b)?;
+            }
+            Node::POP(a, b) => {
+                self.push(".POP ");
+                self.operand(a)?;
+                self.push(" ");
+                self.operand(b)?;
+            }
+            Node::LENGTH(value) => {
+                self.push(".LENGTH ");
+                self.operand(value)?;
+            }
+            Node::AND(a, b) => {
+                self.push(".AND ");
+                self.operands(&[a, b])?;
+            }
+            Node::OR(a, b) => {
+                self.push(".OR ");
+                self.operands(&[a, b])?;
+            }
+            Node::COMPOUND(compound) => self.compound(compound)?,
+            Node::HASHMAP(hashmap) => {
+                self.push(&format!(
+                    ".HASHMAP {} {} ",
+                    type_name(&hashmap.key_type)?,
+                    type_name(&hashmap.value_type)?
+                ));
+
+                let values: Vec<&Node> = hashmap
+                    .values
+                    .iter()
+                    .flat_map(|(key, value)| vec![key, value])
+                    .collect();
+                self.operands(&values)?;
+            }
+            Node::KEYS(value) => {
+                self.push(".KEYS ");
+                self.operand(value)?;
+            }
+            Node::RANGE(range) => {
+                self.push(".RANGE ");
+                self.operand(&range.from)?;
+                self.push(" ");
+                self.operand(&range.till)?;
+                self.push(" ");
+                self.operand(&range.step)?;
+            }
+            Node::BREAK => self.push(".BREAK"),
+            Node::CONTINUE => self.push(".CONTINUE"),
+            Node::LOCATION(location) => {
+                self.push(&format!(
+                    ".LOCATION {} {}",
+                    string(&location.file)?,
+                    location.line
+                ));
+            }
+        }
+
+        self.push(";");
+
+        Ok(())
+    }
+
+    fn function(
+        &mut self,
+        name: &str,
+        unique_identifier: i64,
+        return_type: &Type,
+        parameters: &[Type],
+        free: &[Type],
+        body: &[Node],
+    ) -> Result<(), PrintError> {
+        self.push(&format!(
+            ".FUNCTION {} {} {} ARGUMENTS {} FREE {} THEN ",
+            string(name)?,
+            unique_identifier,
+            type_name(return_type)?,
+            type_arguments(parameters)?,
+            type_arguments(free)?
+        ));
+
+        self.block(body)
+    }
+
+    fn compound(&mut self, compound: &CompoundType) -> Result<(), PrintError> {
+        self.push(&format!(
+            ".COMPOUND {} {}",
+            string(&compound.name)?,
+            type_arguments(&compound.values)?
+        ));
+
+        if let Some((class, fields)) = &compound.fields {
+            let fields = fields
+                .iter()
+                .map(|field| Ok(format!(" {};", string(field)?)))
+                .collect::<Result<String, PrintError>>()?;
+
+            self.push(&format!(" FIELDS {} {{{} }}", string(class)?, fields));
+        }
+
+        Ok(())
+    }
+
+    /// The type and value of a constant, without the semicolon
+    fn constant(&mut self, value: &ValueType) -> Result<(), PrintError> {
+        match value {
+            ValueType::Integer(int) => self.push(&format!("INT {}", int)),
+            ValueType::Float(float) => self.push(&format!("FLOAT {}", float_literal(*float)?)),
+            ValueType::Boolean(bool) => self.push(&format!("BOOL {}", bool)),
+            ValueType::Character(char) => self.push(&format!("CHAR {}", char_literal(*char)?)),
+            ValueType::Void => self.push("VOID"),
+            ValueType::Array(values) => {
+                self.push(&format!("{} ", array_type(values)));
+
+                match string_literal(values) {
+                    Some(string) => self.push(&string),
+                    None => {
+                        self.push("[");
+
+                        for value in values.iter() {
+                            self.push(" .CONSTANT ");
+                            self.constant(value)?;
+                            self.push(";");
+                        }
+
+                        self.push(if values.is_empty() { "]" } else { " ]" });
+                    }
+                }
+            }
+            ValueType::Compound(name, values) => {
+                self.push(&format!("{} {{", name));
+                self.depth += 1;
+
+                // The methods of a class are functions without a name
+                for value in values.iter() {
+                    self.separator();
+
+                    match value {
+                        ValueType::Function(return_type, parameters, unique_identifier, body) => {
+                            self.function(
+                                "",
+                                *unique_identifier as i64,
+                                return_type,
+                                parameters,
+                                &[],
+                                body,
+                            )?;
+                        }
+                        value => {
+                            self.push(".CONSTANT ");
+                            self.constant(value)?;
+                        }
+                    }
+
+                    self.push(";");
+                }
+
+                self.depth -= 1;
+                self.separator();
+                self.push("}");
+            }
+            ValueType::Hashmap(_) | ValueType::Function(..) | ValueType::Instance(..) => {
+                return Err(PrintError::Value(value.clone()))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn operation_name(operation: &BinaryOperation) -> &'static str {
+    match operation {
+        BinaryOperation::ADD => "ADD",
+        BinaryOperation::SUBTRACT => "SUBTRACT",
+        BinaryOperation::MULTIPLY => "MULTIPLY",
+        BinaryOperation::DIVIDE => "DIVIDE",
+        BinaryOperation::POWER => "POWER",
+        BinaryOperation::GREATERTHAN => "GREATERTHAN",
+        BinaryOperation::LESSTHAN => "LESSTHAN",
+        BinaryOperation::GREATERTHANOREQUAL => "GREATERTHANOREQUAL",
+        BinaryOperation::LESSTHANOREQUAL => "LESSTHANOREQUAL",
+        BinaryOperation::EQUALS => "EQUALS",
+        BinaryOperation::NOTEQUALS => "NOTEQUALS",
+        BinaryOperation::MODULO => "MODULO",
+    }
+}
+
+fn type_name(tp: &Type) -> Result<String, PrintError> {
+    match tp {
+        Type::INT => Ok("INT".to_string()),
+        Type::FLOAT => Ok("FLOAT".to_string()),
+        Type::BOOL => Ok("BOOL".to_string()),
+        Type::CHAR => Ok("CHAR".to_string()),
+        Type::VOID => Ok("VOID".to_string()),
+        // Only the names of classes themselves are known to the lexer, not arrays of them
+        Type::ARRAY(inner) => match inner.as_ref() {
+            Type::Compound(..) | Type::Function(..) => Err(PrintError::Type(tp.clone())),
+            inner => Ok(format!("{}[]", type_name(inner)?)),
+        },
+        Type::Compound(name, _) => Ok(name.clone()),
+        Type::HASHMAP(key, value) => Ok(format!(
+            "HASHMAP[{},{}]",
+            type_name(key)?,
+            type_name(value)?
+        )),
+        Type::Function(..) => Err(PrintError::Type(tp.clone())),
+    }
+}
+
+/// Types that are each followed by a semicolon, eg. `{ INT; BOOL; }`
+fn type_arguments(types: &[Type]) -> Result<String, PrintError> {
+    let mut arc = String::from("{");
+
+    for tp in types {
+        arc.push_str(&format!(" {};", type_name(tp)?));
+    }
+
+    arc.push_str(" }");
+
+    Ok(arc)
+}
+
+/// The parser does not look at the type of an array, it only has to be one the lexer knows
+fn array_type(values: &[ValueType]) -> String {
+    let element = match values.first() {
+        Some(ValueType::Integer(_)) => "INT".to_string(),
+        Some(ValueType::Float(_)) => "FLOAT".to_string(),
+        Some(ValueType::Boolean(_)) => "BOOL".to_string(),
+        Some(ValueType::Character(_)) => "CHAR".to_string(),
+        Some(ValueType::Array(values)) => array_type(values),
+        _ => "VOID".to_string(),
+    };
+
+    format!("{}[]", element)
+}
+
+/// Arrays of characters are written as a string when the lexer reads every character back as it is
+fn string_literal(values: &[ValueType]) -> Option<String> {
+    let mut string = String::from("\"");
+
+    for value in values {
+        match value {
+            ValueType::Character(char) if *char != '"' && *char != '\\' => string.push(*char),
+            _ => return None,
+        }
+    }
+
+    string.push('"');
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(string)
+    }
+}
+
+fn string(value: &str) -> Result<String, PrintError> {
+    if value.contains('"') || value.contains('\\') {
+        return Err(PrintError::String(value.to_string()));
+    }
+
+    Ok(format!("\"{}\"", value))
+}
+
+fn char_literal(char: char) -> Result<String, PrintError> {
+    if char == '\n' {
+        return Err(PrintError::Value(ValueType::Character(char)));
+    }
+
+    Ok(format!("'{}'", char))
+}
+
+/// Floats always need digits after the dot, and can not be written with an exponent
+fn float_literal(float: f64) -> Result<String, PrintError> {
+    if !float.is_finite() {
+        return Err(PrintError::Value(ValueType::Float(float)));
+    }
+
+    // Display never uses an exponent and is the shortest text that is read back as the same float
+    let literal = float.to_string();
+
+    if literal.contains('.') {
+        Ok(literal)
+    } else {
+        Ok(format!("{}.0", literal))
+    }
+}
+
+/// The library and function of a call to a library, eg. `std::print`
+fn namespace(call: &Node) -> Option<String> {
+    let values = match call {
+        Node::CONSTANT(ValueType::Array(values)) => values,
+        _ => return None,
+    };
+
+    let mut namespace = String::new();
+    for value in values.iter() {
+        match value {
+            ValueType::Character(char) => namespace.push(*char),
+            _ => return None,
+        }
+    }
+
+    let is_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+
+    match namespace.split_once("::") {
+        Some((library, function)) if is_part(library) && is_part(function) => Some(namespace),
+        _ => None,
+    }
+}
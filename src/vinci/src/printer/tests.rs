@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod tests {
+    use crate::ast::instructions::function::Call;
+    use crate::ast::instructions::Node;
+    use crate::ast::AST;
+    use crate::parse;
+    use crate::printer::{print, print_indented, PrintError};
+    use crate::types::{Type, ValueType};
+
+    /// Parses the Arc, and checks that printing it with and without indentation parses into the
+    /// same AST again
+    fn round_trip(arc: &str) {
+        let ast = parse(arc).unwrap();
+        assert!(!ast.nodes.is_empty());
+
+        let printed = print(&ast).unwrap();
+        assert_eq!(parse(&printed).unwrap(), ast, "{}", printed);
+
+        let indented = print_indented(&ast, 4).unwrap();
+        assert_eq!(parse(&indented).unwrap(), ast, "{}", indented);
+    }
+
+    #[test]
+    fn constant() {
+        round_trip(".CONSTANT INT 50; .CONSTANT INT -3; .CONSTANT BOOL false; .CONSTANT VOID;");
+        round_trip(".CONSTANT FLOAT 2.5; .CONSTANT FLOAT 3.0; .CONSTANT FLOAT -0.000001;");
+        round_trip(".CONSTANT CHAR 'a'; .CONSTANT CHAR '\"'; .CONSTANT CHAR ' ';");
+        round_trip(".CONSTANT CHAR[] \"hello world\"; .CONSTANT CHAR[] \"\";");
+        round_trip(".CONSTANT INT[] [.CONSTANT INT 1; .CONSTANT INT 2;]; .CONSTANT INT[] [];");
+        round_trip(
+            ".CONSTANT INT[][] [.CONSTANT INT[] [.CONSTANT INT 1;]; .CONSTANT CHAR[] \"a\";];",
+        );
+        round_trip(".CONSTANT CHAR[] [.CONSTANT CHAR '\"'; .CONSTANT CHAR 'a';];");
+    }
+
+    #[test]
+    fn constant_compound() {
+        round_trip(
+            ".COMPOUND \"class_0\" { INT; VOID; } FIELDS \"Person\" { \"age\"; \"greet\"; }; \
+            .CONSTANT class_0 { .CONSTANT INT 10; .FUNCTION \"\" 3 INT ARGUMENTS { INT; } FREE { } \
+            THEN { .RETURN { .LOAD PARAMETER 3 0; }; }; };",
+        );
+    }
+
+    #[test]
+    fn load_and_store() {
+        round_trip(".STORE 0 { .CONSTANT INT 10; }; .LOAD VARIABLE 0; .LOAD PARAMETER 4 1;");
+    }
+
+    #[test]
+    fn math() {
+        for operation in [
+            "ADD",
+            "SUBTRACT",
+            "MULTIPLY",
+            "DIVIDE",
+            "POWER",
+            "GREATERTHAN",
+            "LESSTHAN",
+            "GREATERTHANOREQUAL",
+            "LESSTHANOREQUAL",
+            "EQUALS",
+            "NOTEQUALS",
+            "MODULO",
+        ] {
+            round_trip(&format!(
+                ".{} {{ .CONSTANT INT 1; .LOAD VARIABLE 0; }};",
+                operation
+            ));
+        }
+    }
+
+    #[test]
+    fn prefix() {
+        round_trip(".NOT { .CONSTANT BOOL true; }; .NEGATE { .CONSTANT FLOAT 1.5; };");
+    }
+
+    #[test]
+    fn and_or() {
+        round_trip(
+            ".AND { .CONSTANT BOOL true; .OR { .CONSTANT BOOL false; .LOAD VARIABLE 0; }; };",
+        );
+    }
+
+    #[test]
+    fn conditional() {
+        round_trip(
+            ".IF CONDITION { .CONSTANT BOOL true; } THEN { .CONSTANT INT 1; } ELSE { .CONSTANT INT 2; };",
+        );
+        round_trip(".IF CONDITION { .LOAD VARIABLE 0; } THEN { };");
+    }
+
+    #[test]
+    fn function() {
+        round_trip(
+            ".FUNCTION \"var_0\" 0 INT ARGUMENTS { INT; CHAR[]; } FREE { BOOL; } THEN { \
+            .RETURN { .ADD { .LOAD PARAMETER 0 0; .CONSTANT INT 1; }; }; };",
+        );
+        round_trip(".FUNCTION \"\" 1 VOID ARGUMENTS { } FREE { } THEN { };");
+    }
+
+    #[test]
+    fn call() {
+        round_trip(".CALL { .LOAD VARIABLE 0; } { .CONSTANT INT 10; .CONSTANT INT 30; };");
+        round_trip(".CALL { .LOAD VARIABLE 0; } { };");
+        round_trip(".CALL std::println { .CONSTANT CHAR[] \"hi\"; };");
+    }
+
+    #[test]
+    fn call_namespace() {
+        let ast = parse(".CALL std::println { .CONSTANT INT 1; };").unwrap();
+
+        assert_eq!(
+            print(&ast).unwrap(),
+            ".CALL std::println { .CONSTANT INT 1; };"
+        );
+    }
+
+    #[test]
+    fn while_loop() {
+        round_trip(
+            ".WHILE CONDITION { .CONSTANT BOOL true; } THEN { .IF CONDITION { .CONSTANT BOOL true; } \
+            THEN { .BREAK; } ELSE { .CONTINUE; }; };",
+        );
+    }
+
+    #[test]
+    fn memory() {
+        round_trip(".INDEX { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };");
+        round_trip(".INDEX HASHMAP { .LOAD VARIABLE 0; } { .CONSTANT CHAR[] \"key\"; };");
+        round_trip(".PUSH { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };");
+        round_trip(".SLICE { .LOAD VARIABLE 0; } { .CONSTANT INT 0; } { .CONSTANT INT 1; };");
+        round_trip(".COPY { .LOAD VARIABLE 0; };");
+        round_trip(".ASSIGN { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };");
+        round_trip(".POP { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };");
+        round_trip(".LENGTH { .LOAD VARIABLE 0; }; .KEYS { .LOAD VARIABLE 0; };");
+        round_trip(".RANGE { .CONSTANT INT 0; } { .CONSTANT INT 10; } { .CONSTANT INT 2; };");
+    }
+
+    #[test]
+    fn loadlib() {
+        round_trip(".LOADLIB { .CONSTANT CHAR[] \"./lib.so\"; } \"lib\";");
+    }
+
+    #[test]
+    fn compound() {
+        round_trip(".COMPOUND \"class_0\" { INT; CHAR[]; HASHMAP[INT,BOOL][]; };");
+        round_trip(".COMPOUND \"class_0\" { } FIELDS \"Empty\" { };");
+    }
+
+    #[test]
+    fn hashmap() {
+        round_trip(
+            ".HASHMAP INT BOOL { .CONSTANT INT 10; .CONSTANT BOOL true; .CONSTANT INT 11; \
+            .CONSTANT BOOL false; };",
+        );
+        round_trip(".HASHMAP CHAR[] HASHMAP[INT,INT] { };");
+    }
+
+    #[test]
+    fn location() {
+        round_trip(".LOCATION \"src/main.loop\" 3; .CONSTANT INT 1;");
+    }
+
+    #[test]
+    fn indented() {
+        let ast = parse(
+            ".STORE 0 { .FUNCTION \"var_0\" 0 VOID ARGUMENTS { } FREE { } THEN { \
+            .IF CONDITION { .CONSTANT BOOL true; } THEN { .BREAK; }; }; }; .LOAD VARIABLE 0;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            print_indented(&ast, 2).unwrap(),
+            ".STORE 0 { .FUNCTION \"var_0\" 0 VOID ARGUMENTS { } FREE { } THEN {\n  \
+            .IF CONDITION { .CONSTANT BOOL true; } THEN {\n    .BREAK;\n  };\n}; };\n\
+            .LOAD VARIABLE 0;\n"
+        );
+    }
+
+    #[test]
+    fn unprintable() {
+        let hashmap = ValueType::Hashmap(Box::default());
+        let ast = AST::new_mock(vec![Node::CONSTANT(hashmap.clone())]);
+        assert_eq!(print(&ast), Err(PrintError::Value(hashmap)));
+
+        let ast = AST::new_mock(vec![Node::CONSTANT(ValueType::Float(f64::NAN))]);
+        assert!(matches!(print(&ast), Err(PrintError::Value(_))));
+
+        let ast = parse(".LOADLIB { .CONSTANT CHAR[] \"lib.so\"; } \"lib\";").unwrap();
+        let mut nodes = ast.nodes;
+        if let Node::LOADLIB(library) = &mut nodes[0] {
+            library.namespace = "a\"b".to_string();
+        }
+        assert_eq!(
+            print(&AST::new_mock(nodes)),
+            Err(PrintError::String("a\"b".to_string()))
+        );
+
+        let function = Type::Function(Box::new(Type::INT), Box::default());
+        let ast = parse(".FUNCTION \"f\" 0 INT ARGUMENTS { } FREE { } THEN { };").unwrap();
+        let mut nodes = ast.nodes;
+        if let Node::FUNCTION(f) = &mut nodes[0] {
+            f.parameters.push(function.clone());
+        }
+        assert_eq!(
+            print(&AST::new_mock(nodes)),
+            Err(PrintError::Type(function))
+        );
+
+        let call = Node::CALL(Box::new(Call {
+            call: Node::CONSTANT(ValueType::Character('\n')),
+            arguments: vec![],
+        }));
+        assert!(print(&AST::new_mock(vec![call])).is_err());
+    }
+}
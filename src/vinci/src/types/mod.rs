@@ -10,8 +10,12 @@ pub enum ValueType {
     Array(Box<Vec<ValueType>>),
     Void,
     Compound(String, Box<Vec<ValueType>>),
+    // Keys and values, in the order they were defined
+    Hashmap(Box<Vec<(ValueType, ValueType)>>),
     // Return type, arguments, unique ID, body
     Function(Box<Type>, Box<Vec<Type>>, u32, Box<Vec<Node>>),
+    // Name of the class and its properties by name, only created from values of the backend
+    Instance(String, Box<Vec<(String, ValueType)>>),
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -26,6 +30,8 @@ pub enum Type {
     // Compound name and values
     Compound(String, Box<Vec<Type>>),
     Function(Box<Type>, Box<Vec<Type>>),
+    // Key and value type
+    HASHMAP(Box<Type>, Box<Type>),
 }
 
 impl Display for ValueType {
@@ -49,6 +55,12 @@ impl Display for ValueType {
             ValueType::Compound(name, cmp) => {
                 write!(f, "COMPOUND {} {:?}", name, cmp)
             }
+            ValueType::Hashmap(values) => {
+                write!(f, "HASHMAP {:?}", *values.clone())
+            }
+            ValueType::Instance(name, properties) => {
+                write!(f, "INSTANCE {} {:?}", name, *properties.clone())
+            }
             _ => write!(f, "unknown type"),
         }
     }
@@ -69,6 +81,9 @@ impl Debug for ValueType {
             ValueType::Character(char) => {
                 write!(f, "CHAR {}", char)
             }
+            ValueType::Hashmap(values) => {
+                write!(f, "HASHMAP [{:?}]", *values.clone())
+            }
             _ => write!(f, "unknown type"),
         }
     }
@@ -88,4 +103,97 @@ impl ValueType {
         }
         panic!("\"char_arr_to_string()\" Expects an char array");
     }
+
+    /// Formats the value the way Loop shows it to the user, eg: `[1, "two", 3.5]` or
+    /// `Person { age: 10, name: "bob" }`
+    pub fn format(&self) -> String {
+        self.format_nested(false)
+    }
+
+    /// Turns whole numbers back into floats where the type says the value holds floats, after
+    /// running `2.0` can not be told apart from `2` anymore
+    pub fn with_type(self, tp: &Type) -> ValueType {
+        match (self, tp) {
+            (ValueType::Integer(int), Type::FLOAT) => ValueType::Float(int as f64),
+            (ValueType::Array(values), Type::ARRAY(tp)) => ValueType::Array(Box::new(
+                values
+                    .into_iter()
+                    .map(|value| value.with_type(tp))
+                    .collect(),
+            )),
+            (ValueType::Hashmap(values), Type::HASHMAP(key_type, value_type)) => {
+                ValueType::Hashmap(Box::new(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (key.with_type(key_type), value.with_type(value_type)))
+                        .collect(),
+                ))
+            }
+            (value, _) => value,
+        }
+    }
+
+    // Strings and characters are quoted when they are part of another value
+    fn format_nested(&self, nested: bool) -> String {
+        match self {
+            ValueType::Integer(int) => int.to_string(),
+            ValueType::Float(float) => format!("{:?}", float),
+            ValueType::Boolean(bool) => bool.to_string(),
+            ValueType::Character(char) if nested => format!("'{}'", char),
+            ValueType::Character(char) => char.to_string(),
+            ValueType::Array(_) if self.is_string() => {
+                let mut value = self.clone();
+                let string = value.char_arr_to_string();
+
+                if nested {
+                    format!("{:?}", string)
+                } else {
+                    string
+                }
+            }
+            ValueType::Array(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.format_nested(true)).collect();
+
+                format!("[{}]", values.join(", "))
+            }
+            ValueType::Void => "null".to_string(),
+            ValueType::Compound(name, values) => {
+                let values: Vec<String> = values.iter().map(|v| v.format_nested(true)).collect();
+
+                format!("{} {{ {} }}", name, values.join(", "))
+            }
+            ValueType::Hashmap(values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|(key, value)| {
+                        format!("{}: {}", key.format_nested(true), value.format_nested(true))
+                    })
+                    .collect();
+
+                format!("{{{}}}", values.join(", "))
+            }
+            ValueType::Function(..) => "fn".to_string(),
+            ValueType::Instance(name, properties) if properties.is_empty() => {
+                format!("{} {{}}", name)
+            }
+            ValueType::Instance(name, properties) => {
+                let properties: Vec<String> = properties
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value.format_nested(true)))
+                    .collect();
+
+                format!("{} {{ {} }}", name, properties.join(", "))
+            }
+        }
+    }
+
+    // Strings are arrays of characters, an empty array can not be told apart from an empty string
+    fn is_string(&self) -> bool {
+        match self {
+            ValueType::Array(values) => {
+                !values.is_empty() && values.iter().all(|v| matches!(v, ValueType::Character(_)))
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,559 @@
+//! Checks Arc that parsed for mistakes that would otherwise only fail once it runs, like loading
+//! a variable that was never stored. Meant for Arc that was not compiled by Loop, as every error is
+//! found before any of it is given to a backend. Only whole programs can be validated, as code
+//! that is parsed later might store variables or define compounds that are used.
+use crate::ast::instructions::function::{Call, Function};
+use crate::ast::instructions::location::Location;
+use crate::ast::instructions::memory::{CompoundType, LoadType};
+use crate::ast::instructions::Node;
+use crate::ast::AST;
+use crate::diagnostic::Diagnostic;
+use crate::types::{Type, ValueType};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+mod tests;
+
+/// An error together with the last `.LOCATION` before it, which tells where in the source it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub kind: ValidationErrorKind,
+    pub location: Option<Location>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// A variable that is loaded before anything is stored in it
+    UndefinedVariable(u64),
+    /// A parameter of a function, by its unique identifier, loaded outside of that function
+    ParameterOutsideFunction(u64),
+    /// (Unique identifier of the function, index, amount of parameters)
+    ParameterOutOfRange(u64, u64, usize),
+    ReturnOutsideFunction,
+    /// A `.BREAK` or `.CONTINUE`, which only belong in a `.WHILE` of the same function
+    OutsideLoop(&'static str),
+    /// Only functions and the namespaces of imports can be called
+    NotCallable(ValueType),
+    /// (Parameters, arguments)
+    ArgumentCount(usize, usize),
+    /// (Declared type, constant)
+    TypeMismatch(Type, ValueType),
+    /// A compound type that is used before it is defined with `.COMPOUND`
+    UnknownCompound(String),
+    CompoundAlreadyExists(String),
+    /// The path of a `.LOADLIB` has to be a constant string
+    LibraryPath,
+}
+
+impl ValidationError {
+    /// Turns the error into a diagnostic pointing at its location, `text` is the code written on
+    /// that line
+    pub fn diagnostic(&self, text: Option<String>) -> Diagnostic {
+        let diagnostic = Diagnostic::error("arc::validation", self.kind.to_string());
+
+        match &self.location {
+            Some(location) => diagnostic.with_span(location.diagnostic_span(text)),
+            None => diagnostic,
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} (at {})", self.kind, location),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationErrorKind::UndefinedVariable(index) => {
+                write!(f, "variable {} is loaded before it is stored", index)
+            }
+            ValidationErrorKind::ParameterOutsideFunction(function) => write!(
+                f,
+                "a parameter of function {} is loaded outside of it",
+                function
+            ),
+            ValidationErrorKind::ParameterOutOfRange(function, index, parameters) => write!(
+                f,
+                "parameter {} is loaded, but function {} has {} parameters",
+                index, function, parameters
+            ),
+            ValidationErrorKind::ReturnOutsideFunction => {
+                write!(f, ".RETURN can only be used inside of a function")
+            }
+            ValidationErrorKind::OutsideLoop(instruction) => {
+                write!(f, ".{} can only be used inside of a loop", instruction)
+            }
+            ValidationErrorKind::NotCallable(value) => {
+                write!(f, "only functions can be called. got={:?}", value)
+            }
+            ValidationErrorKind::ArgumentCount(parameters, arguments) => write!(
+                f,
+                "the function has {} parameters, but is called with {} arguments",
+                parameters, arguments
+            ),
+            ValidationErrorKind::TypeMismatch(expected, got) => {
+                write!(f, "expected={:?}. got={:?}", expected, got)
+            }
+            ValidationErrorKind::UnknownCompound(name) => write!(
+                f,
+                "Type is unknown, did you define it using .COMPOUND? got=\"{}\"",
+                name
+            ),
+            ValidationErrorKind::CompoundAlreadyExists(name) => {
+                write!(f, "Compound type's name already in use. got=\"{}\"", name)
+            }
+            ValidationErrorKind::LibraryPath => {
+                write!(f, "the path of .LOADLIB has to be a constant string")
+            }
+        }
+    }
+}
+
+/// Checks the whole program, returning every error that was found
+pub fn validate(ast: &AST) -> Result<(), Vec<ValidationError>> {
+    let mut validator = Validator::default();
+
+    validator.nodes(&ast.nodes);
+    validator.calls();
+
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
+
+/// A function the current node is in
+struct Scope {
+    unique_identifier: i64,
+    parameters: Vec<Type>,
+    return_type: Type,
+}
+
+/// A call of a variable, which can only be checked once every store is known
+struct VariableCall {
+    index: u64,
+    arguments: Vec<Node>,
+    location: Option<Location>,
+}
+
+#[derive(Default)]
+struct Validator {
+    stored: HashSet<u64>,
+    // Every store of a variable, a variable that is stored once with a function is always that
+    // function, which means calls of it can be checked
+    stores: HashMap<u64, Vec<Option<Vec<Type>>>>,
+    variable_calls: Vec<VariableCall>,
+    compounds: HashSet<String>,
+    // Innermost last
+    scopes: Vec<Scope>,
+    // Loops around the current node, inside of the current function
+    loops: usize,
+    // Whether the current node is the value of a store or return, a while used as a value is a
+    // function that can return it
+    value: bool,
+    // If and while expressions around the current node, inside of the current function
+    expressions: usize,
+    location: Option<Location>,
+    errors: Vec<ValidationError>,
+}
+
+impl Validator {
+    fn error(&mut self, kind: ValidationErrorKind) {
+        self.errors.push(ValidationError {
+            kind,
+            location: self.location.clone(),
+        });
+    }
+
+    fn nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            self.node(node);
+        }
+    }
+
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::CONSTANT(value) => self.constant(value),
+            Node::LOAD(load) => match load.load_type {
+                LoadType::VARIABLE => {
+                    if !self.stored.contains(&load.index) {
+                        self.error(ValidationErrorKind::UndefinedVariable(load.index));
+                    }
+                }
+                LoadType::PARAMETER(function) => self.parameter(function, load.index),
+            },
+            Node::STORE(store) => {
+                // A function can call itself, so it is stored before its body is checked
+                let parameters = match store.value.as_ref() {
+                    Node::FUNCTION(function) => Some(function.parameters.clone()),
+                    _ => None,
+                };
+                if parameters.is_some() {
+                    self.stored.insert(store.index);
+                }
+
+                self.value(&store.value);
+                self.store(store.index, parameters);
+            }
+            Node::SUFFIX(suffix) => {
+                self.node(&suffix.left);
+                self.node(&suffix.right);
+            }
+            Node::NOT(value) | Node::NEGATE(value) | Node::LENGTH(value) | Node::KEYS(value) => {
+                self.node(value)
+            }
+            Node::RETURN(value) => {
+                match self.scopes.last() {
+                    // Returns the value of an if or while expression
+                    _ if self.expressions > 0 => (),
+                    Some(scope) => {
+                        if let Node::CONSTANT(constant) = value.as_ref() {
+                            // Void is used as return type when it is not known
+                            if scope.return_type != Type::VOID {
+                                let return_type = scope.return_type.clone();
+                                self.check_constant(&return_type, constant);
+                            }
+                        }
+                    }
+                    None => self.error(ValidationErrorKind::ReturnOutsideFunction),
+                }
+
+                self.value(value);
+            }
+            Node::CONDITIONAL(conditional) => {
+                self.node(&conditional.condition);
+                // The last expression of a branch is returned as the value of the if, whether it is
+                // used or not, eg: `if x { f() }` on its own
+                self.expression(true, |validator| {
+                    validator.nodes(&conditional.body);
+                    validator.nodes(&conditional.alternative);
+                });
+            }
+            Node::FUNCTION(function) => {
+                // Named functions are stored in the variable of their name, eg: "var_0"
+                let index = function
+                    .name
+                    .strip_prefix("var_")
+                    .and_then(|index| index.parse().ok());
+                if let Some(index) = index {
+                    self.stored.insert(index);
+                }
+
+                self.function(function);
+
+                if let Some(index) = index {
+                    self.store(index, Some(function.parameters.clone()));
+                }
+            }
+            Node::CALL(call) => self.call(call),
+            Node::WHILE(while_loop) => {
+                self.node(&while_loop.condition);
+                let value = self.value;
+                self.expression(value, |validator| {
+                    validator.loops += 1;
+                    validator.nodes(&while_loop.body);
+                    validator.loops -= 1;
+                });
+            }
+            Node::BREAK => {
+                if self.loops == 0 {
+                    self.error(ValidationErrorKind::OutsideLoop("BREAK"));
+                }
+            }
+            Node::CONTINUE => {
+                if self.loops == 0 {
+                    self.error(ValidationErrorKind::OutsideLoop("CONTINUE"));
+                }
+            }
+            Node::INDEX(index) => {
+                self.node(&index.to_index);
+                self.node(&index.index);
+            }
+            Node::SLICE(slice) => {
+                self.node(&slice.to_slice);
+                self.node(&slice.from);
+                self.node(&slice.to);
+            }
+            Node::PUSH(push) => {
+                self.node(&push.to_push);
+                self.node(&push.item);
+            }
+            Node::COPY(copy) => self.node(&copy.object),
+            Node::LOADLIB(library) => match library.path.as_ref() {
+                Node::CONSTANT(ValueType::Array(values))
+                    if values
+                        .iter()
+                        .all(|value| matches!(value, ValueType::Character(_))) => {}
+                _ => self.error(ValidationErrorKind::LibraryPath),
+            },
+            Node::ASSIGN(a, b) | Node::POP(a, b) | Node::AND(a, b) | Node::OR(a, b) => {
+                self.node(a);
+                self.node(b);
+            }
+            Node::COMPOUND(compound) => self.compound(compound),
+            Node::HASHMAP(hashmap) => {
+                // Hashmaps can have keys and values of any type, the declared types are only checked
+                // to exist
+                self.check_type(&hashmap.key_type);
+                self.check_type(&hashmap.value_type);
+
+                for (key, value) in &hashmap.values {
+                    self.node(key);
+                    self.node(value);
+                }
+            }
+            Node::RANGE(range) => {
+                self.node(&range.from);
+                self.node(&range.till);
+                self.node(&range.step);
+            }
+            Node::LOCATION(location) => self.location = Some(location.clone()),
+        }
+    }
+
+    fn store(&mut self, index: u64, parameters: Option<Vec<Type>>) {
+        self.stored.insert(index);
+        self.stores.entry(index).or_default().push(parameters);
+    }
+
+    /// A node of which the value is used by a store or return
+    fn value(&mut self, node: &Node) {
+        let value = std::mem::replace(&mut self.value, true);
+        self.node(node);
+        self.value = value;
+    }
+
+    /// The body of an if or while, in which `.RETURN` gives the value of the expression when
+    /// `returns` is set
+    fn expression(&mut self, returns: bool, body: impl FnOnce(&mut Validator)) {
+        let expression = returns as usize;
+        let value = std::mem::replace(&mut self.value, false);
+
+        self.expressions += expression;
+        body(self);
+        self.expressions -= expression;
+
+        self.value = value;
+    }
+
+    fn constant(&mut self, value: &ValueType) {
+        match value {
+            ValueType::Array(values) => {
+                for value in values.iter() {
+                    self.constant(value);
+                }
+            }
+            ValueType::Compound(name, values) => {
+                if !self.compounds.contains(name) {
+                    self.error(ValidationErrorKind::UnknownCompound(name.clone()));
+                }
+
+                for value in values.iter() {
+                    self.constant(value);
+                }
+            }
+            ValueType::Hashmap(values) => {
+                for (key, value) in values.iter() {
+                    self.constant(key);
+                    self.constant(value);
+                }
+            }
+            ValueType::Function(return_type, parameters, unique_identifier, body) => {
+                self.scope(
+                    Scope {
+                        unique_identifier: *unique_identifier as i64,
+                        parameters: *parameters.clone(),
+                        return_type: *return_type.clone(),
+                    },
+                    body,
+                );
+            }
+            ValueType::Instance(_, properties) => {
+                for (_, value) in properties.iter() {
+                    self.constant(value);
+                }
+            }
+            ValueType::Integer(_)
+            | ValueType::Float(_)
+            | ValueType::Boolean(_)
+            | ValueType::Character(_)
+            | ValueType::Void => (),
+        }
+    }
+
+    fn function(&mut self, function: &Function) {
+        self.scope(
+            Scope {
+                unique_identifier: function.unique_identifier as i64,
+                parameters: function.parameters.clone(),
+                return_type: function.return_type.clone(),
+            },
+            &function.body,
+        );
+    }
+
+    fn scope(&mut self, scope: Scope, body: &[Node]) {
+        self.check_type(&scope.return_type);
+        for parameter in &scope.parameters {
+            self.check_type(parameter);
+        }
+
+        // Loops and expressions around a function do not continue inside of it
+        let loops = std::mem::take(&mut self.loops);
+        let expressions = std::mem::take(&mut self.expressions);
+        let value = std::mem::take(&mut self.value);
+        self.scopes.push(scope);
+
+        self.nodes(body);
+
+        self.scopes.pop();
+        self.loops = loops;
+        self.expressions = expressions;
+        self.value = value;
+    }
+
+    fn parameter(&mut self, function: u64, index: u64) {
+        let parameters = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.unique_identifier == function as i64)
+            .map(|scope| scope.parameters.len());
+
+        match parameters {
+            None => self.error(ValidationErrorKind::ParameterOutsideFunction(function)),
+            Some(parameters) if index >= parameters as u64 => self.error(
+                ValidationErrorKind::ParameterOutOfRange(function, index, parameters),
+            ),
+            Some(_) => (),
+        }
+    }
+
+    fn call(&mut self, call: &Call) {
+        match &call.call {
+            // The namespace of an import, eg: "std::println"
+            Node::CONSTANT(ValueType::Array(values))
+                if values
+                    .iter()
+                    .all(|value| matches!(value, ValueType::Character(_))) => {}
+            Node::CONSTANT(ValueType::Function(_, parameters, _, _)) => {
+                self.arguments(parameters, &call.arguments)
+            }
+            Node::CONSTANT(value) => self.error(ValidationErrorKind::NotCallable(value.clone())),
+            Node::FUNCTION(function) => self.arguments(&function.parameters, &call.arguments),
+            Node::LOAD(load) if load.load_type == LoadType::VARIABLE => {
+                self.variable_calls.push(VariableCall {
+                    index: load.index,
+                    arguments: call.arguments.clone(),
+                    location: self.location.clone(),
+                })
+            }
+            _ => (),
+        }
+
+        self.node(&call.call);
+        self.nodes(&call.arguments);
+    }
+
+    /// Checks calls of variables that always contain the same function
+    fn calls(&mut self) {
+        for call in std::mem::take(&mut self.variable_calls) {
+            let parameters = match self.stores.get(&call.index).map(Vec::as_slice) {
+                Some([Some(parameters)]) => parameters.clone(),
+                _ => continue,
+            };
+
+            self.location = call.location;
+            self.arguments(&parameters, &call.arguments);
+        }
+    }
+
+    fn arguments(&mut self, parameters: &[Type], arguments: &[Node]) {
+        if parameters.len() != arguments.len() {
+            self.error(ValidationErrorKind::ArgumentCount(
+                parameters.len(),
+                arguments.len(),
+            ));
+            return;
+        }
+
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            if let Node::CONSTANT(constant) = argument {
+                self.check_constant(parameter, constant);
+            }
+        }
+    }
+
+    fn compound(&mut self, compound: &CompoundType) {
+        for tp in compound.values.iter() {
+            self.check_type(tp);
+        }
+
+        if !self.compounds.insert(compound.name.clone()) {
+            self.error(ValidationErrorKind::CompoundAlreadyExists(
+                compound.name.clone(),
+            ));
+        }
+    }
+
+    /// Compound types have to be defined before they are used
+    fn check_type(&mut self, tp: &Type) {
+        match tp {
+            Type::Compound(name, types) => {
+                if !self.compounds.contains(name) {
+                    self.error(ValidationErrorKind::UnknownCompound(name.clone()));
+                }
+
+                for tp in types.iter() {
+                    self.check_type(tp);
+                }
+            }
+            Type::ARRAY(tp) => self.check_type(tp),
+            Type::HASHMAP(key, value) => {
+                self.check_type(key);
+                self.check_type(value);
+            }
+            Type::Function(return_type, parameters) => {
+                self.check_type(return_type);
+                for tp in parameters.iter() {
+                    self.check_type(tp);
+                }
+            }
+            Type::INT | Type::FLOAT | Type::BOOL | Type::CHAR | Type::VOID => (),
+        }
+    }
+
+    fn check_constant(&mut self, tp: &Type, constant: &ValueType) {
+        if !fits(tp, constant) {
+            self.error(ValidationErrorKind::TypeMismatch(
+                tp.clone(),
+                constant.clone(),
+            ));
+        }
+    }
+}
+
+/// Whether a constant can be used where the type is declared
+fn fits(tp: &Type, constant: &ValueType) -> bool {
+    match (tp, constant) {
+        (Type::VOID, _) => true,
+        (Type::INT, ValueType::Integer(_)) => true,
+        // Integers are converted to floats where a float is expected
+        (Type::FLOAT, ValueType::Float(_) | ValueType::Integer(_)) => true,
+        (Type::BOOL, ValueType::Boolean(_)) => true,
+        (Type::CHAR, ValueType::Character(_)) => true,
+        (Type::ARRAY(tp), ValueType::Array(values)) => values.iter().all(|value| fits(tp, value)),
+        (Type::HASHMAP(_, _), ValueType::Hashmap(_)) => true,
+        (Type::Compound(name, _), ValueType::Compound(compound, _)) => name == compound,
+        (Type::Compound(_, _), ValueType::Instance(_, _)) => true,
+        (Type::Function(_, _), ValueType::Function(_, _, _, _)) => true,
+        _ => false,
+    }
+}
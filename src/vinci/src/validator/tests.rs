@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use crate::ast::instructions::location::Location;
+    use crate::ast::instructions::memory::{CompoundType, Load, LoadLib, LoadType};
+    use crate::ast::instructions::Node;
+    use crate::ast::AST;
+    use crate::parse;
+    use crate::types::{Type, ValueType};
+    use crate::validator::{validate, ValidationError, ValidationErrorKind};
+
+    /// The kinds of every error found in the Arc
+    fn errors(arc: &str) -> Vec<ValidationErrorKind> {
+        match validate(&parse(arc).unwrap()) {
+            Ok(()) => vec![],
+            Err(errors) => errors.into_iter().map(|error| error.kind).collect(),
+        }
+    }
+
+    #[test]
+    fn valid_program() {
+        assert_eq!(
+            errors(
+                ".COMPOUND \"class_0\" { INT; } FIELDS \"Point\" { \"x\"; };
+                .STORE 0 { .CONSTANT class_0 { .CONSTANT INT 1; }; };
+                .FUNCTION \"var_1\" 0 INT ARGUMENTS { INT; class_0; } FREE { } THEN {
+                    .WHILE CONDITION { .CONSTANT BOOL true; } THEN { .BREAK; };
+                    .RETURN { .CALL { .LOAD VARIABLE 1; } { .LOAD PARAMETER 0 0; .LOAD VARIABLE 0; }; };
+                };
+                .STORE 2 { .IF CONDITION { .CONSTANT BOOL true; } THEN { .RETURN { .CONSTANT INT 1; }; }; };
+                .CALL std::println { .CALL { .LOAD VARIABLE 1; } { .CONSTANT INT 5; .LOAD VARIABLE 0; }; };"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(
+            errors(
+                ".LOAD VARIABLE 7;
+                .STORE 0 { .ADD { .LOAD VARIABLE 0; .CONSTANT INT 1; }; };
+                .WHILE CONDITION { .LOAD VARIABLE 1; } THEN { .STORE 1 { .CONSTANT BOOL true; }; };
+                .LOAD VARIABLE 1;"
+            ),
+            vec![
+                ValidationErrorKind::UndefinedVariable(7),
+                ValidationErrorKind::UndefinedVariable(0),
+                ValidationErrorKind::UndefinedVariable(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn recursive_functions() {
+        assert_eq!(
+            errors(
+                ".STORE 0 { .FUNCTION \"\" 0 INT ARGUMENTS { INT; } FREE { } THEN {
+                    .RETURN { .CALL { .LOAD VARIABLE 0; } { .LOAD PARAMETER 0 0; }; };
+                }; };"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        assert_eq!(
+            errors(
+                ".LOAD PARAMETER 0 0;
+                .FUNCTION \"\" 1 VOID ARGUMENTS { INT; } FREE { } THEN {
+                    .LOAD PARAMETER 1 1;
+                    .FUNCTION \"\" 2 VOID ARGUMENTS { } FREE { } THEN { .LOAD PARAMETER 1 0; };
+                };
+                .LOAD PARAMETER 1 0;"
+            ),
+            vec![
+                ValidationErrorKind::ParameterOutsideFunction(0),
+                ValidationErrorKind::ParameterOutOfRange(1, 1, 1),
+                ValidationErrorKind::ParameterOutsideFunction(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn control_flow() {
+        assert_eq!(
+            errors(
+                ".RETURN { .CONSTANT INT 1; };
+                .BREAK;
+                .WHILE CONDITION { .CONSTANT BOOL true; } THEN {
+                    .CONTINUE;
+                    .FUNCTION \"\" 0 VOID ARGUMENTS { } FREE { } THEN { .BREAK; };
+                };
+                .WHILE CONDITION { .CONSTANT BOOL true; } THEN { .RETURN { .CONSTANT INT 1; }; };
+                .IF CONDITION { .CONSTANT BOOL true; } THEN { .RETURN { .CONSTANT INT 1; }; };"
+            ),
+            vec![
+                ValidationErrorKind::ReturnOutsideFunction,
+                ValidationErrorKind::OutsideLoop("BREAK"),
+                ValidationErrorKind::OutsideLoop("BREAK"),
+                ValidationErrorKind::ReturnOutsideFunction,
+            ]
+        );
+    }
+
+    #[test]
+    fn calls() {
+        assert_eq!(
+            errors(
+                ".CALL { .CONSTANT INT 10; } { };
+                .STORE 0 { .FUNCTION \"\" 0 VOID ARGUMENTS { INT; CHAR[]; } FREE { } THEN { }; };
+                .CALL { .LOAD VARIABLE 0; } { .CONSTANT INT 1; };
+                .CALL { .LOAD VARIABLE 0; } { .CONSTANT BOOL true; .CONSTANT CHAR[] \"a\"; };
+                .CALL { .FUNCTION \"\" 1 VOID ARGUMENTS { FLOAT; } FREE { } THEN { }; } { .CONSTANT INT 1; };"
+            ),
+            vec![
+                ValidationErrorKind::NotCallable(ValueType::Integer(10)),
+                ValidationErrorKind::ArgumentCount(2, 1),
+                ValidationErrorKind::TypeMismatch(Type::INT, ValueType::Boolean(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn variables_stored_more_than_once() {
+        // Which function is called depends on the store that ran last
+        assert_eq!(
+            errors(
+                ".STORE 0 { .FUNCTION \"\" 0 VOID ARGUMENTS { INT; } FREE { } THEN { }; };
+                .STORE 0 { .FUNCTION \"\" 1 VOID ARGUMENTS { } FREE { } THEN { }; };
+                .CALL { .LOAD VARIABLE 0; } { };"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn return_types() {
+        assert_eq!(
+            errors(
+                ".FUNCTION \"\" 0 INT[] ARGUMENTS { } FREE { } THEN {
+                    .RETURN { .CONSTANT INT[] [.CONSTANT INT 1;]; };
+                    .RETURN { .CONSTANT CHAR[] \"a\"; };
+                };
+                .FUNCTION \"\" 1 VOID ARGUMENTS { } FREE { } THEN { .RETURN { .CONSTANT INT 1; }; };"
+            ),
+            vec![ValidationErrorKind::TypeMismatch(
+                Type::ARRAY(Box::new(Type::INT)),
+                ValueType::Array(Box::new(vec![ValueType::Character('a')]))
+            )]
+        );
+    }
+
+    #[test]
+    fn compounds() {
+        // The parser only accepts compounds that are defined, so these are built directly
+        let ast = AST::new_mock(vec![
+            Node::CONSTANT(ValueType::Compound("class_0".to_string(), Box::default())),
+            Node::COMPOUND(CompoundType {
+                name: "class_0".to_string(),
+                values: Box::new(vec![Type::Compound("class_1".to_string(), Box::default())]),
+                fields: None,
+            }),
+            Node::COMPOUND(CompoundType {
+                name: "class_0".to_string(),
+                values: Box::default(),
+                fields: None,
+            }),
+            Node::CONSTANT(ValueType::Compound("class_0".to_string(), Box::default())),
+        ]);
+
+        assert_eq!(
+            validate(&ast)
+                .unwrap_err()
+                .into_iter()
+                .map(|error| error.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                ValidationErrorKind::UnknownCompound("class_0".to_string()),
+                ValidationErrorKind::UnknownCompound("class_1".to_string()),
+                ValidationErrorKind::CompoundAlreadyExists("class_0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn library_path() {
+        let ast = AST::new_mock(vec![Node::LOADLIB(LoadLib {
+            path: Box::new(Node::CONSTANT(ValueType::Integer(1))),
+            namespace: "lib".to_string(),
+        })]);
+
+        assert_eq!(
+            validate(&ast).unwrap_err()[0].kind,
+            ValidationErrorKind::LibraryPath
+        );
+    }
+
+    #[test]
+    fn locations() {
+        let ast = AST::new_mock(vec![
+            Node::LOAD(Load {
+                load_type: LoadType::VARIABLE,
+                index: 0,
+            }),
+            Node::LOCATION(Location {
+                file: "main.loop".to_string(),
+                line: 3,
+            }),
+            Node::BREAK,
+        ]);
+
+        let errors = validate(&ast).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError {
+                    kind: ValidationErrorKind::UndefinedVariable(0),
+                    location: None,
+                },
+                ValidationError {
+                    kind: ValidationErrorKind::OutsideLoop("BREAK"),
+                    location: Some(Location {
+                        file: "main.loop".to_string(),
+                        line: 3,
+                    }),
+                },
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            ".BREAK can only be used inside of a loop (at main.loop:3)"
+        );
+
+        let diagnostic = errors[1].diagnostic(Some("    break".to_string()));
+        assert_eq!(diagnostic.code, "arc::validation");
+        assert_eq!(diagnostic.spans[0].file, "main.loop");
+        assert_eq!(
+            (
+                diagnostic.spans[0].line_start,
+                diagnostic.spans[0].column_start,
+                diagnostic.spans[0].column_end
+            ),
+            (3, 5, 9)
+        );
+        assert!(errors[0].diagnostic(None).spans.is_empty());
+    }
+}
@@ -3,7 +3,7 @@ import sys
 # Imports the test library
 from testlib import run_tests, add_test
 
-def main(verbose: bool):
+def main(verbose: bool, flags):
     # Add your tests right here.
     # AddTest( FILE LOCATION OF LOOP FILE, EXPECTED ANSWER )
 
@@ -15,6 +15,8 @@ def main(verbose: bool):
     add_test("test_string_index.loop", "2")
     add_test("test_string_slice.loop", "o, W")
     add_test("test_string_length.loop", "7")
+    add_test("test_string_methods.loop", "LOOP-IS-FUN a_b 48")
+    add_test("test_string_unicode.loop", "5")
     if platform.system() == "Windows": # Windows handles "new_lines" different then Unix
         add_test("test_string.loop", "123Hello\r\nWorld!")
     else:
@@ -27,25 +29,41 @@ def main(verbose: bool):
     add_test("test_array_remove.loop", "20")
     add_test("test_array_3d_assign_index.loop", "200")
     add_test("test_array_length.loop", "5")
+    add_test("test_array_methods.loop", "9,0,1,3,5,8 8:5:3:1:0:9 38/4")
+    add_test("test_printing.loop", "Line { from_point: Point { x: 1, y: 2 }, label: \"<origin>\", scale: 1.5 }")
+    add_test("test_printing_hashmap.loop", "{1: 2, 2: 4}")
+    add_test("test_printing_float.loop", "2.0")
+    add_test("test_printing_float_array.loop", "[1.0, 2.5]")
+
+    # Tests relating hashmaps
+    add_test("test_hashmaps.loop", "30")
+    add_test("test_hashmaps_nested_assign.loop", "true")
+    add_test("test_hashmaps_operations.loop", "91")
 
     # Tests relating conditionals and if-expression
     add_test("test_conditional_true.loop", "100")
     add_test("test_conditional_false.loop", "300")
     add_test("test_conditional_null.loop", "50")
     add_test("test_logical_operators.loop", "false")
+    add_test("test_comparisons.loop", "4")
+    add_test("test_prefix_operators.loop", "-7.0")
+    add_test("test_operator_types.loop", "abc!")
     add_test("test_if_expression_return.loop", "110")
 
     # Tests relating operators and math
     add_test("test_division_float.loop", "2.45")
     add_test("test_division_integer.loop", "1")
     add_test("test_modulo.loop", "0.5")
-    add_test("test_expression.loop", "-13")
+    add_test("test_expression.loop", "-13.0")
     add_test("test_expression_precedence.loop", "-29.616")
 
     # Tests relating loops
     add_test("test_loop_while.loop", "20")
     add_test("test_loop_iterator.loop", "20")
     add_test("test_loop_iterator_array.loop", "46")
+    add_test("test_loop_iterator_array_strings.loop", ">ab")
+    add_test("test_loop_range.loop", "321266920")
+    add_test("test_loop_break_continue.loop", "746")
 
     # Tests relating classes and extend
     add_test("test_classes.loop", "400")
@@ -54,7 +72,9 @@ def main(verbose: bool):
 
     # Tests relating packages and FFI
     add_test("test_import_lib.loop", "hello")
-    add_test("test_multi_level_import.loop", "32")
+    add_test("test_std.loop", "loop-is-fun 31")
+    add_test("test_multi_level_import.loop", "64")
+    add_test("test_import_same_name.loop", "20")
     add_test("test_import_export.loop", "430")
 
     # Tests relating functions
@@ -74,7 +94,7 @@ def main(verbose: bool):
     # has_failed, a boolean:
     #   > True: One or more test have failed.
     #   > False: All tests passed
-    output, has_failed = run_tests(verbose)
+    output, has_failed = run_tests(verbose, flags)
 
     # Asserts if 'has_failed' is false, otherwise prints a red failure message
     if has_failed:
@@ -85,10 +105,17 @@ def main(verbose: bool):
     print(output + "\033[92mEnd2End Tests Have Succeeded...\033[0m")
 
 if __name__ == "__main__":
-    if len(sys.argv) > 1:
-        if sys.argv[1] == "-v":
-            main(True)
+    verbose = False
+    # Flags that are passed on to Loop, like "--backend=interp". The tests always compile, a
+    # program from the cache would hide changes to the compiler.
+    flags = ["--no-cache"]
+    for arg in sys.argv[1:]:
+        if arg == "-v":
+            verbose = True
+        elif arg.startswith("--backend="):
+            flags.append(arg)
         else:
-            print(f"ERROR: Unknown flag '{sys.argv[1]}'")
-    else:
-        main(False)
+            print(f"ERROR: Unknown flag '{arg}'")
+            exit(1)
+
+    main(verbose, flags)
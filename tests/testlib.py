@@ -24,7 +24,7 @@ def has_succeeded(stdout, stderr, answer):
 
     return output.strip() == answer
 
-def run_tests(verbose: bool):
+def run_tests(verbose: bool, flags=[]):
     # Finds the executable regardless of platform
     exe = distutils.spawn.find_executable(BUILD + "/loop")
     output = "End2End Test Results:\n"
@@ -32,7 +32,7 @@ def run_tests(verbose: bool):
     test_count = 0
     for test in tests:
         try:
-            process = subprocess.Popen([exe, test.file_loc], stdout=subprocess.PIPE, stderr=subprocess.PIPE)
+            process = subprocess.Popen([exe, *flags, test.file_loc], stdout=subprocess.PIPE, stderr=subprocess.PIPE)
             # After 3 minutes (180 seconds) the program will crash, to prevent eternal loops
             stdout, stderr = process.communicate(timeout=180)
 